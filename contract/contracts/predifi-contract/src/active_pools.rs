@@ -0,0 +1,96 @@
+//! # Active Pool Count
+//!
+//! Running count of pools in the Active state, enforced against
+//! `Config.max_active_pools`. Instances deployed before the counter existed
+//! start from 0 after an upgrade, so an admin seeds the real count once with
+//! `seed_active_pool_count`; new deployments are marked seeded by `init`.
+
+use super::*;
+
+/// Storage keys of the active pool counter.
+#[contracttype]
+#[derive(Clone)]
+pub enum ActivePoolsKey {
+    /// Set once the counter holds the real number of Active pools.
+    Seeded,
+}
+
+#[contractevent(topics = ["active_pool_count_seeded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivePoolCountSeededEvent {
+    pub admin: Address,
+    /// Value the counter held before seeding.
+    pub previous: u32,
+    pub count: u32,
+}
+
+impl PredifiContract {
+    pub(crate) fn read_active_pool_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActivePoolCount)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn write_active_pool_count(env: &Env, count: u32) {
+        env.storage()
+            .instance()
+            .set(&DataKey::ActivePoolCount, &count);
+        Self::extend_instance(env);
+    }
+
+    /// Mark the counter as seeded; called by `init` on new deployments.
+    pub(crate) fn mark_active_pool_count_seeded(env: &Env) {
+        env.storage().instance().set(&ActivePoolsKey::Seeded, &true);
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Set the active pool counter to the number of pools currently Active,
+    /// once, on an instance upgraded from a release without the counter.
+    /// Pools created or settled between the upgrade and this call are
+    /// expected to be included in `count`. Caller must have Admin role (0).
+    ///
+    /// # Errors
+    /// - `ActivePoolCountSeeded` if the counter was already seeded, or the
+    ///   instance was deployed with it.
+    pub fn seed_active_pool_count(
+        env: Env,
+        admin: Address,
+        count: u32,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "seed_active_pool_count"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if env.storage().instance().has(&ActivePoolsKey::Seeded) {
+            return Err(PredifiError::ActivePoolCountSeeded);
+        }
+
+        let previous = Self::read_active_pool_count(&env);
+        Self::write_active_pool_count(&env, count);
+        Self::mark_active_pool_count_seeded(&env);
+
+        ActivePoolCountSeededEvent {
+            admin,
+            previous,
+            count,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the number of pools currently in the Active state.
+    pub fn get_active_pool_count(env: Env) -> u32 {
+        let count = Self::read_active_pool_count(&env);
+        Self::extend_instance(&env);
+        count
+    }
+}
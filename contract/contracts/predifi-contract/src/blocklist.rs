@@ -0,0 +1,123 @@
+//! # Address Blocklist
+//!
+//! Blocked addresses cannot bet, and payouts owed to them are held until
+//! they are unblocked.
+
+use super::*;
+
+/// Storage keys of the address blocklist.
+#[contracttype]
+#[derive(Clone)]
+pub enum BlocklistKey {
+    /// Set while an address is on the global blocklist.
+    Blocked(Address),
+}
+
+#[contractevent(topics = ["address_block_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressBlockUpdateEvent {
+    pub admin: Address,
+    pub address: Address,
+    pub blocked: bool,
+}
+
+/// 🟡 MEDIUM ALERT — a blocked address tried to create a pool, bet or claim.
+/// Claims settle with the payout deferred until the address is unblocked.
+#[contractevent(topics = ["blocked_address_hit"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockedAddressHitEvent {
+    #[topic]
+    pub address: Address,
+    pub operation: Symbol,
+    pub timestamp: u64,
+}
+
+impl PredifiContract {
+    pub(crate) fn is_address_blocked(env: &Env, address: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&BlocklistKey::Blocked(address.clone()))
+    }
+
+    /// `Unauthorized` with a `BlockedAddressHitEvent` if `address` is on the
+    /// blocklist.
+    pub(crate) fn require_not_blocked(
+        env: &Env,
+        address: &Address,
+        operation: &str,
+    ) -> Result<(), PredifiError> {
+        if !Self::is_address_blocked(env, address) {
+            return Ok(());
+        }
+        BlockedAddressHitEvent {
+            address: address.clone(),
+            operation: Symbol::new(env, operation),
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+        Err(PredifiError::Unauthorized)
+    }
+
+    pub(crate) fn set_address_blocked(
+        env: Env,
+        admin: Address,
+        address: Address,
+        blocked: bool,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(
+                    &env,
+                    if blocked {
+                        "block_address"
+                    } else {
+                        "unblock_address"
+                    },
+                ),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = BlocklistKey::Blocked(address.clone());
+        if blocked {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        AddressBlockUpdateEvent {
+            admin,
+            address,
+            blocked,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Add an address to the global blocklist. A blocked address cannot
+    /// create pools or bet; its claims settle but the payout waits in
+    /// `get_held_payout` until it is unblocked and `retry_payout` is
+    /// called. Caller must have Admin role (0).
+    pub fn block_address(env: Env, admin: Address, address: Address) -> Result<(), PredifiError> {
+        Self::set_address_blocked(env, admin, address, true)
+    }
+
+    /// Remove an address from the global blocklist. Caller must have Admin
+    /// role (0).
+    pub fn unblock_address(env: Env, admin: Address, address: Address) -> Result<(), PredifiError> {
+        Self::set_address_blocked(env, admin, address, false)
+    }
+
+    /// Returns true if the address is on the global blocklist.
+    pub fn is_blocked(env: Env, address: Address) -> bool {
+        Self::is_address_blocked(&env, &address)
+    }
+}
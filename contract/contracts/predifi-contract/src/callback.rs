@@ -0,0 +1,214 @@
+//! # Pool Lifecycle Callbacks
+//!
+//! A pool creator may register a contract to be told when the pool settles.
+//! Settlement only queues the hook; it is delivered by a separate,
+//! permissionless `dispatch_pool_callback` call. A callback that traps or
+//! burns the whole CPU / memory budget therefore only fails its own dispatch
+//! and can never block `resolve_pool`, `cancel_pool` or `finalize_pool`.
+//! Operators can remove a misbehaving callback with `clear_pool_callback`.
+
+use super::*;
+
+/// Storage keys of the callback feature.
+#[contracttype]
+#[derive(Clone)]
+pub enum CallbackKey {
+    /// Hook queued at settlement, awaiting `dispatch_pool_callback`.
+    PendingHook(u64),
+}
+
+/// Settlement a queued hook reports.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PoolHook {
+    /// `on_pool_resolved` with the winning outcome.
+    Resolved(u32),
+    /// `on_pool_canceled`.
+    Canceled,
+}
+
+/// Interface a downstream contract (vault, parlay engine, insurance, ...)
+/// implements to be notified when a pool it is registered on settles.
+/// Registered via `set_pool_callback`; delivered by `dispatch_pool_callback`,
+/// so a failing callback never blocks resolution or cancellation.
+pub trait PoolLifecycleCallback {
+    fn on_pool_resolved(env: Env, pool_id: u64, outcome: u32);
+    fn on_pool_canceled(env: Env, pool_id: u64);
+}
+
+#[contractevent(topics = ["pool_callback_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCallbackSetEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    /// `None` when the callback was removed.
+    pub callback: Option<Address>,
+}
+
+/// 🟡 MEDIUM ALERT — emitted when an operator removes a pool's callback.
+#[contractevent(topics = ["pool_callback_cleared"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCallbackClearedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+}
+
+/// 🟡 MEDIUM ALERT — emitted when a registered pool callback fails.  The
+/// callee's state changes are rolled back and the hook is dropped.
+#[contractevent(topics = ["pool_callback_failed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCallbackFailedEvent {
+    pub pool_id: u64,
+    pub callback: Address,
+    /// Name of the hook that failed (`on_pool_resolved` / `on_pool_canceled`).
+    pub hook: Symbol,
+}
+
+impl PredifiContract {
+    /// Queue `hook` for the pool's registered callback, if it has one. A
+    /// later settlement (e.g. a disputed outcome) replaces the queued hook.
+    pub(crate) fn queue_pool_callback(env: &Env, pool_id: u64, hook: PoolHook) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::PoolCallback(pool_id))
+        {
+            return;
+        }
+        let key = CallbackKey::PendingHook(pool_id);
+        env.storage().persistent().set(&key, &hook);
+        Self::extend_persistent(env, &key);
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Register (or clear, with `None`) a contract to be notified when the pool
+    /// is resolved or canceled. Only the pool creator may set it, and only
+    /// while the pool is still Active. The callback must implement
+    /// `PoolLifecycleCallback`; hooks are delivered by `dispatch_pool_callback`.
+    pub fn set_pool_callback(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        callback: Option<Address>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .expect("Pool not found");
+
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = DataKey::PoolCallback(pool_id);
+        match &callback {
+            Some(addr) => {
+                env.storage().persistent().set(&key, addr);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        PoolCallbackSetEvent {
+            pool_id,
+            creator,
+            callback,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Remove a pool's callback and any hook queued for it, in any pool
+    /// state. Caller must have Operator role (1).
+    pub fn clear_pool_callback(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+    ) -> Result<(), PredifiError> {
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PoolCallback(pool_id));
+        env.storage()
+            .persistent()
+            .remove(&CallbackKey::PendingHook(pool_id));
+
+        PoolCallbackClearedEvent { pool_id, operator }.publish(&env);
+        Ok(())
+    }
+
+    /// Deliver the hook queued when the pool settled to its callback.
+    /// Permissionless. The hook is consumed whether or not the callee
+    /// succeeds; a failure is isolated and reported with
+    /// `PoolCallbackFailedEvent`. Returns whether the callee succeeded.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if no hook is queued for the pool.
+    pub fn dispatch_pool_callback(env: Env, pool_id: u64) -> Result<bool, PredifiError> {
+        let key = CallbackKey::PendingHook(pool_id);
+        let hook: PoolHook = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        env.storage().persistent().remove(&key);
+
+        let callback: Address = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::PoolCallback(pool_id))
+        {
+            Some(callback) => callback,
+            None => return Ok(false),
+        };
+        let (name, args) = match hook {
+            PoolHook::Resolved(outcome) => (
+                Symbol::new(&env, "on_pool_resolved"),
+                soroban_sdk::vec![&env, pool_id.into_val(&env), outcome.into_val(&env)],
+            ),
+            PoolHook::Canceled => (
+                Symbol::new(&env, "on_pool_canceled"),
+                soroban_sdk::vec![&env, pool_id.into_val(&env)],
+            ),
+        };
+
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(&callback, &name, args);
+        let delivered = matches!(result, Ok(Ok(())));
+        if !delivered {
+            PoolCallbackFailedEvent {
+                pool_id,
+                callback,
+                hook: name,
+            }
+            .publish(&env);
+        }
+        Ok(delivered)
+    }
+
+    /// Returns the lifecycle callback registered for a pool, if any.
+    pub fn get_pool_callback(env: Env, pool_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PoolCallback(pool_id))
+    }
+
+    /// Returns the hook awaiting `dispatch_pool_callback` for a pool, if any.
+    pub fn get_pending_pool_callback(env: Env, pool_id: u64) -> Option<PoolHook> {
+        env.storage()
+            .persistent()
+            .get(&CallbackKey::PendingHook(pool_id))
+    }
+}
@@ -0,0 +1,199 @@
+//! # Market Curation
+//!
+//! With curation enabled, pools from creators without the Admin or Operator
+//! role start as drafts that take no bets until an operator approves them
+//! or the auto-approval delay passes.
+
+use super::*;
+
+/// Storage keys of market curation.
+#[contracttype]
+#[derive(Clone)]
+pub enum CurationKey {
+    /// Market curation settings (`CurationConfig`).
+    CurationConfig,
+    /// Set while a pool awaits review: PoolDraft(pool_id) -> auto-approval
+    /// time (0 = manual review only).
+    PoolDraft(u64),
+}
+
+/// Market curation settings: whether pools from creators without the Admin
+/// or Operator role start as drafts, and after how long a draft is approved
+/// automatically (0 = never).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct CurationConfig {
+    pub enabled: bool,
+    pub auto_approve_after: u64,
+}
+
+#[contractevent(topics = ["curation_config_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurationConfigUpdateEvent {
+    pub admin: Address,
+    pub enabled: bool,
+    pub auto_approve_after: u64,
+}
+
+#[contractevent(topics = ["pool_submitted_for_review"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSubmittedForReviewEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    /// When the draft goes live without review (0 = manual review only).
+    pub auto_approve_at: u64,
+}
+
+#[contractevent(topics = ["pool_approved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolApprovedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+}
+
+#[contractevent(topics = ["pool_rejected"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolRejectedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub reason: String,
+}
+
+impl PredifiContract {
+    /// True while a pool awaits curation review and has not been approved
+    /// automatically.
+    pub(crate) fn is_draft(env: &Env, pool_id: u64) -> bool {
+        let auto_approve_at: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&CurationKey::PoolDraft(pool_id));
+        match auto_approve_at {
+            Some(0) => true,
+            Some(at) => env.ledger().timestamp() < at,
+            None => false,
+        }
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Configure the curation queue. While enabled, pools created by
+    /// addresses without the Admin (0) or Operator (1) role start as drafts
+    /// that accept no bets until an operator approves them, or until
+    /// `auto_approve_after` seconds have passed (0 = manual review only).
+    /// Existing drafts keep the timeout they were created with.
+    /// Caller must have Admin role (0).
+    pub fn set_curation_config(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+        auto_approve_after: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_curation_config"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let old = Self::get_curation_config(env.clone());
+        Self::record_config_change(
+            &env,
+            &admin,
+            "curation_enabled",
+            ConfigValue::U32(old.enabled as u32),
+            ConfigValue::U32(enabled as u32),
+        );
+        Self::record_config_change(
+            &env,
+            &admin,
+            "auto_approve_after",
+            ConfigValue::U64(old.auto_approve_after),
+            ConfigValue::U64(auto_approve_after),
+        );
+        env.storage().instance().set(
+            &CurationKey::CurationConfig,
+            &CurationConfig {
+                enabled,
+                auto_approve_after,
+            },
+        );
+        Self::extend_instance(&env);
+
+        CurationConfigUpdateEvent {
+            admin,
+            enabled,
+            auto_approve_after,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the curation queue settings.
+    pub fn get_curation_config(env: Env) -> CurationConfig {
+        env.storage()
+            .instance()
+            .get(&CurationKey::CurationConfig)
+            .unwrap_or_default()
+    }
+
+    /// Returns true while a pool awaits curation review.
+    pub fn is_pool_draft(env: Env, pool_id: u64) -> bool {
+        Self::is_draft(&env, pool_id)
+    }
+
+    /// Approve a draft pool, opening it for betting.
+    /// Caller must have Operator role (1).
+    pub fn approve_pool(env: Env, operator: Address, pool_id: u64) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        let key = CurationKey::PoolDraft(pool_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        env.storage().persistent().remove(&key);
+
+        PoolApprovedEvent { pool_id, operator }.publish(&env);
+        Ok(())
+    }
+
+    /// Reject a draft pool with a short reason (max 256 bytes). The pool is
+    /// canceled, returning the creator's liquidity and bond.
+    /// Caller must have Operator role (1).
+    pub fn reject_pool(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        reason: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        Self::check_note(&reason)?;
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if !Self::is_draft(&env, pool_id) || pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        env.storage()
+            .persistent()
+            .remove(&CurationKey::PoolDraft(pool_id));
+
+        Self::finalize_cancellation(&env, pool_id, pool, operator.clone(), reason.clone());
+        PoolRejectedEvent {
+            pool_id,
+            operator,
+            reason,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! # Display Keys
+//!
+//! Internal symbols (categories, event operation names) can be mapped to
+//! display keys that frontends resolve to localized labels.
+
+use super::*;
+
+/// Storage keys of display keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum LabelKey {
+    /// Display key a frontend resolves to a localized label for an internal
+    /// symbol.
+    DisplayKey(Symbol),
+}
+
+#[contractevent(topics = ["display_key_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisplayKeySetEvent {
+    pub admin: Address,
+    pub symbol: Symbol,
+    /// `None` when the mapping was removed.
+    pub display_key: Option<String>,
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Map an internal symbol (a category, or an operation name carried by
+    /// events) to the display key frontends localize it with, e.g.
+    /// `Tech` -> `"category.technology"`. Symbols stay the stable identifiers
+    /// in storage and events; `None` removes the mapping. Keys are at most
+    /// 64 bytes. Caller must have Admin role (0).
+    pub fn set_display_key(
+        env: Env,
+        admin: Address,
+        symbol: Symbol,
+        display_key: Option<String>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_display_key"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = LabelKey::DisplayKey(symbol.clone());
+        match &display_key {
+            Some(display_key) => {
+                assert!(
+                    !display_key.is_empty() && display_key.len() <= MAX_DISPLAY_KEY_LEN,
+                    "display key must be 1 to 64 bytes"
+                );
+                env.storage().persistent().set(&key, display_key);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        DisplayKeySetEvent {
+            admin,
+            symbol,
+            display_key,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the display key registered for a symbol, if any.
+    pub fn get_display_key(env: Env, symbol: Symbol) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&LabelKey::DisplayKey(symbol))
+    }
+
+    /// Returns the display keys registered for `symbols`, at most
+    /// `MAX_BATCH_SIZE` per call. Symbols without one are left out.
+    pub fn get_display_keys(env: Env, symbols: Vec<Symbol>) -> Map<Symbol, String> {
+        let mut keys = Map::new(&env);
+        for symbol in symbols.iter().take(MAX_BATCH_SIZE as usize) {
+            if let Some(display_key) = Self::get_display_key(env.clone(), symbol.clone()) {
+                keys.set(symbol, display_key);
+            }
+        }
+        keys
+    }
+}
@@ -0,0 +1,156 @@
+//! # Pool Fee Rate
+//!
+//! The protocol fee rate of a pool comes from admin settings
+//! (`Config.fee_bps` and the fee tiers) that may change while its claims
+//! are still outstanding. The effective rate is snapshotted when the pool
+//! resolves, so every payout of the pool, including later partial claims,
+//! is charged the same rate. Collected fees are booked per token until an
+//! admin sends them to the treasury with `withdraw_fees`.
+
+use super::*;
+
+/// Storage keys of the pool fee rate.
+#[contracttype]
+#[derive(Clone)]
+pub enum FeeKey {
+    /// Protocol fee rate in basis points locked in at resolution.
+    PoolFeeBps(u64),
+    /// Protocol fees collected in a token and not yet sent to the treasury
+    /// by `withdraw_fees`.
+    FeesAccrued(Address),
+}
+
+#[contractevent(topics = ["protocol_fee_accrued"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFeeAccruedEvent {
+    pub pool_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    /// Fees accrued in `token` after this one.
+    pub total_accrued: i128,
+}
+
+#[contractevent(topics = ["fees_withdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesWithdrawnEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
+impl PredifiContract {
+    /// Protocol fee rate for a pool of the given final size: the highest
+    /// configured tier it reaches, or Config.fee_bps when no tier applies.
+    pub(crate) fn effective_fee_bps(env: &Env, total_stake: i128) -> u32 {
+        let tiers: Vec<FeeTier> = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeTiers)
+            .unwrap_or(Vec::new(env));
+        let mut fee_bps = None;
+        for tier in tiers.iter() {
+            if total_stake < tier.min_total_stake {
+                break;
+            }
+            fee_bps = Some(tier.fee_bps);
+        }
+        fee_bps.unwrap_or_else(|| Self::load_config(env).fee_bps)
+    }
+
+    /// Lock in the protocol fee rate of a pool being resolved.
+    pub(crate) fn snapshot_pool_fee_bps(env: &Env, pool_id: u64, pool: &Pool) {
+        let key = FeeKey::PoolFeeBps(pool_id);
+        let fee_bps = Self::effective_fee_bps(env, pool.total_stake);
+        env.storage().persistent().set(&key, &fee_bps);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Protocol fee rate charged on a resolved pool's payouts. Pools resolved
+    /// before rates were snapshotted fall back to the current rate.
+    pub(crate) fn pool_fee_bps(env: &Env, pool_id: u64, pool: &Pool) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&FeeKey::PoolFeeBps(pool_id))
+            .unwrap_or_else(|| Self::effective_fee_bps(env, pool.total_stake))
+    }
+
+    /// Book a protocol fee into the per-token fee ledger. The tokens stay in
+    /// the contract until an admin calls `withdraw_fees`.
+    pub(crate) fn accrue_protocol_fee(env: &Env, pool_id: u64, token: &Address, fee: i128) {
+        if fee <= 0 {
+            return;
+        }
+        let key = FeeKey::FeesAccrued(token.clone());
+        let total_accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0) + fee;
+        env.storage().persistent().set(&key, &total_accrued);
+        Self::extend_persistent(env, &key);
+        ProtocolFeeAccruedEvent {
+            pool_id,
+            token: token.clone(),
+            amount: fee,
+            total_accrued,
+        }
+        .publish(env);
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Send the protocol fees accrued in `token` to the treasury. Returns the
+    /// amount transferred (0 if nothing was accrued).
+    /// Caller must have Admin role (0).
+    pub fn withdraw_fees(env: Env, admin: Address, token: Address) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "withdraw_fees"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = FeeKey::FeesAccrued(token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Ok(0);
+        }
+        env.storage().persistent().remove(&key);
+
+        let treasury = Self::load_config(&env).treasury;
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &treasury,
+            &amount,
+        );
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &treasury,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "fee",
+        );
+
+        FeesWithdrawnEvent {
+            admin,
+            token,
+            amount,
+            treasury,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    /// Returns the protocol fees accrued in `token` and not yet withdrawn.
+    pub fn get_fees_accrued(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&FeeKey::FeesAccrued(token))
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,105 @@
+//! # Hot Pools
+//!
+//! A pool expecting heavy betting can run in hot mode: bets accumulate their
+//! stake in a separate delta entry instead of rewriting the pool record,
+//! and the delta is folded into `Pool.total_stake` when the pool is next
+//! written.
+
+use super::*;
+
+/// Storage keys of hot pools.
+#[contracttype]
+#[derive(Clone)]
+pub enum HotPoolKey {
+    /// Set while a pool runs in hot mode (see `set_hot_pool`).
+    HotPool(u64),
+    /// Stake placed on a hot pool not yet folded into `Pool.total_stake`.
+    StakeDelta(u64),
+}
+
+#[contractevent(topics = ["hot_pool_mode"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HotPoolModeEvent {
+    pub pool_id: u64,
+    pub enabled: bool,
+}
+
+impl PredifiContract {
+    /// True while the pool runs in hot mode (see `set_hot_pool`).
+    pub(crate) fn read_hot_pool(env: &Env, pool_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&HotPoolKey::HotPool(pool_id))
+    }
+
+    /// Stake accumulated on a hot pool since `Pool.total_stake` was last
+    /// written.
+    pub(crate) fn stake_delta(env: &Env, pool_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&HotPoolKey::StakeDelta(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Fold a hot pool's pending stake delta into `pool` and clear it. The
+    /// caller must persist `pool`.
+    pub(crate) fn flush_stake_delta(env: &Env, pool_id: u64, pool: &mut Pool) {
+        let delta = Self::stake_delta(env, pool_id);
+        if delta != 0 {
+            pool.total_stake = pool.total_stake.checked_add(delta).expect("overflow");
+            env.storage()
+                .persistent()
+                .remove(&HotPoolKey::StakeDelta(pool_id));
+        }
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Switch a pool's hot mode for markets expecting heavy betting. While
+    /// enabled, `place_prediction` accumulates stake in a small delta entry
+    /// instead of rewriting the Pool (folded back on settlement, by other
+    /// bet paths and in `get_pool`). Bets are still listed by
+    /// `get_user_predictions`. Caller must be the pool creator or have
+    /// Operator role (1).
+    pub fn set_hot_pool(
+        env: Env,
+        caller: Address,
+        pool_id: u64,
+        enabled: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != caller {
+            Self::require_role(&env, &caller, 1)?;
+        }
+        if pool.state != MarketState::Active || Self::lmsr_state(&env, pool_id).is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = HotPoolKey::HotPool(pool_id);
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+            Self::flush_stake_delta(&env, pool_id, &mut pool);
+            env.storage().persistent().set(&pool_key, &pool);
+            Self::extend_persistent(&env, &pool_key);
+        }
+
+        HotPoolModeEvent { pool_id, enabled }.publish(&env);
+        Ok(())
+    }
+
+    /// Returns true while a pool runs in hot mode.
+    pub fn is_hot_pool(env: Env, pool_id: u64) -> bool {
+        Self::read_hot_pool(&env, pool_id)
+    }
+}
@@ -1,33 +1,13 @@
 #![cfg(test)]
 
 use super::*;
-use crate::test_utils::TokenTestContext;
+use crate::test_utils::{dummy_access_control, MarketTestContext, TokenTestContext};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
     Address, Env, String,
 };
 
-mod dummy_access_control {
-    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
-
-    #[contract]
-    pub struct DummyAccessControl;
-
-    #[contractimpl]
-    impl DummyAccessControl {
-        pub fn grant_role(env: Env, user: Address, role: u32) {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().set(&key, &true);
-        }
-
-        pub fn has_role(env: Env, user: Address, role: u32) -> bool {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().get(&key).unwrap_or(false)
-        }
-    }
-}
-
 const ROLE_ADMIN: u32 = 0;
 const ROLE_OPERATOR: u32 = 1;
 
@@ -268,3 +248,37 @@ fn test_market_resolution_multiple_winners() {
     assert_eq!(token_ctx.token.balance(&user2), 1300); // 1000 - 300 + 600
     assert_eq!(token_ctx.token.balance(&client.address), 0);
 }
+
+#[test]
+fn test_market_test_context_harness() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let market = MarketTestContext::setup(&env);
+    let creator = Address::generate(&env);
+    let user = Address::generate(&env);
+    market.token.mint(&user, 100);
+
+    let pool_id = market.client.create_pool(
+        &creator,
+        &3600u64,
+        &market.token.token_address,
+        &2u32,
+        &String::from_str(&env, "Harness Pool"),
+        &String::from_str(&env, "ipfs://harness"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    market.client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 3601);
+    market.client.oracle_resolve(
+        &market.oracle,
+        &pool_id,
+        &0u32,
+        &String::from_str(&env, "proof"),
+    );
+    assert_eq!(market.client.claim_winnings(&user, &pool_id), 100);
+}
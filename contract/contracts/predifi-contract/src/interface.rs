@@ -0,0 +1,382 @@
+//! # PrediFi Read Interface
+//!
+//! Read-only view of the PrediFi contract API for integrators. Other Soroban
+//! contracts can depend on this module to query pools, predictions and
+//! settlement data through the generated [`PredifiReadClient`] without
+//! linking against the contract implementation.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use predifi_contract::interface::PredifiReadClient;
+//!
+//! let predifi = PredifiReadClient::new(&env, &predifi_address);
+//! let pool = predifi.get_pool(&pool_id);
+//! if pool.state == MarketState::Resolved {
+//!     let attestation = predifi.get_settlement_attestation(&pool_id);
+//! }
+//! ```
+
+use soroban_sdk::{contractclient, Address, BytesN, Env, Map, String, Symbol, Vec};
+
+pub use crate::{
+    AirdropEntry, AirdropEpoch, AmmFeeConfig, AuthorizedOutcome, BetLeg, CancelRequest,
+    Capabilities, ClaimProgress, Config, ConfigChange, CreatorBond, CurationConfig, DeadManSwitch,
+    DerivativeLink, FeeTier, HeldPayout, KeeperConfig, LmsrPosition, LmsrState, MarketState,
+    Mechanism, OddsAlert, OperatorNote, OperatorSlaStats, OptimisticConfig, OutcomeAssertion,
+    ParentCondition, Pool, PoolAnalytics, PoolEscrow, PoolHook, PoolKeeper, PoolLiquidity,
+    PoolNote, PoolSponsor, PoolStats, PoolSummary, PoolTemplate, PositionView, PredifiError,
+    PriceMarket, PublicGoodsShare, Quote, ResolutionCommitment, SettlementAttestation, SimAction,
+    SimResult, SweepSchedule, TokenParams, UserActivity, UserPredictionDetail, WinnersSummary,
+    YieldAdapterConfig,
+};
+
+/// Read-only entrypoints exposed by the PrediFi contract.
+///
+/// Signatures must match the `#[contractimpl]` functions of the same name.
+/// New views belong here too, with a call in
+/// `test_read_interface_covers_every_view`, which dispatches every method.
+#[contractclient(name = "PredifiReadClient")]
+pub trait PredifiRead {
+    /// Returns a pool by id. Panics if the pool does not exist.
+    fn get_pool(env: Env, pool_id: u64) -> Pool;
+
+    /// Returns a pool without its description and metadata URL.
+    fn get_pool_summary(env: Env, pool_id: u64) -> Result<PoolSummary, PredifiError>;
+
+    /// Returns the number of pools ever created.
+    fn get_pool_count(env: Env) -> u64;
+
+    /// Returns a page of visible pool summaries in creation order.
+    fn get_pools(env: Env, offset: u64, limit: u32) -> Vec<PoolSummary>;
+
+    /// Returns true if a pool is hidden from the discovery views.
+    fn is_pool_hidden(env: Env, pool_id: u64) -> bool;
+
+    /// Returns the stake placed on every outcome of a pool.
+    fn get_pool_outcome_stakes(env: Env, pool_id: u64) -> Vec<i128>;
+
+    /// Returns the implied probability of each outcome in bps.
+    fn get_implied_odds(env: Env, pool_id: u64) -> Vec<u32>;
+
+    /// Returns the stake placed on a single outcome, or 0 if unknown.
+    fn get_outcome_stake(env: Env, pool_id: u64, outcome: u32) -> i128;
+
+    /// Returns aggregate statistics for a pool.
+    fn get_pool_stats(env: Env, pool_id: u64) -> PoolStats;
+
+    /// Returns a page of visible pool ids in a category.
+    fn get_pools_by_category(env: Env, category: Symbol, offset: u32, limit: u32) -> Vec<u64>;
+
+    /// Returns a page of a pool's positions, largest first per outcome.
+    fn get_top_positions(env: Env, pool_id: u64, offset: u32, limit: u32) -> Vec<PositionView>;
+
+    /// Returns a page of a user's predictions.
+    fn get_user_predictions(
+        env: Env,
+        user: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<UserPredictionDetail>;
+
+    /// Returns how much a user has already claimed from a pool.
+    fn get_claimed_amount(env: Env, user: Address, pool_id: u64) -> i128;
+
+    /// Returns the number of pools that are not yet resolved or canceled.
+    fn get_active_pool_count(env: Env) -> u32;
+
+    /// Returns the optional subsystems enabled on this deployment.
+    fn get_capabilities(env: Env) -> Capabilities;
+
+    /// Returns whether a token may be used to create pools.
+    fn is_token_allowed(env: Env, token: Address) -> bool;
+
+    /// Returns the operator note recorded on resolution or cancellation.
+    fn get_operator_note(env: Env, pool_id: u64) -> Option<OperatorNote>;
+
+    /// Returns the status update anchors of a pool, oldest first.
+    fn get_pool_notes(env: Env, pool_id: u64) -> Vec<PoolNote>;
+
+    /// Returns the sponsor branding of a pool, if any.
+    fn get_pool_sponsor(env: Env, pool_id: u64) -> Option<PoolSponsor>;
+
+    /// Returns the display key registered for a category or operation
+    /// symbol, for resolving localized labels.
+    fn get_display_key(env: Env, symbol: Symbol) -> Option<String>;
+
+    /// Returns the display keys registered for several symbols.
+    fn get_display_keys(env: Env, symbols: Vec<Symbol>) -> Map<Symbol, String>;
+
+    /// Returns the reference of a derivative pool, if any.
+    fn get_derivative_reference(env: Env, pool_id: u64) -> Option<DerivativeLink>;
+
+    /// Returns the payout held in escrow for (user, pool), if any.
+    fn get_held_payout(env: Env, user: Address, pool_id: u64) -> Option<HeldPayout>;
+
+    /// Returns the finalized winners merkle summary of a pool, if published.
+    fn get_winners_summary(env: Env, pool_id: u64) -> Option<WinnersSummary>;
+
+    /// Verifies a winners merkle proof.
+    fn verify_winner_proof(
+        env: Env,
+        pool_id: u64,
+        user: Address,
+        entitlement: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> bool;
+
+    /// Returns the settlement attestation of a resolved pool.
+    fn get_settlement_attestation(
+        env: Env,
+        pool_id: u64,
+    ) -> Result<SettlementAttestation, PredifiError>;
+
+    /// Returns the mask of individually paused operations (`PAUSE_*` bits).
+    fn get_pause_scopes(env: Env) -> u32;
+
+    /// Returns the dead-man switch configuration, if armed.
+    fn get_dead_man_switch(env: Env) -> Option<DeadManSwitch>;
+
+    /// Returns the timestamp of the last successful admin action.
+    fn get_last_admin_action(env: Env) -> u64;
+
+    /// Returns when the dead-man switch fired, if it has.
+    fn get_dead_man_triggered_at(env: Env) -> Option<u64>;
+
+    /// Returns the bond new pool creators must post, if any.
+    fn get_creator_bond(env: Env) -> Option<CreatorBond>;
+
+    /// Returns the creator bond held for a pool, if any.
+    fn get_pool_bond(env: Env, pool_id: u64) -> Option<CreatorBond>;
+
+    /// Returns the longest allowed pool duration in seconds (0 = unlimited).
+    fn get_max_pool_duration(env: Env) -> u64;
+
+    /// Returns the configured fee schedule by pool size (empty = flat fee).
+    fn get_fee_tiers(env: Env) -> Vec<FeeTier>;
+
+    /// Returns the resolution latency record of an operator or oracle.
+    fn get_operator_sla_stats(env: Env, operator: Address) -> OperatorSlaStats;
+
+    /// Returns the number of matching oracle submissions required to resolve a pool (1 unless the
+    /// pool was created with a quorum).
+    fn get_oracle_quorum(env: Env, pool_id: u64) -> u32;
+
+    /// Returns the pending oracle submissions (oracle → outcome) of a pool that has not reached
+    /// quorum yet.
+    fn get_oracle_submissions(env: Env, pool_id: u64) -> Map<Address, u32>;
+
+    /// Returns the timestamp from which claims on a pool are accepted (0 when no settlement hold
+    /// applies).
+    fn get_claims_open_at(env: Env, pool_id: u64) -> u64;
+
+    /// Returns the protocol configuration.
+    fn get_config(env: Env) -> Config;
+
+    /// Returns whether the contract is globally paused.
+    fn is_paused(env: Env) -> bool;
+
+    /// Returns retained config changes in chronological order.
+    fn get_config_history(env: Env, offset: u32, limit: u32) -> Vec<ConfigChange>;
+
+    /// Returns the remaining compensation reserve for a token.
+    fn get_compensation_reserve(env: Env, token: Address) -> i128;
+
+    /// Returns the yield adapter registered for a token, if any.
+    fn get_yield_adapter(env: Env, token: Address) -> Option<YieldAdapterConfig>;
+
+    /// Returns the escrow of a pool currently deposited into a yield adapter.
+    fn get_yield_deposit(env: Env, pool_id: u64) -> i128;
+
+    /// Returns true if the address is flagged at the compliance gate.
+    fn is_flagged(env: Env, user: Address) -> bool;
+
+    /// Returns the claim deadline of a pool, if it has one, including any extension granted by a
+    /// fired dead-man switch.
+    fn get_claim_deadline(env: Env, pool_id: u64) -> Option<u64>;
+
+    /// Returns the betting analytics checkpointed when a pool was resolved.
+    fn get_pool_analytics(env: Env, pool_id: u64) -> Option<PoolAnalytics>;
+
+    /// Returns the time from which a pool stops accepting bets (its end_time unless an earlier lock
+    /// time was set at creation).
+    fn get_lock_time(env: Env, pool_id: u64) -> u64;
+
+    /// Returns the funds still held for a pool: its escrow minus everything paid out so far
+    /// (payouts, held payouts, fees and liquidity returns).
+    fn get_pool_unclaimed(env: Env, pool_id: u64) -> i128;
+
+    /// Returns the segregated escrow buckets of a pool, if it is tracked.
+    fn get_pool_escrow(env: Env, pool_id: u64) -> Option<PoolEscrow>;
+
+    /// Returns the announced sweep of a pool, if any.
+    fn get_sweep_schedule(env: Env, pool_id: u64) -> Option<SweepSchedule>;
+
+    /// Returns the per-outcome claim counters of a settled pool and the share of its payable stake
+    /// whose claims are complete, so ops can decide when to push payouts or schedule a sweep.
+    fn get_claim_progress(env: Env, pool_id: u64) -> Result<ClaimProgress, PredifiError>;
+
+    /// Returns when a pool's per-bettor records were deleted by `close_pool`.
+    fn get_pool_archived_at(env: Env, pool_id: u64) -> Option<u64>;
+
+    /// Returns whether `recipient` is in the public-goods registry.
+    fn is_public_goods_recipient(env: Env, recipient: Address) -> bool;
+
+    /// Returns the summary of an airdrop snapshot, if one was taken.
+    fn get_airdrop_epoch(env: Env, epoch: u32) -> Option<AirdropEpoch>;
+
+    /// Returns a page of an airdrop snapshot's entries, in submission order.
+    fn get_airdrop_snapshot(env: Env, epoch: u32, offset: u32, limit: u32) -> Vec<AirdropEntry>;
+
+    /// Returns a user's running activity counters.
+    fn get_user_activity(env: Env, user: Address) -> UserActivity;
+
+    /// Returns the semver string of the deployed build.
+    fn version(env: Env) -> String;
+
+    /// Returns the storage schema revision the instance has been migrated to.
+    fn storage_version(env: Env) -> u32;
+
+    /// Returns the pending resolution commitment of a pool, if any.
+    fn get_resolution_commitment(env: Env, pool_id: u64) -> Option<ResolutionCommitment>;
+
+    /// Returns the optimistic resolution settings, if enabled.
+    fn get_optimistic_config(env: Env) -> Option<OptimisticConfig>;
+
+    /// Returns the pending outcome assertion of a pool, if any.
+    fn get_outcome_assertion(env: Env, pool_id: u64) -> Option<OutcomeAssertion>;
+
+    /// Dry-runs a sequence of bets against current state.
+    fn simulate_batch(env: Env, actions: Vec<SimAction>) -> Vec<SimResult>;
+
+    /// Returns the net payout a new bet would receive if its outcome won.
+    fn quote_prediction(
+        env: Env,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<i128, PredifiError>;
+
+    /// Returns what `claim_winnings` would pay `user` right now.
+    fn simulate_claim(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError>;
+
+    /// Returns the handicap payout multiplier of each outcome of a pool, in bps (empty for
+    /// unweighted pools).
+    fn get_outcome_weights(env: Env, pool_id: u64) -> Vec<u32>;
+
+    /// Returns the label of each outcome of a pool (empty if unlabeled).
+    fn get_outcome_labels(env: Env, pool_id: u64) -> Vec<String>;
+
+    /// Returns the numeric range of each outcome of a scalar pool (empty for categorical pools).
+    fn get_scalar_buckets(env: Env, pool_id: u64) -> Vec<(i128, i128)>;
+
+    /// Returns the bettor cancellation request of a pool, if any.
+    fn get_cancel_request(env: Env, pool_id: u64) -> Option<CancelRequest>;
+
+    /// Returns the odds alerts registered on a pool.
+    fn get_odds_alerts(env: Env, pool_id: u64) -> Vec<OddsAlert>;
+
+    /// Returns the legs of a user's multi-leg bet on a pool, if any.
+    fn get_bet_legs(env: Env, user: Address, pool_id: u64) -> Option<Vec<BetLeg>>;
+
+    /// Returns a market template, if registered.
+    fn get_template(env: Env, template_id: u32) -> Option<PoolTemplate>;
+
+    /// Returns the creator fees accrued in `token` and not yet claimed.
+    fn get_creator_fees_accrued(env: Env, creator: Address, token: Address) -> i128;
+
+    /// Returns the public-goods share of a pool's creator fee, if any.
+    fn get_pool_public_goods(env: Env, pool_id: u64) -> Option<PublicGoodsShare>;
+
+    /// Returns the public-goods fees accrued in `token` and not yet withdrawn.
+    fn get_public_goods_accrued(env: Env, recipient: Address, token: Address) -> i128;
+
+    /// Returns the claim tip `user` grants to relayers, in bps.
+    fn get_claim_tip(env: Env, user: Address) -> u32;
+
+    /// Returns the number of claims paid out of a pool so far.
+    fn get_claim_sequence(env: Env, pool_id: u64) -> u32;
+
+    /// Returns the parent condition of a conditional pool, if any.
+    fn get_parent_condition(env: Env, pool_id: u64) -> Option<ParentCondition>;
+
+    /// Returns the maker/taker fees of a pool's trading mode, if configured.
+    fn get_pool_amm_fees(env: Env, pool_id: u64) -> Option<AmmFeeConfig>;
+
+    /// Returns true if `market_maker` is a designated market maker of a pool.
+    fn is_market_maker(env: Env, pool_id: u64, market_maker: Address) -> bool;
+
+    /// Returns the live quotes of a market maker on a pool.
+    fn get_quotes(env: Env, pool_id: u64, market_maker: Address) -> Vec<Quote>;
+
+    /// Returns the trading fee `trader` pays in a pool's trading mode: the maker fee for designated
+    /// market makers, the taker fee otherwise (0 when the pool has no trading fees).
+    fn get_trading_fee_bps(env: Env, pool_id: u64, trader: Address) -> u32;
+
+    /// Returns how a pool prices positions.
+    fn get_pool_mechanism(env: Env, pool_id: u64) -> Mechanism;
+
+    /// Returns the market state of an LMSR pool, if it is one.
+    fn get_lmsr_state(env: Env, pool_id: u64) -> Option<LmsrState>;
+
+    /// Returns a trader's unredeemed holdings in an LMSR pool.
+    fn get_lmsr_position(env: Env, pool_id: u64, trader: Address) -> Option<LmsrPosition>;
+
+    /// Returns how a pool's escrow splits between house liquidity and bettor stakes.
+    fn get_pool_liquidity(env: Env, pool_id: u64) -> Result<PoolLiquidity, PredifiError>;
+
+    /// Returns true if the address is on the global blocklist.
+    fn is_blocked(env: Env, address: Address) -> bool;
+
+    /// Returns the lifecycle callback registered for a pool, if any.
+    fn get_pool_callback(env: Env, pool_id: u64) -> Option<Address>;
+
+    /// Returns the hook awaiting `dispatch_pool_callback` for a pool, if any.
+    fn get_pending_pool_callback(env: Env, pool_id: u64) -> Option<PoolHook>;
+
+    /// Returns the curation queue settings.
+    fn get_curation_config(env: Env) -> CurationConfig;
+
+    /// Returns true while a pool awaits curation review.
+    fn is_pool_draft(env: Env, pool_id: u64) -> bool;
+
+    /// Returns the protocol fees accrued in `token` and not yet withdrawn.
+    fn get_fees_accrued(env: Env, token: Address) -> i128;
+
+    /// Returns true while a pool runs in hot mode.
+    fn is_hot_pool(env: Env, pool_id: u64) -> bool;
+
+    /// Returns the keeper reward and auto-cancel settings.
+    fn get_keeper_config(env: Env) -> KeeperConfig;
+
+    /// Returns the outcome authorized for a pool and awaiting `finalize_pool`, if any.
+    fn get_authorized_outcome(env: Env, pool_id: u64) -> Option<AuthorizedOutcome>;
+
+    /// Returns the keeper who finalized a pool and its reward rate, if any.
+    fn get_pool_keeper(env: Env, pool_id: u64) -> Option<PoolKeeper>;
+
+    /// Returns the pool a user agreed to have their `from_pool` position moved to, if any.
+    fn get_migration_consent(env: Env, user: Address, from_pool: u64) -> Option<u64>;
+
+    /// Returns the price feed a pool resolves from, if it is a price market.
+    fn get_price_market(env: Env, pool_id: u64) -> Option<PriceMarket>;
+
+    /// Returns true if betting on a pool is restricted to its allowlist.
+    fn is_pool_private(env: Env, pool_id: u64) -> bool;
+
+    /// Returns true if `user` may bet on the pool: always for public pools, only if allowlisted for
+    /// private ones.
+    fn is_allowed_bettor(env: Env, pool_id: u64, user: Address) -> bool;
+
+    /// Returns the owner of a referral code, if registered.
+    fn get_ref_code_owner(env: Env, code: Symbol) -> Option<Address>;
+
+    /// Returns the referrer recorded for a user, if any.
+    fn get_referrer(env: Env, user: Address) -> Option<Address>;
+
+    /// Returns the referral rewards accrued in `token` and not yet claimed.
+    fn get_referral_rewards(env: Env, referrer: Address, token: Address) -> i128;
+
+    /// Returns the decimals and minimum stake registered for a token.
+    fn get_token_params(env: Env, token: Address) -> TokenParams;
+}
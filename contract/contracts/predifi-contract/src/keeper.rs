@@ -0,0 +1,349 @@
+//! # Keeper Finalization
+//!
+//! An operator authorizes a pool's outcome; anyone may then apply it with
+//! `finalize_pool` and earn the configured keeper reward out of every payout
+//! of the pool as it is claimed.
+
+use super::*;
+
+/// Storage keys of keeper finalization.
+#[contracttype]
+#[derive(Clone)]
+pub enum KeeperKey {
+    /// Keeper reward and auto-cancel window (`KeeperConfig`).
+    KeeperConfig,
+    /// Outcome an operator authorized, awaiting `finalize_pool`.
+    AuthorizedOutcome(u64),
+    /// Keeper who finalized a pool and the reward rate locked in for it.
+    PoolKeeper(u64),
+}
+
+/// Permissionless finalization settings: the share of each payout rewarded
+/// to the keeper who calls `finalize_pool`, and how long after `end_time` an
+/// unresolved pool may be auto-canceled (0 = never).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct KeeperConfig {
+    pub reward_bps: u32,
+    pub auto_cancel_after: u64,
+}
+
+/// Outcome an operator authorized for a pool, applied by `finalize_pool`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizedOutcome {
+    pub operator: Address,
+    pub outcome: u32,
+    pub authorized_at: u64,
+}
+
+/// Keeper who finalized a pool. Receives `reward_bps` of every position's
+/// gross payout (fee-weighted like the protocol fee) as it is claimed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolKeeper {
+    pub keeper: Address,
+    pub reward_bps: u32,
+}
+
+#[contractevent(topics = ["keeper_config_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperConfigUpdateEvent {
+    pub admin: Address,
+    pub reward_bps: u32,
+    pub auto_cancel_after: u64,
+}
+
+#[contractevent(topics = ["outcome_authorized"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeAuthorizedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub outcome: u32,
+}
+
+#[contractevent(topics = ["pool_finalized"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolFinalizedEvent {
+    pub pool_id: u64,
+    pub keeper: Address,
+    /// Resolved, or Canceled when the auto-cancel window was hit.
+    pub state: MarketState,
+    pub reward_bps: u32,
+}
+
+#[contractevent(topics = ["keeper_reward_paid"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperRewardPaidEvent {
+    pub pool_id: u64,
+    pub keeper: Address,
+    pub amount: i128,
+}
+
+impl PredifiContract {
+    /// Keeper reward owed out of a settled position's gross payout: weighted
+    /// like the protocol fee for resolved pools, a flat share of refunds for
+    /// canceled ones. 0 if no keeper finalized the pool.
+    pub(crate) fn keeper_fee(env: &Env, pool_id: u64, pool: &Pool, gross: i128) -> i128 {
+        let Some(keeper) = Self::get_pool_keeper(env.clone(), pool_id) else {
+            return 0;
+        };
+        if pool.state == MarketState::Resolved {
+            Self::calculate_protocol_fee(
+                gross,
+                pool.total_stake,
+                Self::bettor_stake(env, pool_id, pool),
+                keeper.reward_bps,
+            )
+        } else {
+            gross * keeper.reward_bps as i128 / 10_000
+        }
+    }
+
+    /// Send a claim's keeper reward to the keeper who finalized the pool.
+    /// Called only once every storage effect of the claim is written.
+    pub(crate) fn transfer_keeper_reward(env: &Env, pool_id: u64, token: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let Some(keeper) = Self::get_pool_keeper(env.clone(), pool_id) else {
+            return;
+        };
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(&env.current_contract_address(), &keeper.keeper, &amount);
+        Self::record_ledger_entry(
+            env,
+            Some(pool_id),
+            &keeper.keeper,
+            token,
+            LedgerDirection::Out,
+            amount,
+            "keeper_reward",
+        );
+        KeeperRewardPaidEvent {
+            pool_id,
+            keeper: keeper.keeper,
+            amount,
+        }
+        .publish(env);
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Configure permissionless finalization: keepers calling `finalize_pool`
+    /// earn `reward_bps` (at most `MAX_RESOLVER_REWARD_BPS`) of each payout of
+    /// the pool they finalize, and pools still unresolved `auto_cancel_after`
+    /// seconds past `end_time` may be canceled by any keeper (0 = never).
+    /// Caller must have Admin role (0).
+    pub fn set_keeper_config(
+        env: Env,
+        admin: Address,
+        reward_bps: u32,
+        auto_cancel_after: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_keeper_config"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if reward_bps > MAX_RESOLVER_REWARD_BPS {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let old = Self::get_keeper_config(env.clone());
+        Self::record_config_change(
+            &env,
+            &admin,
+            "resolver_reward_bps",
+            ConfigValue::U64(old.reward_bps as u64),
+            ConfigValue::U64(reward_bps as u64),
+        );
+        Self::record_config_change(
+            &env,
+            &admin,
+            "auto_cancel_after",
+            ConfigValue::U64(old.auto_cancel_after),
+            ConfigValue::U64(auto_cancel_after),
+        );
+        env.storage().instance().set(
+            &KeeperKey::KeeperConfig,
+            &KeeperConfig {
+                reward_bps,
+                auto_cancel_after,
+            },
+        );
+        Self::extend_instance(&env);
+
+        KeeperConfigUpdateEvent {
+            admin,
+            reward_bps,
+            auto_cancel_after,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the keeper reward and auto-cancel settings.
+    pub fn get_keeper_config(env: Env) -> KeeperConfig {
+        env.storage()
+            .instance()
+            .get(&KeeperKey::KeeperConfig)
+            .unwrap_or_default()
+    }
+
+    /// Authorize the outcome of an ended pool without settling it; any
+    /// keeper then applies it with `finalize_pool`. Calling again replaces a
+    /// pending authorization. Caller must have Operator role (1).
+    pub fn authorize_outcome(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        if let Err(e) = Self::require_role(&env, &operator, 1) {
+            // 🔴 HIGH ALERT: unauthorized attempt to resolve a pool.
+            UnauthorizedResolveAttemptEvent {
+                caller: operator,
+                pool_id,
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = KeeperKey::AuthorizedOutcome(pool_id);
+        env.storage().persistent().remove(&key);
+        Self::check_resolvable(&env, pool_id, outcome, false)?;
+        env.storage().persistent().set(
+            &key,
+            &AuthorizedOutcome {
+                operator: operator.clone(),
+                outcome,
+                authorized_at: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        OutcomeAuthorizedEvent {
+            pool_id,
+            operator,
+            outcome,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the outcome authorized for a pool and awaiting
+    /// `finalize_pool`, if any.
+    pub fn get_authorized_outcome(env: Env, pool_id: u64) -> Option<AuthorizedOutcome> {
+        env.storage()
+            .persistent()
+            .get(&KeeperKey::AuthorizedOutcome(pool_id))
+    }
+
+    /// Settle a pool on the operator's behalf: apply its authorized outcome
+    /// or, with none pending, cancel it once the auto-cancel window has
+    /// passed. `keeper` earns the configured reward out of the pool's
+    /// payouts as they are claimed. Returns the pool's new state.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if nothing is authorized and auto-cancel is off
+    ///   or another resolution flow is pending.
+    /// - `ResolutionDelayNotMet` before the auto-cancel window ends.
+    pub fn finalize_pool(
+        env: Env,
+        keeper: Address,
+        pool_id: u64,
+    ) -> Result<MarketState, PredifiError> {
+        Self::require_not_paused(&env);
+        keeper.require_auth();
+        let config = Self::get_keeper_config(env.clone());
+        let auth_key = KeeperKey::AuthorizedOutcome(pool_id);
+        let authorized: Option<AuthorizedOutcome> = env.storage().persistent().get(&auth_key);
+
+        let pool = match &authorized {
+            Some(authorized) => {
+                env.storage().persistent().remove(&auth_key);
+                Self::check_resolvable(&env, pool_id, authorized.outcome, false)?
+            }
+            None => {
+                if config.auto_cancel_after == 0 || Self::is_resolution_locked(&env, pool_id) {
+                    return Err(PredifiError::InvalidPoolState);
+                }
+                let pool = Self::check_cancelable(&env, pool_id)?;
+                let cancel_at = pool.end_time.saturating_add(config.auto_cancel_after);
+                let now = env.ledger().timestamp();
+                if now < cancel_at {
+                    return Err(Self::error_detail(
+                        &env,
+                        PredifiError::ResolutionDelayNotMet,
+                        Some(pool_id),
+                        cancel_at as i128,
+                        now as i128,
+                    ));
+                }
+                pool
+            }
+        };
+
+        if config.reward_bps > 0 {
+            let keeper_key = KeeperKey::PoolKeeper(pool_id);
+            env.storage().persistent().set(
+                &keeper_key,
+                &PoolKeeper {
+                    keeper: keeper.clone(),
+                    reward_bps: config.reward_bps,
+                },
+            );
+            Self::extend_persistent(&env, &keeper_key);
+        }
+
+        let state = match authorized {
+            Some(authorized) => {
+                Self::finalize_resolution(
+                    &env,
+                    pool_id,
+                    pool,
+                    authorized.outcome,
+                    authorized.operator,
+                    String::from_str(&env, "keeper"),
+                );
+                MarketState::Resolved
+            }
+            None => {
+                Self::finalize_cancellation(
+                    &env,
+                    pool_id,
+                    pool,
+                    keeper.clone(),
+                    String::from_str(&env, "auto_cancel"),
+                );
+                MarketState::Canceled
+            }
+        };
+
+        PoolFinalizedEvent {
+            pool_id,
+            keeper,
+            state,
+            reward_bps: config.reward_bps,
+        }
+        .publish(&env);
+        Ok(state)
+    }
+
+    /// Returns the keeper who finalized a pool and its reward rate, if any.
+    pub fn get_pool_keeper(env: Env, pool_id: u64) -> Option<PoolKeeper> {
+        env.storage()
+            .persistent()
+            .get(&KeeperKey::PoolKeeper(pool_id))
+    }
+}
@@ -0,0 +1,191 @@
+//! # Frozen Storage Layouts
+//!
+//! `Pool`, `Prediction` and `Config` are persisted as `#[contracttype]` maps
+//! keyed by field name, so adding, removing or renaming a field changes how
+//! existing entries decode after an upgrade. Their current layouts are pinned
+//! here:
+//!
+//! - each layout has a versioned alias (`PoolV1`, `PredictionV1`, `ConfigV3`)
+//!   and a `*_LAYOUT_VERSION` marker;
+//! - `assert_*_layout` destructures every field with its type, so any change
+//!   to a frozen struct fails to compile until it is handled deliberately;
+//! - superseded layouts are kept as standalone types (`ConfigV1`, `ConfigV2`)
+//!   and decoded through a migration shim, so storage written by an older
+//!   version is always readable by the current one.
+//!
+//! ## Changing a frozen type
+//!
+//! 1. Copy the current struct into a new `*V{n}` type in this module.
+//! 2. Add a `From<*V{n}>` conversion into the new layout.
+//! 3. Extend the matching `read_*` shim to detect and upgrade the old layout.
+//! 4. Bump the layout version and update the assertion.
+//! 5. If existing entries should be rewritten eagerly, bump `STORAGE_VERSION`
+//!    and add the matching step to `migrate_state`.
+
+use crate::{Config, MarketState, Pool, Prediction};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, TryFromVal, Val};
+
+/// Layout version of [`Pool`] entries.
+pub const POOL_LAYOUT_VERSION: u32 = 1;
+/// Layout version of [`Prediction`] entries.
+pub const PREDICTION_LAYOUT_VERSION: u32 = 1;
+/// Layout version of the [`Config`] entry.
+pub const CONFIG_LAYOUT_VERSION: u32 = 3;
+/// Revision of the storage schema as a whole. Bump it together with a new
+/// step in `migrate_state` whenever stored data must be rewritten.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// Current pool layout.
+pub type PoolV1 = Pool;
+/// Current prediction layout.
+pub type PredictionV1 = Prediction;
+/// Current config layout (adds the settlement hold settings).
+pub type ConfigV3 = Config;
+
+/// Config as written before `max_active_pools` was introduced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigV1 {
+    pub fee_bps: u32,
+    pub treasury: Address,
+    pub access_control: Address,
+    pub resolution_delay: u64,
+}
+
+/// Config as written before the settlement hold settings were introduced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigV2 {
+    pub fee_bps: u32,
+    pub treasury: Address,
+    pub access_control: Address,
+    pub resolution_delay: u64,
+    pub max_active_pools: u32,
+}
+
+impl From<ConfigV1> for ConfigV2 {
+    fn from(v1: ConfigV1) -> Self {
+        ConfigV2 {
+            fee_bps: v1.fee_bps,
+            treasury: v1.treasury,
+            access_control: v1.access_control,
+            resolution_delay: v1.resolution_delay,
+            // v1 had no cap on active pools.
+            max_active_pools: 0,
+        }
+    }
+}
+
+impl From<ConfigV2> for ConfigV3 {
+    fn from(v2: ConfigV2) -> Self {
+        Config {
+            fee_bps: v2.fee_bps,
+            treasury: v2.treasury,
+            access_control: v2.access_control,
+            resolution_delay: v2.resolution_delay,
+            max_active_pools: v2.max_active_pools,
+            // v2 had no settlement hold.
+            settlement_hold_threshold: 0,
+            settlement_hold_delay: 0,
+        }
+    }
+}
+
+/// Decode a stored config of any known layout into the current one.
+///
+/// Layouts are told apart by their field names rather than by attempting a
+/// decode, since a failed struct conversion traps inside the host.
+pub(crate) fn read_config(env: &Env, raw: Val) -> Config {
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).expect("Config corrupted");
+    if fields.contains_key(Symbol::new(env, "settlement_hold_threshold")) {
+        ConfigV3::try_from_val(env, &raw).expect("Config corrupted")
+    } else if fields.contains_key(Symbol::new(env, "max_active_pools")) {
+        ConfigV2::try_from_val(env, &raw)
+            .expect("Config corrupted")
+            .into()
+    } else {
+        ConfigV2::from(ConfigV1::try_from_val(env, &raw).expect("Config corrupted")).into()
+    }
+}
+
+#[allow(dead_code, clippy::type_complexity)]
+fn assert_pool_layout(pool: PoolV1) {
+    let PoolV1 {
+        end_time,
+        resolved,
+        canceled,
+        state,
+        outcome,
+        token,
+        total_stake,
+        category,
+        description,
+        metadata_url,
+        options_count,
+        min_stake,
+        max_stake,
+        initial_liquidity,
+        creator,
+    } = pool;
+    let _: (
+        u64,
+        bool,
+        bool,
+        MarketState,
+        u32,
+        Address,
+        i128,
+        Symbol,
+        String,
+        String,
+        u32,
+        i128,
+        i128,
+        i128,
+        Address,
+    ) = (
+        end_time,
+        resolved,
+        canceled,
+        state,
+        outcome,
+        token,
+        total_stake,
+        category,
+        description,
+        metadata_url,
+        options_count,
+        min_stake,
+        max_stake,
+        initial_liquidity,
+        creator,
+    );
+}
+
+#[allow(dead_code)]
+fn assert_prediction_layout(prediction: PredictionV1) {
+    let PredictionV1 { amount, outcome } = prediction;
+    let _: (i128, u32) = (amount, outcome);
+}
+
+#[allow(dead_code)]
+fn assert_config_layout(config: ConfigV3) {
+    let ConfigV3 {
+        fee_bps,
+        treasury,
+        access_control,
+        resolution_delay,
+        max_active_pools,
+        settlement_hold_threshold,
+        settlement_hold_delay,
+    } = config;
+    let _: (u32, Address, Address, u64, u32, i128, u64) = (
+        fee_bps,
+        treasury,
+        access_control,
+        resolution_delay,
+        max_active_pools,
+        settlement_hold_threshold,
+        settlement_hold_delay,
+    );
+}
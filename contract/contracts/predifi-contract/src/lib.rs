@@ -1,22 +1,83 @@
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 
+mod active_pools;
+mod blocklist;
+mod callback;
+mod curation;
+mod display;
+mod fees;
+mod hot_pool;
+pub mod interface;
+mod keeper;
+mod layout;
+mod migration;
+mod payouts;
 mod price_feed_simple;
+mod price_market;
+mod private_pool;
+#[cfg(all(test, feature = "proptest"))]
+mod proptest_invariants;
+#[cfg(all(test, feature = "reentrancy-probe"))]
+mod reentrancy_probe;
+mod referral;
+mod reflector;
 mod safe_math;
 #[cfg(test)]
 mod safe_math_examples;
 #[cfg(test)]
 mod stress_test;
-#[cfg(test)]
-mod test_utils;
+#[cfg(any(test, feature = "testutils"))]
+pub mod test_utils;
+mod tokens;
 
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, token,
-    Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+pub use active_pools::{ActivePoolCountSeededEvent, ActivePoolsKey};
+pub use blocklist::{AddressBlockUpdateEvent, BlockedAddressHitEvent, BlocklistKey};
+pub use callback::{
+    CallbackKey, PoolCallbackClearedEvent, PoolCallbackFailedEvent, PoolCallbackSetEvent, PoolHook,
+    PoolLifecycleCallback,
+};
+pub use curation::{
+    CurationConfig, CurationConfigUpdateEvent, CurationKey, PoolApprovedEvent, PoolRejectedEvent,
+    PoolSubmittedForReviewEvent,
+};
+pub use display::{DisplayKeySetEvent, LabelKey};
+pub use fees::{FeeKey, FeesWithdrawnEvent, ProtocolFeeAccruedEvent};
+pub use hot_pool::{HotPoolKey, HotPoolModeEvent};
+pub use keeper::{
+    AuthorizedOutcome, KeeperConfig, KeeperConfigUpdateEvent, KeeperKey, KeeperRewardPaidEvent,
+    OutcomeAuthorizedEvent, PoolFinalizedEvent, PoolKeeper,
+};
+pub use layout::{
+    ConfigV1, ConfigV2, ConfigV3, PoolV1, PredictionV1, CONFIG_LAYOUT_VERSION, POOL_LAYOUT_VERSION,
+    PREDICTION_LAYOUT_VERSION, STORAGE_VERSION,
+};
+pub use migration::{
+    MigrationConsentEvent, MigrationKey, PoolMigratedEvent, PositionMigratedEvent,
+};
+pub use payouts::{
+    HeldPayout, HeldPayoutForfeitedEvent, HeldPayoutReleasedEvent, HoldReason, PayoutDeferredEvent,
+    PayoutHeldEvent, PayoutKey, PendingPayoutReleasedEvent,
+};
 pub use price_feed_simple::PriceFeedAdapter;
+pub use price_market::{PriceMarketKey, PriceMarketSetEvent};
+pub use private_pool::{AllowedBettorUpdateEvent, PoolPrivacySetEvent, PrivatePoolKey};
+pub use referral::{
+    RefCodeRegisteredEvent, RefCodeRevokedEvent, RefCodeTransferredEvent, ReferralKey,
+    ReferralRecordedEvent, ReferralRewardAccruedEvent, ReferralRewardsClaimedEvent,
+    ReferralShareUpdateEvent,
+};
+pub use reflector::{PriceData, PriceMarket, ReflectorAsset};
 pub use safe_math::{RoundingMode, SafeMath};
+pub use tokens::{
+    TokenKey, TokenParams, TokenParamsSetEvent, TokenWhitelistAddedEvent,
+    TokenWhitelistRemovedEvent,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // MARKET CATEGORY CONSTANTS
@@ -49,18 +110,39 @@ pub const CATEGORY_TECH: Symbol = symbol_short!("Tech");
 /// Miscellaneous predictions that don't fit other categories
 pub const CATEGORY_OTHER: Symbol = symbol_short!("Other");
 
+// ═══════════════════════════════════════════════════════════════════════════
+// PAUSE SCOPES
+// ═══════════════════════════════════════════════════════════════════════════
+// Bits of the mask passed to `set_pause_scopes`. The global `pause` switch
+// still freezes everything.
+
+/// New predictions, stake increases and share trades
+pub const PAUSE_BETTING: u32 = 1 << 0;
+
+/// Winnings, refund, liquidity and fee claims
+pub const PAUSE_CLAIMS: u32 = 1 << 1;
+
+/// New pool creation
+pub const PAUSE_CREATION: u32 = 1 << 2;
+
+const PAUSE_ALL_SCOPES: u32 = PAUSE_BETTING | PAUSE_CLAIMS | PAUSE_CREATION;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // PROTOCOL INVARIANTS (for formal verification)
 // ═══════════════════════════════════════════════════════════════════════════
 //
-// INV-1: Pool.total_stake = Σ(OutcomeStake(pool_id, outcome)) for all outcomes
-// INV-2: Pool.state transitions: Active → {Resolved | Canceled}, never reversed
+// INV-1: Pool.total_stake = Pool.initial_liquidity + Σ(OutcomeStake(pool_id, outcome))
+//        (house liquidity and bettor stakes are accounted separately)
+// INV-2: Pool.state transitions: Active → {Resolved | Canceled}, never reversed;
+//        Resolved → Disputed → Resolved only while claims are not open
 // INV-3: HasClaimed(user, pool) is write-once (prevents double-claim)
 // INV-4: Winnings ≤ Pool.total_stake (no value creation)
 // INV-5: For resolved pools: Σ(claimed_winnings) ≤ Pool.total_stake
 // INV-6: Config.fee_bps ≤ 10_000 (max 100%)
 // INV-7: Prediction.amount > 0 (no zero-stakes)
 // INV-8: Pool.end_time > creation_time (pools must have future end)
+// INV-9: PoolEscrow.locked + PoolEscrow.claimable = Pool.total_stake - PoolDisbursed
+//        (a pool's escrow is segregated; no payout draws on another pool)
 //
 // ═══════════════════════════════════════════════════════════════════════════
 
@@ -72,28 +154,134 @@ const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 const MIN_POOL_DURATION: u64 = 3600;
 /// Maximum number of options allowed in a pool
 const MAX_OPTIONS_COUNT: u32 = 100;
+/// Maximum length of an operator resolution/cancellation note in bytes
+const MAX_NOTE_LEN: u32 = 256;
+/// Maximum length of an outcome label, in bytes.
+const MAX_OUTCOME_LABEL_LEN: u32 = 64;
+/// Maximum length of a display key in the symbol registry, in bytes.
+const MAX_DISPLAY_KEY_LEN: u32 = 64;
+/// Maximum bettor slots scanned per `get_top_positions` page
+const MAX_TOP_POSITIONS: u32 = 50;
+/// Maximum items processed per batch call, keeping each call within the
+/// per-transaction read/write entry budget.
+const MAX_BATCH_SIZE: u32 = 25;
+/// Maximum tokens updated per `set_token_whitelist_batch` call. Each entry
+/// writes two entries and reads the token contract for its decimals.
+const MAX_WHITELIST_BATCH_SIZE: u32 = 15;
+/// Time between announcing a sweep of unclaimed funds and executing it (7 days),
+/// during which affected users can still claim.
+const SWEEP_GRACE_PERIOD: u64 = 7 * 24 * 60 * 60;
+/// Number of most recent config changes retained on-chain.
+const CONFIG_HISTORY_CAPACITY: u32 = 64;
+/// Maximum number of tiers in the pool-size fee schedule.
+const MAX_FEE_TIERS: u32 = 10;
+/// Maximum number of pools returned by one `get_pools` page.
+const MAX_POOLS_PAGE_SIZE: u32 = 50;
+/// Maximum number of oracle submissions a pool may require to resolve.
+const MAX_ORACLE_QUORUM: u32 = 10;
+/// Number of equal slices of the betting window in pool analytics.
+const ANALYTICS_TIME_BUCKETS: u32 = 10;
+/// Maximum number of odds alerts registered on one pool.
+const MAX_ODDS_ALERTS: u32 = 20;
+/// Outcome count from which bets emit one `OutcomeStakesUpdatedEvent` instead
+/// of a per-outcome `OutcomeStakeDeltaEvent`.
+const BATCH_STAKE_EVENT_MIN_OPTIONS: u32 = 16;
+/// Maximum number of outcomes covered by one multi-leg bet.
+const MAX_BET_LEGS: u32 = 20;
+/// Maximum tip, in bps of the winnings, a bettor may grant to claim relayers.
+const MAX_CLAIM_TIP_BPS: u32 = 1_000;
+/// Maximum number of bettors co-signing one cancellation request.
+const MAX_CANCEL_SUPPORTERS: u32 = 50;
+/// Largest share of a pool's escrow that may be deposited into a yield adapter.
+const MAX_YIELD_SHARE_BPS: u32 = 5_000;
+/// Maximum number of conditional pools gated on one parent pool.
+const MAX_CHILD_POOLS: u32 = 20;
+/// Maximum number of bettors whose records one `close_pool` call deletes.
+const MAX_CLOSE_BATCH: u32 = 50;
+/// Maximum number of status update anchors posted to one pool.
+const MAX_POOL_NOTES: u32 = 32;
+/// Largest share of each payout a pool's keeper may be rewarded with.
+const MAX_RESOLVER_REWARD_BPS: u32 = 100;
+/// Shortest admin inactivity period the dead-man switch may be armed with.
+const MIN_DEAD_MAN_PERIOD: u64 = 30 * 24 * 60 * 60;
+/// How long claim deadlines are pushed out once the dead-man switch fires.
+const DEAD_MAN_CLAIM_EXTENSION: u64 = 365 * 24 * 60 * 60;
 /// Maximum initial liquidity that can be provided (100M tokens at 7 decimals)
 const MAX_INITIAL_LIQUIDITY: i128 = 100_000_000_000_000;
 /// Stake amount (in base token units) above which a `HighValuePredictionEvent`
 /// is emitted so off-chain monitors can apply extra scrutiny.
-/// At 7 decimal places (e.g. USDC on Stellar) this equals 100 USDC.
+/// At 7 decimal places (e.g. USDC on Stellar) this equals 100 USDC; it is
+/// rescaled to the decimals registered for the pool's token.
 const HIGH_VALUE_THRESHOLD: i128 = 1_000_000;
+/// Decimals assumed for tokens whitelisted without `TokenParams`.
+const DEFAULT_TOKEN_DECIMALS: u32 = 7;
+/// Most decimals a whitelisted token may declare.
+const MAX_TOKEN_DECIMALS: u32 = 18;
+/// Domain separator for settlement attestation digests consumed by bridges.
+const ATTESTATION_DOMAIN: &[u8] = b"predifi.settlement.v1";
+/// Semver of this build, taken from the crate manifest.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PredifiError {
     Unauthorized = 10,
+    /// No pool exists with the given id.
+    PoolNotFound = 20,
     PoolNotResolved = 22,
     InvalidPoolState = 24,
     /// The provided category symbol is not in the allowed list
     InvalidCategory = 25,
+    /// A referenced pool is missing, invalid, or not yet settled.
+    InvalidPoolReference = 26,
+    /// The protocol-wide cap on simultaneously Active pools has been reached.
+    ActivePoolLimitReached = 27,
+    /// Outcome index is not within the pool's options.
+    InvalidOutcome = 28,
+    /// Nobody staked on the outcome; use `force_resolve_pool` to confirm.
+    ZeroStakeOutcome = 29,
     AlreadyClaimed = 60,
+    /// No payout is being held for this (user, pool).
+    PayoutNotHeld = 61,
+    /// Unclaimed funds of this pool have been swept to the treasury.
+    PoolSwept = 62,
+    /// No sweep has been scheduled for this pool.
+    SweepNotScheduled = 63,
+    /// A sweep is already scheduled (or executed) for this pool.
+    SweepAlreadyScheduled = 64,
+    /// A pushed winner entry is not a winning position or is out of order.
+    InvalidWinnerEntry = 90,
+    /// Pushed winner entries do not add up to the winning outcome's stake.
+    WinnersSummaryMismatch = 121,
     PoolCanceled = 70,
     ResolutionDelayNotMet = 81,
+    /// The announced sweep grace period has not elapsed yet.
+    SweepGracePeriodActive = 82,
+    /// Claims on this pool are not open yet (dispute window or settlement hold).
+    ClaimsNotOpen = 83,
+    /// The resolution can no longer be disputed: claims are open.
+    DisputeWindowClosed = 84,
+    /// The pool has no claim deadline or it has not passed yet.
+    ClaimDeadlineNotReached = 85,
     /// Token is not on the allowed betting whitelist.
     TokenNotWhitelisted = 91,
+    /// Fee tiers must be sorted by threshold with non-increasing fees ≤ 10_000 bps.
+    InvalidFeeSchedule = 93,
+    /// The requested creator fee exceeds the admin-set maximum.
+    CreatorFeeTooHigh = 94,
+    /// The user has no prediction on this pool.
+    PredictionNotFound = 40,
+    /// A bounded per-pool list (e.g. odds alerts) is full.
+    CapacityReached = 46,
+    /// The revealed outcome and salt do not match the resolution commitment.
+    CommitmentMismatch = 86,
+    /// The user's existing position on the pool does not allow the operation
+    /// (e.g. topping up a multi-leg bet).
+    PositionConflict = 41,
     /// Invalid amount provided (e.g., zero or negative).
     InvalidAmount = 42,
+    /// The pool no longer accepts stake changes.
+    PredictionTooLate = 43,
     /// Insufficient balance for the operation.
     InsufficientBalance = 44,
     /// Oracle not initialized.
@@ -104,6 +292,42 @@ pub enum PredifiError {
     PriceDataInvalid = 102,
     /// Price condition not set for pool.
     PriceConditionNotSet = 103,
+    /// The bet would push the pool's total stake past its cap.
+    PoolStakeCapReached = 45,
+    /// Oracle quorum exceeds `MAX_ORACLE_QUORUM`.
+    InvalidOracleQuorum = 104,
+    /// This oracle already submitted a result for the pool.
+    OracleAlreadySubmitted = 105,
+    /// A trade's price moved past the caller's `max_cost` / `min_proceeds`.
+    SlippageExceeded = 47,
+    /// No market template exists with the given id.
+    TemplateNotFound = 48,
+    /// The pool would run longer than the admin-set maximum duration.
+    PoolDurationTooLong = 49,
+    /// The dead-man switch has fired; new pools cannot be created.
+    DeadManSwitchTriggered = 50,
+    /// The admin has acted within the dead-man switch inactivity period.
+    AdminStillActive = 51,
+    /// The access-control contract could not be queried (missing contract,
+    /// trap or unexpected return value), as opposed to a missing role.
+    AccessControlUnavailable = 52,
+    /// The outcome assertion can still be challenged; it settles once its
+    /// liveness window has passed.
+    AssertionLivenessActive = 53,
+    /// The outcome assertion's liveness window has passed; it can no longer
+    /// be challenged.
+    AssertionLivenessExpired = 54,
+    /// Optimistic resolution is not configured on this deployment.
+    OptimisticResolutionDisabled = 55,
+    /// The token refused the payout transfer (e.g. frozen or deauthorized
+    /// trustline); the payout stays pending.
+    PayoutTransferFailed = 56,
+    /// The referral code is already registered to another address.
+    ReferralCodeTaken = 57,
+    /// The active pool count has already been seeded.
+    ActivePoolCountSeeded = 58,
+    /// An operator note or reason exceeds `MAX_NOTE_LEN` bytes.
+    NoteTooLong = 59,
 }
 
 #[contracttype]
@@ -112,8 +336,12 @@ pub enum MarketState {
     Active = 0,
     Resolved = 1,
     Canceled = 2,
+    /// Transitional state while an operator corrects a resolution during the
+    /// dispute window (see `dispute_resolution`).
+    Disputed = 3,
 }
 
+/// Frozen storage layout: see the `layout` module before changing any field.
 #[contracttype]
 #[derive(Clone)]
 pub struct Pool {
@@ -137,12 +365,33 @@ pub struct Pool {
     /// Maximum stake amount per prediction (0 = no limit).
     pub max_stake: i128,
     /// Initial liquidity provided by the pool creator (house money).
-    /// This is part of total_stake but excluded from fee calculations.
+    /// This is part of total_stake but excluded from fee calculations. It is
+    /// modelled as an equal position on every outcome; the creator recovers
+    /// the winning share via `claim_initial_liquidity`.
     pub initial_liquidity: i128,
     /// Address of the pool creator.
     pub creator: Address,
 }
 
+/// A pool without its variable-length `description` and `metadata_url`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSummary {
+    pub pool_id: u64,
+    pub end_time: u64,
+    pub state: MarketState,
+    /// Winning outcome; only meaningful once `state` is Resolved.
+    pub outcome: u32,
+    pub token: Address,
+    pub total_stake: i128,
+    pub category: Symbol,
+    pub options_count: u32,
+    pub min_stake: i128,
+    pub max_stake: i128,
+    pub initial_liquidity: i128,
+    pub creator: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct PoolStats {
@@ -153,1068 +402,9381 @@ pub struct PoolStats {
     pub current_odds: Vec<u64>, // Fixed-point with 4 decimals (e.g., 10000 = 1.00x)
 }
 
+/// Split of a pool's escrow between the creator's house liquidity and the
+/// bettors' stakes. Only the bettor-funded share of a payout is subject to
+/// protocol and creator fees.
 #[contracttype]
-#[derive(Clone)]
-pub struct Config {
-    pub fee_bps: u32,
-    pub treasury: Address,
-    pub access_control: Address,
-    pub resolution_delay: u64,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolLiquidity {
+    pub house_liquidity: i128,
+    /// Σ OutcomeStake: stakes placed by bettors (INV-1).
+    pub bettor_stake: i128,
+    /// House position per outcome (`house_liquidity / options_count`).
+    pub seed_per_outcome: i128,
 }
 
+/// Sealed resolution of a pool: `commitment` is
+/// `sha256(pool_id as 8 big-endian bytes || outcome as 4 big-endian bytes || salt)`.
 #[contracttype]
-#[derive(Clone)]
-pub struct UserPredictionDetail {
-    pub pool_id: u64,
-    pub amount: i128,
-    pub user_outcome: u32,
-    pub pool_end_time: u64,
-    pub pool_state: MarketState,
-    pub pool_outcome: u32,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionCommitment {
+    pub operator: Address,
+    pub commitment: BytesN<32>,
+    pub committed_at: u64,
 }
 
+/// Bettors' request to cancel a pool. Once the supporters' stake exceeds the
+/// configured share of bettor stake, the pool may be canceled by anyone at
+/// `executable_at` (0 = threshold not reached) unless an operator vetoes.
 #[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    Pool(u64),
-    Prediction(Address, u64),
-    PoolIdCounter,
-    HasClaimed(Address, u64),
-    OutcomeStake(u64, u32),
-    /// Optimized storage for markets with many outcomes (e.g., 32+ teams).
-    /// Stores all outcome stakes as a single Vec<i128> to reduce storage reads.
-    OutcomeStakes(u64),
-    UserPredictionCount(Address),
-    UserPredictionIndex(Address, u32),
-    Config,
-    Paused,
-    ReentrancyGuard,
-    CategoryPoolCount(Symbol),
-    CategoryPoolIndex(Symbol, u32),
-    /// Token whitelist: TokenWhitelist(token_address) -> true if allowed for betting.
-    TokenWhitelist(Address),
-    ParticipantsCount(u64),
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelRequest {
+    pub supporters: Vec<Address>,
+    pub executable_at: u64,
+    pub vetoed: bool,
 }
 
+/// A bettor's subscription to the odds of one outcome. Odds use the fixed
+/// point of `PoolStats::current_odds` (10_000 = 1.00x).
 #[contracttype]
-#[derive(Clone)]
-pub struct Prediction {
-    pub amount: i128,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OddsAlert {
+    pub subscriber: Address,
     pub outcome: u32,
+    pub threshold_bps: u64,
 }
 
-// ── Events ───────────────────────────────────────────────────────────────────
+/// Logical segregation of a pool's escrow. `locked` holds stakes and house
+/// liquidity while the pool is Active, `claimable` what a settled pool still
+/// owes, and `fees` the fees carved out of the pool so far.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PoolEscrow {
+    pub locked: i128,
+    pub claimable: i128,
+    pub fees: i128,
+}
 
-#[contractevent(topics = ["init"])]
+/// Yield adapter registered for a token and the share of a pool's escrow it
+/// may hold.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct InitEvent {
-    pub access_control: Address,
-    pub treasury: Address,
-    pub fee_bps: u32,
-    pub resolution_delay: u64,
+pub struct YieldAdapterConfig {
+    pub adapter: Address,
+    pub max_share_bps: u32,
 }
 
-#[contractevent(topics = ["pause"])]
+/// A market maker's standing offer on one outcome: `size` tokens at
+/// `odds_bps` (10_000 = 1.00x).
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PauseEvent {
-    pub admin: Address,
+pub struct Quote {
+    pub outcome: u32,
+    pub odds_bps: u64,
+    pub size: i128,
 }
 
-#[contractevent(topics = ["unpause"])]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct UnpauseEvent {
-    pub admin: Address,
+/// Direction of a token movement, seen from the contract's escrow.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LedgerDirection {
+    In,
+    Out,
 }
 
-#[contractevent(topics = ["fee_update"])]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct FeeUpdateEvent {
-    pub admin: Address,
-    pub fee_bps: u32,
+/// How a pool prices positions.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mechanism {
+    /// Bets share the pool pro rata among the winners.
+    Parimutuel,
+    /// Shares are priced by a logarithmic market scoring rule funded by the
+    /// initial liquidity; each winning share redeems for one token unit.
+    Lmsr,
 }
 
-#[contractevent(topics = ["treasury_update"])]
+/// Market state of an LMSR pool.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TreasuryUpdateEvent {
-    pub admin: Address,
-    pub treasury: Address,
+pub struct LmsrState {
+    /// Liquidity parameter: `initial_liquidity / ln(options_count)`, which
+    /// bounds the market maker's loss by the initial liquidity.
+    pub b: i128,
+    /// Outstanding shares per outcome.
+    pub shares: Vec<i128>,
+    /// Trade costs received minus proceeds paid out, fees excluded.
+    pub collected: i128,
+    /// Sum of the traders' positive cost bases (refunded on cancellation).
+    pub open_basis: i128,
 }
 
-#[contractevent(topics = ["resolution_delay_update"])]
+/// A trader's holdings in an LMSR pool.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ResolutionDelayUpdateEvent {
-    pub admin: Address,
-    pub delay: u64,
+pub struct LmsrPosition {
+    /// Shares held per outcome.
+    pub shares: Vec<i128>,
+    /// Costs paid minus proceeds received, fees excluded (may be negative).
+    pub basis: i128,
 }
 
-#[contractevent(topics = ["pool_ready"])]
+/// Stake a multi-leg bet placed on one outcome.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PoolReadyForResolutionEvent {
-    pub pool_id: u64,
-    pub timestamp: u64,
+pub struct BetLeg {
+    pub outcome: u32,
+    pub amount: i128,
 }
 
-#[contractevent(topics = ["pool_created"])]
+/// Running betting counters of a pool, maintained by `place_prediction` and
+/// `increase_stake` (withdrawals are not subtracted).
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PoolCreatedEvent {
-    pub pool_id: u64,
-    pub end_time: u64,
-    pub token: Address,
-    pub options_count: u32,
-    pub metadata_url: String,
-    pub initial_liquidity: i128,
-    pub category: Symbol,
+pub struct BetCurve {
+    /// Start of the betting window (pool creation).
+    pub opened_at: u64,
+    pub bet_count: u32,
+    pub stake_sum: i128,
+    /// Bets per power-of-ten stake bucket: entry k counts stakes in
+    /// [10^k, 10^(k+1)).
+    pub size_buckets: Vec<u32>,
+    /// Stake placed in each of `ANALYTICS_TIME_BUCKETS` equal slices of the
+    /// betting window (creation → lock time).
+    pub time_buckets: Vec<i128>,
 }
 
-#[contractevent(topics = ["initial_liquidity_provided"])]
+/// Betting analytics of a pool, checkpointed at resolution.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct InitialLiquidityProvidedEvent {
-    pub pool_id: u64,
-    pub creator: Address,
-    pub amount: i128,
+pub struct PoolAnalytics {
+    pub bet_count: u32,
+    pub total_bet_stake: i128,
+    pub mean_stake: i128,
+    /// Lower bound (10^k) of the stake bucket holding the median bet.
+    pub median_stake_floor: i128,
+    pub size_buckets: Vec<u32>,
+    pub time_buckets: Vec<i128>,
+    pub resolved_at: u64,
 }
 
-#[contractevent(topics = ["pool_resolved"])]
+/// Resolution latency record of an operator (or oracle). Latency is measured
+/// from the earliest moment a pool can be resolved (`end_time` +
+/// `resolution_delay`) to its resolution.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperatorSlaStats {
+    pub resolutions: u32,
+    /// Resolutions whose latency exceeded the SLA in force at the time.
+    pub late_resolutions: u32,
+    pub total_latency: u64,
+    pub max_latency: u64,
+}
+
+/// Admin-configured compensation paid on refunds of pools that were canceled
+/// long after their end_time, funded from the per-token compensation reserve.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PoolResolvedEvent {
-    pub pool_id: u64,
-    pub operator: Address,
-    pub outcome: u32,
+pub struct CompensationConfig {
+    /// Compensation accrued per full day of delay, in bps of the refunded stake.
+    pub rate_bps_per_day: u32,
+    /// Upper bound on the total compensation, in bps of the refunded stake.
+    pub cap_bps: u32,
+    /// Delay after end_time (seconds) that is not compensated.
+    pub grace_period: u64,
 }
 
-#[contractevent(topics = ["oracle_resolved"])]
+/// Protocol fee applied to pools whose final total_stake reaches
+/// `min_total_stake`.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct OracleResolvedEvent {
-    pub pool_id: u64,
-    pub oracle: Address,
-    pub outcome: u32,
-    pub proof: String,
+pub struct FeeTier {
+    pub min_total_stake: i128,
+    pub fee_bps: u32,
 }
 
-#[contractevent(topics = ["pool_canceled"])]
+/// Per-pool fees of the outcome-share trading mode. Makers (liquidity added,
+/// resting orders) pay `maker_fee_bps`, takers pay `taker_fee_bps`; both
+/// accrue to the pool's liquidity providers.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PoolCanceledEvent {
-    pub pool_id: u64,
-    pub caller: Address,
-    pub reason: String,
-    pub operator: Address,
+pub struct AmmFeeConfig {
+    pub maker_fee_bps: u32,
+    pub taker_fee_bps: u32,
 }
 
-#[contractevent(topics = ["stake_limits_updated"])]
+/// Arguments of `create_pool_with_params`: those of `create_pool` plus
+/// options that no longer fit its argument list.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct StakeLimitsUpdatedEvent {
-    pub pool_id: u64,
-    pub operator: Address,
+pub struct CreatePoolParams {
+    pub end_time: u64,
+    pub token: Address,
+    pub options_count: u32,
+    pub description: String,
+    pub metadata_url: String,
     pub min_stake: i128,
     pub max_stake: i128,
+    pub initial_liquidity: i128,
+    pub category: Symbol,
+    /// Share of winning payouts accrued to the creator, in bps
+    /// (≤ the admin-set maximum, see `set_max_creator_fee_bps`).
+    pub creator_fee_bps: u32,
+    /// Matching oracle submissions required to resolve the pool
+    /// (0 or 1 = the first oracle result resolves it).
+    pub oracle_quorum: u32,
+    /// Time from which bets are no longer accepted (0 = end_time).
+    /// Resolution still keys off end_time.
+    pub lock_time: u64,
+    /// Cap on the pool's total_stake, house liquidity included (0 = no cap).
+    pub max_total_stake: i128,
+    /// Truncate bets that would exceed `max_total_stake` to the remaining
+    /// capacity instead of rejecting them.
+    pub truncate_at_cap: bool,
+    /// Handicap: payout multiplier per outcome in bps, each in 1..=10_000
+    /// (empty = unweighted). The share withheld from winners goes to the
+    /// creator with `claim_initial_liquidity`.
+    pub outcome_weights: Vec<u32>,
+    /// Scalar market: the numeric range `[low, high)` of each outcome, in
+    /// ascending, non-overlapping order (empty = categorical pool). Resolved
+    /// from an observed value with `resolve_scalar`.
+    pub scalar_buckets: Vec<(i128, i128)>,
+    /// Label of each outcome, 1..=64 bytes each (empty = unlabeled).
+    pub outcome_labels: Vec<String>,
+    /// Pricing mechanism. `Lmsr` pools need initial liquidity and take no
+    /// outcome weights or stake cap; they trade through `buy_shares` and
+    /// `sell_shares` instead of `place_prediction`.
+    pub mechanism: Mechanism,
 }
 
-#[contractevent(topics = ["prediction_placed"])]
+/// Reusable pool settings for recurring markets. Only the owner may create
+/// pools from a template, except for templates registered by an admin,
+/// which are public.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PredictionPlacedEvent {
-    pub pool_id: u64,
-    pub user: Address,
-    pub amount: i128,
-    pub outcome: u32,
+pub struct PoolTemplate {
+    pub owner: Address,
+    pub public: bool,
+    /// Settings of every pool created from the template; `end_time` and
+    /// `lock_time` are ignored.
+    pub params: CreatePoolParams,
 }
 
-#[contractevent(topics = ["winnings_claimed"])]
+/// Creator-set limit on a pool's total_stake.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct WinningsClaimedEvent {
-    pub pool_id: u64,
-    pub user: Address,
-    pub amount: i128,
+pub struct StakeCap {
+    pub max_total_stake: i128,
+    pub truncate_at_cap: bool,
 }
 
-// ── Monitoring & Alert Events ─────────────────────────────────────────────────
-// These events are classified by severity and are intended for consumption by
-// off-chain monitoring tools (Horizon event streaming, Grafana, SIEM, etc.).
-// See MONITORING.md at the repo root for scraping patterns and alert rules.
-
-/// 🔴 HIGH ALERT — emitted when `resolve_pool` is called by an address that
-/// does not hold the Operator role.  Indicates a potential attack or
-/// misconfigured access-control contract.
-#[contractevent(topics = ["unauthorized_resolution"])]
+/// Value of a `Config` field, as recorded in the config change history.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct UnauthorizedResolveAttemptEvent {
-    /// The address that attempted to resolve without authorization.
-    pub caller: Address,
-    /// The pool that was targeted.
-    pub pool_id: u64,
-    /// Ledger timestamp at the time of the attempt.
-    pub timestamp: u64,
+pub enum ConfigValue {
+    U32(u32),
+    U64(u64),
+    I128(i128),
+    Address(Address),
 }
 
-/// 🔴 HIGH ALERT — emitted when an admin-restricted operation (`set_fee_bps`,
-/// `set_treasury`, `pause`, `unpause`) is called by an address that does not
-/// hold the Admin role.
-#[contractevent(topics = ["unauthorized_admin_op"])]
+/// One entry of the on-chain config change history.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct UnauthorizedAdminAttemptEvent {
-    /// The address that attempted the restricted operation.
-    pub caller: Address,
-    /// Short name of the operation that was attempted.
-    pub operation: Symbol,
-    /// Ledger timestamp at the time of the attempt.
+pub struct ConfigChange {
+    /// Sequence number of the change (0 = first change ever recorded).
+    pub seq: u32,
+    /// Name of the changed setting (a `Config` field or standalone setting).
+    pub field: Symbol,
+    pub old_value: ConfigValue,
+    pub new_value: ConfigValue,
+    pub admin: Address,
     pub timestamp: u64,
 }
 
-/// 🔴 HIGH ALERT — emitted when `claim_winnings` is called after winnings have
-/// already been claimed for the same (user, pool) pair.  Repeated attempts may
-/// indicate a re-entrancy probe or a front-end bug worth investigating.
-#[contractevent(topics = ["double_claim_attempt"])]
+/// Announced sweep of a settled pool's unclaimed funds to the treasury.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct SuspiciousDoubleClaimEvent {
-    /// The address that attempted to double-claim.
-    pub user: Address,
-    /// The pool for which the claim was already made.
-    pub pool_id: u64,
-    /// Ledger timestamp at the time of the attempt.
-    pub timestamp: u64,
+pub struct SweepSchedule {
+    pub scheduled_by: Address,
+    /// Earliest timestamp at which `execute_sweep` may run.
+    pub executable_at: u64,
+    pub executed: bool,
 }
 
-/// 🔴 HIGH ALERT — emitted alongside `PauseEvent` whenever the contract is
-/// successfully paused.  Having a dedicated alert topic makes it easy to set
-/// a zero-tolerance PagerDuty rule that fires on any pause.
-#[contractevent(topics = ["contract_paused_alert"])]
+/// Winner leaves accumulated by `push_winner_entries`.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ContractPausedAlertEvent {
-    /// The admin that triggered the pause.
-    pub admin: Address,
-    /// Ledger timestamp at pause time.
-    pub timestamp: u64,
+pub struct WinnersDraft {
+    /// Leaf hashes in strictly increasing order (rules out duplicates).
+    pub leaves: Vec<BytesN<32>>,
+    /// Sum of the pushed winners' stakes, checked against OutcomeStakes.
+    pub stake_total: i128,
+    pub entitlement_total: i128,
 }
 
-/// 🟡 MEDIUM ALERT — emitted in `place_prediction` when the staked amount
-/// meets or exceeds `HIGH_VALUE_THRESHOLD`.  Useful for liquidity monitoring
-/// and detecting unusual betting patterns.
-#[contractevent(topics = ["high_value_prediction"])]
+/// Compact, light-client-friendly summary of a resolved pool's winners.
+/// Leaves are `sha256(xdr(user) || entitlement as 16 big-endian bytes)`;
+/// internal nodes hash the sorted pair of children.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct HighValuePredictionEvent {
-    pub pool_id: u64,
-    pub user: Address,
-    pub amount: i128,
-    pub outcome: u32,
-    /// The threshold that was breached (aids display in dashboards).
-    pub threshold: i128,
+pub struct WinnersSummary {
+    pub root: BytesN<32>,
+    pub leaf_count: u32,
+    /// Σ entitlements of all leaves (≤ pool.total_stake).
+    pub total_entitlement: i128,
 }
 
-/// 🟢 INFO — emitted alongside `PoolResolvedEvent` with enriched numeric
-/// context so monitors can calculate implied payouts and flag anomalies
-/// (e.g., winning_stake == 0 meaning no winners).
-#[contractevent(topics = ["pool_resolved_diag"])]
+/// Settlement attestation for bridge relayers. `digest` is
+/// `sha256(ATTESTATION_DOMAIN || xdr(contract) || pool_id || outcome ||
+/// total_stake || winning_stake || winners_root)` with integers encoded
+/// big-endian, so relayers can sign it and EVM mirrors can recompute it.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PoolResolvedDiagEvent {
+pub struct SettlementAttestation {
+    pub contract: Address,
     pub pool_id: u64,
     pub outcome: u32,
-    /// Total stake across all outcomes at resolution time.
     pub total_stake: i128,
-    /// Stake on the winning outcome (0 ⟹ no winners — notable anomaly).
     pub winning_stake: i128,
-    /// Ledger timestamp at resolution time.
+    /// Winners merkle root, or all zeros when no summary has been published.
+    pub winners_root: BytesN<32>,
+    pub digest: BytesN<32>,
+}
+
+/// Justification recorded alongside a pool's resolution or cancellation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorNote {
+    pub author: Address,
+    /// Short free-form note, e.g. a source URL or a content hash.
+    pub note: String,
     pub timestamp: u64,
 }
 
-/// 🟢 INFO — emitted when all outcome stakes are updated in a single operation.
-/// Useful for markets with many outcomes (e.g., 32+ teams tournament) where
-/// emitting individual events per outcome would be impractical.
-#[contractevent(topics = ["outcome_stakes_updated"])]
+/// Sponsor branding of a pool. The logo and link are referenced by the
+/// sha256 hash of their URI so frontends can verify what they display.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct OutcomeStakesUpdatedEvent {
+pub struct PoolSponsor {
+    pub name: Symbol,
+    pub logo_uri_hash: BytesN<32>,
+    pub link_hash: BytesN<32>,
+}
+
+/// Status update anchored to a pool by its creator or an operator. Only the
+/// hash of the off-chain message is stored; anyone holding the message can
+/// check it against the anchor.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolNote {
+    pub author: Address,
+    pub hash: BytesN<32>,
+    pub posted_at: u64,
+}
+
+/// Per-item result of a batch operation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchItemResult {
     pub pool_id: u64,
-    /// Number of outcomes in this pool.
-    pub options_count: u32,
-    /// Total stake across all outcomes after the update.
-    pub total_stake: i128,
+    /// `PredifiError` code of a failed item, or 0 on success.
+    pub error: u32,
 }
 
-#[contractevent(topics = ["token_whitelist_added"])]
+/// A hypothetical bet evaluated by `simulate_batch`.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TokenWhitelistAddedEvent {
-    pub admin: Address,
-    pub token: Address,
+pub struct SimAction {
+    pub pool_id: u64,
+    pub outcome: u32,
+    pub amount: i128,
 }
 
-#[contractevent(topics = ["token_whitelist_removed"])]
+/// Result of one `SimAction`, given every earlier action of the batch.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TokenWhitelistRemovedEvent {
-    pub admin: Address,
-    pub token: Address,
+pub struct SimResult {
+    pub pool_id: u64,
+    /// `PredifiError` code the bet would fail with, or 0.
+    pub error: u32,
+    /// Stake that would be accepted (after stake-cap truncation).
+    pub accepted: i128,
+    /// Decimal odds of the outcome after the bet (10_000 = 1.00x).
+    pub odds_bps: u64,
+    /// Net payout of the bet if its outcome wins and no further bets arrive.
+    pub payout: i128,
 }
 
-#[contractevent(topics = ["treasury_withdrawn"])]
+/// Result of a batch operation. Items beyond `processed` were not attempted
+/// and should be resubmitted in a later call.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TreasuryWithdrawnEvent {
-    pub admin: Address,
-    pub token: Address,
+pub struct BatchResult {
+    pub processed: u32,
+    pub results: Vec<BatchItemResult>,
+}
+
+/// A single bettor's position, as returned by `get_top_positions`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionView {
+    pub user: Address,
+    pub outcome: u32,
     pub amount: i128,
-    pub recipient: Address,
-    pub timestamp: u64,
 }
-#[contractevent(topics = ["upgrade"])]
+
+/// Frozen storage layout: see the `layout` module before changing any field.
+#[contracttype]
+#[derive(Clone)]
+pub struct Config {
+    pub fee_bps: u32,
+    pub treasury: Address,
+    pub access_control: Address,
+    pub resolution_delay: u64,
+    /// Maximum number of simultaneously Active pools (0 = unlimited).
+    /// Operators may create pools beyond this cap.
+    pub max_active_pools: u32,
+    /// Pools whose total_stake reaches this amount get a settlement hold on
+    /// resolution (0 = disabled).
+    pub settlement_hold_threshold: i128,
+    /// Delay in seconds between resolution and claims opening for held pools.
+    pub settlement_hold_delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct UserPredictionDetail {
+    pub pool_id: u64,
+    pub amount: i128,
+    pub user_outcome: u32,
+    pub pool_end_time: u64,
+    pub pool_state: MarketState,
+    pub pool_outcome: u32,
+}
+
+/// Storage keys of the core contract. Feature modules declare their own key
+/// enums; a key encodes only its variant name, so variant names must be
+/// unique across all of them.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Pool(u64),
+    Prediction(Address, u64),
+    PoolIdCounter,
+    HasClaimed(Address, u64),
+    /// Cumulative amount paid out to (user, pool) so far; enables partial claims.
+    ClaimedAmount(Address, u64),
+    OutcomeStake(u64, u32),
+    /// Optimized storage for markets with many outcomes (e.g., 32+ teams).
+    /// Stores all outcome stakes as a single Vec<i128> to reduce storage reads.
+    OutcomeStakes(u64),
+    UserPredictionCount(Address),
+    UserPredictionIndex(Address, u32),
+    Config,
+    Paused,
+    ReentrancyGuard,
+    CategoryPoolCount(Symbol),
+    CategoryPoolIndex(Symbol, u32),
+    /// Token whitelist: TokenWhitelist(token_address) -> true if allowed for betting.
+    TokenWhitelist(Address),
+    ParticipantsCount(u64),
+    /// Optional lifecycle callback contract registered by the pool creator.
+    PoolCallback(u64),
+    /// Derivative pools: the referenced pool/outcome this pool is about.
+    DerivativeLink(u64),
+    /// Per-pool bettor index: PoolBettor(pool_id, n) -> n-th distinct participant.
+    PoolBettor(u64, u32),
+    /// Number of pools currently in the Active state (protocol-wide).
+    ActivePoolCount,
+    /// Operator note (source link / justification) recorded at settlement.
+    OperatorNote(u64),
+    /// Ledger timestamp at which a pool was canceled.
+    CanceledAt(u64),
+    /// Optional compensation for refunds of pools canceled long after end_time.
+    CompensationConfig,
+    /// Compensation reserve per token, funded explicitly (e.g. by the treasury).
+    CompensationReserve(Address),
+    /// Compliance gate: addresses whose payouts are held for review.
+    ComplianceFlag(Address),
+    /// Winner leaves pushed by operators, before the merkle root is finalized.
+    WinnersDraft(u64),
+    /// Finalized merkle summary of (user, entitlement) pairs for a pool.
+    WinnersSummary(u64),
+    /// Total paid out of a pool's escrow (payouts, held payouts and fees).
+    PoolDisbursed(u64),
+    /// Announced sweep of a pool's unclaimed funds.
+    SweepSchedule(u64),
+    /// Ring buffer slot of the config change history.
+    ConfigHistory(u32),
+    /// Number of config changes ever recorded.
+    ConfigHistoryCount,
+    /// Fee in bps charged on stakes withdrawn before a pool ends.
+    EarlyExitFeeBps,
+    /// Optional protocol fee schedule by pool size (`Vec<FeeTier>`).
+    FeeTiers,
+    /// Per-pool maker/taker fees of the outcome-share trading mode.
+    AmmFees(u64),
+    /// Admin-set cap on the creator fee of new pools, in bps.
+    MaxCreatorFeeBps,
+    /// Creator fee of a pool, in bps of winning payouts.
+    CreatorFeeBps(u64),
+    /// Creator fees accrued and not yet claimed, per (creator, token).
+    CreatorFeesAccrued(Address, Address),
+    /// Timestamp from which claims on a resolved pool are accepted.
+    ClaimsOpenAt(u64),
+    /// Seconds after resolution during which claims are blocked and the
+    /// outcome can be corrected.
+    DisputeWindow,
+    /// Set once the creator has recovered a pool's initial liquidity.
+    LiquidityClaimed(u64),
+    /// Seconds after end_time until which winnings can be claimed, applied
+    /// to new pools (0 = no deadline).
+    ClaimPeriod,
+    /// Claim deadline of a pool, fixed at creation.
+    ClaimDeadline(u64),
+    /// Matching oracle submissions required to resolve a pool (absent = 1).
+    OracleQuorum(u64),
+    /// Oracle results submitted so far for a quorum pool (`Map<Address, u32>`).
+    OracleSubmissions(u64),
+    /// Maximum resolution latency in seconds before an SLA breach is reported
+    /// (0 = disabled).
+    ResolutionSla,
+    /// Resolution latency record per resolver address.
+    OperatorSlaStats(Address),
+    /// Betting lock time of a pool, when earlier than its end_time.
+    LockTime(u64),
+    /// Keys added once this enum reached the contract spec's 50-variant limit.
+    Ext(ExtKey),
+}
+
+/// Storage keys nested under `DataKey::Ext`.
+#[contracttype]
+#[derive(Clone)]
+pub enum ExtKey {
+    /// Betting counters of an Active pool, folded into analytics at resolution.
+    BetCurve(u64),
+    /// Betting analytics checkpointed when a pool was resolved.
+    PoolAnalytics(u64),
+    /// Optional cap on a pool's total_stake.
+    StakeCap(u64),
+    /// Tip in bps of the winnings a bettor grants to whoever claims for them.
+    ClaimTipBps(Address),
+    /// Legs of a multi-leg bet: BetLegs(user, pool_id) -> Vec<BetLeg>.
+    BetLegs(Address, u64),
+    /// Handicap payout multipliers of a pool, in bps per outcome.
+    OutcomeWeights(u64),
+    /// Sealed outcome of a pool awaiting `reveal_resolution`.
+    ResolutionCommitment(u64),
+    /// Bettor odds alerts registered on a pool (`Vec<OddsAlert>`).
+    OddsAlerts(u64),
+    /// Storage schema revision the instance has been migrated to (absent = 0).
+    StorageVersion,
+    /// Wasm hash installed by the last `upgrade_contract` call.
+    WasmHash,
+    /// Yield adapter idle escrow in a token may be deposited into.
+    YieldAdapter(Address),
+    /// Principal currently deposited into the yield adapter of a token.
+    YieldPrincipal(Address),
+    /// Escrow of a pool currently deposited into its token's yield adapter.
+    YieldDeposit(u64),
+    /// Set once a pool's stakes live only in `OutcomeStakes`; the legacy
+    /// per-outcome `OutcomeStake` keys are no longer written.
+    OutcomeStakesMigrated(u64),
+    /// Segregated escrow buckets of a pool (`PoolEscrow`).
+    PoolEscrow(u64),
+    /// Set when an address is a designated market maker of a pool.
+    MarketMaker(u64, Address),
+    /// Quote book of a market maker on a pool: (pool_id, maker) -> Vec<Quote>.
+    Quotes(u64, Address),
+    /// Numeric range `[low, high)` of each outcome of a scalar pool.
+    ScalarBuckets(u64),
+    /// Human-readable label of each outcome of a pool.
+    OutcomeLabels(u64),
+    /// Share of bettor stake, in bps, whose support cancels a pool (0 = off).
+    BettorCancelThresholdBps,
+    /// Delay between bettor support reaching the threshold and cancellation.
+    BettorCancelDelay,
+    /// Bettor-initiated cancellation request of a pool.
+    CancelRequest(u64),
+    /// Market state of an LMSR pool (absent for parimutuel pools).
+    LmsrState(u64),
+    /// A trader's holdings in an LMSR pool.
+    LmsrPosition(u64, Address),
+    /// Set while a pool is hidden from discovery views.
+    Hidden(u64),
+    /// Parent outcome a conditional pool is gated on.
+    ParentCondition(u64),
+    /// Conditional pools gated on a parent pool.
+    ChildPools(u64),
+    /// Number of `WinningsClaimedEvent`s emitted for a pool.
+    ClaimSequence(u64),
+    /// Number of market templates ever registered.
+    TemplateCount,
+    /// A registered market template.
+    Template(u32),
+    /// Longest allowed pool duration in seconds (0 = unlimited).
+    MaxPoolDuration,
+    /// Timestamp of the last successful Admin-role check.
+    LastAdminAction,
+    /// Operations paused individually (`PAUSE_*` bitmask).
+    PausedScopes,
+    /// Guardian and inactivity period of the dead-man switch.
+    DeadManSwitch,
+    /// Timestamp at which the dead-man switch fired, if it has.
+    DeadManTriggered,
+    /// Timestamp at which a settled pool's per-bettor records were deleted.
+    Archived(u64),
+    /// Admin-curated registry of charity / public-goods fee recipients.
+    PublicGoodsRecipient(Address),
+    /// Share of a pool's creator fee directed to a public-goods recipient.
+    PoolPublicGoods(u64),
+    /// Public-goods fees accrued and not yet withdrawn, per (recipient, token).
+    PublicGoodsAccrued(Address, Address),
+    /// Running activity counters of a user, copied into airdrop snapshots.
+    UserActivity(Address),
+    /// Summary of an airdrop eligibility snapshot.
+    AirdropEpoch(u32),
+    /// Entry n of an airdrop eligibility snapshot: AirdropEntry(epoch, n).
+    AirdropEntry(u32, u32),
+    /// Per-outcome claim counters of a settled pool.
+    ClaimStats(u64),
+    /// Bond new pool creators must post (`CreatorBond`; absent = none).
+    CreatorBondConfig,
+    /// Bond posted by a pool's creator, held until the pool settles.
+    CreatorBond(u64),
+    /// Bond and liveness window of optimistic resolution (`OptimisticConfig`).
+    OptimisticConfig,
+    /// Pending bonded outcome assertion of a pool.
+    OutcomeAssertion(u64),
+    /// Status update anchors of a pool, oldest first.
+    PoolNotes(u64),
+    /// Sponsor branding of a pool, locked once the first bet is placed.
+    PoolSponsor(u64),
+}
+
+/// Refundable creator bond: the admin-set requirement, and the amount posted
+/// per pool.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct UpgradeEvent {
-    pub admin: Address,
-    pub new_wasm_hash: BytesN<32>,
+pub struct CreatorBond {
+    pub token: Address,
+    pub amount: i128,
 }
 
-#[contractevent(topics = ["oracle_init"])]
+/// Optimistic resolution settings: the smallest bond an assertion must post
+/// (in the pool's token) and how long it stays open to challenges.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct OracleInitEvent {
-    pub admin: Address,
-    pub pyth_contract: Address,
-    pub max_price_age: u64,
-    pub min_confidence_ratio: u32,
+pub struct OptimisticConfig {
+    pub min_bond: i128,
+    pub liveness: u64,
 }
 
-#[contractevent(topics = ["price_feed_updated"])]
+/// Bonded outcome proposal for a pool. Unchallenged, it resolves the pool
+/// once `expires_at` has passed; a challenger matching the bond escalates it
+/// to an Operator or Oracle ruling.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceFeedUpdatedEvent {
-    pub oracle: Address,
-    pub feed_pair: Symbol,
-    pub price: i128,
-    pub confidence: i128,
-    pub timestamp: u64,
+pub struct OutcomeAssertion {
+    pub asserter: Address,
+    pub outcome: u32,
+    pub bond: i128,
     pub expires_at: u64,
+    pub challenger: Option<Address>,
 }
 
-#[contractevent(topics = ["price_condition_set"])]
+/// Per-outcome claim counters of a settled pool, indexed by the claimant's
+/// (winning, or for refunds predicted) outcome.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceConditionSetEvent {
-    pub pool_id: u64,
-    pub feed_pair: Symbol,
-    pub target_price: i128,
-    pub operator: u32,
-    pub tolerance_bps: u32,
+pub struct ClaimStats {
+    /// Claimants that received a first payout.
+    pub claims: Vec<u32>,
+    /// Net amount paid out.
+    pub paid: Vec<i128>,
+    /// Stake of claimants whose entitlement is fully paid.
+    pub settled_stake: Vec<i128>,
 }
 
-#[contractevent(topics = ["price_resolved"])]
+/// Settlement progress of a pool, as returned by `get_claim_progress`.
+#[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceResolvedEvent {
-    pub pool_id: u64,
-    pub feed_pair: Symbol,
-    pub current_price: i128,
-    pub target_price: i128,
-    pub outcome: u32,
+pub struct ClaimProgress {
+    pub stats: ClaimStats,
+    /// Share of the payable stake (the winning outcome's stake, or all
+    /// stakes of a canceled pool) whose claims are complete, in bps.
+    pub claimed_bps: u32,
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
+/// Optional subsystems enabled on this deployment, so one frontend build can
+/// adapt to differently-configured instances.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Capabilities {
+    /// Resolutions can be disputed during a post-resolution window.
+    pub disputes: bool,
+    /// LMSR outcome-share trading pools can be created.
+    pub amm: bool,
+    /// A single bet may be spread over several outcomes.
+    pub parlays: bool,
+    /// Users hold internal balances instead of transferring per bet.
+    /// Not part of this build.
+    pub internal_balances: bool,
+    /// Promotional credits / free bets. Not part of this build.
+    pub promos: bool,
+    /// Unclaimed winnings can be swept after a claim deadline.
+    pub claim_deadlines: bool,
+    /// Refunds of late cancellations earn compensation.
+    pub compensation: bool,
+    /// A guardian dead-man switch is armed.
+    pub dead_man_switch: bool,
+    /// Anyone may resolve pools through bonded outcome assertions.
+    pub optimistic_resolution: bool,
+}
 
-pub trait OracleCallback {
-    /// Resolve a pool based on external oracle data.
-    /// Caller must have Oracle role (3).
-    /// Cannot resolve a canceled pool.
-    fn oracle_resolve(
-        env: Env,
-        oracle: Address,
-        pool_id: u64,
-        outcome: u32,
-        proof: String,
-    ) -> Result<(), PredifiError>;
+/// Running activity counters of a user. Wins are counted when claimed.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct UserActivity {
+    pub volume: i128,
+    pub wins: u32,
+    pub pools: u32,
 }
 
-#[contract]
-pub struct PredifiContract;
+/// One user's activity as frozen into an airdrop snapshot.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AirdropEntry {
+    pub user: Address,
+    pub volume: i128,
+    pub wins: u32,
+    pub pools: u32,
+}
 
-#[contractimpl]
-impl PredifiContract {
-    // ====== Pure Helper Functions (side-effect free, verifiable) ======
+/// Summary of an airdrop eligibility snapshot. Entries can be appended until
+/// the epoch is frozen.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AirdropEpoch {
+    pub entries: u32,
+    pub taken_at: u64,
+    pub frozen: bool,
+}
 
-    /// Validate that a category symbol is in the allowed list.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `category` - The category symbol to validate
-    ///
-    /// # Returns
-    /// `true` if the category is valid, `false` otherwise
-    fn validate_category(env: &Env, category: &Symbol) -> bool {
-        let mut allowed = Vec::new(env);
-        allowed.push_back(CATEGORY_SPORTS);
-        allowed.push_back(CATEGORY_FINANCE);
-        allowed.push_back(CATEGORY_CRYPTO);
-        allowed.push_back(CATEGORY_POLITICS);
-        allowed.push_back(CATEGORY_ENTERTAIN);
-        allowed.push_back(CATEGORY_TECH);
-        allowed.push_back(CATEGORY_OTHER);
+/// Share of a pool's creator fee directed to a registered public-goods
+/// recipient, and the total attributed to it so far.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicGoodsShare {
+    pub recipient: Address,
+    pub share_bps: u32,
+    pub total_accrued: i128,
+}
 
-        for i in 0..allowed.len() {
-            if let Some(allowed_cat) = allowed.get(i) {
-                if &allowed_cat == category {
-                    return true;
-                }
-            }
-        }
-        false
-    }
+/// Dead-man switch: once the admin has been inactive for `inactivity_period`
+/// seconds, `guardian` may freeze pool creation and extend claim deadlines.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadManSwitch {
+    pub guardian: Address,
+    pub inactivity_period: u64,
+}
 
-    /// Pure: Calculate winnings for a user given pool state
-    /// PRE: winning_stake > 0
-    /// POST: result ≤ total_stake (INV-4)
-    fn calculate_winnings(user_stake: i128, winning_stake: i128, total_stake: i128) -> i128 {
-        if winning_stake == 0 {
-            return 0;
-        }
-        // (user_stake / winning_stake) * total_stake
-        user_stake
-            .checked_mul(total_stake)
-            .expect("overflow in winnings calculation")
-            .checked_div(winning_stake)
-            .expect("division by zero")
-    }
+/// Subject of a derivative ("meta") market: a binary pool asking whether
+/// `reference_pool_id` resolves to `reference_outcome` by the derivative's
+/// end_time. Outcome 1 = yes, 0 = no.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivativeLink {
+    pub reference_pool_id: u64,
+    pub reference_outcome: u32,
+}
 
-    /// Pure: Check if pool state transition is valid
-    /// PRE: current_state is valid MarketState
-    /// POST: returns true only for valid transitions (INV-2)
-    fn is_valid_state_transition(current: MarketState, next: MarketState) -> bool {
-        matches!(
-            (current, next),
-            (MarketState::Active, MarketState::Resolved)
-                | (MarketState::Active, MarketState::Canceled)
-        )
-    }
+/// Condition of a conditional market: the pool only settles if
+/// `parent_pool_id` resolves to `parent_outcome`, and is canceled otherwise.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParentCondition {
+    pub parent_pool_id: u64,
+    pub parent_outcome: u32,
+}
 
-    /// Pure: Validate fee basis points
-    /// POST: returns true iff fee_bps ≤ 10_000 (INV-6)
-    fn is_valid_fee_bps(fee_bps: u32) -> bool {
-        fee_bps <= 10_000
-    }
+/// Frozen storage layout: see the `layout` module before changing any field.
+#[contracttype]
+#[derive(Clone)]
+pub struct Prediction {
+    pub amount: i128,
+    pub outcome: u32,
+}
 
-    /// Pure: Initialize outcome stakes vector with zeros
-    /// Used for markets with many outcomes (e.g., 32+ teams tournament)
-    #[allow(dead_code)]
-    fn init_outcome_stakes(env: &Env, options_count: u32) -> Vec<i128> {
-        let mut stakes = Vec::new(env);
-        for _ in 0..options_count {
-            stakes.push_back(0);
-        }
-        stakes
-    }
+// ── Events ───────────────────────────────────────────────────────────────────
 
-    /// Get outcome stakes for a pool using optimized batch storage.
-    /// Falls back to individual storage keys for backward compatibility.
-    fn get_outcome_stakes(env: &Env, pool_id: u64, options_count: u32) -> Vec<i128> {
-        let key = DataKey::OutcomeStakes(pool_id);
-        if let Some(stakes) = env.storage().persistent().get(&key) {
-            Self::extend_persistent(env, &key);
-            stakes
-        } else {
-            // Fallback: reconstruct from individual outcome stakes (backward compatibility)
-            let mut stakes = Vec::new(env);
-            for i in 0..options_count {
-                let outcome_key = DataKey::OutcomeStake(pool_id, i);
-                let stake: i128 = env.storage().persistent().get(&outcome_key).unwrap_or(0);
-                stakes.push_back(stake);
-            }
+#[contractevent(topics = ["init"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitEvent {
+    pub access_control: Address,
+    pub treasury: Address,
+    pub fee_bps: u32,
+    pub resolution_delay: u64,
+}
+
+#[contractevent(topics = ["pause"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseEvent {
+    pub admin: Address,
+}
+
+#[contractevent(topics = ["pause_scopes_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseScopesUpdateEvent {
+    pub admin: Address,
+    pub scopes: u32,
+}
+
+#[contractevent(topics = ["unpause"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpauseEvent {
+    pub admin: Address,
+}
+
+#[contractevent(topics = ["fee_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeUpdateEvent {
+    pub admin: Address,
+    pub fee_bps: u32,
+}
+
+#[contractevent(topics = ["treasury_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryUpdateEvent {
+    pub admin: Address,
+    pub treasury: Address,
+}
+
+#[contractevent(topics = ["resolution_delay_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionDelayUpdateEvent {
+    pub admin: Address,
+    pub delay: u64,
+}
+
+#[contractevent(topics = ["max_active_pools_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxActivePoolsUpdateEvent {
+    pub admin: Address,
+    pub max_active_pools: u32,
+}
+
+#[contractevent(topics = ["max_pool_duration_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxPoolDurationUpdateEvent {
+    pub admin: Address,
+    pub max_duration: u64,
+}
+
+#[contractevent(topics = ["ttl_extended"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlExtendedEvent {
+    pub pool_id: u64,
+    pub live_until_ledger: u32,
+}
+
+#[contractevent(topics = ["pool_archived"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolArchivedEvent {
+    pub pool_id: u64,
+    pub state: MarketState,
+    pub total_stake: i128,
+    pub archived_at: u64,
+}
+
+#[contractevent(topics = ["dead_man_switch_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadManSwitchUpdateEvent {
+    pub admin: Address,
+    pub guardian: Address,
+    pub inactivity_period: u64,
+}
+
+#[contractevent(topics = ["dead_man_switch_triggered"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadManSwitchTriggeredEvent {
+    pub guardian: Address,
+    pub last_admin_action: u64,
+    pub claims_extended_until: u64,
+}
+
+#[contractevent(topics = ["compensation_config_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompensationConfigUpdateEvent {
+    pub admin: Address,
+    pub rate_bps_per_day: u32,
+    pub cap_bps: u32,
+    pub grace_period: u64,
+}
+
+#[contractevent(topics = ["compensation_funded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompensationReserveFundedEvent {
+    pub funder: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub reserve: i128,
+}
+
+/// 🔴 ERROR — a payout exceeded the escrow bucket of its pool, i.e. it drew on
+/// funds belonging to another pool.
+#[contractevent(topics = ["escrow_shortfall"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowShortfallEvent {
+    pub pool_id: u64,
+    pub required: i128,
+    pub available: i128,
+}
+
+/// 🔴 ERROR — a pool's segregated escrow no longer reconciles with its stake
+/// and disbursement records.
+#[contractevent(topics = ["escrow_mismatch"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowMismatchEvent {
+    pub pool_id: u64,
+    pub recorded: i128,
+    pub expected: i128,
+}
+
+#[contractevent(topics = ["yield_adapter_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldAdapterUpdateEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub adapter: Option<Address>,
+    pub max_share_bps: u32,
+}
+
+#[contractevent(topics = ["yield_deposited"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldDepositedEvent {
+    pub pool_id: u64,
+    pub adapter: Address,
+    pub amount: i128,
+    pub deposited: i128,
+}
+
+#[contractevent(topics = ["yield_withdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldWithdrawnEvent {
+    pub pool_id: u64,
+    pub adapter: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["yield_harvested"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldHarvestedEvent {
+    pub token: Address,
+    pub adapter: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
+#[contractevent(topics = ["compensation_paid"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompensationPaidEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub amount: i128,
+    /// Seconds between end_time and cancellation.
+    pub delay: u64,
+}
+
+#[contractevent(topics = ["compliance_flag_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplianceFlagUpdateEvent {
+    pub admin: Address,
+    pub user: Address,
+    pub flagged: bool,
+}
+
+/// 🟡 MEDIUM — unclaimed funds of a pool will be swept after the grace period.
+#[contractevent(topics = ["sweep_scheduled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepScheduledEvent {
+    pub pool_id: u64,
+    pub admin: Address,
+    pub executable_at: u64,
+    /// Funds still held for the pool when the sweep was announced.
+    pub unclaimed: i128,
+}
+
+#[contractevent(topics = ["sweep_executed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepExecutedEvent {
+    pub pool_id: u64,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
+#[contractevent(topics = ["winners_root_published"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinnersRootPublishedEvent {
+    pub pool_id: u64,
+    pub root: BytesN<32>,
+    pub leaf_count: u32,
+    pub total_entitlement: i128,
+}
+
+/// 🟢 INFO — standardized settlement attestation for cross-chain relayers.
+#[contractevent(topics = ["settlement_attestation"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementAttestationEvent {
+    pub contract: Address,
+    pub pool_id: u64,
+    pub outcome: u32,
+    pub total_stake: i128,
+    pub winning_stake: i128,
+    pub winners_root: BytesN<32>,
+    pub digest: BytesN<32>,
+}
+
+#[contractevent(topics = ["pool_ready"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolReadyForResolutionEvent {
+    pub pool_id: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["pool_created"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCreatedEvent {
+    pub pool_id: u64,
+    pub end_time: u64,
+    pub token: Address,
+    pub options_count: u32,
+    pub metadata_url: String,
+    pub initial_liquidity: i128,
+    pub category: Symbol,
+}
+
+#[contractevent(topics = ["initial_liquidity_provided"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitialLiquidityProvidedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["pool_resolved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolResolvedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub outcome: u32,
+    /// Label of the winning outcome (empty if the pool has no labels).
+    pub outcome_label: String,
+    /// Operator note explaining the result (empty if none was given).
+    pub note: String,
+}
+
+#[contractevent(topics = ["oracle_resolved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleResolvedEvent {
+    pub pool_id: u64,
+    pub oracle: Address,
+    pub outcome: u32,
+    pub proof: String,
+}
+
+#[contractevent(topics = ["oracle_submitted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleResultSubmittedEvent {
+    pub pool_id: u64,
+    pub oracle: Address,
+    pub outcome: u32,
+    /// Submissions for `outcome` so far, including this one.
+    pub votes: u32,
+    pub quorum: u32,
+}
+
+#[contractevent(topics = ["pool_canceled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCanceledEvent {
+    pub pool_id: u64,
+    pub caller: Address,
+    pub reason: String,
+    pub operator: Address,
+}
+
+#[contractevent(topics = ["stake_limits_updated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeLimitsUpdatedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub min_stake: i128,
+    pub max_stake: i128,
+}
+
+/// Topics: `["prediction_placed", user]`, so wallets can subscribe to
+/// their own activity with an RPC topic filter.
+#[contractevent(topics = ["prediction_placed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionPlacedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    pub outcome: u32,
+}
+
+/// Receipt of a multi-leg bet: one stake split across several outcomes.
+#[contractevent(topics = ["multi_prediction_placed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultiPredictionPlacedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    pub legs: Vec<BetLeg>,
+}
+
+/// 🟢 INFO — a bet moved an outcome's odds across a subscriber's threshold.
+#[contractevent(topics = ["odds_alert"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OddsAlertTriggeredEvent {
+    pub subscriber: Address,
+    pub pool_id: u64,
+    pub outcome: u32,
+    pub threshold_bps: u64,
+    pub odds_bps: u64,
+}
+
+#[contractevent(topics = ["prediction_updated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionUpdatedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub outcome: u32,
+    /// Stake added by this update.
+    pub added: i128,
+    /// Position size after the update.
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["prediction_withdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictionWithdrawnEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub outcome: u32,
+    /// Stake removed from the pool.
+    pub amount: i128,
+    /// Early-exit fee accrued to the protocol fee ledger out of `amount`.
+    pub fee: i128,
+    /// Position size left after the withdrawal (0 = fully withdrawn).
+    pub remaining: i128,
+}
+
+#[contractevent(topics = ["fee_tiers_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTiersUpdateEvent {
+    pub admin: Address,
+    pub tiers: Vec<FeeTier>,
+}
+
+#[contractevent(topics = ["bettor_cancel_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BettorCancelUpdateEvent {
+    pub admin: Address,
+    pub threshold_bps: u32,
+    pub delay: u64,
+}
+
+#[contractevent(topics = ["cancel_supported"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelSupportedEvent {
+    pub pool_id: u64,
+    pub bettor: Address,
+    pub support_stake: i128,
+    pub bettor_stake: i128,
+}
+
+/// 🟡 WARN — bettors holding a supermajority of the stake asked to cancel a
+/// pool; it is canceled at `executable_at` unless an operator vetoes.
+#[contractevent(topics = ["cancel_requested"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelRequestedEvent {
+    pub pool_id: u64,
+    pub support_stake: i128,
+    pub executable_at: u64,
+}
+
+#[contractevent(topics = ["cancel_vetoed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelVetoedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+}
+
+#[contractevent(topics = ["scalar_resolved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScalarResolvedEvent {
+    pub pool_id: u64,
+    pub observed_value: i128,
+    pub outcome: u32,
+}
+
+#[contractevent(topics = ["market_maker_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketMakerSetEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub market_maker: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["quotes_refreshed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuotesRefreshedEvent {
+    pub pool_id: u64,
+    pub market_maker: Address,
+    pub updated: u32,
+    pub live: u32,
+}
+
+/// One leg of every token movement into or out of the contract, so
+/// accounting can reconcile escrow from a single event type. `pool_id` is
+/// None for movements not tied to a pool (treasury, reserves, yield).
+#[contractevent(topics = ["ledger"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerEntryEvent {
+    pub pool_id: Option<u64>,
+    pub account: Address,
+    pub token: Address,
+    pub direction: LedgerDirection,
+    pub amount: i128,
+    pub reason: Symbol,
+}
+
+#[contractevent(topics = ["shares_traded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesTradedEvent {
+    pub pool_id: u64,
+    pub trader: Address,
+    pub outcome: u32,
+    /// Positive for buys, negative for sells.
+    pub shares: i128,
+    /// Cost paid or proceeds received, fee excluded.
+    pub amount: i128,
+    pub fee: i128,
+}
+
+#[contractevent(topics = ["shares_redeemed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharesRedeemedEvent {
+    pub pool_id: u64,
+    pub trader: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["amm_fees_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmmFeesSetEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub maker_fee_bps: u32,
+    pub taker_fee_bps: u32,
+}
+
+#[contractevent(topics = ["settlement_hold_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementHoldUpdateEvent {
+    pub admin: Address,
+    pub threshold: i128,
+    pub delay: u64,
+}
+
+/// 🟡 MEDIUM — a high-value pool was resolved; claims open at `claims_open_at`
+/// so monitoring can react to a suspicious resolution.
+#[contractevent(topics = ["settlement_hold_applied"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementHoldAppliedEvent {
+    pub pool_id: u64,
+    pub total_stake: i128,
+    pub claims_open_at: u64,
+}
+
+#[contractevent(topics = ["pool_visibility"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolVisibilityEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub hidden: bool,
+}
+
+#[contractevent(topics = ["pool_metadata_updated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolMetadataUpdatedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub description: String,
+    pub metadata_url: String,
+}
+
+#[contractevent(topics = ["claim_tip_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimTipUpdateEvent {
+    pub user: Address,
+    pub tip_bps: u32,
+}
+
+#[contractevent(topics = ["claim_relayed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimRelayedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub beneficiary: Address,
+    pub relayer: Address,
+    pub tip: i128,
+}
+
+/// 🟢 INFO — a bet or liquidity deposit was rejected because the payer's
+/// token balance does not cover it.
+#[contractevent(topics = ["insufficient_balance"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsufficientBalanceEvent {
+    pub user: Address,
+    pub token: Address,
+    pub required: i128,
+    pub available: i128,
+}
+
+/// 🟢 INFO — a pool's total stake reached its cap; no further bets fit.
+#[contractevent(topics = ["pool_cap_reached"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCapReachedEvent {
+    pub pool_id: u64,
+    pub max_total_stake: i128,
+}
+
+#[contractevent(topics = ["claim_period_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimPeriodUpdateEvent {
+    pub admin: Address,
+    pub claim_period: u64,
+}
+
+#[contractevent(topics = ["unclaimed_swept"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnclaimedSweptEvent {
+    pub pool_id: u64,
+    pub admin: Address,
+    pub amount: i128,
+    pub recipient: Address,
+    pub claim_deadline: u64,
+}
+
+#[contractevent(topics = ["resolution_committed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionCommittedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub commitment: BytesN<32>,
+}
+
+#[contractevent(topics = ["dispute_window_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeWindowUpdateEvent {
+    pub admin: Address,
+    pub dispute_window: u64,
+}
+
+/// 🔴 HIGH ALERT — an operator corrected the outcome of a resolved pool.
+#[contractevent(topics = ["pool_disputed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolDisputedEvent {
+    pub pool_id: u64,
+    pub operator: Address,
+    pub old_outcome: u32,
+    pub new_outcome: u32,
+    /// When claims open under the corrected outcome.
+    pub claims_open_at: u64,
+}
+
+#[contractevent(topics = ["resolution_sla_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionSlaUpdateEvent {
+    pub admin: Address,
+    pub resolution_sla: u64,
+}
+
+/// 🟡 MEDIUM ALERT — a pool was resolved later than the resolution SLA allows.
+#[contractevent(topics = ["resolution_sla_breached"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionSlaBreachedEvent {
+    pub operator: Address,
+    pub pool_id: u64,
+    pub latency: u64,
+    pub resolution_sla: u64,
+}
+
+#[contractevent(topics = ["early_exit_fee_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyExitFeeUpdateEvent {
+    pub admin: Address,
+    pub fee_bps: u32,
+}
+
+/// Emitted for winnings and for refunds of canceled pools. Topics:
+/// `["winnings_claimed", user]`, giving each recipient a notification stream.
+#[contractevent(topics = ["winnings_claimed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinningsClaimedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    /// Net amount paid to the user (equal to `net`, kept for existing indexers).
+    pub amount: i128,
+    /// Amount released by this claim before fees:
+    /// `net + fee + creator_fee + keeper_fee`. A position's fees are all
+    /// charged with its first payout, so later partial claims of it report
+    /// zero fees and `gross == net`.
+    pub gross: i128,
+    /// Protocol fee accrued by this claim (see `withdraw_fees`).
+    pub fee: i128,
+    /// Creator fee accrued to the pool creator by this claim.
+    pub creator_fee: i128,
+    /// Keeper reward paid to the pool's keeper by this claim.
+    pub keeper_fee: i128,
+    pub net: i128,
+    /// Position of this claim among the pool's claim events, from 1 with no
+    /// gaps, so indexers can detect dropped or reordered events.
+    pub sequence: u32,
+}
+
+#[contractevent(topics = ["creator_fee_accrued"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorFeeAccruedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["creator_fee_claimed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorFeeClaimedEvent {
+    pub creator: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Diagnostic context of a failed call: the error code plus the expected
+/// bound and the actual value that violated it. Rolled back with the call,
+/// but visible in diagnostic / simulation output.
+#[contractevent(topics = ["error_detail"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorDetailEvent {
+    pub code: u32,
+    pub pool_id: Option<u64>,
+    pub expected: i128,
+    pub actual: i128,
+}
+
+#[contractevent(topics = ["creator_bond_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorBondUpdateEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["creator_bond_settled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorBondSettledEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// True if the bond went to the treasury instead of the creator.
+    pub forfeited: bool,
+}
+
+#[contractevent(topics = ["pool_sponsor_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSponsorSetEvent {
+    pub pool_id: u64,
+    /// New branding, or None if it was removed.
+    pub sponsor: Option<PoolSponsor>,
+}
+
+#[contractevent(topics = ["pool_note_posted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolNotePostedEvent {
+    pub pool_id: u64,
+    pub author: Address,
+    /// Position of the note in the pool's feed.
+    pub index: u32,
+    pub hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["optimistic_config_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptimisticConfigUpdateEvent {
+    pub admin: Address,
+    pub min_bond: i128,
+    pub liveness: u64,
+}
+
+#[contractevent(topics = ["outcome_asserted"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeAssertedEvent {
+    pub pool_id: u64,
+    pub asserter: Address,
+    pub outcome: u32,
+    pub bond: i128,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["assertion_challenged"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssertionChallengedEvent {
+    pub pool_id: u64,
+    pub challenger: Address,
+    pub bond: i128,
+}
+
+#[contractevent(topics = ["assertion_settled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssertionSettledEvent {
+    pub pool_id: u64,
+    pub outcome: u32,
+    /// Asserter, or the challenger if the ruling went against the assertion.
+    pub winner: Address,
+    /// Bonds paid to the winner (both bonds if the assertion was challenged).
+    pub payout: i128,
+}
+
+#[contractevent(topics = ["airdrop_snapshot_frozen"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AirdropSnapshotFrozenEvent {
+    pub admin: Address,
+    pub epoch: u32,
+    pub entries: u32,
+}
+
+#[contractevent(topics = ["public_goods_registry"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicGoodsRegistryEvent {
+    pub admin: Address,
+    pub recipient: Address,
+    pub registered: bool,
+}
+
+#[contractevent(topics = ["pool_public_goods_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolPublicGoodsSetEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    pub recipient: Address,
+    pub share_bps: u32,
+}
+
+#[contractevent(topics = ["public_goods_fee_accrued"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicGoodsFeeAccruedEvent {
+    pub pool_id: u64,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["public_goods_fee_claimed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicGoodsFeeClaimedEvent {
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["liquidity_returned"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidityReturnedEvent {
+    pub pool_id: u64,
+    pub creator: Address,
+    /// Amount transferred to the creator, net of the protocol fee.
+    pub amount: i128,
+    pub fee: i128,
+}
+
+#[contractevent(topics = ["max_creator_fee_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxCreatorFeeUpdateEvent {
+    pub admin: Address,
+    pub max_creator_fee_bps: u32,
+}
+
+#[contractevent(topics = ["derivative_linked"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivativeLinkedEvent {
+    pub pool_id: u64,
+    pub reference_pool_id: u64,
+    pub reference_outcome: u32,
+}
+
+#[contractevent(topics = ["template_created"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateCreatedEvent {
+    pub template_id: u32,
+    pub owner: Address,
+    pub public: bool,
+}
+
+#[contractevent(topics = ["parent_condition_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParentConditionSetEvent {
+    pub pool_id: u64,
+    pub parent_pool_id: u64,
+    pub parent_outcome: u32,
+}
+
+// ── Monitoring & Alert Events ─────────────────────────────────────────────────
+// These events are classified by severity and are intended for consumption by
+// off-chain monitoring tools (Horizon event streaming, Grafana, SIEM, etc.).
+// See MONITORING.md at the repo root for scraping patterns and alert rules.
+
+/// 🔴 HIGH ALERT — emitted when `resolve_pool` is called by an address that
+/// does not hold the Operator role.  Indicates a potential attack or
+/// misconfigured access-control contract.
+#[contractevent(topics = ["unauthorized_resolution"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnauthorizedResolveAttemptEvent {
+    /// The address that attempted to resolve without authorization.
+    pub caller: Address,
+    /// The pool that was targeted.
+    pub pool_id: u64,
+    /// Ledger timestamp at the time of the attempt.
+    pub timestamp: u64,
+}
+
+/// 🔴 HIGH ALERT — emitted when an admin-restricted operation (`set_fee_bps`,
+/// `set_treasury`, `pause`, `unpause`) is called by an address that does not
+/// hold the Admin role.
+#[contractevent(topics = ["unauthorized_admin_op"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnauthorizedAdminAttemptEvent {
+    /// The address that attempted the restricted operation.
+    pub caller: Address,
+    /// Short name of the operation that was attempted.
+    pub operation: Symbol,
+    /// Ledger timestamp at the time of the attempt.
+    pub timestamp: u64,
+}
+
+/// 🔴 HIGH ALERT — emitted when `claim_winnings` is called after winnings have
+/// already been claimed for the same (user, pool) pair.  Repeated attempts may
+/// indicate a re-entrancy probe or a front-end bug worth investigating.
+#[contractevent(topics = ["double_claim_attempt"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SuspiciousDoubleClaimEvent {
+    /// The address that attempted to double-claim.
+    pub user: Address,
+    /// The pool for which the claim was already made.
+    pub pool_id: u64,
+    /// Ledger timestamp at the time of the attempt.
+    pub timestamp: u64,
+}
+
+/// 🔴 CRITICAL ALERT — emitted when the access-control contract's `has_role`
+/// call fails, so operators can tell an infrastructure failure from a caller
+/// missing a role. Rolled back with the failing call, but visible in
+/// diagnostic / simulation output.
+#[contractevent(topics = ["access_control_unavailable"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessControlUnavailableEvent {
+    /// The access-control contract that failed.
+    pub access_control: Address,
+    /// The address whose role was being checked.
+    pub user: Address,
+    /// The role that was being checked.
+    pub role: u32,
+    /// Ledger timestamp at the time of the failure.
+    pub timestamp: u64,
+}
+
+/// 🔴 HIGH ALERT — emitted alongside `PauseEvent` whenever the contract is
+/// successfully paused.  Having a dedicated alert topic makes it easy to set
+/// a zero-tolerance PagerDuty rule that fires on any pause.
+#[contractevent(topics = ["contract_paused_alert"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractPausedAlertEvent {
+    /// The admin that triggered the pause.
+    pub admin: Address,
+    /// Ledger timestamp at pause time.
+    pub timestamp: u64,
+}
+
+/// 🟡 MEDIUM ALERT — emitted in `place_prediction` when the staked amount
+/// meets or exceeds `HIGH_VALUE_THRESHOLD` (rescaled to the token's
+/// decimals).  Useful for liquidity monitoring and detecting unusual betting
+/// patterns.
+#[contractevent(topics = ["high_value_prediction"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HighValuePredictionEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub amount: i128,
+    pub outcome: u32,
+    /// The threshold that was breached (aids display in dashboards).
+    pub threshold: i128,
+}
+
+/// 🟡 MEDIUM — summary of a `cancel_pools_batch` call. Each canceled pool
+/// also emits its own `PoolCanceledEvent` carrying the shared reason.
+#[contractevent(topics = ["pools_batch_canceled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolsBatchCanceledEvent {
+    pub operator: Address,
+    pub processed: u32,
+    pub failed: u32,
+    pub reason: String,
+}
+
+/// 🟢 INFO — summary of a `resolve_pools_batch` call. Each resolved pool also
+/// emits its own `PoolResolvedEvent`.
+#[contractevent(topics = ["pools_batch_resolved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolsBatchResolvedEvent {
+    pub operator: Address,
+    pub processed: u32,
+    pub failed: u32,
+}
+
+/// 🟢 INFO — emitted alongside `PoolResolvedEvent` with enriched numeric
+/// context so monitors can calculate implied payouts and flag anomalies
+/// (e.g., winning_stake == 0 meaning no winners).
+#[contractevent(topics = ["pool_resolved_diag"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolResolvedDiagEvent {
+    pub pool_id: u64,
+    pub outcome: u32,
+    /// Total stake across all outcomes at resolution time.
+    pub total_stake: i128,
+    /// Stake on the winning outcome (0 ⟹ no winners — notable anomaly).
+    pub winning_stake: i128,
+    /// Ledger timestamp at resolution time.
+    pub timestamp: u64,
+}
+
+/// 🟢 INFO — emitted when a pool's legacy `OutcomeStake` keys have been folded
+/// into its `OutcomeStakes` vector.
+#[contractevent(topics = ["outcome_stakes_migrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeStakesMigratedEvent {
+    pub pool_id: u64,
+    pub legacy_keys_removed: u32,
+}
+
+/// 🟢 INFO — emitted when all outcome stakes are updated in a single operation.
+/// Useful for markets with many outcomes (e.g., 32+ teams tournament) where
+/// emitting individual events per outcome would be impractical.
+#[contractevent(topics = ["outcome_stakes_updated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeStakesUpdatedEvent {
+    pub pool_id: u64,
+    /// Number of outcomes in this pool.
+    pub options_count: u32,
+    /// Total stake across all outcomes after the update.
+    pub total_stake: i128,
+}
+
+/// 🟢 INFO — per-bet change of one outcome's stake, for incremental odds
+/// feeds. Emitted for pools with fewer than `BATCH_STAKE_EVENT_MIN_OPTIONS`
+/// outcomes; larger markets emit `OutcomeStakesUpdatedEvent` instead.
+#[contractevent(topics = ["outcome_stake_delta"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeStakeDeltaEvent {
+    pub pool_id: u64,
+    pub outcome: u32,
+    /// Signed change of the outcome stake (negative on withdrawals).
+    pub delta: i128,
+    /// Stake on the outcome after the change.
+    pub new_total: i128,
+}
+
+#[contractevent(topics = ["treasury_withdrawn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawnEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+#[contractevent(topics = ["upgrade"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeEvent {
+    pub admin: Address,
+    /// Hash installed by the previous upgrade; all zeroes for the code the
+    /// contract was originally deployed with.
+    pub old_wasm_hash: BytesN<32>,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["storage_migrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageMigratedEvent {
+    pub admin: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[contractevent(topics = ["oracle_init"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleInitEvent {
+    pub admin: Address,
+    pub pyth_contract: Address,
+    pub max_price_age: u64,
+    pub min_confidence_ratio: u32,
+}
+
+#[contractevent(topics = ["price_feed_updated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceFeedUpdatedEvent {
+    pub oracle: Address,
+    pub feed_pair: Symbol,
+    pub price: i128,
+    pub confidence: i128,
+    pub timestamp: u64,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["price_condition_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceConditionSetEvent {
+    pub pool_id: u64,
+    pub feed_pair: Symbol,
+    pub target_price: i128,
+    pub operator: u32,
+    pub tolerance_bps: u32,
+}
+
+#[contractevent(topics = ["price_resolved"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceResolvedEvent {
+    pub pool_id: u64,
+    pub feed_pair: Symbol,
+    pub current_price: i128,
+    pub target_price: i128,
+    pub outcome: u32,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub trait OracleCallback {
+    /// Resolve a pool based on external oracle data.
+    /// Caller must have Oracle role (3).
+    /// Cannot resolve a canceled pool.
+    /// On pools created with an oracle quorum this records a submission, as
+    /// `submit_oracle_result` does.
+    fn oracle_resolve(
+        env: Env,
+        oracle: Address,
+        pool_id: u64,
+        outcome: u32,
+        proof: String,
+    ) -> Result<(), PredifiError>;
+
+    /// Submit an oracle result for a pool. Results are tallied per outcome and
+    /// the pool resolves once one outcome reaches the pool's quorum.
+    /// Each oracle may submit once per pool. Caller must have Oracle role (3).
+    /// Returns true if this submission resolved the pool.
+    fn submit_oracle_result(
+        env: Env,
+        oracle: Address,
+        pool_id: u64,
+        outcome: u32,
+        proof: String,
+    ) -> Result<bool, PredifiError>;
+}
+
+/// Interface of an escrow yield adapter, e.g. a thin wrapper around a
+/// Blend-compatible lending pool. Registered per token via
+/// `set_yield_adapter`. Tokens are transferred to the adapter before
+/// `deposit` is called; `withdraw` must transfer `amount` back to `owner`.
+pub trait YieldAdapter {
+    fn deposit(env: Env, owner: Address, token: Address, amount: i128);
+    fn withdraw(env: Env, owner: Address, token: Address, amount: i128);
+    /// Principal plus accrued yield held for `owner`.
+    fn balance(env: Env, owner: Address, token: Address) -> i128;
+}
+
+#[contract]
+pub struct PredifiContract;
+
+#[contractimpl]
+impl PredifiContract {
+    // ====== Pure Helper Functions (side-effect free, verifiable) ======
+
+    /// Validate that a category symbol is in the allowed list.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `category` - The category symbol to validate
+    ///
+    /// # Returns
+    /// `true` if the category is valid, `false` otherwise
+    fn validate_category(env: &Env, category: &Symbol) -> bool {
+        let mut allowed = Vec::new(env);
+        allowed.push_back(CATEGORY_SPORTS);
+        allowed.push_back(CATEGORY_FINANCE);
+        allowed.push_back(CATEGORY_CRYPTO);
+        allowed.push_back(CATEGORY_POLITICS);
+        allowed.push_back(CATEGORY_ENTERTAIN);
+        allowed.push_back(CATEGORY_TECH);
+        allowed.push_back(CATEGORY_OTHER);
+
+        for i in 0..allowed.len() {
+            if let Some(allowed_cat) = allowed.get(i) {
+                if &allowed_cat == category {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Pure: Calculate winnings for a user given pool state
+    /// PRE: winning_stake > 0
+    /// POST: result ≤ total_stake (INV-4)
+    fn calculate_winnings(user_stake: i128, winning_stake: i128, total_stake: i128) -> i128 {
+        if winning_stake == 0 {
+            return 0;
+        }
+        // (user_stake / winning_stake) * total_stake
+        user_stake
+            .checked_mul(total_stake)
+            .expect("overflow in winnings calculation")
+            .checked_div(winning_stake)
+            .expect("division by zero")
+    }
+
+    /// Pure: Protocol fee owed on `winnings`.
+    /// Only the share of winnings funded by bettors (`bettor_stake` out of
+    /// `total_stake`) is in the fee base; the part funded by the creator's
+    /// initial liquidity is exempt.
+    /// POST: 0 ≤ fee ≤ winnings (given fee_bps ≤ 10_000, INV-6)
+    fn calculate_protocol_fee(
+        winnings: i128,
+        total_stake: i128,
+        bettor_stake: i128,
+        fee_bps: u32,
+    ) -> i128 {
+        if winnings <= 0 || total_stake <= 0 || bettor_stake <= 0 || fee_bps == 0 {
+            return 0;
+        }
+        let fee_base = winnings
+            .checked_mul(bettor_stake)
+            .expect("overflow in fee calculation")
+            / total_stake;
+        fee_base
+            .checked_mul(fee_bps as i128)
+            .expect("overflow in fee calculation")
+            / 10_000
+    }
+
+    /// Pure: Check if pool state transition is valid
+    /// PRE: current_state is valid MarketState
+    /// POST: returns true only for valid transitions (INV-2)
+    fn is_valid_state_transition(current: MarketState, next: MarketState) -> bool {
+        matches!(
+            (current, next),
+            (MarketState::Active, MarketState::Resolved)
+                | (MarketState::Active, MarketState::Canceled)
+                | (MarketState::Resolved, MarketState::Disputed)
+                | (MarketState::Disputed, MarketState::Resolved)
+        )
+    }
+
+    /// Pure: Validate fee basis points
+    /// POST: returns true iff fee_bps ≤ 10_000 (INV-6)
+    fn is_valid_fee_bps(fee_bps: u32) -> bool {
+        fee_bps <= 10_000
+    }
+
+    /// Pure: Compensation owed on a refund of `amount` for a pool canceled
+    /// `delay` seconds after its end_time.
+    /// POST: result ≤ amount * cap_bps / 10_000
+    fn calculate_compensation(amount: i128, delay: u64, config: &CompensationConfig) -> i128 {
+        if delay <= config.grace_period {
+            return 0;
+        }
+        let days = (delay - config.grace_period) / 86_400;
+        let bps = core::cmp::min(
+            days.saturating_mul(config.rate_bps_per_day as u64),
+            config.cap_bps as u64,
+        );
+        amount
+            .checked_mul(bps as i128)
+            .expect("overflow in compensation calculation")
+            / 10_000
+    }
+
+    /// Pure: Merkle leaf for a (user, entitlement) pair.
+    fn winner_leaf(env: &Env, user: &Address, entitlement: i128) -> BytesN<32> {
+        let mut buf = user.clone().to_xdr(env);
+        buf.extend_from_array(&entitlement.to_be_bytes());
+        env.crypto().sha256(&buf).into()
+    }
+
+    /// Pure: Commitment to a pool's outcome, see `ResolutionCommitment`.
+    fn resolution_commitment(
+        env: &Env,
+        pool_id: u64,
+        outcome: u32,
+        salt: &BytesN<32>,
+    ) -> BytesN<32> {
+        let mut buf = Bytes::new(env);
+        buf.extend_from_array(&pool_id.to_be_bytes());
+        buf.extend_from_array(&outcome.to_be_bytes());
+        buf.append(&salt.clone().into());
+        env.crypto().sha256(&buf).into()
+    }
+
+    /// Pure: Parent of two merkle nodes, hashed as a sorted pair so proofs do
+    /// not need to carry left/right positions.
+    fn merkle_parent(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        let mut buf = Bytes::new(env);
+        buf.append(&lo.clone().into());
+        buf.append(&hi.clone().into());
+        env.crypto().sha256(&buf).into()
+    }
+
+    /// Pure: Merkle root of a non-empty leaf list (odd nodes are promoted).
+    fn merkle_root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut level = leaves.clone();
+        while level.len() > 1 {
+            let mut next = Vec::new(env);
+            let mut i = 0;
+            while i < level.len() {
+                if i + 1 < level.len() {
+                    next.push_back(Self::merkle_parent(
+                        env,
+                        &level.get_unchecked(i),
+                        &level.get_unchecked(i + 1),
+                    ));
+                } else {
+                    next.push_back(level.get_unchecked(i));
+                }
+                i += 2;
+            }
+            level = next;
+        }
+        level.get_unchecked(0)
+    }
+
+    /// Build the settlement attestation of a resolved pool.
+    fn build_settlement_attestation(env: &Env, pool_id: u64, pool: &Pool) -> SettlementAttestation {
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let winning_stake = stakes.get(pool.outcome).unwrap_or(0);
+        let summary: Option<WinnersSummary> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WinnersSummary(pool_id));
+        let winners_root = match summary {
+            Some(s) => s.root,
+            None => BytesN::from_array(env, &[0u8; 32]),
+        };
+        let contract = env.current_contract_address();
+
+        let mut payload = Bytes::from_slice(env, ATTESTATION_DOMAIN);
+        payload.append(&contract.clone().to_xdr(env));
+        payload.extend_from_array(&pool_id.to_be_bytes());
+        payload.extend_from_array(&pool.outcome.to_be_bytes());
+        payload.extend_from_array(&pool.total_stake.to_be_bytes());
+        payload.extend_from_array(&winning_stake.to_be_bytes());
+        payload.append(&winners_root.clone().into());
+        let digest: BytesN<32> = env.crypto().sha256(&payload).into();
+
+        SettlementAttestation {
+            contract,
+            pool_id,
+            outcome: pool.outcome,
+            total_stake: pool.total_stake,
+            winning_stake,
+            winners_root,
+            digest,
+        }
+    }
+
+    fn publish_settlement_attestation(env: &Env, attestation: SettlementAttestation) {
+        SettlementAttestationEvent {
+            contract: attestation.contract,
+            pool_id: attestation.pool_id,
+            outcome: attestation.outcome,
+            total_stake: attestation.total_stake,
+            winning_stake: attestation.winning_stake,
+            winners_root: attestation.winners_root,
+            digest: attestation.digest,
+        }
+        .publish(env);
+    }
+
+    /// Pure: Initialize outcome stakes vector with zeros
+    /// Used for markets with many outcomes (e.g., 32+ teams tournament)
+    #[allow(dead_code)]
+    fn init_outcome_stakes(env: &Env, options_count: u32) -> Vec<i128> {
+        let mut stakes = Vec::new(env);
+        for _ in 0..options_count {
+            stakes.push_back(0);
+        }
+        stakes
+    }
+
+    /// Get outcome stakes for a pool using optimized batch storage.
+    /// Falls back to individual storage keys for backward compatibility.
+    fn get_outcome_stakes(env: &Env, pool_id: u64, options_count: u32) -> Vec<i128> {
+        let key = DataKey::OutcomeStakes(pool_id);
+        if let Some(stakes) = env.storage().persistent().get(&key) {
+            Self::extend_persistent(env, &key);
+            stakes
+        } else {
+            // Fallback: reconstruct from individual outcome stakes (backward compatibility)
+            let mut stakes = Vec::new(env);
+            for i in 0..options_count {
+                let outcome_key = DataKey::OutcomeStake(pool_id, i);
+                let stake: i128 = env.storage().persistent().get(&outcome_key).unwrap_or(0);
+                stakes.push_back(stake);
+            }
             stakes
         }
     }
 
-    /// Update outcome stake at a specific index and persist using optimized batch storage.
-    /// Also maintains backward compatibility with individual outcome stake keys.
-    fn update_outcome_stake(
-        env: &Env,
+    fn outcome_stakes_migrated(env: &Env, pool_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Ext(ExtKey::OutcomeStakesMigrated(pool_id)))
+    }
+
+    fn mark_outcome_stakes_migrated(env: &Env, pool_id: u64) {
+        let key = DataKey::Ext(ExtKey::OutcomeStakesMigrated(pool_id));
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Update outcome stake at a specific index and persist using optimized batch storage.
+    /// Pools not yet migrated also keep their individual outcome stake keys in sync.
+    fn update_outcome_stake(
+        env: &Env,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+        options_count: u32,
+    ) -> Vec<i128> {
+        let mut stakes = Self::get_outcome_stakes(env, pool_id, options_count);
+        let current = stakes.get(outcome).unwrap_or(0);
+        stakes.set(outcome, current + amount);
+
+        // Store using optimized batch key
+        let key = DataKey::OutcomeStakes(pool_id);
+        env.storage().persistent().set(&key, &stakes);
+        Self::extend_persistent(env, &key);
+
+        // Also update individual key for backward compatibility
+        if !Self::outcome_stakes_migrated(env, pool_id) {
+            let outcome_key = DataKey::OutcomeStake(pool_id, outcome);
+            env.storage()
+                .persistent()
+                .set(&outcome_key, &(current + amount));
+            Self::extend_persistent(env, &outcome_key);
+        }
+
+        if options_count < BATCH_STAKE_EVENT_MIN_OPTIONS {
+            OutcomeStakeDeltaEvent {
+                pool_id,
+                outcome,
+                delta: amount,
+                new_total: current + amount,
+            }
+            .publish(env);
+        }
+
+        stakes
+    }
+
+    // ── Invariant Checks (debug builds only) ──────────────────────────────────
+
+    /// Executable form of the documented pool invariants, run at the end of
+    /// every state-mutating path. Compiled out of release wasm.
+    #[cfg(debug_assertions)]
+    fn check_pool_invariants(env: &Env, pool_id: u64) {
+        let pool: Pool = match env.storage().persistent().get(&DataKey::Pool(pool_id)) {
+            Some(pool) => pool,
+            None => return,
+        };
+        // Archived pools no longer keep the outcome stakes INV-1 sums.
+        if Self::is_pool_archived(env, pool_id) {
+            return;
+        }
+
+        // INV-1: escrow = house liquidity + Σ outcome stakes
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let mut staked: i128 = 0;
+        for stake in stakes.iter() {
+            assert!(stake >= 0, "INV-1: negative outcome stake");
+            staked += stake;
+        }
+        let total_stake = pool.total_stake + Self::stake_delta(env, pool_id);
+        assert!(
+            total_stake == pool.initial_liquidity + staked,
+            "INV-1: total_stake != initial_liquidity + Σ outcome stakes"
+        );
+
+        // INV-2: flags agree with the state; Disputed is never persisted
+        assert!(
+            pool.state != MarketState::Disputed,
+            "INV-2: pool persisted in Disputed state"
+        );
+        assert!(
+            pool.resolved == (pool.state == MarketState::Resolved)
+                && pool.canceled == (pool.state == MarketState::Canceled),
+            "INV-2: state flags out of sync"
+        );
+        assert!(
+            pool.state != MarketState::Resolved || pool.outcome < pool.options_count,
+            "INV-2: resolved outcome out of range"
+        );
+
+        // INV-4 / INV-5: payouts never exceed the pool's escrow
+        let disbursed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PoolDisbursed(pool_id))
+            .unwrap_or(0);
+        assert!(
+            disbursed >= 0 && disbursed <= total_stake,
+            "INV-5: disbursed exceeds total_stake"
+        );
+
+        // INV-9: the segregated escrow matches what the pool still holds
+        if let Some(escrow) = Self::pool_escrow(env, pool_id) {
+            assert!(
+                escrow.locked >= 0 && escrow.claimable >= 0,
+                "INV-9: escrow bucket overdrawn"
+            );
+            assert!(
+                escrow.locked + escrow.claimable == total_stake - disbursed,
+                "INV-9: escrow != total_stake - disbursed"
+            );
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    fn check_pool_invariants(_env: &Env, _pool_id: u64) {}
+
+    // ── Storage & Side-Effect Functions ───────────────────────────────────────
+
+    fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(BUMP_THRESHOLD, BUMP_AMOUNT);
+    }
+
+    fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    }
+
+    /// Query the access-control contract. A failing call (missing contract,
+    /// trap, wrong return type) is reported as `AccessControlUnavailable`
+    /// with an `AccessControlUnavailableEvent` rather than trapping opaquely.
+    /// Budget exhaustion still aborts the whole transaction.
+    fn check_role(
+        env: &Env,
+        contract: &Address,
+        user: &Address,
+        role: u32,
+    ) -> Result<bool, PredifiError> {
+        let result = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            contract,
+            &Symbol::new(env, "has_role"),
+            soroban_sdk::vec![env, user.into_val(env), role.into_val(env)],
+        );
+        match result {
+            Ok(Ok(has_role)) => Ok(has_role),
+            _ => {
+                AccessControlUnavailableEvent {
+                    access_control: contract.clone(),
+                    user: user.clone(),
+                    role,
+                    timestamp: env.ledger().timestamp(),
+                }
+                .publish(env);
+                Err(PredifiError::AccessControlUnavailable)
+            }
+        }
+    }
+
+    fn has_role(env: &Env, contract: &Address, user: &Address, role: u32) -> bool {
+        Self::check_role(env, contract, user, role)
+            .unwrap_or_else(|e| soroban_sdk::panic_with_error!(env, e))
+    }
+
+    fn require_role(env: &Env, user: &Address, role: u32) -> Result<(), PredifiError> {
+        let config = Self::load_config(env);
+        if !Self::check_role(env, &config.access_control, user, role)? {
+            return Err(PredifiError::Unauthorized);
+        }
+        if role == 0 {
+            // Every admin entry point passes through here, which keeps the
+            // dead-man switch's inactivity clock current.
+            env.storage().instance().set(
+                &DataKey::Ext(ExtKey::LastAdminAction),
+                &env.ledger().timestamp(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Earliest time unclaimed funds may be swept while the dead-man switch
+    /// is in effect (0 if it never fired).
+    fn dead_man_claims_until(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::Ext(ExtKey::DeadManTriggered))
+            .map(|at| at.saturating_add(DEAD_MAN_CLAIM_EXTENSION))
+            .unwrap_or(0)
+    }
+
+    fn load_config(env: &Env) -> Config {
+        let raw: Val = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config)
+            .expect("Config not set");
+        Self::extend_instance(env);
+        layout::read_config(env, raw)
+    }
+
+    fn read_paused(env: &Env) -> bool {
+        let paused = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        Self::extend_instance(env);
+        paused
+    }
+
+    /// Publish an `ErrorDetailEvent` describing why `error` fired and return
+    /// it, so a failed transaction can be diagnosed from its simulation
+    /// instead of a local reproduction.
+    fn error_detail(
+        env: &Env,
+        error: PredifiError,
+        pool_id: Option<u64>,
+        expected: i128,
+        actual: i128,
+    ) -> PredifiError {
+        ErrorDetailEvent {
+            code: error as u32,
+            pool_id,
+            expected,
+            actual,
+        }
+        .publish(env);
+        error
+    }
+
+    fn require_not_paused(env: &Env) {
+        if Self::read_paused(env) {
+            panic!("Contract is paused");
+        }
+    }
+
+    /// Like `require_not_paused`, but also honours the per-operation pause
+    /// bit `scope` (one of the `PAUSE_*` constants).
+    fn require_scope_not_paused(env: &Env, scope: u32) {
+        Self::require_not_paused(env);
+        if Self::get_pause_scopes(env.clone()) & scope != 0 {
+            panic!("Operation is paused");
+        }
+    }
+
+    fn enter_reentrancy_guard(env: &Env) {
+        let key = DataKey::ReentrancyGuard;
+        if env.storage().temporary().has(&key) {
+            panic!("Reentrancy detected");
+        }
+        env.storage().temporary().set(&key, &true);
+    }
+
+    fn exit_reentrancy_guard(env: &Env) {
+        env.storage().temporary().remove(&DataKey::ReentrancyGuard);
+    }
+
+    /// `HIGH_VALUE_THRESHOLD` rescaled from 7 decimals to those of `token`.
+    fn high_value_threshold(env: &Env, token: &Address) -> i128 {
+        let decimals = Self::token_params(env, token).decimals;
+        if decimals >= DEFAULT_TOKEN_DECIMALS {
+            HIGH_VALUE_THRESHOLD * 10i128.pow(decimals - DEFAULT_TOKEN_DECIMALS)
+        } else {
+            HIGH_VALUE_THRESHOLD / 10i128.pow(DEFAULT_TOKEN_DECIMALS - decimals)
+        }
+    }
+
+    /// Segregated escrow of a pool; absent for pools created before escrow
+    /// accounting existed, which are then not tracked.
+    fn pool_escrow(env: &Env, pool_id: u64) -> Option<PoolEscrow> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PoolEscrow(pool_id)))
+    }
+
+    fn write_pool_escrow(env: &Env, pool_id: u64, escrow: &PoolEscrow) {
+        let key = DataKey::Ext(ExtKey::PoolEscrow(pool_id));
+        env.storage().persistent().set(&key, escrow);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Book funds received by an Active pool into its locked bucket.
+    fn credit_pool_escrow(env: &Env, pool_id: u64, amount: i128) {
+        if let Some(mut escrow) = Self::pool_escrow(env, pool_id) {
+            escrow.locked += amount;
+            Self::write_pool_escrow(env, pool_id, &escrow);
+        }
+    }
+
+    /// Move a settling pool's locked funds into its claimable bucket.
+    fn release_pool_escrow(env: &Env, pool_id: u64) {
+        if let Some(mut escrow) = Self::pool_escrow(env, pool_id) {
+            escrow.claimable += escrow.locked;
+            escrow.locked = 0;
+            Self::write_pool_escrow(env, pool_id, &escrow);
+        }
+    }
+
+    /// Book `paid + fee` leaving a pool: from its locked bucket while Active,
+    /// its claimable bucket once settled. A debit larger than the bucket means
+    /// the payout is funded by another pool and raises `EscrowShortfallEvent`.
+    fn debit_pool_escrow(env: &Env, pool_id: u64, pool: &Pool, paid: i128, fee: i128) {
+        let Some(mut escrow) = Self::pool_escrow(env, pool_id) else {
+            return;
+        };
+        let required = paid + fee;
+        let bucket = if pool.state == MarketState::Active {
+            &mut escrow.locked
+        } else {
+            &mut escrow.claimable
+        };
+        if required > *bucket {
+            EscrowShortfallEvent {
+                pool_id,
+                required,
+                available: *bucket,
+            }
+            .publish(env);
+        }
+        *bucket -= required;
+        escrow.fees += fee;
+        Self::write_pool_escrow(env, pool_id, &escrow);
+    }
+
+    fn yield_adapter(env: &Env, token: &Address) -> Option<YieldAdapterConfig> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::YieldAdapter(token.clone())))
+    }
+
+    fn yield_deposit(env: &Env, pool_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::YieldDeposit(pool_id)))
+            .unwrap_or(0)
+    }
+
+    /// Emit the ledger entry of a token movement between the contract and
+    /// `account`. Zero amounts are skipped.
+    fn record_ledger_entry(
+        env: &Env,
+        pool_id: Option<u64>,
+        account: &Address,
+        token: &Address,
+        direction: LedgerDirection,
+        amount: i128,
+        reason: &str,
+    ) {
+        if amount <= 0 {
+            return;
+        }
+        LedgerEntryEvent {
+            pool_id,
+            account: account.clone(),
+            token: token.clone(),
+            direction,
+            amount,
+            reason: Symbol::new(env, reason),
+        }
+        .publish(env);
+    }
+
+    /// Pull `amount` of `token` back from its yield adapter. Panics unless the
+    /// contract's balance grew by at least `amount`, so a faulty adapter can
+    /// never leave escrow short.
+    fn withdraw_from_adapter(
+        env: &Env,
+        pool_id: Option<u64>,
+        adapter: &Address,
+        token: &Address,
+        amount: i128,
+    ) {
+        let this = env.current_contract_address();
+        let token_client = token::Client::new(env, token);
+        let before = token_client.balance(&this);
+        env.invoke_contract::<()>(
+            adapter,
+            &Symbol::new(env, "withdraw"),
+            soroban_sdk::vec![
+                env,
+                this.into_val(env),
+                token.into_val(env),
+                amount.into_val(env)
+            ],
+        );
+        assert!(
+            token_client.balance(&this) - before >= amount,
+            "Yield adapter returned less than requested"
+        );
+        Self::record_ledger_entry(
+            env,
+            pool_id,
+            adapter,
+            token,
+            LedgerDirection::In,
+            amount,
+            "yield_withdraw",
+        );
+    }
+
+    /// Return a pool's escrow deposited into the yield adapter, if any. Runs
+    /// before any payout from the pool so settlement always sees its full
+    /// escrow on hand.
+    fn recall_yield_deposit(env: &Env, pool_id: u64, pool: &Pool) -> i128 {
+        let deposited = Self::yield_deposit(env, pool_id);
+        if deposited == 0 {
+            return 0;
+        }
+        let config = Self::yield_adapter(env, &pool.token).expect("Yield adapter not set");
+        Self::withdraw_from_adapter(env, Some(pool_id), &config.adapter, &pool.token, deposited);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Ext(ExtKey::YieldDeposit(pool_id)));
+        let principal_key = DataKey::Ext(ExtKey::YieldPrincipal(pool.token.clone()));
+        let principal: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&principal_key, &(principal - deposited));
+        Self::extend_persistent(env, &principal_key);
+
+        YieldWithdrawnEvent {
+            pool_id,
+            adapter: config.adapter,
+            amount: deposited,
+        }
+        .publish(env);
+        deposited
+    }
+
+    /// Reject operator notes longer than `MAX_NOTE_LEN` bytes.
+    fn check_note(note: &String) -> Result<(), PredifiError> {
+        if note.len() > MAX_NOTE_LEN {
+            return Err(PredifiError::NoteTooLong);
+        }
+        Ok(())
+    }
+
+    /// Persist a non-empty settlement note for the pool. Entry points taking
+    /// a note from the caller validate it with `check_note` first.
+    fn record_operator_note(env: &Env, pool_id: u64, author: &Address, note: &String) {
+        if note.is_empty() {
+            return;
+        }
+        let key = DataKey::OperatorNote(pool_id);
+        env.storage().persistent().set(
+            &key,
+            &OperatorNote {
+                author: author.clone(),
+                note: note.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Panic with the legacy messages if a pool is missing or already
+    /// settled. Single-pool resolution keeps these panics; all other rules
+    /// live in `check_resolvable`.
+    fn assert_not_settled(env: &Env, pool_id: u64) {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .expect("Pool not found");
+        assert!(!pool.resolved, "Pool already resolved");
+        assert!(!pool.canceled, "Cannot resolve a canceled pool");
+    }
+
+    /// Resolution rules shared by every resolution entry point (operator,
+    /// oracle, batch, keeper, optimistic): the pool passes
+    /// `check_settleable`, is not a derivative market (those only settle
+    /// through `resolve_derivative_pool`) and `outcome` is in range.
+    /// `allow_zero_stake` accepts an outcome nobody staked on (see
+    /// `force_resolve_pool`).
+    fn check_resolvable(
+        env: &Env,
+        pool_id: u64,
+        outcome: u32,
+        allow_zero_stake: bool,
+    ) -> Result<Pool, PredifiError> {
+        let pool = Self::check_settleable(env, pool_id)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::DerivativeLink(pool_id))
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+        if !allow_zero_stake && Self::is_zero_stake_outcome(env, pool_id, &pool, outcome) {
+            return Err(PredifiError::ZeroStakeOutcome);
+        }
+        Ok(pool)
+    }
+
+    /// Checks every settlement path shares: the pool is Active and not
+    /// locked by another resolution flow, the resolution delay has passed
+    /// and a conditional pool's parent is settled.
+    fn check_settleable(env: &Env, pool_id: u64) -> Result<Pool, PredifiError> {
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        if pool.state != MarketState::Active
+            || pool.resolved
+            || pool.canceled
+            || Self::is_resolution_locked(env, pool_id)
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let config = Self::load_config(env);
+        let resolvable_at = pool.end_time.saturating_add(config.resolution_delay);
+        if env.ledger().timestamp() < resolvable_at {
+            return Err(Self::error_detail(
+                env,
+                PredifiError::ResolutionDelayNotMet,
+                Some(pool_id),
+                resolvable_at as i128,
+                env.ledger().timestamp() as i128,
+            ));
+        }
+        Self::require_parent_resolved(env, pool_id)?;
+        Ok(pool)
+    }
+
+    /// Time a pool was resolved, as checkpointed with its analytics. Pools
+    /// resolved before analytics were recorded report 0.
+    fn resolved_at(env: &Env, pool_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get::<_, PoolAnalytics>(&DataKey::Ext(ExtKey::PoolAnalytics(pool_id)))
+            .map(|analytics| analytics.resolved_at)
+            .unwrap_or(0)
+    }
+
+    /// A conditional pool may only resolve once its parent has resolved to
+    /// the required outcome (it is canceled if the parent settles otherwise).
+    fn require_parent_resolved(env: &Env, pool_id: u64) -> Result<(), PredifiError> {
+        let condition: Option<ParentCondition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ParentCondition(pool_id)));
+        let Some(condition) = condition else {
+            return Ok(());
+        };
+        let parent: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(condition.parent_pool_id))
+            .ok_or(PredifiError::InvalidPoolReference)?;
+        match parent.state {
+            MarketState::Resolved if parent.outcome == condition.parent_outcome => Ok(()),
+            MarketState::Active | MarketState::Disputed => Err(PredifiError::PoolNotResolved),
+            _ => Err(PredifiError::InvalidPoolReference),
+        }
+    }
+
+    /// Cancel the still-active conditional pools of a settled parent whose
+    /// condition failed: all of them on cancellation (`outcome` = None),
+    /// those gated on another outcome on resolution.
+    fn cancel_failed_children(env: &Env, pool_id: u64, outcome: Option<u32>) {
+        let children: Vec<u64> = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ChildPools(pool_id)))
+        {
+            Some(children) => children,
+            None => return,
+        };
+        for child_id in children.iter() {
+            let condition: ParentCondition = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Ext(ExtKey::ParentCondition(child_id)))
+            {
+                Some(condition) => condition,
+                None => continue,
+            };
+            if outcome == Some(condition.parent_outcome) {
+                continue;
+            }
+            let child: Pool = match env.storage().persistent().get(&DataKey::Pool(child_id)) {
+                Some(child) => child,
+                None => continue,
+            };
+            if child.state != MarketState::Active {
+                continue;
+            }
+            let reason = String::from_str(env, "parent condition not met");
+            Self::finalize_cancellation(
+                env,
+                child_id,
+                child,
+                env.current_contract_address(),
+                reason,
+            );
+        }
+    }
+
+    /// True while a pool's result is pending a sealed commitment, an outcome
+    /// assertion or an authorized outcome; such pools only resolve through
+    /// that flow.
+    fn is_resolution_locked(env: &Env, pool_id: u64) -> bool {
+        let storage = env.storage().persistent();
+        storage.has(&DataKey::Ext(ExtKey::ResolutionCommitment(pool_id)))
+            || storage.has(&DataKey::Ext(ExtKey::OutcomeAssertion(pool_id)))
+            || storage.has(&KeeperKey::AuthorizedOutcome(pool_id))
+    }
+
+    /// True when the pool has bets but none of them are on `outcome`, i.e.
+    /// resolving to it would make every bettor lose. This is far more likely
+    /// to be an input error than a genuine result.
+    fn is_zero_stake_outcome(env: &Env, pool_id: u64, pool: &Pool, outcome: u32) -> bool {
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let mut staked = false;
+        for stake in stakes.iter() {
+            staked = staked || stake > 0;
+        }
+        staked && stakes.get(outcome).unwrap_or(0) == 0
+    }
+
+    /// Time from which a pool stops accepting bets: its lock time if one was
+    /// set at creation, else its end_time.
+    fn lock_time(env: &Env, pool_id: u64, pool: &Pool) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LockTime(pool_id))
+            .unwrap_or(pool.end_time)
+    }
+
+    /// Pre-check that `from` holds `amount` of `token`, so an underfunded
+    /// transfer fails with `InsufficientBalance` instead of a token host error.
+    fn check_balance(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        let available = token::Client::new(env, token).balance(from);
+        if available < amount {
+            InsufficientBalanceEvent {
+                user: from.clone(),
+                token: token.clone(),
+                required: amount,
+                available,
+            }
+            .publish(env);
+            return Err(PredifiError::InsufficientBalance);
+        }
+        Ok(())
+    }
+
+    /// Check a bet against the pool's stake cap. Returns the amount to accept:
+    /// `amount`, or the remaining capacity if the pool truncates bets at its
+    /// cap. Emits `PoolCapReachedEvent` when the accepted bet fills the pool.
+    fn apply_stake_cap(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        let cap: Option<StakeCap> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::StakeCap(pool_id)));
+        let Some(cap) = cap else {
+            return Ok(amount);
+        };
+        let remaining = cap.max_total_stake.saturating_sub(pool.total_stake);
+        if remaining <= 0 || (amount > remaining && !cap.truncate_at_cap) {
+            return Err(Self::error_detail(
+                env,
+                PredifiError::PoolStakeCapReached,
+                Some(pool_id),
+                remaining.max(0),
+                amount,
+            ));
+        }
+        let accepted = core::cmp::min(amount, remaining);
+        if accepted == remaining {
+            PoolCapReachedEvent {
+                pool_id,
+                max_total_stake: cap.max_total_stake,
+            }
+            .publish(env);
+        }
+        Ok(accepted)
+    }
+
+    /// Pure: decimal odds of an outcome, as in `PoolStats::current_odds`
+    /// (0 while nobody staked on it).
+    fn implied_odds(total_stake: i128, outcome_stake: i128) -> u64 {
+        if outcome_stake <= 0 {
+            return 0;
+        }
+        (total_stake.saturating_mul(10_000) / outcome_stake) as u64
+    }
+
+    /// Notify odds alert subscribers whose threshold was crossed by a bet that
+    /// added `legs` to the pool (already applied to `pool` and its stakes).
+    fn check_odds_alerts(env: &Env, pool_id: u64, pool: &Pool, legs: &Vec<BetLeg>) {
+        let alerts: Vec<OddsAlert> = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OddsAlerts(pool_id)))
+        {
+            Some(alerts) => alerts,
+            None => return,
+        };
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let mut added_total: i128 = 0;
+        for leg in legs.iter() {
+            added_total += leg.amount;
+        }
+
+        for alert in alerts.iter() {
+            let after = stakes.get(alert.outcome).unwrap_or(0);
+            let mut before = after;
+            for leg in legs.iter() {
+                if leg.outcome == alert.outcome {
+                    before -= leg.amount;
+                }
+            }
+            let odds_before = Self::implied_odds(pool.total_stake - added_total, before);
+            let odds_after = Self::implied_odds(pool.total_stake, after);
+            if (odds_before < alert.threshold_bps) != (odds_after < alert.threshold_bps) {
+                OddsAlertTriggeredEvent {
+                    subscriber: alert.subscriber,
+                    pool_id,
+                    outcome: alert.outcome,
+                    threshold_bps: alert.threshold_bps,
+                    odds_bps: odds_after,
+                }
+                .publish(env);
+            }
+        }
+    }
+
+    fn new_bet_curve(env: &Env) -> BetCurve {
+        let mut time_buckets = Vec::new(env);
+        for _ in 0..ANALYTICS_TIME_BUCKETS {
+            time_buckets.push_back(0i128);
+        }
+        BetCurve {
+            opened_at: env.ledger().timestamp(),
+            bet_count: 0,
+            stake_sum: 0,
+            size_buckets: Vec::new(env),
+            time_buckets,
+        }
+    }
+
+    /// Add a bet to the pool's running betting counters.
+    fn record_bet(env: &Env, pool_id: u64, pool: &Pool, amount: i128) {
+        let curve_key = DataKey::Ext(ExtKey::BetCurve(pool_id));
+        let mut curve: BetCurve = env
+            .storage()
+            .persistent()
+            .get(&curve_key)
+            .unwrap_or_else(|| Self::new_bet_curve(env));
+        curve.bet_count = curve.bet_count.saturating_add(1);
+        curve.stake_sum = curve.stake_sum.saturating_add(amount);
+
+        let mut size_bucket: u32 = 0;
+        let mut rest = amount / 10;
+        while rest > 0 {
+            size_bucket += 1;
+            rest /= 10;
+        }
+        while curve.size_buckets.len() <= size_bucket {
+            curve.size_buckets.push_back(0);
+        }
+        let bets = curve.size_buckets.get(size_bucket).unwrap_or(0);
+        curve.size_buckets.set(size_bucket, bets.saturating_add(1));
+
+        let now = env.ledger().timestamp();
+        let window = Self::lock_time(env, pool_id, pool)
+            .saturating_sub(curve.opened_at)
+            .max(1);
+        let elapsed = now.saturating_sub(curve.opened_at);
+        let time_bucket = core::cmp::min(
+            elapsed.saturating_mul(ANALYTICS_TIME_BUCKETS as u64) / window,
+            (ANALYTICS_TIME_BUCKETS - 1) as u64,
+        ) as u32;
+        let placed = curve.time_buckets.get(time_bucket).unwrap_or(0);
+        curve
+            .time_buckets
+            .set(time_bucket, placed.saturating_add(amount));
+
+        env.storage().persistent().set(&curve_key, &curve);
+        Self::extend_persistent(env, &curve_key);
+    }
+
+    /// Add to a user's running activity counters.
+    fn record_user_activity(env: &Env, user: &Address, volume: i128, pools: u32, wins: u32) {
+        let key = DataKey::Ext(ExtKey::UserActivity(user.clone()));
+        let mut activity: UserActivity = env.storage().persistent().get(&key).unwrap_or_default();
+        activity.volume = activity.volume.saturating_add(volume);
+        activity.pools = activity.pools.saturating_add(pools);
+        activity.wins = activity.wins.saturating_add(wins);
+        env.storage().persistent().set(&key, &activity);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Fold a resolved pool's betting counters into its analytics checkpoint.
+    fn checkpoint_pool_analytics(env: &Env, pool_id: u64) {
+        let curve_key = DataKey::Ext(ExtKey::BetCurve(pool_id));
+        let curve: BetCurve = env
+            .storage()
+            .persistent()
+            .get(&curve_key)
+            .unwrap_or_else(|| Self::new_bet_curve(env));
+
+        // The median bet is the ceil(n/2)-th one in stake order.
+        let median_rank = curve.bet_count.div_ceil(2);
+        let mut seen: u32 = 0;
+        let mut median_stake_floor: i128 = 0;
+        let mut floor: i128 = 1;
+        for bets in curve.size_buckets.iter() {
+            seen = seen.saturating_add(bets);
+            if median_rank > 0 && seen >= median_rank {
+                median_stake_floor = floor;
+                break;
+            }
+            floor = floor.saturating_mul(10);
+        }
+
+        let analytics = PoolAnalytics {
+            bet_count: curve.bet_count,
+            total_bet_stake: curve.stake_sum,
+            mean_stake: if curve.bet_count > 0 {
+                curve.stake_sum / curve.bet_count as i128
+            } else {
+                0
+            },
+            median_stake_floor,
+            size_buckets: curve.size_buckets,
+            time_buckets: curve.time_buckets,
+            resolved_at: env.ledger().timestamp(),
+        };
+        let analytics_key = DataKey::Ext(ExtKey::PoolAnalytics(pool_id));
+        env.storage().persistent().set(&analytics_key, &analytics);
+        Self::extend_persistent(env, &analytics_key);
+        env.storage().persistent().remove(&curve_key);
+    }
+
+    /// Record the latency of a resolution against the resolver's SLA stats and
+    /// report a breach when it exceeds the configured SLA.
+    fn record_resolution_latency(env: &Env, pool_id: u64, pool: &Pool, resolver: &Address) {
+        let config = Self::load_config(env);
+        let resolvable_at = pool.end_time.saturating_add(config.resolution_delay);
+        let latency = env.ledger().timestamp().saturating_sub(resolvable_at);
+        let resolution_sla: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ResolutionSla)
+            .unwrap_or(0);
+        let breached = resolution_sla > 0 && latency > resolution_sla;
+
+        let stats_key = DataKey::OperatorSlaStats(resolver.clone());
+        let mut stats: OperatorSlaStats = env
+            .storage()
+            .persistent()
+            .get(&stats_key)
+            .unwrap_or_default();
+        stats.resolutions = stats.resolutions.saturating_add(1);
+        stats.total_latency = stats.total_latency.saturating_add(latency);
+        stats.max_latency = core::cmp::max(stats.max_latency, latency);
+        if breached {
+            stats.late_resolutions = stats.late_resolutions.saturating_add(1);
+        }
+        env.storage().persistent().set(&stats_key, &stats);
+        Self::extend_persistent(env, &stats_key);
+
+        if breached {
+            ResolutionSlaBreachedEvent {
+                operator: resolver.clone(),
+                pool_id,
+                latency,
+                resolution_sla,
+            }
+            .publish(env);
+        }
+    }
+
+    /// Return a pool's creator bond to the creator, or forfeit it to the
+    /// treasury. No-op if no bond is held.
+    fn settle_creator_bond(env: &Env, pool_id: u64, pool: &Pool, forfeit: bool) {
+        let key = DataKey::Ext(ExtKey::CreatorBond(pool_id));
+        let Some(bond) = env.storage().persistent().get::<_, CreatorBond>(&key) else {
+            return;
+        };
+        env.storage().persistent().remove(&key);
+
+        let recipient = if forfeit {
+            Self::load_config(env).treasury
+        } else {
+            pool.creator.clone()
+        };
+        let token_client = token::Client::new(env, &bond.token);
+        token_client.transfer(&env.current_contract_address(), &recipient, &bond.amount);
+        Self::record_ledger_entry(
+            env,
+            Some(pool_id),
+            &recipient,
+            &bond.token,
+            LedgerDirection::Out,
+            bond.amount,
+            if forfeit {
+                "bond_forfeit"
+            } else {
+                "bond_return"
+            },
+        );
+
+        CreatorBondSettledEvent {
+            pool_id,
+            creator: pool.creator.clone(),
+            token: bond.token,
+            amount: bond.amount,
+            forfeited: forfeit,
+        }
+        .publish(env);
+    }
+
+    /// Pay out assertion bonds held for a pool in its token.
+    fn pay_assertion_bond(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        recipient: &Address,
+        amount: i128,
+        reason: &str,
+    ) {
+        let token_client = token::Client::new(env, &pool.token);
+        token_client.transfer(&env.current_contract_address(), recipient, &amount);
+        Self::record_ledger_entry(
+            env,
+            Some(pool_id),
+            recipient,
+            &pool.token,
+            LedgerDirection::Out,
+            amount,
+            reason,
+        );
+    }
+
+    /// Return the bonds of a pending outcome assertion when its pool is
+    /// canceled. No-op if none is pending.
+    fn refund_outcome_assertion(env: &Env, pool_id: u64, pool: &Pool) {
+        let key = DataKey::Ext(ExtKey::OutcomeAssertion(pool_id));
+        let Some(assertion) = env.storage().persistent().get::<_, OutcomeAssertion>(&key) else {
+            return;
+        };
+        env.storage().persistent().remove(&key);
+
+        Self::pay_assertion_bond(
+            env,
+            pool_id,
+            pool,
+            &assertion.asserter,
+            assertion.bond,
+            "assertion_refund",
+        );
+        if let Some(challenger) = assertion.challenger {
+            Self::pay_assertion_bond(
+                env,
+                pool_id,
+                pool,
+                &challenger,
+                assertion.bond,
+                "assertion_refund",
+            );
+        }
+    }
+
+    /// Cancellation rules shared by every cancellation entry point (operator,
+    /// batch, keeper, bettor supermajority): resolved pools report
+    /// `PoolNotResolved`, canceled ones `InvalidPoolState` (INV-2).
+    fn check_cancelable(env: &Env, pool_id: u64) -> Result<Pool, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        if pool.resolved {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if pool.canceled || !Self::is_valid_state_transition(pool.state, MarketState::Canceled) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        Ok(pool)
+    }
+
+    /// Block claims on a freshly (re)resolved pool for the dispute window and,
+    /// for high-value pools, the settlement hold. Returns when claims open.
+    fn schedule_claims_opening(env: &Env, pool_id: u64, pool: &Pool) -> u64 {
+        let now = env.ledger().timestamp();
+        let dispute_window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeWindow)
+            .unwrap_or(0);
+        let mut claims_open_at = if dispute_window > 0 {
+            now.saturating_add(dispute_window)
+        } else {
+            0
+        };
+
+        let config = Self::load_config(env);
+        let held = config.settlement_hold_threshold > 0
+            && pool.total_stake >= config.settlement_hold_threshold;
+        if held {
+            claims_open_at = core::cmp::max(
+                claims_open_at,
+                now.saturating_add(config.settlement_hold_delay),
+            );
+        }
+
+        let open_key = DataKey::ClaimsOpenAt(pool_id);
+        if claims_open_at > now {
+            env.storage().persistent().set(&open_key, &claims_open_at);
+            Self::extend_persistent(env, &open_key);
+        } else {
+            env.storage().persistent().remove(&open_key);
+        }
+
+        if held {
+            SettlementHoldAppliedEvent {
+                pool_id,
+                total_stake: pool.total_stake,
+                claims_open_at,
+            }
+            .publish(env);
+        }
+        claims_open_at
+    }
+
+    /// Effects shared by every resolution path (operator, oracle, derivative):
+    /// transition the pool to Resolved, persist it, emit the resolved events and
+    /// notify the lifecycle callback.
+    /// PRE: caller has validated authorization, timing and `outcome`
+    /// POST: pool.state = Resolved (INV-2)
+    fn finalize_resolution(
+        env: &Env,
+        pool_id: u64,
+        mut pool: Pool,
+        outcome: u32,
+        resolver: Address,
+        note: String,
+    ) {
+        Self::flush_stake_delta(env, pool_id, &mut pool);
+        Self::recall_yield_deposit(env, pool_id, &pool);
+        Self::release_pool_escrow(env, pool_id);
+        pool.state = MarketState::Resolved;
+        pool.resolved = true;
+        pool.outcome = outcome;
+
+        let pool_key = DataKey::Pool(pool_id);
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(env, &pool_key);
+        Self::write_active_pool_count(env, Self::read_active_pool_count(env).saturating_sub(1));
+        Self::snapshot_pool_fee_bps(env, pool_id, &pool);
+
+        Self::schedule_claims_opening(env, pool_id, &pool);
+        Self::record_resolution_latency(env, pool_id, &pool, &resolver);
+        Self::checkpoint_pool_analytics(env, pool_id);
+
+        // Retrieve winning-outcome stake for the diagnostic event using optimized batch storage
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let winning_stake: i128 = stakes.get(outcome).unwrap_or(0);
+
+        Self::record_operator_note(env, pool_id, &resolver, &note);
+
+        PoolResolvedEvent {
+            pool_id,
+            operator: resolver,
+            outcome,
+            outcome_label: Self::outcome_label(env, pool_id, outcome),
+            note,
+        }
+        .publish(env);
+
+        // 🟢 INFO: enriched diagnostics alongside the standard resolved event.
+        PoolResolvedDiagEvent {
+            pool_id,
+            outcome,
+            total_stake: pool.total_stake,
+            winning_stake,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+
+        let attestation = Self::build_settlement_attestation(env, pool_id, &pool);
+        Self::publish_settlement_attestation(env, attestation);
+
+        // Conditional children are part of this pool's effects: cancel them
+        // before the bond transfer and callback, so neither observes a child
+        // still open on a failed condition.
+        Self::cancel_failed_children(env, pool_id, Some(outcome));
+
+        // --- INTERACTIONS ---
+
+        Self::settle_creator_bond(env, pool_id, &pool, false);
+        Self::queue_pool_callback(env, pool_id, PoolHook::Resolved(outcome));
+        Self::check_pool_invariants(env, pool_id);
+    }
+
+    /// Effects shared by every cancellation path.
+    /// PRE: caller has validated authorization and that the pool is Active
+    /// POST: pool.state = Canceled (INV-2)
+    fn finalize_cancellation(
+        env: &Env,
+        pool_id: u64,
+        mut pool: Pool,
+        caller: Address,
+        reason: String,
+    ) {
+        Self::flush_stake_delta(env, pool_id, &mut pool);
+        Self::recall_yield_deposit(env, pool_id, &pool);
+        Self::release_pool_escrow(env, pool_id);
+        pool.state = MarketState::Canceled;
+        pool.canceled = true;
+
+        let pool_key = DataKey::Pool(pool_id);
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(env, &pool_key);
+        Self::write_active_pool_count(env, Self::read_active_pool_count(env).saturating_sub(1));
+
+        Self::record_operator_note(env, pool_id, &caller, &reason);
+
+        let canceled_at_key = DataKey::CanceledAt(pool_id);
+        env.storage()
+            .persistent()
+            .set(&canceled_at_key, &env.ledger().timestamp());
+        Self::extend_persistent(env, &canceled_at_key);
+
+        PoolCanceledEvent {
+            pool_id,
+            caller: caller.clone(),
+            reason,
+            operator: caller,
+        }
+        .publish(env);
+
+        // As on resolution, conditional children are canceled before this
+        // pool's transfers and callback.
+        Self::cancel_failed_children(env, pool_id, None);
+
+        // The house liquidity goes straight back to the creator; bettors'
+        // stakes are refunded through claim_winnings (LMSR traders' cost
+        // bases through redeem_shares).
+        if pool.initial_liquidity > 0 {
+            let refund = Self::calculate_liquidity_return(env, pool_id, &pool);
+            let amount = refund + Self::lmsr_trading_surplus(env, pool_id, &pool);
+            let claimed_key = DataKey::LiquidityClaimed(pool_id);
+            env.storage().persistent().set(&claimed_key, &true);
+            Self::extend_persistent(env, &claimed_key);
+            let disbursed_key = DataKey::PoolDisbursed(pool_id);
+            let disbursed: i128 = env.storage().persistent().get(&disbursed_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&disbursed_key, &(disbursed + refund));
+            Self::extend_persistent(env, &disbursed_key);
+            Self::debit_pool_escrow(env, pool_id, &pool, refund, 0);
+
+            if amount > 0 {
+                let token_client = token::Client::new(env, &pool.token);
+                token_client.transfer(&env.current_contract_address(), &pool.creator, &amount);
+                Self::record_ledger_entry(
+                    env,
+                    Some(pool_id),
+                    &pool.creator,
+                    &pool.token,
+                    LedgerDirection::Out,
+                    amount,
+                    "liquidity_return",
+                );
+            }
+            LiquidityReturnedEvent {
+                pool_id,
+                creator: pool.creator.clone(),
+                amount,
+                fee: 0,
+            }
+            .publish(env);
+        }
+
+        Self::refund_outcome_assertion(env, pool_id, &pool);
+        Self::settle_creator_bond(env, pool_id, &pool, false);
+        Self::queue_pool_callback(env, pool_id, PoolHook::Canceled);
+        Self::check_pool_invariants(env, pool_id);
+    }
+
+    /// Pure (storage reads only): the total amount a prediction is entitled to
+    /// once the pool has settled — the stake back for canceled pools, the
+    /// pro-rata share of the pool net of the protocol fee for winners, 0
+    /// otherwise.
+    /// POST: result ≤ pool.total_stake (INV-4)
+    fn calculate_entitlement(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: &Prediction,
+    ) -> i128 {
+        let (gross, fee, creator_fee) =
+            Self::calculate_gross_entitlement(env, pool_id, pool, prediction);
+        gross - fee - creator_fee - Self::keeper_fee(env, pool_id, pool, gross)
+    }
+
+    /// Gross amount owed to a position and the (protocol, creator) fees due
+    /// on it. Refunds of canceled pools are fee-free.
+    fn calculate_gross_entitlement(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: &Prediction,
+    ) -> (i128, i128, i128) {
+        let gross = Self::calculate_gross_winnings(env, pool_id, pool, prediction);
+        if pool.state != MarketState::Resolved {
+            return (gross, 0, 0);
+        }
+        let bettor_stake = Self::bettor_stake(env, pool_id, pool);
+        let fee = Self::calculate_protocol_fee(
+            gross,
+            pool.total_stake,
+            bettor_stake,
+            Self::pool_fee_bps(env, pool_id, pool),
+        );
+        let creator_fee_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreatorFeeBps(pool_id))
+            .unwrap_or(0);
+        let creator_fee =
+            Self::calculate_protocol_fee(gross, pool.total_stake, bettor_stake, creator_fee_bps);
+        (gross, fee, creator_fee)
+    }
+
+    fn calculate_gross_winnings(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: &Prediction,
+    ) -> i128 {
+        if pool.state == MarketState::Canceled {
+            return prediction.amount;
+        }
+        if prediction.outcome != pool.outcome {
+            return 0;
+        }
+
+        // Get winning stake using optimized batch storage
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let winning_stake: i128 = stakes.get(pool.outcome).unwrap_or(0);
+
+        // Use pure function for winnings calculation (verifiable). The house
+        // seed on the winning outcome shares the pool with the bettors.
+        let winnings = Self::calculate_winnings(
+            prediction.amount,
+            winning_stake + Self::liquidity_seed(pool),
+            pool.total_stake,
+        );
+        // Handicap pools scale the payout by the winning outcome's weight.
+        let winnings =
+            winnings * Self::outcome_weight_bps(env, pool_id, pool.outcome) as i128 / 10_000;
+
+        // Verify invariant: winnings ≤ total_stake (INV-4)
+        assert!(winnings <= pool.total_stake, "Winnings exceed total stake");
+        winnings
+    }
+
+    /// Stakes placed by bettors, i.e. the pool's escrow excluding the house
+    /// liquidity (INV-1).
+    fn bettor_stake(env: &Env, pool_id: u64, pool: &Pool) -> i128 {
+        let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+        let mut total: i128 = 0;
+        for stake in stakes.iter() {
+            total = total.checked_add(stake).expect("overflow");
+        }
+        total
+    }
+
+    /// Pure: the creator's virtual position on each outcome. Initial liquidity
+    /// is spread evenly over the outcomes; the remainder of the division is
+    /// left to the winners.
+    fn liquidity_seed(pool: &Pool) -> i128 {
+        if pool.options_count == 0 {
+            return 0;
+        }
+        pool.initial_liquidity / pool.options_count as i128
+    }
+
+    /// Label of an outcome, or an empty string if the pool has no labels.
+    fn outcome_label(env: &Env, pool_id: u64, outcome: u32) -> String {
+        let labels: Option<Vec<String>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OutcomeLabels(pool_id)));
+        labels
+            .and_then(|l| l.get(outcome))
+            .unwrap_or(String::from_str(env, ""))
+    }
+
+    /// Payout multiplier of an outcome in bps (10_000 unless the pool was
+    /// created with handicap weights).
+    fn outcome_weight_bps(env: &Env, pool_id: u64, outcome: u32) -> u32 {
+        let weights: Option<Vec<u32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OutcomeWeights(pool_id)));
+        weights.and_then(|w| w.get(outcome)).unwrap_or(10_000)
+    }
+
+    /// Gross amount of a settled pool owed back to its creator for the
+    /// initial liquidity:
+    /// - Canceled: `initial_liquidity` in full.
+    /// - Resolved: `seed * total_stake / (winning_stake + seed)` where
+    ///   `seed = initial_liquidity / options_count`, i.e. the seed on the
+    ///   winning outcome is paid like any winning position and the seeds on
+    ///   losing outcomes go to the winners. With no winning stake at all the
+    ///   whole pool returns to the creator.
+    /// - LMSR pools: what the subsidy still covers once traders are owed
+    ///   their winning shares (resolved) or cost basis (canceled), capped at
+    ///   `initial_liquidity`; see `lmsr_trading_surplus` for the rest.
+    fn calculate_liquidity_return(env: &Env, pool_id: u64, pool: &Pool) -> i128 {
+        if let Some(state) = Self::lmsr_state(env, pool_id) {
+            let shortfall = Self::lmsr_liability(pool, &state) - state.collected;
+            return (pool.initial_liquidity - shortfall.max(0)).max(0);
+        }
+        match pool.state {
+            MarketState::Canceled => pool.initial_liquidity,
+            MarketState::Resolved => {
+                let stakes = Self::get_outcome_stakes(env, pool_id, pool.options_count);
+                let winning_stake: i128 = stakes.get(pool.outcome).unwrap_or(0);
+                let seed = Self::liquidity_seed(pool);
+                if winning_stake + seed == 0 {
+                    pool.total_stake
+                } else {
+                    // The house seed's share, plus what handicap weights
+                    // withheld from the winners' share.
+                    let winners_share = Self::calculate_winnings(
+                        winning_stake,
+                        winning_stake + seed,
+                        pool.total_stake,
+                    );
+                    let weight = Self::outcome_weight_bps(env, pool_id, pool.outcome) as i128;
+                    Self::calculate_winnings(seed, winning_stake + seed, pool.total_stake)
+                        + winners_share * (10_000 - weight) / 10_000
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Market state of an LMSR pool, or None for parimutuel pools.
+    fn lmsr_state(env: &Env, pool_id: u64) -> Option<LmsrState> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::LmsrState(pool_id)))
+    }
+
+    fn zero_shares(env: &Env, options_count: u32) -> Vec<i128> {
+        let mut shares = Vec::new(env);
+        for _ in 0..options_count {
+            shares.push_back(0i128);
+        }
+        shares
+    }
+
+    /// What a settled LMSR pool owes its traders: one unit per winning share
+    /// once resolved, the positive cost bases once canceled.
+    fn lmsr_liability(pool: &Pool, state: &LmsrState) -> i128 {
+        match pool.state {
+            MarketState::Resolved => state.shares.get(pool.outcome).unwrap_or(0),
+            MarketState::Canceled => state.open_basis,
+            _ => 0,
+        }
+    }
+
+    /// Trade proceeds of a settled LMSR pool left after its liability. Held
+    /// outside the pool escrow and paid to the creator with the liquidity.
+    fn lmsr_trading_surplus(env: &Env, pool_id: u64, pool: &Pool) -> i128 {
+        match Self::lmsr_state(env, pool_id) {
+            Some(state) => (state.collected - Self::lmsr_liability(pool, &state)).max(0),
+            None => 0,
+        }
+    }
+
+    /// LMSR cost function C(q) = b·ln Σ exp(q_i / b), evaluated as
+    /// max(q) + b·ln Σ exp((q_i − max(q)) / b) so every exponent is ≤ 0.
+    fn lmsr_cost(b: i128, shares: &Vec<i128>) -> Result<i128, PredifiError> {
+        let max = shares.iter().max().unwrap_or(0);
+        let mut sum: i128 = 0;
+        for q in shares.iter() {
+            let x = (q - max)
+                .checked_mul(safe_math::WAD)
+                .ok_or(PredifiError::InvalidAmount)?
+                / b;
+            sum += SafeMath::exp_wad(x).map_err(|_| PredifiError::InvalidAmount)?;
+        }
+        let ln = SafeMath::ln_wad(sum).map_err(|_| PredifiError::InvalidAmount)?;
+        let scaled = ln.checked_mul(b).ok_or(PredifiError::InvalidAmount)?;
+        max.checked_add(scaled / safe_math::WAD)
+            .ok_or(PredifiError::InvalidAmount)
+    }
+
+    /// Shared checks and pricing of an LMSR trade of `delta` shares
+    /// (negative to sell). Returns the state, the trader's position and the
+    /// cost change C(q') − C(q), rounded up by one unit against the trader.
+    fn lmsr_trade(
+        env: &Env,
+        trader: &Address,
+        pool_id: u64,
+        outcome: u32,
+        delta: i128,
+    ) -> Result<(Pool, LmsrState, LmsrPosition, i128), PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let mut state = Self::lmsr_state(env, pool_id).ok_or(PredifiError::InvalidPoolState)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let lock_time = Self::lock_time(env, pool_id, &pool);
+        if env.ledger().timestamp() >= lock_time {
+            return Err(Self::error_detail(
+                env,
+                PredifiError::PredictionTooLate,
+                Some(pool_id),
+                lock_time as i128,
+                env.ledger().timestamp() as i128,
+            ));
+        }
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+
+        let mut position: LmsrPosition = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::LmsrPosition(pool_id, trader.clone())))
+            .unwrap_or(LmsrPosition {
+                shares: Self::zero_shares(env, pool.options_count),
+                basis: 0,
+            });
+        let held = position.shares.get(outcome).unwrap_or(0) + delta;
+        if held < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        position.shares.set(outcome, held);
+
+        let before = Self::lmsr_cost(state.b, &state.shares)?;
+        let outstanding = state
+            .shares
+            .get(outcome)
+            .unwrap_or(0)
+            .checked_add(delta)
+            .ok_or(PredifiError::InvalidAmount)?;
+        state.shares.set(outcome, outstanding);
+        let after = Self::lmsr_cost(state.b, &state.shares)?;
+
+        Ok((pool, state, position, after - before + 1))
+    }
+
+    /// Persist an LMSR trade: moves `amount` (positive = paid in) into the
+    /// pool's proceeds and the trader's cost basis, accrues `fee` to the
+    /// creator and emits the trade event.
+    #[allow(clippy::too_many_arguments)]
+    fn commit_lmsr_trade(
+        env: &Env,
+        trader: &Address,
+        pool_id: u64,
+        pool: &Pool,
+        mut state: LmsrState,
+        mut position: LmsrPosition,
+        outcome: u32,
+        delta: i128,
+        amount: i128,
+        fee: i128,
+    ) {
+        let old_basis = position.basis.max(0);
+        position.basis += amount;
+        state.collected += amount;
+        state.open_basis += position.basis.max(0) - old_basis;
+
+        let state_key = DataKey::Ext(ExtKey::LmsrState(pool_id));
+        env.storage().persistent().set(&state_key, &state);
+        Self::extend_persistent(env, &state_key);
+        let position_key = DataKey::Ext(ExtKey::LmsrPosition(pool_id, trader.clone()));
+        env.storage().persistent().set(&position_key, &position);
+        Self::extend_persistent(env, &position_key);
+
+        if fee > 0 {
+            Self::accrue_creator_fee(env, pool_id, pool, fee);
+        }
+
+        SharesTradedEvent {
+            pool_id,
+            trader: trader.clone(),
+            outcome,
+            shares: delta,
+            amount: amount.abs(),
+            fee,
+        }
+        .publish(env);
+    }
+
+    /// Reserve the delay compensation for a completed refund, if enabled.
+    /// Pays at most what the token's compensation reserve still holds.
+    /// Returns (compensation, delay).
+    fn take_cancellation_compensation(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        refund: i128,
+    ) -> (i128, u64) {
+        let config: Option<CompensationConfig> =
+            env.storage().instance().get(&DataKey::CompensationConfig);
+        let config = match config {
+            Some(c) => c,
+            None => return (0, 0),
+        };
+        let canceled_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CanceledAt(pool_id))
+            .unwrap_or(0);
+        let delay = canceled_at.saturating_sub(pool.end_time);
+        let owed = Self::calculate_compensation(refund, delay, &config);
+        if owed == 0 {
+            return (0, delay);
+        }
+
+        let reserve_key = DataKey::CompensationReserve(pool.token.clone());
+        let reserve: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+        let paid = core::cmp::min(owed, reserve);
+        if paid > 0 {
+            env.storage()
+                .persistent()
+                .set(&reserve_key, &(reserve - paid));
+            Self::extend_persistent(env, &reserve_key);
+        }
+        (paid, delay)
+    }
+
+    fn is_compliance_flagged(env: &Env, user: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ComplianceFlag(user.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Append a change to the config history ring buffer, overwriting the
+    /// oldest entry once `CONFIG_HISTORY_CAPACITY` is reached.
+    fn record_config_change(
+        env: &Env,
+        admin: &Address,
+        field: &str,
+        old_value: ConfigValue,
+        new_value: ConfigValue,
+    ) {
+        let seq: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConfigHistoryCount)
+            .unwrap_or(0);
+        let key = DataKey::ConfigHistory(seq % CONFIG_HISTORY_CAPACITY);
+        env.storage().persistent().set(
+            &key,
+            &ConfigChange {
+                seq,
+                field: Symbol::new(env, field),
+                old_value,
+                new_value,
+                admin: admin.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(env, &key);
+        env.storage()
+            .instance()
+            .set(&DataKey::ConfigHistoryCount, &(seq + 1));
+    }
+
+    /// Drop every `UserPredictionIndex` entry of `user` pointing at `pool_id`,
+    /// swapping the last entry into each freed slot.
+    fn remove_user_prediction_index(env: &Env, user: &Address, pool_id: u64) {
+        let count_key = DataKey::UserPredictionCount(user.clone());
+        let mut count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut i = count;
+        while i > 0 {
+            i -= 1;
+            let index_key = DataKey::UserPredictionIndex(user.clone(), i);
+            let indexed: Option<u64> = env.storage().persistent().get(&index_key);
+            if indexed != Some(pool_id) {
+                continue;
+            }
+            count -= 1;
+            let last_key = DataKey::UserPredictionIndex(user.clone(), count);
+            if i != count {
+                let last: u64 = env.storage().persistent().get(&last_key).unwrap();
+                env.storage().persistent().set(&index_key, &last);
+                Self::extend_persistent(env, &index_key);
+            }
+            env.storage().persistent().remove(&last_key);
+        }
+        env.storage().persistent().set(&count_key, &count);
+        Self::extend_persistent(env, &count_key);
+    }
+
+    /// Drop `user` from the pool's bettor index, swapping the last bettor into
+    /// the freed slot and decrementing the participant count.
+    fn remove_pool_bettor(env: &Env, pool_id: u64, user: &Address) {
+        let pc_key = DataKey::ParticipantsCount(pool_id);
+        let pc: u32 = env.storage().persistent().get(&pc_key).unwrap_or(0);
+        for i in 0..pc {
+            let slot_key = DataKey::PoolBettor(pool_id, i);
+            let bettor: Option<Address> = env.storage().persistent().get(&slot_key);
+            if bettor.as_ref() != Some(user) {
+                continue;
+            }
+            let last_key = DataKey::PoolBettor(pool_id, pc - 1);
+            if i != pc - 1 {
+                let last: Address = env.storage().persistent().get(&last_key).unwrap();
+                env.storage().persistent().set(&slot_key, &last);
+                Self::extend_persistent(env, &slot_key);
+            }
+            env.storage().persistent().remove(&last_key);
+            env.storage().persistent().set(&pc_key, &(pc - 1));
+            Self::extend_persistent(env, &pc_key);
+            return;
+        }
+    }
+
+    /// Funds still held in escrow for a pool.
+    fn pool_unclaimed(env: &Env, pool_id: u64, pool: &Pool) -> i128 {
+        let disbursed: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PoolDisbursed(pool_id))
+            .unwrap_or(0);
+        pool.total_stake + Self::stake_delta(env, pool_id) - disbursed
+    }
+
+    fn summarize_pool(pool_id: u64, pool: Pool) -> PoolSummary {
+        PoolSummary {
+            pool_id,
+            end_time: pool.end_time,
+            state: pool.state,
+            outcome: pool.outcome,
+            token: pool.token,
+            total_stake: pool.total_stake,
+            category: pool.category,
+            options_count: pool.options_count,
+            min_stake: pool.min_stake,
+            max_stake: pool.max_stake,
+            initial_liquidity: pool.initial_liquidity,
+            creator: pool.creator,
+        }
+    }
+
+    fn oracle_quorum(env: &Env, pool_id: u64) -> u32 {
+        let quorum: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OracleQuorum(pool_id))
+            .unwrap_or(1);
+        quorum.max(1)
+    }
+
+    fn bet_legs(env: &Env, user: &Address, pool_id: u64) -> Option<Vec<BetLeg>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::BetLegs(user.clone(), pool_id)))
+    }
+
+    /// The position a prediction settles as. A multi-leg bet on a resolved
+    /// pool settles as its leg on the winning outcome (a losing position if
+    /// none); otherwise the stored prediction is used as is.
+    fn settled_prediction(
+        env: &Env,
+        user: &Address,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: Prediction,
+    ) -> Prediction {
+        if pool.state != MarketState::Resolved {
+            return prediction;
+        }
+        let Some(legs) = Self::bet_legs(env, user, pool_id) else {
+            return prediction;
+        };
+        for leg in legs.iter() {
+            if leg.outcome == pool.outcome {
+                return Prediction {
+                    amount: leg.amount,
+                    outcome: leg.outcome,
+                };
+            }
+        }
+        prediction
+    }
+
+    fn claim_stats(env: &Env, pool_id: u64, options_count: u32) -> ClaimStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ClaimStats(pool_id)))
+            .unwrap_or_else(|| {
+                let mut stats = ClaimStats {
+                    claims: Vec::new(env),
+                    paid: Vec::new(env),
+                    settled_stake: Vec::new(env),
+                };
+                for _ in 0..options_count {
+                    stats.claims.push_back(0);
+                    stats.paid.push_back(0);
+                    stats.settled_stake.push_back(0);
+                }
+                stats
+            })
+    }
+
+    /// Add a payout to the pool's per-outcome claim counters.
+    fn record_claim_stats(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        prediction: &Prediction,
+        first_payout: bool,
+        amount: i128,
+        settled: bool,
+    ) {
+        let mut stats = Self::claim_stats(env, pool_id, pool.options_count);
+        let outcome = prediction.outcome;
+        if first_payout {
+            let claims = stats.claims.get(outcome).unwrap_or(0);
+            stats.claims.set(outcome, claims.saturating_add(1));
+        }
+        let paid = stats.paid.get(outcome).unwrap_or(0);
+        stats.paid.set(outcome, paid + amount);
+        if settled {
+            let stake = stats.settled_stake.get(outcome).unwrap_or(0);
+            stats.settled_stake.set(outcome, stake + prediction.amount);
+        }
+        let key = DataKey::Ext(ExtKey::ClaimStats(pool_id));
+        env.storage().persistent().set(&key, &stats);
+        Self::extend_persistent(env, &key);
+    }
+
+    fn claim_tip_bps(env: &Env, user: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ClaimTipBps(user.clone())))
+            .unwrap_or(0)
+    }
+
+    /// Timestamp from which claims on a resolved pool are accepted (0 = open).
+    fn claims_open_at(env: &Env, pool_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimsOpenAt(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Record an executed sweep and transfer the pool's unclaimed funds to the
+    /// treasury. Returns (amount, treasury).
+    fn sweep_pool_to_treasury(
+        env: &Env,
+        pool_id: u64,
+        schedule: &SweepSchedule,
+    ) -> (i128, Address) {
+        let pool = Self::get_pool(env.clone(), pool_id);
+        let amount = Self::pool_unclaimed(env, pool_id, &pool);
+
+        let key = DataKey::SweepSchedule(pool_id);
+        env.storage().persistent().set(&key, schedule);
+        Self::extend_persistent(env, &key);
+        let disbursed_key = DataKey::PoolDisbursed(pool_id);
+        env.storage()
+            .persistent()
+            .set(&disbursed_key, &pool.total_stake);
+        Self::extend_persistent(env, &disbursed_key);
+        Self::debit_pool_escrow(env, pool_id, &pool, amount, 0);
+
+        let config = Self::load_config(env);
+        if amount > 0 {
+            let token_client = token::Client::new(env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &config.treasury, &amount);
+            Self::record_ledger_entry(
+                env,
+                Some(pool_id),
+                &config.treasury,
+                &pool.token,
+                LedgerDirection::Out,
+                amount,
+                "sweep",
+            );
+        }
+        Self::check_pool_invariants(env, pool_id);
+        (amount, config.treasury)
+    }
+
+    /// Credit a pool's creator fee, directing the pool's public-goods share
+    /// (if any) to its recipient instead of the creator.
+    fn accrue_creator_fee(env: &Env, pool_id: u64, pool: &Pool, fee: i128) {
+        let share_key = DataKey::Ext(ExtKey::PoolPublicGoods(pool_id));
+        let mut creator_fee = fee;
+        if let Some(mut share) = env
+            .storage()
+            .persistent()
+            .get::<_, PublicGoodsShare>(&share_key)
+        {
+            let donated = fee * share.share_bps as i128 / 10_000;
+            if donated > 0 {
+                creator_fee -= donated;
+                let key = DataKey::Ext(ExtKey::PublicGoodsAccrued(
+                    share.recipient.clone(),
+                    pool.token.clone(),
+                ));
+                let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(&key, &(accrued + donated));
+                Self::extend_persistent(env, &key);
+                share.total_accrued += donated;
+                env.storage().persistent().set(&share_key, &share);
+                Self::extend_persistent(env, &share_key);
+                PublicGoodsFeeAccruedEvent {
+                    pool_id,
+                    recipient: share.recipient,
+                    token: pool.token.clone(),
+                    amount: donated,
+                }
+                .publish(env);
+            }
+        }
+        if creator_fee > 0 {
+            let accrued_key = DataKey::CreatorFeesAccrued(pool.creator.clone(), pool.token.clone());
+            let accrued: i128 = env.storage().persistent().get(&accrued_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&accrued_key, &(accrued + creator_fee));
+            Self::extend_persistent(env, &accrued_key);
+            CreatorFeeAccruedEvent {
+                pool_id,
+                creator: pool.creator.clone(),
+                token: pool.token.clone(),
+                amount: creator_fee,
+            }
+            .publish(env);
+        }
+    }
+
+    fn is_pool_archived(env: &Env, pool_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Ext(ExtKey::Archived(pool_id)))
+    }
+
+    fn is_pool_swept(env: &Env, pool_id: u64) -> bool {
+        let schedule: Option<SweepSchedule> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SweepSchedule(pool_id));
+        schedule.map(|s| s.executed).unwrap_or(false)
+    }
+
+    /// Shared claim path. Pays `requested` (or everything still owed when
+    /// `None`) and tracks the cumulative payout per (user, pool). HasClaimed is
+    /// only written once the entitlement is exhausted, preserving INV-3.
+    /// When a `relayer` triggered the claim, it receives the user's claim tip
+    /// out of the winnings, unless the payout is deferred.
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    fn settle_claim(
+        env: &Env,
+        user: &Address,
+        pool_id: u64,
+        requested: Option<i128>,
+        relayer: Option<&Address>,
+    ) -> Result<i128, PredifiError> {
+        Self::enter_reentrancy_guard(env);
+
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .expect("Pool not found");
+        Self::extend_persistent(env, &pool_key);
+
+        if pool.state == MarketState::Active {
+            Self::exit_reentrancy_guard(env);
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if Self::is_pool_swept(env, pool_id) {
+            Self::exit_reentrancy_guard(env);
+            return Err(PredifiError::PoolSwept);
+        }
+        let claims_open_at = Self::claims_open_at(env, pool_id);
+        if pool.state == MarketState::Resolved && env.ledger().timestamp() < claims_open_at {
+            Self::exit_reentrancy_guard(env);
+            return Err(Self::error_detail(
+                env,
+                PredifiError::ClaimsNotOpen,
+                Some(pool_id),
+                claims_open_at as i128,
+                env.ledger().timestamp() as i128,
+            ));
+        }
+
+        let claimed_key = DataKey::HasClaimed(user.clone(), pool_id);
+        if env.storage().persistent().has(&claimed_key) {
+            // 🔴 HIGH ALERT: repeated claim attempt on an already-claimed pool.
+            SuspiciousDoubleClaimEvent {
+                user: user.clone(),
+                pool_id,
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(env);
+            Self::exit_reentrancy_guard(env);
+            return Err(PredifiError::AlreadyClaimed);
+        }
+
+        // --- CHECKS ---
+
+        let pred_key = DataKey::Prediction(user.clone(), pool_id);
+        let prediction: Option<Prediction> = env.storage().persistent().get(&pred_key);
+
+        let prediction = match prediction {
+            Some(p) => {
+                Self::extend_persistent(env, &pred_key);
+                Self::settled_prediction(env, user, pool_id, &pool, p)
+            }
+            None => {
+                Self::exit_reentrancy_guard(env);
+                return Ok(0);
+            }
+        };
+
+        // Entitlements are tracked net of the protocol fee; the whole fee is
+        // collected with the first payout.
+        let (gross, fee, creator_fee) =
+            Self::calculate_gross_entitlement(env, pool_id, &pool, &prediction);
+        let keeper_fee = Self::keeper_fee(env, pool_id, &pool, gross);
+        let entitlement = gross - fee - creator_fee - keeper_fee;
+        let paid_key = DataKey::ClaimedAmount(user.clone(), pool_id);
+        let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+        let remaining = entitlement - paid;
+
+        let amount = match requested {
+            None => remaining,
+            Some(a) if a > 0 && a <= remaining => a,
+            Some(_) => {
+                Self::exit_reentrancy_guard(env);
+                return Err(PredifiError::InvalidAmount);
+            }
+        };
+
+        // --- EFFECTS ---
+
+        let paid_total = paid + amount;
+        if paid_total == entitlement {
+            // Mark as claimed before any transfer to prevent re-entrancy (INV-3)
+            env.storage().persistent().set(&claimed_key, &true);
+            Self::extend_persistent(env, &claimed_key);
+        }
+        if amount > 0 {
+            env.storage().persistent().set(&paid_key, &paid_total);
+            Self::extend_persistent(env, &paid_key);
+        }
+        // Held payouts emit no claim event, so they take no sequence number.
+        let sequence = if amount > 0 && !Self::is_compliance_flagged(env, user) {
+            let sequence_key = DataKey::Ext(ExtKey::ClaimSequence(pool_id));
+            let sequence: u32 = env.storage().persistent().get(&sequence_key).unwrap_or(0) + 1;
+            env.storage().persistent().set(&sequence_key, &sequence);
+            Self::extend_persistent(env, &sequence_key);
+            sequence
+        } else {
+            0
+        };
+
+        // Refunds of pools canceled long after end_time may earn compensation,
+        // paid once when the refund is complete.
+        let (compensation, delay) = if pool.state == MarketState::Canceled
+            && entitlement > 0
+            && paid_total == entitlement
+        {
+            Self::take_cancellation_compensation(env, pool_id, &pool, entitlement)
+        } else {
+            (0, 0)
+        };
+
+        let first_payout = paid == 0 && amount > 0;
+        if first_payout && pool.state == MarketState::Resolved {
+            Self::record_user_activity(env, user, 0, 0, 1);
+        }
+        if amount > 0 {
+            Self::record_claim_stats(
+                env,
+                pool_id,
+                &pool,
+                &prediction,
+                first_payout,
+                amount,
+                paid_total == entitlement,
+            );
+        }
+        let fee_due = if first_payout { fee } else { 0 };
+        let creator_fee_due = if first_payout { creator_fee } else { 0 };
+        let keeper_fee_due = if first_payout { keeper_fee } else { 0 };
+        if amount > 0 {
+            let disbursed_key = DataKey::PoolDisbursed(pool_id);
+            let disbursed: i128 = env.storage().persistent().get(&disbursed_key).unwrap_or(0);
+            env.storage().persistent().set(
+                &disbursed_key,
+                &(disbursed + amount + fee_due + creator_fee_due + keeper_fee_due),
+            );
+            Self::extend_persistent(env, &disbursed_key);
+            Self::debit_pool_escrow(
+                env,
+                pool_id,
+                &pool,
+                amount,
+                fee_due + creator_fee_due + keeper_fee_due,
+            );
+        }
+        if creator_fee_due > 0 {
+            Self::accrue_creator_fee(env, pool_id, &pool, creator_fee_due);
+        }
+        let referral_fee = Self::accrue_referral_fee(env, pool_id, &pool.token, user, fee_due);
+
+        // Compliance gate: flagged claimants are settled into escrow rather than
+        // reverting, so the pool stays settleable for everyone else.
+        let payout = amount + compensation;
+        if payout > 0 && Self::is_compliance_flagged(env, user) {
+            let total_held = Self::hold_payout(
+                env,
+                user,
+                pool_id,
+                &pool.token,
+                payout,
+                HoldReason::Compliance,
+            );
+
+            // --- INTERACTIONS ---
+
+            Self::accrue_protocol_fee(env, pool_id, &pool.token, fee_due - referral_fee);
+            Self::transfer_keeper_reward(env, pool_id, &pool.token, keeper_fee_due);
+            Self::exit_reentrancy_guard(env);
+            PayoutHeldEvent {
+                pool_id,
+                user: user.clone(),
+                amount: payout,
+                total_held,
+            }
+            .publish(env);
+            Self::check_pool_invariants(env, pool_id);
+            return Ok(amount);
+        }
+
+        // The tip is a share of the winnings only, never of compensation.
+        let mut tip = match relayer {
+            Some(_) if amount > 0 => amount * Self::claim_tip_bps(env, user) as i128 / 10_000,
+            _ => 0,
+        };
+
+        // --- INTERACTIONS ---
+
+        Self::accrue_protocol_fee(env, pool_id, &pool.token, fee_due - referral_fee);
+        Self::transfer_keeper_reward(env, pool_id, &pool.token, keeper_fee_due);
+        if payout > 0 {
+            let token_client = token::Client::new(env, &pool.token);
+            // Blocked addresses are not paid, and clawback-enabled assets can
+            // refuse the transfer (frozen or deauthorized trustline). The
+            // claim still settles; the whole payout waits for `retry_payout`
+            // and the relayer earns no tip, as nothing reached the user.
+            let deferred = if Self::require_not_blocked(env, user, "claim").is_err() {
+                Some(HoldReason::Blocked)
+            } else if matches!(
+                token_client.try_transfer(&env.current_contract_address(), user, &(payout - tip)),
+                Ok(Ok(()))
+            ) {
+                None
+            } else {
+                Some(HoldReason::TransferRefused)
+            };
+            match deferred {
+                None => Self::record_ledger_entry(
+                    env,
+                    Some(pool_id),
+                    user,
+                    &pool.token,
+                    LedgerDirection::Out,
+                    payout - tip,
+                    "payout",
+                ),
+                Some(reason) => {
+                    tip = 0;
+                    let total_pending =
+                        Self::hold_payout(env, user, pool_id, &pool.token, payout, reason);
+                    PayoutDeferredEvent {
+                        pool_id,
+                        user: user.clone(),
+                        amount: payout,
+                        total_pending,
+                        reason,
+                    }
+                    .publish(env);
+                }
+            }
+            if let Some(relayer) = relayer.filter(|_| tip > 0) {
+                token_client.transfer(&env.current_contract_address(), relayer, &tip);
+                Self::record_ledger_entry(
+                    env,
+                    Some(pool_id),
+                    relayer,
+                    &pool.token,
+                    LedgerDirection::Out,
+                    tip,
+                    "claim_tip",
+                );
+            }
+        }
+
+        Self::exit_reentrancy_guard(env);
+
+        if let Some(relayer) = relayer.filter(|_| payout > 0) {
+            ClaimRelayedEvent {
+                pool_id,
+                beneficiary: user.clone(),
+                relayer: relayer.clone(),
+                tip,
+            }
+            .publish(env);
+        }
+
+        if amount > 0 {
+            WinningsClaimedEvent {
+                pool_id,
+                user: user.clone(),
+                amount,
+                gross: amount + fee_due + creator_fee_due + keeper_fee_due,
+                fee: fee_due,
+                creator_fee: creator_fee_due,
+                keeper_fee: keeper_fee_due,
+                net: amount,
+                sequence,
+            }
+            .publish(env);
+        }
+        if compensation > 0 {
+            CompensationPaidEvent {
+                pool_id,
+                user: user.clone(),
+                amount: compensation,
+                delay,
+            }
+            .publish(env);
+        }
+
+        Self::check_pool_invariants(env, pool_id);
+        Ok(amount)
+    }
+
+    // ── Public interface ──────────────────────────────────────────────────────
+
+    /// Initialize the contract. Idempotent — safe to call multiple times.
+    pub fn init(
+        env: Env,
+        access_control: Address,
+        treasury: Address,
+        fee_bps: u32,
+        resolution_delay: u64,
+    ) {
+        if !env.storage().instance().has(&DataKey::Config) {
+            let config = Config {
+                fee_bps,
+                treasury: treasury.clone(),
+                access_control: access_control.clone(),
+                resolution_delay,
+                max_active_pools: 0,
+                settlement_hold_threshold: 0,
+                settlement_hold_delay: 0,
+            };
+            env.storage().instance().set(&DataKey::Config, &config);
+            env.storage().instance().set(&DataKey::PoolIdCounter, &0u64);
+            env.storage()
+                .instance()
+                .set(&DataKey::Ext(ExtKey::StorageVersion), &STORAGE_VERSION);
+            Self::mark_active_pool_count_seeded(&env);
+            Self::extend_instance(&env);
+
+            InitEvent {
+                access_control,
+                treasury,
+                fee_bps,
+                resolution_delay,
+            }
+            .publish(&env);
+        }
+    }
+
+    /// Pause the contract. Only callable by Admin (role 0).
+    pub fn pause(env: Env, admin: Address) {
+        admin.require_auth();
+        if Self::require_role(&env, &admin, 0).is_err() {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "pause"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            panic!("Unauthorized: missing required role");
+        }
+        env.storage().instance().set(&DataKey::Paused, &true);
+        Self::extend_instance(&env);
+
+        // Emit dedicated pause-alert event so monitors can apply zero-tolerance
+        // rules independently of the generic PauseEvent.
+        ContractPausedAlertEvent {
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+        PauseEvent { admin }.publish(&env);
+    }
+
+    /// Unpause the contract. Only callable by Admin (role 0).
+    pub fn unpause(env: Env, admin: Address) {
+        admin.require_auth();
+        if Self::require_role(&env, &admin, 0).is_err() {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "unpause"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            panic!("Unauthorized: missing required role");
+        }
+        env.storage().instance().set(&DataKey::Paused, &false);
+        Self::extend_instance(&env);
+
+        UnpauseEvent { admin }.publish(&env);
+    }
+
+    /// Pause individual operations without freezing the whole contract, e.g.
+    /// `PAUSE_BETTING` to stop new bets while users keep claiming. `scopes`
+    /// replaces the current mask (0 = nothing paused). Only callable by
+    /// Admin (role 0).
+    pub fn set_pause_scopes(env: Env, admin: Address, scopes: u32) {
+        admin.require_auth();
+        if Self::require_role(&env, &admin, 0).is_err() {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_pause_scopes"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            panic!("Unauthorized: missing required role");
+        }
+        assert!(scopes & !PAUSE_ALL_SCOPES == 0, "unknown pause scope");
+        env.storage()
+            .instance()
+            .set(&DataKey::Ext(ExtKey::PausedScopes), &scopes);
+        Self::extend_instance(&env);
+
+        PauseScopesUpdateEvent { admin, scopes }.publish(&env);
+    }
+
+    /// Returns the mask of individually paused operations (`PAUSE_*` bits).
+    pub fn get_pause_scopes(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::PausedScopes))
+            .unwrap_or(0)
+    }
+
+    /// Set fee in basis points. Caller must have Admin role (0).
+    /// PRE: admin has role 0
+    /// POST: Config.fee_bps ≤ 10_000 (INV-6)
+    pub fn set_fee_bps(env: Env, admin: Address, fee_bps: u32) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_fee_bps"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        assert!(Self::is_valid_fee_bps(fee_bps), "fee_bps exceeds 10000");
+        let mut config = Self::load_config(&env);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "fee_bps",
+            ConfigValue::U32(config.fee_bps),
+            ConfigValue::U32(fee_bps),
+        );
+        config.fee_bps = fee_bps;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::extend_instance(&env);
+
+        FeeUpdateEvent { admin, fee_bps }.publish(&env);
+        Ok(())
+    }
+
+    /// Set treasury address. Caller must have Admin role (0).
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_treasury"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let mut config = Self::load_config(&env);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "treasury",
+            ConfigValue::Address(config.treasury.clone()),
+            ConfigValue::Address(treasury.clone()),
+        );
+        config.treasury = treasury.clone();
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::extend_instance(&env);
+
+        TreasuryUpdateEvent { admin, treasury }.publish(&env);
+        Ok(())
+    }
+
+    /// Set resolution delay in seconds. Caller must have Admin role (0).
+    pub fn set_resolution_delay(env: Env, admin: Address, delay: u64) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_resolution_delay"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let mut config = Self::load_config(&env);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "resolution_delay",
+            ConfigValue::U64(config.resolution_delay),
+            ConfigValue::U64(delay),
+        );
+        config.resolution_delay = delay;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::extend_instance(&env);
+
+        ResolutionDelayUpdateEvent { admin, delay }.publish(&env);
+        Ok(())
+    }
+
+    /// Set the protocol-wide cap on simultaneously Active pools (0 = unlimited).
+    /// Caller must have Admin role (0).
+    pub fn set_max_active_pools(
+        env: Env,
+        admin: Address,
+        max_active_pools: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_max_active_pools"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let mut config = Self::load_config(&env);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "max_active_pools",
+            ConfigValue::U32(config.max_active_pools),
+            ConfigValue::U32(max_active_pools),
+        );
+        config.max_active_pools = max_active_pools;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::extend_instance(&env);
+
+        MaxActivePoolsUpdateEvent {
+            admin,
+            max_active_pools,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the longest time a new pool may run, from creation to end_time,
+    /// in seconds (0 = unlimited). Operators may still create longer pools
+    /// for genuinely long-horizon markets. Caller must have Admin role (0).
+    pub fn set_max_pool_duration(
+        env: Env,
+        admin: Address,
+        max_duration: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_max_pool_duration"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if max_duration > 0 && max_duration < MIN_POOL_DURATION {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = DataKey::Ext(ExtKey::MaxPoolDuration);
+        let old: u64 = env.storage().instance().get(&key).unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "max_pool_duration",
+            ConfigValue::U64(old),
+            ConfigValue::U64(max_duration),
+        );
+        env.storage().instance().set(&key, &max_duration);
+        Self::extend_instance(&env);
+
+        MaxPoolDurationUpdateEvent {
+            admin,
+            max_duration,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Arm (or re-arm) the dead-man switch: if no admin action occurs for
+    /// `inactivity_period` seconds, `guardian` may call
+    /// `trigger_dead_man_switch`. Re-arming also lifts a fired switch.
+    /// Caller must have Admin role (0).
+    pub fn set_dead_man_switch(
+        env: Env,
+        admin: Address,
+        guardian: Address,
+        inactivity_period: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_dead_man_switch"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if inactivity_period < MIN_DEAD_MAN_PERIOD {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = DataKey::Ext(ExtKey::DeadManSwitch);
+        let old = env
+            .storage()
+            .instance()
+            .get::<_, DeadManSwitch>(&key)
+            .map(|d| d.inactivity_period)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "dead_man_period",
+            ConfigValue::U64(old),
+            ConfigValue::U64(inactivity_period),
+        );
+        env.storage().instance().set(
+            &key,
+            &DeadManSwitch {
+                guardian: guardian.clone(),
+                inactivity_period,
+            },
+        );
+        env.storage()
+            .instance()
+            .remove(&DataKey::Ext(ExtKey::DeadManTriggered));
+        Self::extend_instance(&env);
+
+        DeadManSwitchUpdateEvent {
+            admin,
+            guardian,
+            inactivity_period,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Fire the dead-man switch after the admin has been inactive for the
+    /// configured period. New pool creation is halted and unclaimed funds
+    /// cannot be swept for `DEAD_MAN_CLAIM_EXTENSION` seconds, until an admin
+    /// re-arms the switch. Caller must be the designated guardian.
+    pub fn trigger_dead_man_switch(env: Env, guardian: Address) -> Result<u64, PredifiError> {
+        guardian.require_auth();
+        let switch: DeadManSwitch = env
+            .storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::DeadManSwitch))
+            .ok_or(PredifiError::Unauthorized)?;
+        if switch.guardian != guardian {
+            return Err(PredifiError::Unauthorized);
+        }
+        let triggered_key = DataKey::Ext(ExtKey::DeadManTriggered);
+        if env.storage().instance().has(&triggered_key) {
+            return Err(PredifiError::DeadManSwitchTriggered);
+        }
+        let last_admin_action = Self::get_last_admin_action(env.clone());
+        let now = env.ledger().timestamp();
+        if now < last_admin_action.saturating_add(switch.inactivity_period) {
+            return Err(PredifiError::AdminStillActive);
+        }
+
+        env.storage().instance().set(&triggered_key, &now);
+        Self::extend_instance(&env);
+        let claims_extended_until = Self::dead_man_claims_until(&env);
+
+        DeadManSwitchTriggeredEvent {
+            guardian,
+            last_admin_action,
+            claims_extended_until,
+        }
+        .publish(&env);
+        Ok(claims_extended_until)
+    }
+
+    /// Returns the dead-man switch configuration, if armed.
+    pub fn get_dead_man_switch(env: Env) -> Option<DeadManSwitch> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::DeadManSwitch))
+    }
+
+    /// Returns the timestamp of the last successful admin action.
+    pub fn get_last_admin_action(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::LastAdminAction))
+            .unwrap_or(0)
+    }
+
+    /// Returns when the dead-man switch fired, if it has.
+    pub fn get_dead_man_triggered_at(env: Env) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::DeadManTriggered))
+    }
+
+    /// Require new pool creators to post a refundable bond of `amount` in
+    /// `token` (0 = no bond). The bond is returned when the pool resolves or
+    /// is canceled, and forfeited to the treasury when an operator cancels it
+    /// via `cancel_pool_for_violation`. Bonds already posted are unaffected.
+    /// Caller must have Admin role (0).
+    pub fn set_creator_bond(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_creator_bond"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if amount < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = DataKey::Ext(ExtKey::CreatorBondConfig);
+        let old = Self::get_creator_bond(env.clone())
+            .map(|b| b.amount)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "creator_bond",
+            ConfigValue::I128(old),
+            ConfigValue::I128(amount),
+        );
+        if amount == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(
+                &key,
+                &CreatorBond {
+                    token: token.clone(),
+                    amount,
+                },
+            );
+        }
+        Self::extend_instance(&env);
+
+        CreatorBondUpdateEvent {
+            admin,
+            token,
+            amount,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the bond new pool creators must post, if any.
+    pub fn get_creator_bond(env: Env) -> Option<CreatorBond> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::CreatorBondConfig))
+    }
+
+    /// Returns the creator bond held for a pool, if any.
+    pub fn get_pool_bond(env: Env, pool_id: u64) -> Option<CreatorBond> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::CreatorBond(pool_id)))
+    }
+
+    /// Returns the longest allowed pool duration in seconds (0 = unlimited).
+    pub fn get_max_pool_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::MaxPoolDuration))
+            .unwrap_or(0)
+    }
+
+    /// Configure the protocol fee schedule by pool size. Tiers must be sorted by
+    /// strictly increasing `min_total_stake` with non-increasing `fee_bps`, so
+    /// larger markets pay less. Pools below the first tier (or all pools, when
+    /// `tiers` is empty) pay Config.fee_bps. The schedule is applied at claim
+    /// time using the pool's final total_stake. Caller must have Admin role (0).
+    pub fn set_fee_tiers(
+        env: Env,
+        admin: Address,
+        tiers: Vec<FeeTier>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_fee_tiers"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if tiers.len() > MAX_FEE_TIERS {
+            return Err(PredifiError::InvalidFeeSchedule);
+        }
+        let mut previous: Option<FeeTier> = None;
+        for tier in tiers.iter() {
+            if !Self::is_valid_fee_bps(tier.fee_bps) || tier.min_total_stake < 0 {
+                return Err(PredifiError::InvalidFeeSchedule);
+            }
+            if let Some(prev) = previous {
+                if tier.min_total_stake <= prev.min_total_stake || tier.fee_bps > prev.fee_bps {
+                    return Err(PredifiError::InvalidFeeSchedule);
+                }
+            }
+            previous = Some(tier);
+        }
+
+        env.storage().instance().set(&DataKey::FeeTiers, &tiers);
+        Self::extend_instance(&env);
+
+        FeeTiersUpdateEvent { admin, tiers }.publish(&env);
+        Ok(())
+    }
+
+    /// Returns the configured fee schedule by pool size (empty = flat fee).
+    pub fn get_fee_tiers(env: Env) -> Vec<FeeTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeTiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set the maximum creator fee new pools may request, in bps.
+    /// Caller must have Admin role (0).
+    pub fn set_max_creator_fee_bps(
+        env: Env,
+        admin: Address,
+        max_creator_fee_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_max_creator_fee_bps"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        assert!(
+            Self::is_valid_fee_bps(max_creator_fee_bps),
+            "fee_bps exceeds 10000"
+        );
+        let old: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxCreatorFeeBps)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "max_creator_fee_bps",
+            ConfigValue::U32(old),
+            ConfigValue::U32(max_creator_fee_bps),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxCreatorFeeBps, &max_creator_fee_bps);
+        Self::extend_instance(&env);
+
+        MaxCreatorFeeUpdateEvent {
+            admin,
+            max_creator_fee_bps,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the fee in basis points charged on early withdrawals
+    /// (`withdraw_prediction`). Caller must have Admin role (0).
+    pub fn set_early_exit_fee_bps(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_early_exit_fee_bps"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        assert!(Self::is_valid_fee_bps(fee_bps), "fee_bps exceeds 10000");
+        let old: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EarlyExitFeeBps)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "early_exit_fee_bps",
+            ConfigValue::U32(old),
+            ConfigValue::U32(fee_bps),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyExitFeeBps, &fee_bps);
+        Self::extend_instance(&env);
+
+        EarlyExitFeeUpdateEvent { admin, fee_bps }.publish(&env);
+        Ok(())
+    }
+
+    /// Configure the settlement hold for high-value pools: pools whose
+    /// total_stake reaches `threshold` at resolution only open claims `delay`
+    /// seconds later (threshold 0 = disabled). Caller must have Admin role (0).
+    pub fn set_settlement_hold(
+        env: Env,
+        admin: Address,
+        threshold: i128,
+        delay: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_settlement_hold"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if threshold < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let mut config = Self::load_config(&env);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "settlement_hold_threshold",
+            ConfigValue::I128(config.settlement_hold_threshold),
+            ConfigValue::I128(threshold),
+        );
+        Self::record_config_change(
+            &env,
+            &admin,
+            "settlement_hold_delay",
+            ConfigValue::U64(config.settlement_hold_delay),
+            ConfigValue::U64(delay),
+        );
+        config.settlement_hold_threshold = threshold;
+        config.settlement_hold_delay = delay;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Self::extend_instance(&env);
+
+        SettlementHoldUpdateEvent {
+            admin,
+            threshold,
+            delay,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the dispute window: seconds after resolution during which claims
+    /// are blocked and operators may correct the outcome with
+    /// `dispute_resolution` (0 = claims open immediately). Caller must have
+    /// Admin role (0).
+    pub fn set_dispute_window(
+        env: Env,
+        admin: Address,
+        dispute_window: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_dispute_window"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let old: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeWindow)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "dispute_window",
+            ConfigValue::U64(old),
+            ConfigValue::U64(dispute_window),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeWindow, &dispute_window);
+        Self::extend_instance(&env);
+
+        DisputeWindowUpdateEvent {
+            admin,
+            dispute_window,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set the resolution SLA: the maximum delay in seconds between a pool
+    /// becoming resolvable (`end_time` + `resolution_delay`) and its resolution
+    /// before the resolver is recorded as late (0 = disabled). Caller must have
+    /// Admin role (0).
+    pub fn set_resolution_sla(
+        env: Env,
+        admin: Address,
+        resolution_sla: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_resolution_sla"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let old: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ResolutionSla)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "resolution_sla",
+            ConfigValue::U64(old),
+            ConfigValue::U64(resolution_sla),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ResolutionSla, &resolution_sla);
+        Self::extend_instance(&env);
+
+        ResolutionSlaUpdateEvent {
+            admin,
+            resolution_sla,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the resolution latency record of an operator or oracle.
+    pub fn get_operator_sla_stats(env: Env, operator: Address) -> OperatorSlaStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OperatorSlaStats(operator))
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of matching oracle submissions required to resolve
+    /// a pool (1 unless the pool was created with a quorum).
+    pub fn get_oracle_quorum(env: Env, pool_id: u64) -> u32 {
+        Self::oracle_quorum(&env, pool_id)
+    }
+
+    /// Returns the pending oracle submissions (oracle → outcome) of a pool
+    /// that has not reached quorum yet.
+    pub fn get_oracle_submissions(env: Env, pool_id: u64) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OracleSubmissions(pool_id))
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Returns the timestamp from which claims on a pool are accepted
+    /// (0 when no settlement hold applies).
+    pub fn get_claims_open_at(env: Env, pool_id: u64) -> u64 {
+        Self::claims_open_at(&env, pool_id)
+    }
+
+    /// Returns the protocol configuration: fee, treasury, access control
+    /// contract, resolution delay and the other `Config` settings.
+    pub fn get_config(env: Env) -> Config {
+        Self::load_config(&env)
+    }
+
+    /// Returns whether the contract is globally paused. Operations paused
+    /// individually are reported by `get_pause_scopes`.
+    pub fn is_paused(env: Env) -> bool {
+        Self::read_paused(&env)
+    }
+
+    /// Returns retained config changes in chronological order. `offset` is
+    /// relative to the oldest retained entry; at most
+    /// `CONFIG_HISTORY_CAPACITY` changes are kept.
+    pub fn get_config_history(env: Env, offset: u32, limit: u32) -> Vec<ConfigChange> {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConfigHistoryCount)
+            .unwrap_or(0);
+        let oldest = count.saturating_sub(CONFIG_HISTORY_CAPACITY);
+        let start = oldest.saturating_add(offset);
+        let end = core::cmp::min(
+            start.saturating_add(core::cmp::min(limit, CONFIG_HISTORY_CAPACITY)),
+            count,
+        );
+
+        let mut changes = Vec::new(&env);
+        for seq in start..end {
+            let key = DataKey::ConfigHistory(seq % CONFIG_HISTORY_CAPACITY);
+            if let Some(change) = env.storage().persistent().get::<_, ConfigChange>(&key) {
+                Self::extend_persistent(&env, &key);
+                changes.push_back(change);
+            }
+        }
+        changes
+    }
+
+    /// Configure compensation for refunds of pools canceled long after their
+    /// end_time: `rate_bps_per_day` of the refunded stake per full day beyond
+    /// `grace_period`, capped at `cap_bps`. A zero rate disables it.
+    /// Caller must have Admin role (0).
+    pub fn set_compensation_config(
+        env: Env,
+        admin: Address,
+        rate_bps_per_day: u32,
+        cap_bps: u32,
+        grace_period: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_compensation_config"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        assert!(Self::is_valid_fee_bps(cap_bps), "cap_bps exceeds 10000");
+
+        if rate_bps_per_day == 0 {
+            env.storage()
+                .instance()
+                .remove(&DataKey::CompensationConfig);
+        } else {
+            env.storage().instance().set(
+                &DataKey::CompensationConfig,
+                &CompensationConfig {
+                    rate_bps_per_day,
+                    cap_bps,
+                    grace_period,
+                },
+            );
+        }
+        Self::extend_instance(&env);
+
+        CompensationConfigUpdateEvent {
+            admin,
+            rate_bps_per_day,
+            cap_bps,
+            grace_period,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Deposit tokens into the compensation reserve for `token`. Anyone (in
+    /// practice the treasury or an insurance fund) may top it up.
+    pub fn fund_compensation_reserve(
+        env: Env,
+        funder: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&funder, &env.current_contract_address(), &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &funder,
+            &token,
+            LedgerDirection::In,
+            amount,
+            "compensation_fund",
+        );
+
+        let key = DataKey::CompensationReserve(token.clone());
+        let reserve: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let reserve = reserve.checked_add(amount).expect("overflow");
+        env.storage().persistent().set(&key, &reserve);
+        Self::extend_persistent(&env, &key);
+
+        CompensationReserveFundedEvent {
+            funder,
+            token,
+            amount,
+            reserve,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the remaining compensation reserve for a token.
+    pub fn get_compensation_reserve(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CompensationReserve(token))
+            .unwrap_or(0)
+    }
+
+    /// Register the yield adapter idle escrow in `token` may be deposited into,
+    /// and the largest share of a pool's escrow it may hold (at most 50%).
+    /// Pass `None` to remove it. The adapter cannot be changed while escrow
+    /// is deposited. Caller must have Admin role (0).
+    pub fn set_yield_adapter(
+        env: Env,
+        admin: Address,
+        token: Address,
+        adapter: Option<Address>,
+        max_share_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_yield_adapter"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if adapter.is_some() && (max_share_bps == 0 || max_share_bps > MAX_YIELD_SHARE_BPS) {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let principal: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::YieldPrincipal(token.clone())))
+            .unwrap_or(0);
+        let current = Self::yield_adapter(&env, &token);
+        if principal > 0 && current.as_ref().map(|c| &c.adapter) != adapter.as_ref() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = DataKey::Ext(ExtKey::YieldAdapter(token.clone()));
+        match &adapter {
+            Some(adapter) => {
+                let config = YieldAdapterConfig {
+                    adapter: adapter.clone(),
+                    max_share_bps,
+                };
+                env.storage().persistent().set(&key, &config);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        YieldAdapterUpdateEvent {
+            admin,
+            token,
+            adapter,
+            max_share_bps,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the yield adapter registered for a token, if any.
+    pub fn get_yield_adapter(env: Env, token: Address) -> Option<YieldAdapterConfig> {
+        Self::yield_adapter(&env, &token)
+    }
+
+    /// Deposit `amount` of an Active pool's escrow into its token's yield
+    /// adapter during the betting window. A pool's total deposit is bounded by
+    /// the adapter's `max_share_bps` of its total_stake. Returns the pool's
+    /// total deposit. Caller must have Operator role (1).
+    pub fn deposit_idle_escrow(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= Self::lock_time(&env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+        let config =
+            Self::yield_adapter(&env, &pool.token).ok_or(PredifiError::InvalidPoolState)?;
+        let deposited = Self::yield_deposit(&env, pool_id)
+            .checked_add(amount)
+            .ok_or(PredifiError::InvalidAmount)?;
+        let cap = pool.total_stake * config.max_share_bps as i128 / 10_000;
+        if amount <= 0 || deposited > cap {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let deposit_key = DataKey::Ext(ExtKey::YieldDeposit(pool_id));
+        env.storage().persistent().set(&deposit_key, &deposited);
+        Self::extend_persistent(&env, &deposit_key);
+        let principal_key = DataKey::Ext(ExtKey::YieldPrincipal(pool.token.clone()));
+        let principal: i128 = env.storage().persistent().get(&principal_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&principal_key, &(principal + amount));
+        Self::extend_persistent(&env, &principal_key);
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &pool.token).transfer(&this, &config.adapter, &amount);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &config.adapter,
+            &pool.token,
+            LedgerDirection::Out,
+            amount,
+            "yield_deposit",
+        );
+        env.invoke_contract::<()>(
+            &config.adapter,
+            &Symbol::new(&env, "deposit"),
+            soroban_sdk::vec![
+                &env,
+                this.into_val(&env),
+                pool.token.into_val(&env),
+                amount.into_val(&env)
+            ],
+        );
+
+        YieldDepositedEvent {
+            pool_id,
+            adapter: config.adapter,
+            amount,
+            deposited,
+        }
+        .publish(&env);
+        Ok(deposited)
+    }
+
+    /// Return a pool's deposited escrow from the yield adapter. Anyone may call
+    /// this once betting has locked; before that it needs the Operator role
+    /// (1). Resolution and cancellation recall the deposit automatically.
+    /// Returns the amount returned.
+    pub fn withdraw_idle_escrow(
+        env: Env,
+        caller: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        caller.require_auth();
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if env.ledger().timestamp() < Self::lock_time(&env, pool_id, &pool) {
+            Self::require_role(&env, &caller, 1)?;
+        }
+        Ok(Self::recall_yield_deposit(&env, pool_id, &pool))
+    }
+
+    /// Fold a pool's legacy per-outcome `OutcomeStake` keys into its
+    /// `OutcomeStakes` vector and delete them; later stake updates then write
+    /// the vector only. Anyone (typically a keeper) may call this; it is a
+    /// no-op on pools already migrated. Returns the number of keys removed.
+    pub fn migrate_outcome_stakes(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if Self::outcome_stakes_migrated(&env, pool_id) {
+            return Ok(0);
+        }
+
+        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+        let key = DataKey::OutcomeStakes(pool_id);
+        env.storage().persistent().set(&key, &stakes);
+        Self::extend_persistent(&env, &key);
+
+        let mut removed = 0u32;
+        for outcome in 0..pool.options_count {
+            let outcome_key = DataKey::OutcomeStake(pool_id, outcome);
+            let value: Option<Val> = env.storage().persistent().get(&outcome_key);
+            // The price-feed adapter stores conditions under this key space;
+            // only stake amounts are legacy entries.
+            if let Some(value) = value {
+                if i128::try_from_val(&env, &value).is_ok() {
+                    env.storage().persistent().remove(&outcome_key);
+                    removed += 1;
+                }
+            }
+        }
+        Self::mark_outcome_stakes_migrated(&env, pool_id);
+
+        OutcomeStakesMigratedEvent {
+            pool_id,
+            legacy_keys_removed: removed,
+        }
+        .publish(&env);
+        Ok(removed)
+    }
+
+    /// Returns the escrow of a pool currently deposited into a yield adapter.
+    pub fn get_yield_deposit(env: Env, pool_id: u64) -> i128 {
+        Self::yield_deposit(&env, pool_id)
+    }
+
+    /// Withdraw the yield the adapter of `token` has accrued above the
+    /// deposited principal and send it to the treasury. Anyone may call this.
+    /// Returns the amount harvested.
+    pub fn harvest_yield(env: Env, token: Address) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        let config = Self::yield_adapter(&env, &token).ok_or(PredifiError::InvalidPoolState)?;
+        let this = env.current_contract_address();
+        let balance: i128 = env.invoke_contract(
+            &config.adapter,
+            &Symbol::new(&env, "balance"),
+            soroban_sdk::vec![&env, this.into_val(&env), token.into_val(&env)],
+        );
+        let principal: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::YieldPrincipal(token.clone())))
+            .unwrap_or(0);
+        let amount = balance - principal;
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        Self::withdraw_from_adapter(&env, None, &config.adapter, &token, amount);
+        let treasury = Self::load_config(&env).treasury;
+        token::Client::new(&env, &token).transfer(&this, &treasury, &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &treasury,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "yield_harvest",
+        );
+
+        YieldHarvestedEvent {
+            token,
+            adapter: config.adapter,
+            amount,
+            treasury,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    /// Flag or unflag an address at the compliance gate. Payouts claimed by a
+    /// flagged address are held in escrow until an admin reviews them.
+    /// Caller must have Admin role (0).
+    pub fn set_compliance_flag(
+        env: Env,
+        admin: Address,
+        user: Address,
+        flagged: bool,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_compliance_flag"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = DataKey::ComplianceFlag(user.clone());
+        if flagged {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        ComplianceFlagUpdateEvent {
+            admin,
+            user,
+            flagged,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns true if the address is flagged at the compliance gate.
+    pub fn is_flagged(env: Env, user: Address) -> bool {
+        Self::is_compliance_flagged(&env, &user)
+    }
+
+    /// Announce a sweep of a settled pool's unclaimed funds to the treasury.
+    /// Caller must have Admin role (0).
+    ///
+    /// Emits `SweepScheduledEvent`; users can keep claiming for
+    /// `SWEEP_GRACE_PERIOD` before `execute_sweep` becomes available.
+    pub fn schedule_sweep(env: Env, admin: Address, pool_id: u64) -> Result<u64, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "schedule_sweep"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.state == MarketState::Active {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        let key = DataKey::SweepSchedule(pool_id);
+        if env.storage().persistent().has(&key) {
+            return Err(PredifiError::SweepAlreadyScheduled);
+        }
+
+        let executable_at = env.ledger().timestamp().saturating_add(SWEEP_GRACE_PERIOD);
+        env.storage().persistent().set(
+            &key,
+            &SweepSchedule {
+                scheduled_by: admin.clone(),
+                executable_at,
+                executed: false,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        SweepScheduledEvent {
+            pool_id,
+            admin,
+            executable_at,
+            unclaimed: Self::pool_unclaimed(&env, pool_id, &pool),
+        }
+        .publish(&env);
+        Ok(executable_at)
+    }
+
+    /// Move a pool's unclaimed funds to the treasury once the announced grace
+    /// period has elapsed. Permissionless, so keepers can automate it.
+    /// Claims on the pool are rejected afterwards.
+    pub fn execute_sweep(env: Env, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        let key = DataKey::SweepSchedule(pool_id);
+        let mut schedule: SweepSchedule = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::SweepNotScheduled)?;
+        if schedule.executed {
+            return Err(PredifiError::PoolSwept);
+        }
+        let executable_at = schedule
+            .executable_at
+            .max(Self::dead_man_claims_until(&env));
+        if env.ledger().timestamp() < executable_at {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::SweepGracePeriodActive,
+                Some(pool_id),
+                executable_at as i128,
+                env.ledger().timestamp() as i128,
+            ));
+        }
+
+        schedule.executed = true;
+        let (amount, recipient) = Self::sweep_pool_to_treasury(&env, pool_id, &schedule);
+
+        SweepExecutedEvent {
+            pool_id,
+            amount,
+            recipient,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    /// Set the claim period: seconds after end_time during which winnings can
+    /// be claimed before `sweep_unclaimed` may move the rest to the treasury
+    /// (0 = no deadline). Applies to pools created afterwards; each pool's
+    /// deadline is fixed at creation. Caller must have Admin role (0).
+    pub fn set_claim_period(
+        env: Env,
+        admin: Address,
+        claim_period: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_claim_period"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let old: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimPeriod)
+            .unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "claim_period",
+            ConfigValue::U64(old),
+            ConfigValue::U64(claim_period),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimPeriod, &claim_period);
+        Self::extend_instance(&env);
+
+        ClaimPeriodUpdateEvent {
+            admin,
+            claim_period,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the claim deadline of a pool, if it has one, including any
+    /// extension granted by a fired dead-man switch.
+    pub fn get_claim_deadline(env: Env, pool_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::ClaimDeadline(pool_id))
+            .map(|deadline| deadline.max(Self::dead_man_claims_until(&env)))
+    }
+
+    /// Returns the betting analytics checkpointed when a pool was resolved.
+    pub fn get_pool_analytics(env: Env, pool_id: u64) -> Option<PoolAnalytics> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PoolAnalytics(pool_id)))
+    }
+
+    /// Returns the time from which a pool stops accepting bets (its end_time
+    /// unless an earlier lock time was set at creation).
+    pub fn get_lock_time(env: Env, pool_id: u64) -> u64 {
+        let pool = Self::get_pool(env.clone(), pool_id);
+        Self::lock_time(&env, pool_id, &pool)
+    }
+
+    /// Returns the funds still held for a pool: its escrow minus everything
+    /// paid out so far (payouts, held payouts, fees and liquidity returns).
+    pub fn get_pool_unclaimed(env: Env, pool_id: u64) -> i128 {
+        let pool = Self::get_pool(env.clone(), pool_id);
+        Self::pool_unclaimed(&env, pool_id, &pool)
+    }
+
+    /// Returns the segregated escrow buckets of a pool, if it is tracked.
+    pub fn get_pool_escrow(env: Env, pool_id: u64) -> Option<PoolEscrow> {
+        Self::pool_escrow(&env, pool_id)
+    }
+
+    /// Reconcile a pool's segregated escrow with its stake and disbursement
+    /// records (INV-9). Anyone may call this; a discrepancy is reported via
+    /// `EscrowMismatchEvent`. Returns true when the pool reconciles or is not
+    /// tracked.
+    pub fn audit_pool_escrow(env: Env, pool_id: u64) -> Result<bool, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        let Some(escrow) = Self::pool_escrow(&env, pool_id) else {
+            return Ok(true);
+        };
+        let recorded = escrow.locked + escrow.claimable;
+        let expected = Self::pool_unclaimed(&env, pool_id, &pool);
+        if recorded != expected || escrow.locked < 0 || escrow.claimable < 0 {
+            EscrowMismatchEvent {
+                pool_id,
+                recorded,
+                expected,
+            }
+            .publish(&env);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Move whatever a settled pool still holds to the treasury once its
+    /// claim deadline has passed. Claims on the pool are rejected afterwards.
+    /// Caller must have Admin role (0). Returns the amount swept.
+    pub fn sweep_unclaimed(env: Env, admin: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "sweep_unclaimed"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state == MarketState::Active || pool.state == MarketState::Disputed {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if Self::is_pool_swept(&env, pool_id) {
+            return Err(PredifiError::PoolSwept);
+        }
+        let claim_deadline = Self::get_claim_deadline(env.clone(), pool_id)
+            .ok_or(PredifiError::ClaimDeadlineNotReached)?;
+        if env.ledger().timestamp() < claim_deadline {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::ClaimDeadlineNotReached,
+                Some(pool_id),
+                claim_deadline as i128,
+                env.ledger().timestamp() as i128,
+            ));
+        }
+
+        let schedule = SweepSchedule {
+            scheduled_by: admin.clone(),
+            executable_at: claim_deadline,
+            executed: true,
+        };
+        let (amount, recipient) = Self::sweep_pool_to_treasury(&env, pool_id, &schedule);
+
+        UnclaimedSweptEvent {
+            pool_id,
+            admin,
+            amount,
+            recipient,
+            claim_deadline,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    /// Returns the announced sweep of a pool, if any.
+    pub fn get_sweep_schedule(env: Env, pool_id: u64) -> Option<SweepSchedule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SweepSchedule(pool_id))
+    }
+
+    /// Delete the per-bettor records of a settled pool to stop paying rent on
+    /// dead data: predictions, claim markers, bet legs, the bettor index and
+    /// outcome stakes. Callable by anyone once the escrow is empty (every
+    /// payout claimed or swept) or the claim deadline has passed; unclaimed
+    /// entitlements are forfeited and can still be swept. Only the Pool record
+    /// and its accounting survive.
+    ///
+    /// Processes up to `MAX_CLOSE_BATCH` bettors per call and returns how many
+    /// remain; the final call emits `PoolArchivedEvent`. LMSR pools, whose
+    /// positions are redeemed rather than claimed, cannot be closed.
+    pub fn close_pool(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state == MarketState::Active || pool.state == MarketState::Disputed {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if Self::is_pool_archived(&env, pool_id) || Self::lmsr_state(&env, pool_id).is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let deadline_passed = Self::get_claim_deadline(env.clone(), pool_id)
+            .map(|deadline| env.ledger().timestamp() >= deadline)
+            .unwrap_or(false);
+        if Self::pool_unclaimed(&env, pool_id, &pool) > 0
+            && !Self::is_pool_swept(&env, pool_id)
+            && !deadline_passed
+        {
+            return Err(PredifiError::ClaimDeadlineNotReached);
+        }
+
+        let storage = env.storage().persistent();
+        let pc_key = DataKey::ParticipantsCount(pool_id);
+        let pc: u32 = storage.get(&pc_key).unwrap_or(0);
+        let batch = pc.min(MAX_CLOSE_BATCH);
+        for i in (pc - batch)..pc {
+            let slot_key = DataKey::PoolBettor(pool_id, i);
+            if let Some(user) = storage.get::<_, Address>(&slot_key) {
+                storage.remove(&DataKey::Prediction(user.clone(), pool_id));
+                storage.remove(&DataKey::HasClaimed(user.clone(), pool_id));
+                storage.remove(&DataKey::ClaimedAmount(user.clone(), pool_id));
+                storage.remove(&DataKey::Ext(ExtKey::BetLegs(user.clone(), pool_id)));
+                Self::remove_user_prediction_index(&env, &user, pool_id);
+            }
+            storage.remove(&slot_key);
+        }
+        let remaining = pc - batch;
+        if remaining > 0 {
+            storage.set(&pc_key, &remaining);
+            Self::extend_persistent(&env, &pc_key);
+            return Ok(remaining);
+        }
+
+        storage.remove(&pc_key);
+        storage.remove(&DataKey::OutcomeStakes(pool_id));
+        for i in 0..pool.options_count {
+            storage.remove(&DataKey::OutcomeStake(pool_id, i));
+        }
+        storage.remove(&DataKey::Ext(ExtKey::OutcomeStakesMigrated(pool_id)));
+        storage.remove(&DataKey::Ext(ExtKey::BetCurve(pool_id)));
+        storage.remove(&DataKey::Ext(ExtKey::OddsAlerts(pool_id)));
+        storage.remove(&DataKey::WinnersDraft(pool_id));
+
+        let archived_at = env.ledger().timestamp();
+        let archived_key = DataKey::Ext(ExtKey::Archived(pool_id));
+        storage.set(&archived_key, &archived_at);
+        Self::extend_persistent(&env, &archived_key);
+
+        PoolArchivedEvent {
+            pool_id,
+            state: pool.state,
+            total_stake: pool.total_stake,
+            archived_at,
+        }
+        .publish(&env);
+        Ok(0)
+    }
+
+    /// Bump the TTL of a pool, its outcome stakes, its per-pool settings and
+    /// the contract instance to the full `BUMP_AMOUNT`, so long-running pools
+    /// do not expire while nobody interacts with them. Callable by anyone
+    /// (e.g. a keeper bot). Returns the new live-until ledger.
+    pub fn extend_pool_ttl(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        let pool_key = DataKey::Pool(pool_id);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        let mut keys = soroban_sdk::vec![
+            &env,
+            pool_key,
+            DataKey::OutcomeStakes(pool_id),
+            DataKey::CreatorFeeBps(pool_id),
+            DataKey::ClaimDeadline(pool_id),
+            DataKey::ClaimsOpenAt(pool_id),
+            DataKey::OracleQuorum(pool_id),
+            DataKey::LockTime(pool_id),
+            DataKey::AmmFees(pool_id),
+            DataKey::PoolDisbursed(pool_id),
+            DataKey::ParticipantsCount(pool_id),
+            DataKey::Ext(ExtKey::PoolEscrow(pool_id)),
+            DataKey::Ext(ExtKey::StakeCap(pool_id)),
+            DataKey::Ext(ExtKey::OutcomeWeights(pool_id)),
+            DataKey::Ext(ExtKey::OutcomeLabels(pool_id)),
+            DataKey::Ext(ExtKey::LmsrState(pool_id)),
+        ];
+        for i in 0..pool.options_count {
+            keys.push_back(DataKey::OutcomeStake(pool_id, i));
+        }
+        let storage = env.storage().persistent();
+        for key in keys.iter() {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, BUMP_AMOUNT, BUMP_AMOUNT);
+            }
+        }
+        let fee_key = FeeKey::PoolFeeBps(pool_id);
+        if storage.has(&fee_key) {
+            storage.extend_ttl(&fee_key, BUMP_AMOUNT, BUMP_AMOUNT);
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(BUMP_AMOUNT, BUMP_AMOUNT);
+
+        let live_until_ledger = env.ledger().sequence() + BUMP_AMOUNT;
+        TtlExtendedEvent {
+            pool_id,
+            live_until_ledger,
+        }
+        .publish(&env);
+        Ok(live_until_ledger)
+    }
+
+    /// Returns the per-outcome claim counters of a settled pool and the share
+    /// of its payable stake whose claims are complete, so ops can decide when
+    /// to push payouts or schedule a sweep.
+    pub fn get_claim_progress(env: Env, pool_id: u64) -> Result<ClaimProgress, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state == MarketState::Active || pool.state == MarketState::Disputed {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        let stats = Self::claim_stats(&env, pool_id, pool.options_count);
+        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+        let (settled, payable) = if pool.state == MarketState::Resolved {
+            (
+                stats.settled_stake.get(pool.outcome).unwrap_or(0),
+                stakes.get(pool.outcome).unwrap_or(0),
+            )
+        } else {
+            (
+                stats.settled_stake.iter().sum::<i128>(),
+                stakes.iter().sum::<i128>(),
+            )
+        };
+        let claimed_bps = if payable > 0 {
+            (settled * 10_000 / payable) as u32
+        } else {
+            10_000
+        };
+        Ok(ClaimProgress { stats, claimed_bps })
+    }
+
+    /// Returns when a pool's per-bettor records were deleted by `close_pool`.
+    pub fn get_pool_archived_at(env: Env, pool_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::Archived(pool_id)))
+    }
+
+    /// Register a charity / public-goods address that creators may direct a
+    /// share of their creator fee to. Caller must have Admin role (0).
+    pub fn add_public_goods_recipient(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "add_public_goods_recipient"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let key = DataKey::Ext(ExtKey::PublicGoodsRecipient(recipient.clone()));
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        PublicGoodsRegistryEvent {
+            admin,
+            recipient,
+            registered: true,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Remove an address from the public-goods registry. Pools already
+    /// directing fees to it keep doing so; new pools cannot select it.
+    /// Caller must have Admin role (0).
+    pub fn remove_public_goods_recipient(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "remove_public_goods_recipient"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Ext(ExtKey::PublicGoodsRecipient(
+                recipient.clone(),
+            )));
+
+        PublicGoodsRegistryEvent {
+            admin,
+            recipient,
+            registered: false,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns whether `recipient` is in the public-goods registry.
+    pub fn is_public_goods_recipient(env: Env, recipient: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Ext(ExtKey::PublicGoodsRecipient(recipient)))
+    }
+
+    /// Append the current activity counters of `users` to the airdrop
+    /// snapshot of `epoch`, so airdrop tooling can read eligibility straight
+    /// from the contract. Users without activity are skipped; each user should
+    /// be submitted once per epoch. Caller must have Admin role (0).
+    ///
+    /// At most `MAX_BATCH_SIZE` users are processed per call; returns the
+    /// number of entries in the snapshot so far.
+    pub fn snapshot_airdrop(
+        env: Env,
+        admin: Address,
+        epoch: u32,
+        users: Vec<Address>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "snapshot_airdrop"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let epoch_key = DataKey::Ext(ExtKey::AirdropEpoch(epoch));
+        let mut summary: AirdropEpoch =
+            env.storage()
+                .persistent()
+                .get(&epoch_key)
+                .unwrap_or(AirdropEpoch {
+                    entries: 0,
+                    taken_at: env.ledger().timestamp(),
+                    frozen: false,
+                });
+        if summary.frozen {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let processed = core::cmp::min(users.len(), MAX_BATCH_SIZE);
+        for i in 0..processed {
+            let user = users.get(i).unwrap();
+            let activity: Option<UserActivity> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Ext(ExtKey::UserActivity(user.clone())));
+            let Some(activity) = activity else {
+                continue;
+            };
+            let entry_key = DataKey::Ext(ExtKey::AirdropEntry(epoch, summary.entries));
+            env.storage().persistent().set(
+                &entry_key,
+                &AirdropEntry {
+                    user,
+                    volume: activity.volume,
+                    wins: activity.wins,
+                    pools: activity.pools,
+                },
+            );
+            Self::extend_persistent(&env, &entry_key);
+            summary.entries += 1;
+        }
+        env.storage().persistent().set(&epoch_key, &summary);
+        Self::extend_persistent(&env, &epoch_key);
+        Ok(summary.entries)
+    }
+
+    /// Freeze the airdrop snapshot of `epoch`; no entries can be added
+    /// afterwards. Caller must have Admin role (0).
+    pub fn freeze_airdrop_snapshot(
+        env: Env,
+        admin: Address,
+        epoch: u32,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "freeze_airdrop_snapshot"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let epoch_key = DataKey::Ext(ExtKey::AirdropEpoch(epoch));
+        let mut summary: AirdropEpoch = env
+            .storage()
+            .persistent()
+            .get(&epoch_key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        if summary.frozen {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        summary.frozen = true;
+        env.storage().persistent().set(&epoch_key, &summary);
+        Self::extend_persistent(&env, &epoch_key);
+
+        AirdropSnapshotFrozenEvent {
+            admin,
+            epoch,
+            entries: summary.entries,
+        }
+        .publish(&env);
+        Ok(summary.entries)
+    }
+
+    /// Returns the summary of an airdrop snapshot, if one was taken.
+    pub fn get_airdrop_epoch(env: Env, epoch: u32) -> Option<AirdropEpoch> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::AirdropEpoch(epoch)))
+    }
+
+    /// Get a page of an airdrop snapshot's entries, in submission order.
+    /// `limit` is capped at `MAX_POOLS_PAGE_SIZE`.
+    pub fn get_airdrop_snapshot(
+        env: Env,
+        epoch: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<AirdropEntry> {
+        let mut entries = Vec::new(&env);
+        let total = Self::get_airdrop_epoch(env.clone(), epoch)
+            .map(|e| e.entries)
+            .unwrap_or(0);
+        let end = core::cmp::min(
+            offset.saturating_add(core::cmp::min(limit, MAX_POOLS_PAGE_SIZE)),
+            total,
+        );
+        for i in offset..end {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Ext(ExtKey::AirdropEntry(epoch, i)))
+            {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    /// Returns a user's running activity counters.
+    pub fn get_user_activity(env: Env, user: Address) -> UserActivity {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::UserActivity(user)))
+            .unwrap_or_default()
+    }
+
+    /// Upgrade the contract Wasm code. Only callable by Admin (role 0).
+    ///
+    /// The new code takes effect once this invocation returns; call
+    /// `migrate_state` afterwards to bring storage up to its schema.
+    pub fn upgrade_contract(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_role(&env, &admin, 0)?;
+
+        let key = DataKey::Ext(ExtKey::WasmHash);
+        let old_wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32]));
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.storage().instance().set(&key, &new_wasm_hash);
+        Self::extend_instance(&env);
+
+        UpgradeEvent {
+            admin: admin.clone(),
+            old_wasm_hash,
+            new_wasm_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Apply the pending storage migrations after an upgrade. Only callable by
+    /// Admin (role 0).
+    ///
+    /// Each step moves storage from one schema revision to the next, so an
+    /// instance several releases behind is walked forward in order. Running it
+    /// on an up-to-date instance is a no-op.
+    pub fn migrate_state(env: Env, admin: Address) -> Result<(), PredifiError> {
+        admin.require_auth();
+        Self::require_role(&env, &admin, 0)?;
+
+        let key = DataKey::Ext(ExtKey::StorageVersion);
+        let from_version: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        let mut version = from_version;
+        while version < STORAGE_VERSION {
+            // 0 -> 1: rewrite Config in its current layout so older layouts
+            // no longer go through the read shim.
+            if version == 0 {
+                let config = Self::load_config(&env);
+                env.storage().instance().set(&DataKey::Config, &config);
+            }
+            version += 1;
+        }
+        if version == from_version {
+            return Ok(());
+        }
+
+        env.storage().instance().set(&key, &version);
+        Self::extend_instance(&env);
+
+        StorageMigratedEvent {
+            admin,
+            from_version,
+            to_version: version,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Semver string of the deployed build.
+    pub fn version(env: Env) -> String {
+        String::from_str(&env, CONTRACT_VERSION)
+    }
+
+    /// Returns the optional subsystems enabled on this deployment.
+    pub fn get_capabilities(env: Env) -> Capabilities {
+        let instance = env.storage().instance();
+        let dispute_window: u64 = instance.get(&DataKey::DisputeWindow).unwrap_or(0);
+        let claim_period: u64 = instance.get(&DataKey::ClaimPeriod).unwrap_or(0);
+        Capabilities {
+            disputes: dispute_window > 0,
+            amm: true,
+            parlays: true,
+            internal_balances: false,
+            promos: false,
+            claim_deadlines: claim_period > 0,
+            compensation: instance.has(&DataKey::CompensationConfig),
+            dead_man_switch: instance.has(&DataKey::Ext(ExtKey::DeadManSwitch)),
+            optimistic_resolution: instance.has(&DataKey::Ext(ExtKey::OptimisticConfig)),
+        }
+    }
+
+    /// Storage schema revision the instance has been migrated to.
+    ///
+    /// Differs from `STORAGE_VERSION` of the installed code until
+    /// `migrate_state` has been run after an upgrade.
+    pub fn storage_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::StorageVersion))
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the given token is on the allowed betting whitelist.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        Self::is_token_whitelisted(&env, &token)
+    }
+
+    /// Withdraw accumulated protocol fees or unused liquidity from the contract.
+    /// Only callable by Admin (role 0).
+    ///
+    /// # Arguments
+    /// * `admin` - Address with Admin role (must provide auth)
+    /// * `token` - The token contract address to withdraw
+    /// * `amount` - Amount to withdraw (must be > 0)
+    /// * `recipient` - Address to receive the withdrawn funds (typically treasury)
+    ///
+    /// # Returns
+    /// Result indicating success or error
+    ///
+    /// # Security
+    /// - Requires Admin role (0)
+    /// - Emits TreasuryWithdrawnEvent for audit trail
+    /// - Validates amount > 0
+    /// - Checks contract has sufficient balance
+    pub fn withdraw_treasury(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+
+        // Verify admin role
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "withdraw_treasury"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        // Validate amount
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        // Get token client and check balance
+        let token_client = token::Client::new(&env, &token);
+        let contract_balance = token_client.balance(&env.current_contract_address());
+
+        if contract_balance < amount {
+            return Err(PredifiError::InsufficientBalance);
+        }
+
+        // Transfer tokens to recipient
+        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &recipient,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "treasury_withdraw",
+        );
+
+        // Emit audit event
+        TreasuryWithdrawnEvent {
+            admin: admin.clone(),
+            token: token.clone(),
+            amount,
+            recipient: recipient.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Create a new prediction pool. Returns the new pool ID.
+    ///
+    /// PRE: end_time > current_time (INV-8)
+    /// POST: Pool.state = Active, Pool.total_stake = initial_liquidity (if provided)
+    ///
+    /// # Arguments
+    /// * `creator`           - Address of the pool creator (must provide auth).
+    /// * `end_time`          - Unix timestamp after which no more predictions are accepted.
+    /// * `token`             - The Stellar token contract address used for staking.
+    /// * `options_count`     - Number of possible outcomes (must be >= 2 and <= MAX_OPTIONS_COUNT).
+    /// * `description`       - Short human-readable description of the event (max 256 bytes).
+    /// * `metadata_url`      - URL pointing to extended metadata, e.g. an IPFS link (max 512 bytes).
+    /// * `min_stake`         - Minimum stake amount per prediction (must be > 0).
+    /// * `max_stake`         - Maximum stake amount per prediction (0 = no limit, else must be >= min_stake).
+    /// * `initial_liquidity` - Optional initial liquidity to provide (house money). Must be > 0 if provided.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pool(
+        env: Env,
+        creator: Address,
+        end_time: u64,
+        token: Address,
+        options_count: u32,
+        description: String,
+        metadata_url: String,
+        min_stake: i128,
+        max_stake: i128,
+        initial_liquidity: i128,
+        category: Symbol,
+    ) -> u64 {
+        Self::create_pool_with_params(
+            env.clone(),
+            creator,
+            CreatePoolParams {
+                end_time,
+                token,
+                options_count,
+                description,
+                metadata_url,
+                min_stake,
+                max_stake,
+                initial_liquidity,
+                category,
+                creator_fee_bps: 0,
+                oracle_quorum: 0,
+                lock_time: 0,
+                max_total_stake: 0,
+                truncate_at_cap: false,
+                outcome_weights: Vec::new(&env),
+                scalar_buckets: Vec::new(&env),
+                outcome_labels: Vec::new(&env),
+                mechanism: Mechanism::Parimutuel,
+            },
+        )
+    }
+
+    /// Create a new prediction pool from a `CreatePoolParams`. Same rules as
+    /// `create_pool`, plus:
+    /// * `creator_fee_bps` - Share of winning payouts accrued to the creator,
+    ///   capped by the admin-set maximum (see `set_max_creator_fee_bps`).
+    /// * `oracle_quorum`   - Matching oracle submissions required to resolve
+    ///   the pool (0 or 1 = single oracle, max `MAX_ORACLE_QUORUM`).
+    /// * `lock_time`       - When betting closes, in `(now, end_time]`
+    ///   (0 = at end_time).
+    /// * `max_total_stake` - Cap on the pool's total_stake (0 = no cap); bets
+    ///   beyond it are rejected, or truncated if `truncate_at_cap` is set.
+    /// * `outcome_weights` - Optional payout multiplier per outcome, in bps.
+    /// * `scalar_buckets`  - Optional numeric range per outcome (scalar pool).
+    /// * `outcome_labels`  - Optional label per outcome.
+    /// * `mechanism`       - Parimutuel pooling or LMSR share trading.
+    pub fn create_pool_with_params(env: Env, creator: Address, params: CreatePoolParams) -> u64 {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+
+        Self::create_pool_unchecked(env, creator, params)
+    }
+
+    /// Body of `create_pool_with_params`, after the pause and auth checks.
+    fn create_pool_unchecked(env: Env, creator: Address, params: CreatePoolParams) -> u64 {
+        Self::require_scope_not_paused(&env, PAUSE_CREATION);
+        if let Err(e) = Self::require_not_blocked(&env, &creator, "create_pool") {
+            soroban_sdk::panic_with_error!(&env, e);
+        }
+        let CreatePoolParams {
+            end_time,
+            token,
+            options_count,
+            description,
+            metadata_url,
+            min_stake,
+            max_stake,
+            initial_liquidity,
+            category,
+            creator_fee_bps,
+            oracle_quorum,
+            lock_time,
+            max_total_stake,
+            truncate_at_cap,
+            outcome_weights,
+            scalar_buckets,
+            outcome_labels,
+            mechanism,
+        } = params;
+
+        // Validate: category must be in the allowed list
+        assert!(
+            Self::validate_category(&env, &category),
+            "category must be one of the allowed categories"
+        );
+
+        // Validate: token must be on the allowed betting whitelist
+        if !Self::is_token_whitelisted(&env, &token) {
+            soroban_sdk::panic_with_error!(&env, PredifiError::TokenNotWhitelisted);
+        }
+
+        let current_time = env.ledger().timestamp();
+
+        // Validate: end_time must be in the future
+        assert!(end_time > current_time, "end_time must be in the future");
+
+        // Validate: minimum pool duration (1 hour)
+        assert!(
+            end_time >= current_time + MIN_POOL_DURATION,
+            "end_time must be at least 1 hour in the future"
+        );
+
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::Ext(ExtKey::DeadManTriggered))
+        {
+            soroban_sdk::panic_with_error!(&env, PredifiError::DeadManSwitchTriggered);
+        }
+
+        // Validate: admin-set maximum duration (operators may override)
+        let max_duration = Self::get_max_pool_duration(env.clone());
+        if max_duration > 0
+            && end_time - current_time > max_duration
+            && !Self::has_role(&env, &Self::load_config(&env).access_control, &creator, 1)
+        {
+            soroban_sdk::panic_with_error!(&env, PredifiError::PoolDurationTooLong);
+        }
+
+        // Validate: betting cannot stay open past end_time
+        assert!(
+            lock_time == 0 || (lock_time > current_time && lock_time <= end_time),
+            "lock_time must be in the future and not after end_time"
+        );
+
+        // Validate: handicap weights, one per outcome in 1..=10_000 bps
+        assert!(
+            outcome_weights.is_empty()
+                || (outcome_weights.len() == options_count
+                    && outcome_weights.iter().all(|w| w > 0 && w <= 10_000)),
+            "outcome_weights must be empty or one weight in 1..=10000 bps per outcome"
+        );
+
+        // Validate: scalar buckets, one ascending non-overlapping range per outcome
+        assert!(
+            scalar_buckets.is_empty()
+                || (scalar_buckets.len() == options_count
+                    && scalar_buckets.iter().all(|(low, high)| low < high)
+                    && (1..scalar_buckets.len()).all(|i| {
+                        scalar_buckets.get_unchecked(i - 1).1 <= scalar_buckets.get_unchecked(i).0
+                    })),
+            "scalar_buckets must be empty or one ascending, non-overlapping range per outcome"
+        );
+
+        // Validate: outcome labels, one non-empty label of at most 64 bytes per outcome
+        assert!(
+            outcome_labels.is_empty()
+                || (outcome_labels.len() == options_count
+                    && outcome_labels
+                        .iter()
+                        .all(|l| !l.is_empty() && l.len() <= MAX_OUTCOME_LABEL_LEN)),
+            "outcome_labels must be empty or one label of 1..=64 bytes per outcome"
+        );
+
+        // Validate: the stake cap must leave room for bets
+        assert!(
+            max_total_stake == 0 || max_total_stake > initial_liquidity,
+            "max_total_stake must be zero (no cap) or exceed initial_liquidity"
+        );
+
+        // Validate: options_count must be at least 2 (binary or more outcomes)
+        assert!(options_count >= 2, "options_count must be at least 2");
+
+        // Validate: LMSR pools are funded by the initial liquidity alone
+        assert!(
+            mechanism == Mechanism::Parimutuel
+                || (initial_liquidity > 0 && outcome_weights.is_empty() && max_total_stake == 0),
+            "lmsr pools need initial_liquidity and no outcome_weights or stake cap"
+        );
+
+        // Validate: options_count must not exceed maximum limit
+        assert!(
+            options_count <= MAX_OPTIONS_COUNT,
+            "options_count exceeds maximum allowed value"
+        );
+
+        // Validate: initial_liquidity must be non-negative if provided
+        assert!(
+            initial_liquidity >= 0,
+            "initial_liquidity must be non-negative"
+        );
+
+        // Validate: initial_liquidity must not exceed maximum limit
+        assert!(
+            initial_liquidity <= MAX_INITIAL_LIQUIDITY,
+            "initial_liquidity exceeds maximum allowed value"
+        );
+
+        // Note: Token address validation is deferred to when the token is actually used.
+        // This is the standard pattern in Soroban - invalid tokens will fail when
+        // transfers are attempted during place_prediction.
+
+        assert!(description.len() <= 256, "description exceeds 256 bytes");
+        assert!(metadata_url.len() <= 512, "metadata_url exceeds 512 bytes");
+
+        // Validate stake limits
+        assert!(min_stake > 0, "min_stake must be greater than zero");
+        assert!(
+            max_stake == 0 || max_stake >= min_stake,
+            "max_stake must be zero (unlimited) or >= min_stake"
+        );
+
+        // Validate: creator fee within the admin-set cap
+        let max_creator_fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxCreatorFeeBps)
+            .unwrap_or(0);
+        if creator_fee_bps > max_creator_fee_bps {
+            soroban_sdk::panic_with_error!(&env, PredifiError::CreatorFeeTooHigh);
+        }
+
+        if oracle_quorum > MAX_ORACLE_QUORUM {
+            soroban_sdk::panic_with_error!(&env, PredifiError::InvalidOracleQuorum);
+        }
+
+        // Validate: the creator can fund the initial liquidity
+        if initial_liquidity > 0 {
+            if let Err(e) = Self::check_balance(&env, &token, &creator, initial_liquidity) {
+                soroban_sdk::panic_with_error!(&env, e);
+            }
+        }
+
+        // Validate: protocol-wide cap on Active pools (operators may override)
+        let config = Self::load_config(&env);
+        let active_pools = Self::read_active_pool_count(&env);
+        if config.max_active_pools > 0
+            && active_pools >= config.max_active_pools
+            && !Self::has_role(&env, &config.access_control, &creator, 1)
+        {
+            soroban_sdk::panic_with_error!(&env, PredifiError::ActivePoolLimitReached);
+        }
+
+        let pool_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PoolIdCounter)
+            .unwrap_or(0);
+        Self::extend_instance(&env);
+
+        let pool = Pool {
+            end_time,
+            resolved: false,
+            canceled: false,
+            state: MarketState::Active,
+            outcome: 0,
+            token: token.clone(),
+            total_stake: initial_liquidity, // Initial liquidity is part of total stake
+            description,
+            metadata_url: metadata_url.clone(),
+            options_count,
+            min_stake,
+            max_stake,
+            initial_liquidity,
+            creator: creator.clone(),
+            category: category.clone(),
+        };
+
+        let pool_key = DataKey::Pool(pool_id);
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+
+        let pc_key = DataKey::ParticipantsCount(pool_id);
+        env.storage().persistent().set(&pc_key, &0u32);
+        Self::extend_persistent(&env, &pc_key);
+
+        if creator_fee_bps > 0 {
+            let fee_key = DataKey::CreatorFeeBps(pool_id);
+            env.storage().persistent().set(&fee_key, &creator_fee_bps);
+            Self::extend_persistent(&env, &fee_key);
+        }
+        let claim_period: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimPeriod)
+            .unwrap_or(0);
+        if claim_period > 0 {
+            let deadline_key = DataKey::ClaimDeadline(pool_id);
+            env.storage()
+                .persistent()
+                .set(&deadline_key, &end_time.saturating_add(claim_period));
+            Self::extend_persistent(&env, &deadline_key);
+        }
+        if oracle_quorum > 1 {
+            let quorum_key = DataKey::OracleQuorum(pool_id);
+            env.storage().persistent().set(&quorum_key, &oracle_quorum);
+            Self::extend_persistent(&env, &quorum_key);
+        }
+        if lock_time > 0 && lock_time < end_time {
+            let lock_key = DataKey::LockTime(pool_id);
+            env.storage().persistent().set(&lock_key, &lock_time);
+            Self::extend_persistent(&env, &lock_key);
+        }
+        if max_total_stake > 0 {
+            let cap_key = DataKey::Ext(ExtKey::StakeCap(pool_id));
+            env.storage().persistent().set(
+                &cap_key,
+                &StakeCap {
+                    max_total_stake,
+                    truncate_at_cap,
+                },
+            );
+            Self::extend_persistent(&env, &cap_key);
+        }
+        if !outcome_weights.is_empty() {
+            let weights_key = DataKey::Ext(ExtKey::OutcomeWeights(pool_id));
+            env.storage()
+                .persistent()
+                .set(&weights_key, &outcome_weights);
+            Self::extend_persistent(&env, &weights_key);
+        }
+        if !outcome_labels.is_empty() {
+            let labels_key = DataKey::Ext(ExtKey::OutcomeLabels(pool_id));
+            env.storage().persistent().set(&labels_key, &outcome_labels);
+            Self::extend_persistent(&env, &labels_key);
+        }
+        if mechanism == Mechanism::Lmsr {
+            let ln_n = SafeMath::ln_wad(options_count as i128 * safe_math::WAD)
+                .expect("ln of options_count");
+            let b = initial_liquidity * safe_math::WAD / ln_n;
+            assert!(b > 0, "initial_liquidity too small for an lmsr pool");
+            let lmsr_key = DataKey::Ext(ExtKey::LmsrState(pool_id));
+            env.storage().persistent().set(
+                &lmsr_key,
+                &LmsrState {
+                    b,
+                    shares: Self::zero_shares(&env, options_count),
+                    collected: 0,
+                    open_basis: 0,
+                },
+            );
+            Self::extend_persistent(&env, &lmsr_key);
+        }
+        if !scalar_buckets.is_empty() {
+            let buckets_key = DataKey::Ext(ExtKey::ScalarBuckets(pool_id));
+            env.storage()
+                .persistent()
+                .set(&buckets_key, &scalar_buckets);
+            Self::extend_persistent(&env, &buckets_key);
+        }
+        let curve_key = DataKey::Ext(ExtKey::BetCurve(pool_id));
+        env.storage()
+            .persistent()
+            .set(&curve_key, &Self::new_bet_curve(&env));
+        Self::extend_persistent(&env, &curve_key);
+        // New pools never write the legacy per-outcome keys.
+        Self::mark_outcome_stakes_migrated(&env, pool_id);
+        Self::write_pool_escrow(
+            &env,
+            pool_id,
+            &PoolEscrow {
+                locked: initial_liquidity,
+                ..PoolEscrow::default()
+            },
+        );
+
+        // Transfer initial liquidity from creator to contract if provided
+        if initial_liquidity > 0 {
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&creator, env.current_contract_address(), &initial_liquidity);
+            Self::record_ledger_entry(
+                &env,
+                Some(pool_id),
+                &creator,
+                &token,
+                LedgerDirection::In,
+                initial_liquidity,
+                "liquidity",
+            );
+        }
+
+        // Post the creator bond, if one is required
+        let bond: Option<CreatorBond> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::CreatorBondConfig));
+        if let Some(bond) = bond {
+            let token_client = token::Client::new(&env, &bond.token);
+            token_client.transfer(&creator, env.current_contract_address(), &bond.amount);
+            Self::record_ledger_entry(
+                &env,
+                Some(pool_id),
+                &creator,
+                &bond.token,
+                LedgerDirection::In,
+                bond.amount,
+                "bond",
+            );
+            let bond_key = DataKey::Ext(ExtKey::CreatorBond(pool_id));
+            env.storage().persistent().set(&bond_key, &bond);
+            Self::extend_persistent(&env, &bond_key);
+        }
+
+        // Update category index
+        let category_count_key = DataKey::CategoryPoolCount(category.clone());
+        let category_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&category_count_key)
+            .unwrap_or(0);
+
+        let category_index_key = DataKey::CategoryPoolIndex(category.clone(), category_count);
+        env.storage()
+            .persistent()
+            .set(&category_index_key, &pool_id);
+        Self::extend_persistent(&env, &category_index_key);
+
+        env.storage()
+            .persistent()
+            .set(&category_count_key, &(category_count + 1));
+        Self::extend_persistent(&env, &category_count_key);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PoolIdCounter, &(pool_id + 1));
+        Self::extend_instance(&env);
+        Self::write_active_pool_count(&env, active_pools + 1);
+
+        // Curation: pools from non-privileged creators wait for review.
+        let curation = Self::get_curation_config(env.clone());
+        let access_control = Self::load_config(&env).access_control;
+        if curation.enabled
+            && !Self::check_role(&env, &access_control, &creator, 0).unwrap_or(false)
+            && !Self::check_role(&env, &access_control, &creator, 1).unwrap_or(false)
+        {
+            let auto_approve_at = if curation.auto_approve_after > 0 {
+                env.ledger()
+                    .timestamp()
+                    .saturating_add(curation.auto_approve_after)
+            } else {
+                0
+            };
+            let draft_key = CurationKey::PoolDraft(pool_id);
+            env.storage().persistent().set(&draft_key, &auto_approve_at);
+            Self::extend_persistent(&env, &draft_key);
+            PoolSubmittedForReviewEvent {
+                pool_id,
+                creator: creator.clone(),
+                auto_approve_at,
+            }
+            .publish(&env);
+        }
+
+        PoolCreatedEvent {
+            pool_id,
+            end_time,
+            token,
+            options_count,
+            metadata_url,
+            initial_liquidity,
+            category,
+        }
+        .publish(&env);
+
+        // Emit initial liquidity event if liquidity was provided
+        if initial_liquidity > 0 {
+            InitialLiquidityProvidedEvent {
+                pool_id,
+                creator,
+                amount: initial_liquidity,
+            }
+            .publish(&env);
+        }
+
+        Self::check_pool_invariants(&env, pool_id);
+        pool_id
+    }
+
+    /// Resolve a pool with a winning outcome. Caller must have Operator role (1).
+    /// Cannot resolve a canceled pool.
+    /// PRE: pool.state = Active, operator has role 1
+    /// POST: pool.state = Resolved, state transition valid (INV-2)
+    pub fn resolve_pool(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> Result<(), PredifiError> {
+        let note = String::from_str(&env, "");
+        Self::resolve_pool_with_note(env, operator, pool_id, outcome, note)
+    }
+
+    /// Resolve a scalar pool from the observed value of its underlying: the
+    /// outcome whose range `[low, high)` contains `observed_value` wins.
+    /// Same rules as `resolve_pool`; fails with `InvalidOutcome` if no range
+    /// contains the value.
+    pub fn resolve_scalar(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        observed_value: i128,
+    ) -> Result<u32, PredifiError> {
+        let buckets = Self::get_scalar_buckets(env.clone(), pool_id);
+        if buckets.is_empty() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let outcome = buckets
+            .iter()
+            .position(|(low, high)| low <= observed_value && observed_value < high)
+            .ok_or(PredifiError::InvalidOutcome)? as u32;
+
+        let note = String::from_str(&env, "");
+        Self::resolve_pool_checked(env.clone(), operator, pool_id, outcome, note, false)?;
+        ScalarResolvedEvent {
+            pool_id,
+            observed_value,
+            outcome,
+        }
+        .publish(&env);
+        Ok(outcome)
+    }
+
+    /// Same as `resolve_pool`, attaching a short operator note (source link,
+    /// justification or content hash, max 256 bytes; longer notes fail with
+    /// `NoteTooLong`). The note is persisted per pool (see
+    /// `get_operator_note`) and included in `PoolResolvedEvent`.
+    ///
+    /// Resolving to an outcome nobody staked on while other outcomes have bets
+    /// fails with `ZeroStakeOutcome`; confirm such results with
+    /// `force_resolve_pool`.
+    pub fn resolve_pool_with_note(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+        note: String,
+    ) -> Result<(), PredifiError> {
+        Self::resolve_pool_checked(env, operator, pool_id, outcome, note, false)
+    }
+
+    /// Resolve a pool to an outcome nobody staked on, confirming a result that
+    /// `resolve_pool` rejected with `ZeroStakeOutcome`. Every bettor loses, so
+    /// the operator must call this entry point explicitly.
+    /// Caller must have Operator role (1).
+    pub fn force_resolve_pool(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+        note: String,
+    ) -> Result<(), PredifiError> {
+        Self::resolve_pool_checked(env, operator, pool_id, outcome, note, true)
+    }
+
+    fn resolve_pool_checked(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+        note: String,
+        allow_zero_stake: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        if let Err(e) = Self::require_role(&env, &operator, 1) {
+            // 🔴 HIGH ALERT: unauthorized attempt to resolve a pool.
+            UnauthorizedResolveAttemptEvent {
+                caller: operator,
+                pool_id,
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        Self::check_note(&note)?;
+
+        Self::assert_not_settled(&env, pool_id);
+        let pool = Self::check_resolvable(&env, pool_id, outcome, allow_zero_stake)?;
+
+        Self::finalize_resolution(&env, pool_id, pool, outcome, operator, note);
+
+        Ok(())
+    }
+
+    /// Seal the outcome of a sensitive market once it has ended: publish only
+    /// the hash of (pool_id, outcome, salt) so that the result cannot be read
+    /// from the operator's transactions before it is revealed. The pool then
+    /// resolves only through `reveal_resolution`. Caller must have Operator
+    /// role (1).
+    /// PRE: pool.state = Active, end_time <= now, no commitment yet
+    pub fn commit_resolution(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        commitment: BytesN<32>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        if let Err(e) = Self::require_role(&env, &operator, 1) {
+            // 🔴 HIGH ALERT: unauthorized attempt to resolve a pool.
+            UnauthorizedResolveAttemptEvent {
+                caller: operator,
+                pool_id,
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active
+            || Self::is_resolution_locked(&env, pool_id)
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::DerivativeLink(pool_id))
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() < pool.end_time {
+            return Err(PredifiError::ResolutionDelayNotMet);
+        }
+
+        let key = DataKey::Ext(ExtKey::ResolutionCommitment(pool_id));
+        env.storage().persistent().set(
+            &key,
+            &ResolutionCommitment {
+                operator: operator.clone(),
+                commitment: commitment.clone(),
+                committed_at: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        ResolutionCommittedEvent {
+            pool_id,
+            operator,
+            commitment,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Resolve a committed pool by revealing its outcome and salt, once the
+    /// resolution delay has passed. Any operator may reveal.
+    ///
+    /// # Errors
+    /// - `CommitmentMismatch` if (pool_id, outcome, salt) does not hash to the
+    ///   commitment.
+    /// - Any error of `resolve_pool_with_note`.
+    pub fn reveal_resolution(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        outcome: u32,
+        salt: BytesN<32>,
+        note: String,
+    ) -> Result<(), PredifiError> {
+        let key = DataKey::Ext(ExtKey::ResolutionCommitment(pool_id));
+        let sealed: ResolutionCommitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        if Self::resolution_commitment(&env, pool_id, outcome, &salt) != sealed.commitment {
+            return Err(PredifiError::CommitmentMismatch);
+        }
+        env.storage().persistent().remove(&key);
+
+        Self::resolve_pool_checked(env, operator, pool_id, outcome, note, false)
+    }
+
+    /// Returns the pending resolution commitment of a pool, if any.
+    pub fn get_resolution_commitment(env: Env, pool_id: u64) -> Option<ResolutionCommitment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ResolutionCommitment(pool_id)))
+    }
+
+    /// Configure optimistic resolution: assertions must post at least
+    /// `min_bond` of the pool's token and stay open to challenges for
+    /// `liveness` seconds. `liveness` = 0 disables new assertions; pending
+    /// ones keep their window. Caller must have Admin role (0).
+    pub fn set_optimistic_config(
+        env: Env,
+        admin: Address,
+        min_bond: i128,
+        liveness: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_optimistic_config"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if min_bond <= 0 && liveness > 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = DataKey::Ext(ExtKey::OptimisticConfig);
+        let old = Self::get_optimistic_config(env.clone());
+        Self::record_config_change(
+            &env,
+            &admin,
+            "optimistic_liveness",
+            ConfigValue::U64(old.as_ref().map(|c| c.liveness).unwrap_or(0)),
+            ConfigValue::U64(liveness),
+        );
+        Self::record_config_change(
+            &env,
+            &admin,
+            "optimistic_min_bond",
+            ConfigValue::I128(old.map(|c| c.min_bond).unwrap_or(0)),
+            ConfigValue::I128(min_bond),
+        );
+        if liveness == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage()
+                .instance()
+                .set(&key, &OptimisticConfig { min_bond, liveness });
+        }
+        Self::extend_instance(&env);
+
+        OptimisticConfigUpdateEvent {
+            admin,
+            min_bond,
+            liveness,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the optimistic resolution settings, if enabled.
+    pub fn get_optimistic_config(env: Env) -> Option<OptimisticConfig> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::OptimisticConfig))
+    }
+
+    /// Propose the outcome of an ended pool, backed by `bond` of the pool's
+    /// token. Unless challenged, anyone may `settle_assertion` once the
+    /// liveness window has passed. While pending, the pool cannot be resolved
+    /// by other means. Returns the end of the liveness window.
+    ///
+    /// # Errors
+    /// - `OptimisticResolutionDisabled` if no liveness window is configured.
+    /// - `InvalidAmount` if `bond` is below the configured minimum.
+    /// - `InvalidPoolState` if the pool already has a pending assertion or
+    ///   commitment, plus any resolution error of `resolve_pool`.
+    pub fn assert_outcome(
+        env: Env,
+        asserter: Address,
+        pool_id: u64,
+        outcome: u32,
+        bond: i128,
+    ) -> Result<u64, PredifiError> {
+        Self::require_not_paused(&env);
+        asserter.require_auth();
+        let config = Self::get_optimistic_config(env.clone())
+            .ok_or(PredifiError::OptimisticResolutionDisabled)?;
+        if bond < config.min_bond {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::InvalidAmount,
+                Some(pool_id),
+                config.min_bond,
+                bond,
+            ));
+        }
+        let pool = Self::check_resolvable(&env, pool_id, outcome, false)?;
+
+        let expires_at = env.ledger().timestamp().saturating_add(config.liveness);
+        let key = DataKey::Ext(ExtKey::OutcomeAssertion(pool_id));
+        env.storage().persistent().set(
+            &key,
+            &OutcomeAssertion {
+                asserter: asserter.clone(),
+                outcome,
+                bond,
+                expires_at,
+                challenger: None,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&asserter, env.current_contract_address(), &bond);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &asserter,
+            &pool.token,
+            LedgerDirection::In,
+            bond,
+            "assertion_bond",
+        );
+
+        OutcomeAssertedEvent {
+            pool_id,
+            asserter,
+            outcome,
+            bond,
+            expires_at,
+        }
+        .publish(&env);
+        Ok(expires_at)
+    }
+
+    /// Dispute a pending assertion by posting a matching bond before its
+    /// liveness window ends. The pool then resolves only through
+    /// `resolve_assertion`.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if no unchallenged assertion is pending.
+    /// - `AssertionLivenessExpired` once the liveness window has passed.
+    pub fn challenge_assertion(
+        env: Env,
+        challenger: Address,
+        pool_id: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        challenger.require_auth();
+        let key = DataKey::Ext(ExtKey::OutcomeAssertion(pool_id));
+        let mut assertion: OutcomeAssertion = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        if assertion.challenger.is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let now = env.ledger().timestamp();
+        if now >= assertion.expires_at {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::AssertionLivenessExpired,
+                Some(pool_id),
+                assertion.expires_at as i128,
+                now as i128,
+            ));
+        }
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        assertion.challenger = Some(challenger.clone());
+        env.storage().persistent().set(&key, &assertion);
+        Self::extend_persistent(&env, &key);
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&challenger, env.current_contract_address(), &assertion.bond);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &challenger,
+            &pool.token,
+            LedgerDirection::In,
+            assertion.bond,
+            "assertion_bond",
+        );
+
+        AssertionChallengedEvent {
+            pool_id,
+            challenger,
+            bond: assertion.bond,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Resolve a pool to its unchallenged asserted outcome once the liveness
+    /// window has passed, returning the bond to the asserter. Callable by
+    /// anyone. Returns the outcome.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if no unchallenged assertion is pending.
+    /// - `AssertionLivenessActive` while the assertion can still be challenged.
+    pub fn settle_assertion(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        let key = DataKey::Ext(ExtKey::OutcomeAssertion(pool_id));
+        let assertion: OutcomeAssertion = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        if assertion.challenger.is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let now = env.ledger().timestamp();
+        if now < assertion.expires_at {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::AssertionLivenessActive,
+                Some(pool_id),
+                assertion.expires_at as i128,
+                now as i128,
+            ));
+        }
+        env.storage().persistent().remove(&key);
+        let pool = Self::check_resolvable(&env, pool_id, assertion.outcome, false)?;
+
+        Self::finalize_resolution(
+            &env,
+            pool_id,
+            pool.clone(),
+            assertion.outcome,
+            assertion.asserter.clone(),
+            String::from_str(&env, "optimistic"),
+        );
+        Self::pay_assertion_bond(
+            &env,
+            pool_id,
+            &pool,
+            &assertion.asserter,
+            assertion.bond,
+            "assertion_return",
+        );
+
+        AssertionSettledEvent {
+            pool_id,
+            outcome: assertion.outcome,
+            winner: assertion.asserter,
+            payout: assertion.bond,
+        }
+        .publish(&env);
+        Ok(assertion.outcome)
+    }
+
+    /// Rule on a challenged assertion and resolve the pool to `outcome`.
+    /// Both bonds go to the asserter if `outcome` matches the assertion, and
+    /// to the challenger otherwise. Caller must have Operator (1) or Oracle
+    /// (3) role. Returns the winner of the bonds.
+    ///
+    /// # Errors
+    /// - `InvalidPoolState` if no challenged assertion is pending.
+    /// - Any resolution error of `resolve_pool`.
+    pub fn resolve_assertion(
+        env: Env,
+        resolver: Address,
+        pool_id: u64,
+        outcome: u32,
+    ) -> Result<Address, PredifiError> {
+        Self::require_not_paused(&env);
+        resolver.require_auth();
+        let authorized = match Self::require_role(&env, &resolver, 1) {
+            Err(PredifiError::Unauthorized) => Self::require_role(&env, &resolver, 3),
+            other => other,
+        };
+        if let Err(e) = authorized {
+            // 🔴 HIGH ALERT: unauthorized attempt to resolve a pool.
+            UnauthorizedResolveAttemptEvent {
+                caller: resolver,
+                pool_id,
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let key = DataKey::Ext(ExtKey::OutcomeAssertion(pool_id));
+        let assertion: OutcomeAssertion = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        let Some(challenger) = assertion.challenger else {
+            return Err(PredifiError::InvalidPoolState);
+        };
+        env.storage().persistent().remove(&key);
+        let pool = Self::check_resolvable(&env, pool_id, outcome, false)?;
+
+        let winner = if outcome == assertion.outcome {
+            assertion.asserter
+        } else {
+            challenger
+        };
+        let payout = assertion.bond.saturating_mul(2);
+
+        Self::finalize_resolution(
+            &env,
+            pool_id,
+            pool.clone(),
+            outcome,
+            resolver,
+            String::from_str(&env, "assertion_dispute"),
+        );
+        Self::pay_assertion_bond(&env, pool_id, &pool, &winner, payout, "assertion_payout");
+
+        AssertionSettledEvent {
+            pool_id,
+            outcome,
+            winner: winner.clone(),
+            payout,
+        }
+        .publish(&env);
+        Ok(winner)
+    }
+
+    /// Returns the pending outcome assertion of a pool, if any.
+    pub fn get_outcome_assertion(env: Env, pool_id: u64) -> Option<OutcomeAssertion> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OutcomeAssertion(pool_id)))
+    }
+
+    /// Resolve several pools in one transaction, e.g. all markets of a match day.
+    ///
+    /// Items are processed in order, at most `MAX_BATCH_SIZE` per call. Each
+    /// item is validated independently: a failing item is reported in the
+    /// result and does not revert the others. Only authorization failures
+    /// abort the whole call.
+    pub fn resolve_pools_batch(
+        env: Env,
+        operator: Address,
+        items: Vec<(u64, u32)>,
+    ) -> Result<BatchResult, PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let processed = core::cmp::min(items.len(), MAX_BATCH_SIZE);
+        let mut results = Vec::new(&env);
+        let mut failed: u32 = 0;
+        for i in 0..processed {
+            let (pool_id, outcome) = items.get(i).unwrap();
+            let error = match Self::check_resolvable(&env, pool_id, outcome, false) {
+                Ok(pool) => {
+                    Self::finalize_resolution(
+                        &env,
+                        pool_id,
+                        pool,
+                        outcome,
+                        operator.clone(),
+                        String::from_str(&env, ""),
+                    );
+                    0
+                }
+                Err(e) => {
+                    failed += 1;
+                    e as u32
+                }
+            };
+            results.push_back(BatchItemResult { pool_id, error });
+        }
+
+        PoolsBatchResolvedEvent {
+            operator,
+            processed,
+            failed,
+        }
+        .publish(&env);
+
+        Ok(BatchResult { processed, results })
+    }
+
+    /// Dry-run a sequence of bets against current state, for bots planning
+    /// multi-step strategies. Each action sees the effect of the earlier
+    /// ones; failing actions are reported and leave the simulated state
+    /// unchanged. Nothing is written. At most `MAX_BATCH_SIZE` actions are
+    /// evaluated.
+    pub fn simulate_batch(env: Env, actions: Vec<SimAction>) -> Vec<SimResult> {
+        let now = env.ledger().timestamp();
+        let mut pools: Map<u64, (Pool, Vec<i128>)> = Map::new(&env);
+        let mut results = Vec::new(&env);
+        for action in actions.iter().take(MAX_BATCH_SIZE as usize) {
+            let pool_id = action.pool_id;
+            let state = match pools.get(pool_id) {
+                Some(state) => Some(state),
+                None => env
+                    .storage()
+                    .persistent()
+                    .get::<_, Pool>(&DataKey::Pool(pool_id))
+                    .map(|mut pool| {
+                        pool.total_stake += Self::stake_delta(&env, pool_id);
+                        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+                        (pool, stakes)
+                    }),
+            };
+            let simulated = match state {
+                None => Err(PredifiError::PoolNotFound),
+                Some((pool, stakes)) => {
+                    Self::simulate_bet(&env, pool_id, pool, stakes, &action, now)
+                }
+            };
+            let result = match simulated {
+                Ok((pool, stakes, accepted)) => {
+                    let outcome_stake = stakes.get(action.outcome).unwrap_or(0);
+                    let prediction = Prediction {
+                        amount: accepted,
+                        outcome: action.outcome,
+                    };
+                    let payout = Self::quote_payout(&env, pool_id, &pool, &stakes, &prediction);
+                    let odds_bps = Self::implied_odds(pool.total_stake, outcome_stake);
+                    pools.set(pool_id, (pool, stakes));
+                    SimResult {
+                        pool_id,
+                        error: 0,
+                        accepted,
+                        odds_bps,
+                        payout,
+                    }
+                }
+                Err(e) => SimResult {
+                    pool_id,
+                    error: e as u32,
+                    accepted: 0,
+                    odds_bps: 0,
+                    payout: 0,
+                },
+            };
+            results.push_back(result);
+        }
+        results
+    }
+
+    /// Net payout a new bet of `amount` on `outcome` would receive if that
+    /// outcome won, given the pool's current stakes plus the bet itself
+    /// (after any stake-cap truncation), net of protocol and creator fees.
+    /// Read-only; applies the same checks as `place_prediction`.
+    pub fn quote_prediction(
+        env: Env,
+        pool_id: u64,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        pool.total_stake += Self::stake_delta(&env, pool_id);
+        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+        let action = SimAction {
+            pool_id,
+            outcome,
+            amount,
+        };
+        let (pool, stakes, accepted) = Self::simulate_bet(
+            &env,
+            pool_id,
+            pool,
+            stakes,
+            &action,
+            env.ledger().timestamp(),
+        )?;
+        let prediction = Prediction {
+            amount: accepted,
+            outcome,
+        };
+        Ok(Self::quote_payout(
+            &env,
+            pool_id,
+            &pool,
+            &stakes,
+            &prediction,
+        ))
+    }
+
+    /// The amount `claim_winnings` would pay `user` right now, with the same
+    /// errors. Read-only; excludes any cancellation compensation.
+    pub fn simulate_claim(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state == MarketState::Active {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if Self::is_pool_swept(&env, pool_id) {
+            return Err(PredifiError::PoolSwept);
+        }
+        if pool.state == MarketState::Resolved
+            && env.ledger().timestamp() < Self::claims_open_at(&env, pool_id)
+        {
+            return Err(PredifiError::ClaimsNotOpen);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::HasClaimed(user.clone(), pool_id))
+        {
+            return Err(PredifiError::AlreadyClaimed);
+        }
+
+        let prediction: Prediction = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Prediction(user.clone(), pool_id))
+        {
+            Some(p) => Self::settled_prediction(&env, &user, pool_id, &pool, p),
+            None => return Ok(0),
+        };
+        let entitlement = Self::calculate_entitlement(&env, pool_id, &pool, &prediction);
+        let paid: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimedAmount(user, pool_id))
+            .unwrap_or(0);
+        Ok(entitlement - paid)
+    }
+
+    /// Apply a hypothetical bet to an in-memory copy of a pool, with the
+    /// checks of `place_prediction`. Returns the updated pool and stakes and
+    /// the accepted amount.
+    fn simulate_bet(
+        env: &Env,
+        pool_id: u64,
+        mut pool: Pool,
+        mut stakes: Vec<i128>,
+        action: &SimAction,
+        now: u64,
+    ) -> Result<(Pool, Vec<i128>, i128), PredifiError> {
+        if pool.state != MarketState::Active || Self::lmsr_state(env, pool_id).is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if now >= Self::lock_time(env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+        if action.outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
+        let mut amount = action.amount;
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let cap: Option<StakeCap> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::StakeCap(pool_id)));
+        if let Some(cap) = cap {
+            let remaining = cap.max_total_stake.saturating_sub(pool.total_stake);
+            if remaining <= 0 || (amount > remaining && !cap.truncate_at_cap) {
+                return Err(PredifiError::PoolStakeCapReached);
+            }
+            amount = core::cmp::min(amount, remaining);
+        }
+        if amount < pool.min_stake
+            || amount < Self::token_params(env, &pool.token).min_stake
+            || (pool.max_stake > 0 && amount > pool.max_stake)
+        {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        pool.total_stake = pool
+            .total_stake
+            .checked_add(amount)
+            .ok_or(PredifiError::InvalidAmount)?;
+        let current = stakes.get(action.outcome).unwrap_or(0);
+        stakes.set(action.outcome, current + amount);
+        Ok((pool, stakes, amount))
+    }
+
+    /// Net payout of `prediction` if its outcome won a pool in the given
+    /// (possibly simulated) state: the pro-rata share of the pool, scaled by
+    /// the outcome weight, minus protocol and creator fees.
+    fn quote_payout(
+        env: &Env,
+        pool_id: u64,
+        pool: &Pool,
+        stakes: &Vec<i128>,
+        prediction: &Prediction,
+    ) -> i128 {
+        let winning_stake = stakes.get(prediction.outcome).unwrap_or(0);
+        let gross = Self::calculate_winnings(
+            prediction.amount,
+            winning_stake + Self::liquidity_seed(pool),
+            pool.total_stake,
+        ) * Self::outcome_weight_bps(env, pool_id, prediction.outcome) as i128
+            / 10_000;
+        let bettor_stake = pool.total_stake - pool.initial_liquidity;
+        let fee = Self::calculate_protocol_fee(
+            gross,
+            pool.total_stake,
+            bettor_stake,
+            Self::pool_fee_bps(env, pool_id, pool),
+        );
+        let creator_fee_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreatorFeeBps(pool_id))
+            .unwrap_or(0);
+        let creator_fee =
+            Self::calculate_protocol_fee(gross, pool.total_stake, bettor_stake, creator_fee_bps);
+        gross - fee - creator_fee
+    }
+
+    /// Correct the outcome of a resolved pool while claims are still blocked
+    /// (dispute window or settlement hold). The pool moves to Disputed and back
+    /// to Resolved with `new_outcome`, and the dispute window restarts. Any
+    /// published winners summary is discarded. Caller must have Operator role (1).
+    /// PRE: pool.state = Resolved, current_time < claims_open_at
+    /// POST: pool.state = Resolved, pool.outcome = new_outcome (INV-2)
+    pub fn dispute_resolution(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        new_outcome: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Resolved {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if env.ledger().timestamp() >= Self::claims_open_at(&env, pool_id) {
+            return Err(PredifiError::DisputeWindowClosed);
+        }
+        if new_outcome >= pool.options_count || new_outcome == pool.outcome {
+            return Err(PredifiError::InvalidOutcome);
+        }
+
+        // Resolved → Disputed → Resolved (INV-2)
+        assert!(Self::is_valid_state_transition(
+            pool.state,
+            MarketState::Disputed
+        ));
+        pool.state = MarketState::Disputed;
+        assert!(Self::is_valid_state_transition(
+            pool.state,
+            MarketState::Resolved
+        ));
+        let old_outcome = pool.outcome;
+        pool.state = MarketState::Resolved;
+        pool.outcome = new_outcome;
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WinnersDraft(pool_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WinnersSummary(pool_id));
+
+        let claims_open_at = Self::schedule_claims_opening(&env, pool_id, &pool);
+
+        PoolDisputedEvent {
+            pool_id,
+            operator: operator.clone(),
+            old_outcome,
+            new_outcome,
+            claims_open_at,
+        }
+        .publish(&env);
+        PoolResolvedEvent {
+            pool_id,
+            operator,
+            outcome: new_outcome,
+            outcome_label: Self::outcome_label(&env, pool_id, new_outcome),
+            note: String::from_str(&env, "dispute"),
+        }
+        .publish(&env);
+
+        let attestation = Self::build_settlement_attestation(&env, pool_id, &pool);
+        Self::publish_settlement_attestation(&env, attestation);
+        Self::queue_pool_callback(&env, pool_id, PoolHook::Resolved(new_outcome));
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(())
+    }
+
+    /// Mark a pool as ready for resolution and emit an event.
+    /// Can be called by anyone once the resolution delay has passed.
+    pub fn mark_pool_ready(env: Env, pool_id: u64) -> Result<(), PredifiError> {
+        Self::check_and_mark_ready(&env, pool_id)
+    }
+
+    /// Readiness check for several pools in one call, for keepers sweeping
+    /// many pools. Emits `PoolReadyForResolutionEvent` for every ready pool.
+    ///
+    /// At most `MAX_BATCH_SIZE` pools are checked per call; each item reports
+    /// 0 if the pool is ready, or the `PredifiError` code `mark_pool_ready`
+    /// would have returned.
+    pub fn mark_pools_ready_batch(env: Env, pool_ids: Vec<u64>) -> BatchResult {
+        let processed = core::cmp::min(pool_ids.len(), MAX_BATCH_SIZE);
+        let mut results = Vec::new(&env);
+        for i in 0..processed {
+            let pool_id = pool_ids.get(i).unwrap();
+            let error = match Self::check_and_mark_ready(&env, pool_id) {
+                Ok(()) => 0,
+                Err(e) => e as u32,
+            };
+            results.push_back(BatchItemResult { pool_id, error });
+        }
+        BatchResult { processed, results }
+    }
+
+    fn check_and_mark_ready(env: &Env, pool_id: u64) -> Result<(), PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let config = Self::load_config(env);
+        let current_time = env.ledger().timestamp();
+
+        if current_time >= pool.end_time.saturating_add(config.resolution_delay) {
+            PoolReadyForResolutionEvent {
+                pool_id,
+                timestamp: current_time,
+            }
+            .publish(env);
+            Ok(())
+        } else {
+            Err(PredifiError::ResolutionDelayNotMet)
+        }
+    }
+
+    /// Cancel an active pool. Caller must have Operator role (1).
+    /// Cancel a pool, freezing all betting and enabling refund process.
+    /// Only callable by Admin (role 0) - can cancel any pool for any reason.
+    ///
+    /// # Arguments
+    /// * `caller`  - The address requesting the cancellation (must be admin).
+    /// * `pool_id` - The ID of the pool to cancel.
+    /// * `reason`  - A short description of why the pool is being canceled.
+    ///
+    /// # Errors
+    /// - `Unauthorized` if caller is not admin.
+    /// - `PoolNotResolved` error (code 22) is returned if trying to cancel an already resolved pool.
+    /// PRE: pool.state = Active, operator has role 1
+    /// POST: pool.state = Canceled, state transition valid (INV-2)
+    pub fn cancel_pool(env: Env, operator: Address, pool_id: u64) -> Result<(), PredifiError> {
+        let reason = String::from_str(&env, "");
+        Self::cancel_pool_with_note(env, operator, pool_id, reason)
+    }
+
+    /// Same as `cancel_pool`, attaching a short operator note explaining the
+    /// cancellation (max 256 bytes). Persisted per pool and emitted as the
+    /// `reason` of `PoolCanceledEvent`.
+    pub fn cancel_pool_with_note(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        reason: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+
+        // Check authorization: operator must have role 1
+        Self::require_role(&env, &operator, 1)?;
+        Self::check_note(&reason)?;
+
+        let pool = Self::check_cancelable(&env, pool_id)?;
+        Self::finalize_cancellation(&env, pool_id, pool, operator, reason);
+
+        Ok(())
+    }
+
+    /// Cancel a pool that violates listing policy (spam, rug-style markets).
+    /// Same as `cancel_pool_with_note`, except the creator bond is forfeited
+    /// to the treasury. Caller must have Operator role (1).
+    pub fn cancel_pool_for_violation(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        reason: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        Self::check_note(&reason)?;
+
+        let pool = Self::check_cancelable(&env, pool_id)?;
+        Self::settle_creator_bond(&env, pool_id, &pool, true);
+        Self::finalize_cancellation(&env, pool_id, pool, operator, reason);
+        Ok(())
+    }
+
+    /// Cancel several pools with a shared reason, e.g. a postponed tournament
+    /// round. Follows the same rules as `resolve_pools_batch`: at most
+    /// `MAX_BATCH_SIZE` items per call, per-item results, and only
+    /// authorization failures abort the whole call.
+    pub fn cancel_pools_batch(
+        env: Env,
+        operator: Address,
+        pool_ids: Vec<u64>,
+        reason: String,
+    ) -> Result<BatchResult, PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        Self::check_note(&reason)?;
+
+        let processed = core::cmp::min(pool_ids.len(), MAX_BATCH_SIZE);
+        let mut results = Vec::new(&env);
+        let mut failed: u32 = 0;
+        for i in 0..processed {
+            let pool_id = pool_ids.get(i).unwrap();
+            let error = match Self::check_cancelable(&env, pool_id) {
+                Ok(pool) => {
+                    Self::finalize_cancellation(
+                        &env,
+                        pool_id,
+                        pool,
+                        operator.clone(),
+                        reason.clone(),
+                    );
+                    0
+                }
+                Err(e) => {
+                    failed += 1;
+                    e as u32
+                }
+            };
+            results.push_back(BatchItemResult { pool_id, error });
+        }
+
+        PoolsBatchCanceledEvent {
+            operator,
+            processed,
+            failed,
+            reason,
+        }
+        .publish(&env);
+
+        Ok(BatchResult { processed, results })
+    }
+
+    /// Place a prediction on a pool. Cannot predict on canceled or resolved pools.
+    /// A repeat bet on the same outcome adds to the existing position; a bet
+    /// on a different outcome fails with `PositionConflict`.
+    /// PRE: amount > 0 (INV-7), pool.state = Active, current_time < lock_time <= pool.end_time
+    /// PRE: pool.min_stake <= amount <= pool.max_stake (unless max_stake == 0)
+    /// POST: pool.total_stake increases by amount, OutcomeStake increases by amount (INV-1)
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    pub fn place_prediction(env: Env, user: Address, pool_id: u64, amount: i128, outcome: u32) {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        user.require_auth();
+        assert!(amount > 0, "amount must be positive");
+
+        Self::enter_reentrancy_guard(&env);
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .expect("Pool not found");
+        let hot = Self::read_hot_pool(&env, pool_id);
+        let delta = if hot {
+            Self::stake_delta(&env, pool_id)
+        } else {
+            0
+        };
+        pool.total_stake = pool.total_stake.checked_add(delta).expect("overflow");
+
+        assert!(!pool.resolved, "Pool already resolved");
+        assert!(!pool.canceled, "Cannot place prediction on canceled pool");
+        assert!(pool.state == MarketState::Active, "Pool is not active");
+        assert!(env.ledger().timestamp() < pool.end_time, "Pool has ended");
+        assert!(
+            env.ledger().timestamp() < Self::lock_time(&env, pool_id, &pool),
+            "Pool is locked for betting"
+        );
+        if Self::lmsr_state(&env, pool_id).is_some() {
+            soroban_sdk::panic_with_error!(&env, PredifiError::InvalidPoolState);
+        }
+
+        // Validate: outcome must be within the valid options range
+        assert!(
+            outcome < pool.options_count,
+            "outcome exceeds options_count"
+        );
+
+        // --- INTERNAL CHECKS & EFFECTS ---
+        // Validate: per-pool total stake cap (may truncate the bet)
+        let amount = match Self::apply_stake_cap(&env, pool_id, &pool, amount) {
+            Ok(accepted) => accepted,
+            Err(e) => soroban_sdk::panic_with_error!(&env, e),
+        };
+
+        // Validate: per-pool stake limits
+        assert!(
+            amount >= pool.min_stake,
+            "amount is below the pool minimum stake"
+        );
+        assert!(
+            amount >= Self::token_params(&env, &pool.token).min_stake,
+            "amount is below the token minimum stake"
+        );
+        if pool.max_stake > 0 {
+            assert!(
+                amount <= pool.max_stake,
+                "amount exceeds the pool maximum stake"
+            );
+        }
+
+        // Validate: the user can fund the bet
+        if let Err(e) = Self::check_balance(&env, &pool.token, &user, amount) {
+            soroban_sdk::panic_with_error!(&env, e);
+        }
+
+        if Self::bet_legs(&env, &user, pool_id).is_some() {
+            soroban_sdk::panic_with_error!(&env, PredifiError::PositionConflict);
+        }
+        if !Self::is_bettor_allowed(&env, pool_id, &user) {
+            soroban_sdk::panic_with_error!(&env, PredifiError::Unauthorized);
+        }
+        if let Err(e) = Self::require_not_blocked(&env, &user, "place_prediction") {
+            soroban_sdk::panic_with_error!(&env, e);
+        }
+        if Self::is_draft(&env, pool_id) {
+            soroban_sdk::panic_with_error!(&env, PredifiError::InvalidPoolState);
+        }
+
+        // A repeat bet on the same outcome tops up the position like
+        // `increase_stake`; a bet on another outcome would orphan the
+        // earlier stake.
+        let pred_key = DataKey::Prediction(user.clone(), pool_id);
+        let existing: Option<Prediction> = env.storage().persistent().get(&pred_key);
+        let new_bettor = existing.is_none();
+        let position_amount = match existing {
+            Some(prediction) => {
+                if prediction.outcome != outcome {
+                    soroban_sdk::panic_with_error!(&env, PredifiError::PositionConflict);
+                }
+                let total = prediction.amount.checked_add(amount).expect("overflow");
+                if pool.max_stake > 0 {
+                    assert!(
+                        total <= pool.max_stake,
+                        "amount exceeds the pool maximum stake"
+                    );
+                }
+                total
+            }
+            None => amount,
+        };
+        if new_bettor {
+            let pc_key = DataKey::ParticipantsCount(pool_id);
+            let pc: u32 = env.storage().persistent().get(&pc_key).unwrap_or(0);
+            env.storage().persistent().set(&pc_key, &(pc + 1));
+            Self::extend_persistent(&env, &pc_key);
+
+            // Bettor index: participant number → address (for position views).
+            let bettor_key = DataKey::PoolBettor(pool_id, pc);
+            env.storage().persistent().set(&bettor_key, &user);
+            Self::extend_persistent(&env, &bettor_key);
+        }
+        env.storage().persistent().set(
+            &pred_key,
+            &Prediction {
+                amount: position_amount,
+                outcome,
+            },
+        );
+        Self::extend_persistent(&env, &pred_key);
+
+        // Update total stake (INV-1). Hot pools accumulate it in a small
+        // delta entry instead of rewriting the whole Pool.
+        pool.total_stake = pool.total_stake.checked_add(amount).expect("overflow");
+        if hot {
+            let delta_key = HotPoolKey::StakeDelta(pool_id);
+            env.storage()
+                .persistent()
+                .set(&delta_key, &(delta + amount));
+            Self::extend_persistent(&env, &delta_key);
+        } else {
+            env.storage().persistent().set(&pool_key, &pool);
+            Self::extend_persistent(&env, &pool_key);
+        }
+        Self::credit_pool_escrow(&env, pool_id, amount);
+
+        // Update outcome stake (INV-1) - using optimized batch storage
+        let _stakes =
+            Self::update_outcome_stake(&env, pool_id, outcome, amount, pool.options_count);
+        Self::record_bet(&env, pool_id, &pool, amount);
+        Self::record_user_activity(&env, &user, amount, new_bettor as u32, 0);
+        Self::check_odds_alerts(
+            &env,
+            pool_id,
+            &pool,
+            &soroban_sdk::vec![&env, BetLeg { outcome, amount }],
+        );
+
+        // Index the pool for new bettors, hot pools included: the entries are
+        // keyed by user, so they add no contention on the pool.
+        if new_bettor {
+            let count_key = DataKey::UserPredictionCount(user.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+            let index_key = DataKey::UserPredictionIndex(user.clone(), count);
+            env.storage().persistent().set(&index_key, &pool_id);
+            Self::extend_persistent(&env, &index_key);
+
+            env.storage().persistent().set(&count_key, &(count + 1));
+            Self::extend_persistent(&env, &count_key);
+        }
+
+        // --- INTERACTIONS ---
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &pool.token,
+            LedgerDirection::In,
+            amount,
+            "stake",
+        );
+
+        Self::exit_reentrancy_guard(&env);
+
+        PredictionPlacedEvent {
+            pool_id,
+            user: user.clone(),
+            amount,
+            outcome,
+        }
+        .publish(&env);
+
+        // 🟡 MEDIUM ALERT: large stake detected — emit supplementary event.
+        let threshold = Self::high_value_threshold(&env, &pool.token);
+        if amount >= threshold {
+            HighValuePredictionEvent {
+                pool_id,
+                user,
+                amount,
+                outcome,
+                threshold,
+            }
+            .publish(&env);
+        }
+
+        // 🟢 INFO: For markets with many outcomes, emit batch stake update event
+        // to avoid emitting individual events per outcome which would be impractical
+        // for large tournaments (e.g., 32-team bracket).
+        if pool.options_count >= BATCH_STAKE_EVENT_MIN_OPTIONS {
+            OutcomeStakesUpdatedEvent {
+                pool_id,
+                options_count: pool.options_count,
+                total_stake: pool.total_stake,
+            }
+            .publish(&env);
+        }
+
+        Self::check_pool_invariants(&env, pool_id);
+    }
+
+    /// Place one bet covering several outcomes of a pool ("any of these
+    /// teams"): `amount` is split evenly across `outcomes`, the remainder going
+    /// to the first leg. If one of them wins, the bet is paid like a single
+    /// prediction of that leg's stake on the winning outcome.
+    ///
+    /// The user must not already hold a position on the pool, and the
+    /// position cannot be topped up or withdrawn afterwards.
+    /// PRE: 2 <= outcomes.len() <= MAX_BET_LEGS, distinct outcomes, amount >= outcomes.len()
+    /// POST: pool.total_stake increases by amount, each leg's OutcomeStake by its share (INV-1)
+    pub fn place_multi_prediction(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+        outcomes: Vec<u32>,
+    ) -> Result<Vec<BetLeg>, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        user.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::flush_stake_delta(&env, pool_id, &mut pool);
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= Self::lock_time(&env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+        if Self::lmsr_state(&env, pool_id).is_some() {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let leg_count = outcomes.len();
+        if !(2..=MAX_BET_LEGS).contains(&leg_count) {
+            return Err(PredifiError::InvalidOutcome);
+        }
+        for (i, outcome) in outcomes.iter().enumerate() {
+            if outcome >= pool.options_count || outcomes.first_index_of(outcome) != Some(i as u32) {
+                return Err(PredifiError::InvalidOutcome);
+            }
+        }
+
+        let pred_key = DataKey::Prediction(user.clone(), pool_id);
+        if env.storage().persistent().has(&pred_key) {
+            return Err(PredifiError::PositionConflict);
+        }
+        if !Self::is_bettor_allowed(&env, pool_id, &user) {
+            return Err(PredifiError::Unauthorized);
+        }
+        Self::require_not_blocked(&env, &user, "place_multi_prediction")?;
+        if Self::is_draft(&env, pool_id) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let amount = Self::apply_stake_cap(&env, pool_id, &pool, amount)?;
+        if amount < leg_count as i128
+            || amount < pool.min_stake
+            || amount < Self::token_params(&env, &pool.token).min_stake
+            || (pool.max_stake > 0 && amount > pool.max_stake)
+        {
+            return Err(PredifiError::InvalidAmount);
+        }
+        Self::check_balance(&env, &pool.token, &user, amount)?;
+
+        Self::enter_reentrancy_guard(&env);
+
+        // --- EFFECTS ---
+
+        let share = amount / leg_count as i128;
+        let mut legs = Vec::new(&env);
+        for (i, outcome) in outcomes.iter().enumerate() {
+            let leg_amount = if i == 0 {
+                amount - share * (leg_count as i128 - 1)
+            } else {
+                share
+            };
+            Self::update_outcome_stake(&env, pool_id, outcome, leg_amount, pool.options_count);
+            legs.push_back(BetLeg {
+                outcome,
+                amount: leg_amount,
+            });
+        }
+
+        let pc_key = DataKey::ParticipantsCount(pool_id);
+        let pc: u32 = env.storage().persistent().get(&pc_key).unwrap_or(0);
+        env.storage().persistent().set(&pc_key, &(pc + 1));
+        Self::extend_persistent(&env, &pc_key);
+        let bettor_key = DataKey::PoolBettor(pool_id, pc);
+        env.storage().persistent().set(&bettor_key, &user);
+        Self::extend_persistent(&env, &bettor_key);
+
+        // The stored prediction carries the whole stake on the first leg, so
+        // position views and refunds of canceled pools see the full amount.
+        env.storage().persistent().set(
+            &pred_key,
+            &Prediction {
+                amount,
+                outcome: outcomes.get(0).unwrap(),
+            },
+        );
+        Self::extend_persistent(&env, &pred_key);
+        let legs_key = DataKey::Ext(ExtKey::BetLegs(user.clone(), pool_id));
+        env.storage().persistent().set(&legs_key, &legs);
+        Self::extend_persistent(&env, &legs_key);
+
+        pool.total_stake = pool.total_stake.checked_add(amount).expect("overflow");
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+        Self::credit_pool_escrow(&env, pool_id, amount);
+        Self::record_bet(&env, pool_id, &pool, amount);
+        Self::record_user_activity(&env, &user, amount, 1, 0);
+        Self::check_odds_alerts(&env, pool_id, &pool, &legs);
+
+        let count_key = DataKey::UserPredictionCount(user.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let index_key = DataKey::UserPredictionIndex(user.clone(), count);
+        env.storage().persistent().set(&index_key, &pool_id);
+        Self::extend_persistent(&env, &index_key);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent(&env, &count_key);
+
+        // --- INTERACTIONS ---
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &pool.token,
+            LedgerDirection::In,
+            amount,
+            "stake",
+        );
+
+        Self::exit_reentrancy_guard(&env);
+
+        MultiPredictionPlacedEvent {
+            pool_id,
+            user,
+            amount,
+            legs: legs.clone(),
+        }
+        .publish(&env);
+
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(legs)
+    }
+
+    /// Returns the handicap payout multiplier of each outcome of a pool, in
+    /// bps (empty for unweighted pools).
+    pub fn get_outcome_weights(env: Env, pool_id: u64) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OutcomeWeights(pool_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the label of each outcome of a pool (empty if unlabeled).
+    pub fn get_outcome_labels(env: Env, pool_id: u64) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OutcomeLabels(pool_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the numeric range of each outcome of a scalar pool (empty for
+    /// categorical pools).
+    pub fn get_scalar_buckets(env: Env, pool_id: u64) -> Vec<(i128, i128)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ScalarBuckets(pool_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Subscribe to the odds of `outcome`: an `OddsAlertTriggeredEvent` naming
+    /// `user` is emitted whenever a bet moves the outcome's decimal odds
+    /// across `threshold_bps` (10_000 = 1.00x), in either direction. Replaces
+    /// the user's previous alert on the same outcome; a threshold of 0
+    /// removes it. At most `MAX_ODDS_ALERTS` alerts per pool.
+    pub fn set_odds_alert(
+        env: Env,
+        user: Address,
         pool_id: u64,
         outcome: u32,
-        amount: i128,
-        options_count: u32,
-    ) -> Vec<i128> {
-        let mut stakes = Self::get_outcome_stakes(env, pool_id, options_count);
-        let current = stakes.get(outcome).unwrap_or(0);
-        stakes.set(outcome, current + amount);
+        threshold_bps: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        user.require_auth();
 
-        // Store using optimized batch key
-        let key = DataKey::OutcomeStakes(pool_id);
-        env.storage().persistent().set(&key, &stakes);
-        Self::extend_persistent(env, &key);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if outcome >= pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
 
-        // Also update individual key for backward compatibility
-        let outcome_key = DataKey::OutcomeStake(pool_id, outcome);
-        env.storage()
+        let key = DataKey::Ext(ExtKey::OddsAlerts(pool_id));
+        let alerts: Vec<OddsAlert> = env
+            .storage()
             .persistent()
-            .set(&outcome_key, &(current + amount));
-        Self::extend_persistent(env, &outcome_key);
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let mut updated = Vec::new(&env);
+        for alert in alerts.iter() {
+            if alert.subscriber != user || alert.outcome != outcome {
+                updated.push_back(alert);
+            }
+        }
+        if threshold_bps > 0 {
+            if updated.len() >= MAX_ODDS_ALERTS {
+                return Err(PredifiError::CapacityReached);
+            }
+            updated.push_back(OddsAlert {
+                subscriber: user,
+                outcome,
+                threshold_bps,
+            });
+        }
 
-        stakes
+        if updated.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &updated);
+            Self::extend_persistent(&env, &key);
+        }
+        Ok(())
     }
 
-    // ── Storage & Side-Effect Functions ───────────────────────────────────────
+    /// Configure bettor-initiated cancellation: bettors holding more than
+    /// `threshold_bps` of a pool's bettor stake (a supermajority, above 50%)
+    /// may request its cancellation, executed `delay` seconds later unless an
+    /// operator vetoes. A threshold of 0 disables it. Caller must have Admin
+    /// role (0).
+    pub fn set_bettor_cancel_params(
+        env: Env,
+        admin: Address,
+        threshold_bps: u32,
+        delay: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_bettor_cancel_params"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if threshold_bps != 0 && !(5_001..=10_000).contains(&threshold_bps) {
+            return Err(PredifiError::InvalidAmount);
+        }
 
-    fn extend_instance(env: &Env) {
-        env.storage()
+        let threshold_key = DataKey::Ext(ExtKey::BettorCancelThresholdBps);
+        let delay_key = DataKey::Ext(ExtKey::BettorCancelDelay);
+        let old_threshold: u32 = env.storage().instance().get(&threshold_key).unwrap_or(0);
+        let old_delay: u64 = env.storage().instance().get(&delay_key).unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "bettor_cancel_threshold_bps",
+            ConfigValue::U32(old_threshold),
+            ConfigValue::U32(threshold_bps),
+        );
+        Self::record_config_change(
+            &env,
+            &admin,
+            "bettor_cancel_delay",
+            ConfigValue::U64(old_delay),
+            ConfigValue::U64(delay),
+        );
+        env.storage().instance().set(&threshold_key, &threshold_bps);
+        env.storage().instance().set(&delay_key, &delay);
+        Self::extend_instance(&env);
+
+        BettorCancelUpdateEvent {
+            admin,
+            threshold_bps,
+            delay,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Stake of a cancellation request's supporters, counted at their current
+    /// positions so withdrawn stake no longer weighs in.
+    fn cancel_support_stake(env: &Env, pool_id: u64, request: &CancelRequest) -> i128 {
+        let mut support: i128 = 0;
+        for supporter in request.supporters.iter() {
+            let prediction: Option<Prediction> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Prediction(supporter, pool_id));
+            support += prediction.map(|p| p.amount).unwrap_or(0);
+        }
+        support
+    }
+
+    /// Co-sign a request to cancel a pool, e.g. because the real-world event
+    /// was voided. Only bettors of the pool may sign. Once the signers hold
+    /// more than the configured share of bettor stake, the pool becomes
+    /// cancelable by anyone after the configured delay. Returns the
+    /// supporters' stake.
+    pub fn support_cancellation(
+        env: Env,
+        bettor: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        Self::require_not_paused(&env);
+        bettor.require_auth();
+        let threshold_bps: u32 = env
+            .storage()
             .instance()
-            .extend_ttl(BUMP_THRESHOLD, BUMP_AMOUNT);
+            .get(&DataKey::Ext(ExtKey::BettorCancelThresholdBps))
+            .unwrap_or(0);
+        if threshold_bps == 0 {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        let pool = Self::check_cancelable(&env, pool_id)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Prediction(bettor.clone(), pool_id))
+        {
+            return Err(PredifiError::PredictionNotFound);
+        }
+
+        let key = DataKey::Ext(ExtKey::CancelRequest(pool_id));
+        let mut request: CancelRequest =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(CancelRequest {
+                    supporters: Vec::new(&env),
+                    executable_at: 0,
+                    vetoed: false,
+                });
+        if request.vetoed {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if !request.supporters.contains(&bettor) {
+            if request.supporters.len() >= MAX_CANCEL_SUPPORTERS {
+                return Err(PredifiError::CapacityReached);
+            }
+            request.supporters.push_back(bettor.clone());
+        }
+
+        let support_stake = Self::cancel_support_stake(&env, pool_id, &request);
+        let bettor_stake = Self::bettor_stake(&env, pool_id, &pool);
+        let reached = support_stake * 10_000 > bettor_stake * threshold_bps as i128;
+        if reached && request.executable_at == 0 {
+            let delay: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Ext(ExtKey::BettorCancelDelay))
+                .unwrap_or(0);
+            request.executable_at = env.ledger().timestamp().saturating_add(delay);
+            CancelRequestedEvent {
+                pool_id,
+                support_stake,
+                executable_at: request.executable_at,
+            }
+            .publish(&env);
+        }
+        env.storage().persistent().set(&key, &request);
+        Self::extend_persistent(&env, &key);
+
+        CancelSupportedEvent {
+            pool_id,
+            bettor,
+            support_stake,
+            bettor_stake,
+        }
+        .publish(&env);
+        Ok(support_stake)
+    }
+
+    /// Cancel a pool whose bettor cancellation request has matured. Anyone
+    /// may call this. Support is re-checked against current positions, so
+    /// supporters who withdrew no longer count.
+    pub fn execute_bettor_cancellation(env: Env, pool_id: u64) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        let pool = Self::check_cancelable(&env, pool_id)?;
+        let key = DataKey::Ext(ExtKey::CancelRequest(pool_id));
+        let request: CancelRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        if request.vetoed || request.executable_at == 0 {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() < request.executable_at {
+            return Err(PredifiError::ResolutionDelayNotMet);
+        }
+        let threshold_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Ext(ExtKey::BettorCancelThresholdBps))
+            .unwrap_or(0);
+        let support_stake = Self::cancel_support_stake(&env, pool_id, &request);
+        let bettor_stake = Self::bettor_stake(&env, pool_id, &pool);
+        if threshold_bps == 0 || support_stake * 10_000 <= bettor_stake * threshold_bps as i128 {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        env.storage().persistent().remove(&key);
+        let reason = String::from_str(&env, "bettor supermajority");
+        Self::finalize_cancellation(&env, pool_id, pool, env.current_contract_address(), reason);
+        Ok(())
+    }
+
+    /// Veto a pool's bettor cancellation request; the pool can no longer be
+    /// canceled by its bettors. Caller must have Operator role (1).
+    pub fn veto_cancellation(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        let key = DataKey::Ext(ExtKey::CancelRequest(pool_id));
+        let mut request: CancelRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::InvalidPoolState)?;
+        request.vetoed = true;
+        env.storage().persistent().set(&key, &request);
+        Self::extend_persistent(&env, &key);
+
+        CancelVetoedEvent { pool_id, operator }.publish(&env);
+        Ok(())
     }
 
-    fn extend_persistent(env: &Env, key: &DataKey) {
+    /// Returns the bettor cancellation request of a pool, if any.
+    pub fn get_cancel_request(env: Env, pool_id: u64) -> Option<CancelRequest> {
         env.storage()
             .persistent()
-            .extend_ttl(key, BUMP_THRESHOLD, BUMP_AMOUNT);
+            .get(&DataKey::Ext(ExtKey::CancelRequest(pool_id)))
     }
 
-    fn has_role(env: &Env, contract: &Address, user: &Address, role: u32) -> bool {
-        env.invoke_contract(
-            contract,
-            &Symbol::new(env, "has_role"),
-            soroban_sdk::vec![env, user.into_val(env), role.into_val(env)],
-        )
+    /// Returns the odds alerts registered on a pool.
+    pub fn get_odds_alerts(env: Env, pool_id: u64) -> Vec<OddsAlert> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::OddsAlerts(pool_id)))
+            .unwrap_or(Vec::new(&env))
     }
 
-    fn require_role(env: &Env, user: &Address, role: u32) -> Result<(), PredifiError> {
-        let config = Self::get_config(env);
-        if !Self::has_role(env, &config.access_control, user, role) {
-            return Err(PredifiError::Unauthorized);
-        }
-        Ok(())
+    /// Returns the legs of a user's multi-leg bet on a pool, if any.
+    pub fn get_bet_legs(env: Env, user: Address, pool_id: u64) -> Option<Vec<BetLeg>> {
+        Self::bet_legs(&env, &user, pool_id)
     }
 
-    fn get_config(env: &Env) -> Config {
-        let config = env
+    /// Top up an existing prediction on the same outcome.
+    /// PRE: amount > 0 (INV-7), pool.state = Active, current_time < lock_time
+    /// PRE: the topped-up position stays within pool.max_stake (unless 0)
+    /// POST: Prediction.amount, pool.total_stake and OutcomeStake all increase by amount (INV-1)
+    pub fn increase_stake(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        user.require_auth();
+        if amount <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        Self::require_not_blocked(&env, &user, "increase_stake")?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
             .storage()
-            .instance()
-            .get(&DataKey::Config)
-            .expect("Config not set");
-        Self::extend_instance(env);
-        config
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::flush_stake_delta(&env, pool_id, &mut pool);
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= Self::lock_time(&env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+
+        let pred_key = DataKey::Prediction(user.clone(), pool_id);
+        let mut prediction: Prediction = env
+            .storage()
+            .persistent()
+            .get(&pred_key)
+            .ok_or(PredifiError::PredictionNotFound)?;
+        if Self::bet_legs(&env, &user, pool_id).is_some() {
+            return Err(PredifiError::PositionConflict);
+        }
+        let amount = Self::apply_stake_cap(&env, pool_id, &pool, amount)?;
+        let new_amount = prediction
+            .amount
+            .checked_add(amount)
+            .ok_or(PredifiError::InvalidAmount)?;
+        if pool.max_stake > 0 && new_amount > pool.max_stake {
+            return Err(PredifiError::InvalidAmount);
+        }
+        Self::check_balance(&env, &pool.token, &user, amount)?;
+
+        Self::enter_reentrancy_guard(&env);
+
+        // --- EFFECTS ---
+
+        prediction.amount = new_amount;
+        env.storage().persistent().set(&pred_key, &prediction);
+        Self::extend_persistent(&env, &pred_key);
+
+        pool.total_stake = pool.total_stake.checked_add(amount).expect("overflow");
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+        Self::credit_pool_escrow(&env, pool_id, amount);
+
+        Self::update_outcome_stake(
+            &env,
+            pool_id,
+            prediction.outcome,
+            amount,
+            pool.options_count,
+        );
+        Self::record_bet(&env, pool_id, &pool, amount);
+        Self::record_user_activity(&env, &user, amount, 0, 0);
+        Self::check_odds_alerts(
+            &env,
+            pool_id,
+            &pool,
+            &soroban_sdk::vec![
+                &env,
+                BetLeg {
+                    outcome: prediction.outcome,
+                    amount,
+                }
+            ],
+        );
+
+        // --- INTERACTIONS ---
+
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &pool.token,
+            LedgerDirection::In,
+            amount,
+            "stake",
+        );
+
+        Self::exit_reentrancy_guard(&env);
+
+        PredictionUpdatedEvent {
+            pool_id,
+            user,
+            outcome: prediction.outcome,
+            added: amount,
+            amount: new_amount,
+        }
+        .publish(&env);
+
+        if pool.options_count >= BATCH_STAKE_EVENT_MIN_OPTIONS {
+            OutcomeStakesUpdatedEvent {
+                pool_id,
+                options_count: pool.options_count,
+                total_stake: pool.total_stake,
+            }
+            .publish(&env);
+        }
+
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(new_amount)
     }
 
-    fn is_paused(env: &Env) -> bool {
-        let paused = env
+    /// Withdraw `amount` (or the whole position when `None`) of a prediction
+    /// while the pool is still open. The early-exit fee (see
+    /// `set_early_exit_fee_bps`) is accrued as a protocol fee and the rest refunded.
+    /// A partial withdrawal must leave at least pool.min_stake in place.
+    /// Returns the amount refunded to the user.
+    /// PRE: pool.state = Active, current_time < lock_time
+    /// POST: Prediction.amount, pool.total_stake and OutcomeStake all decrease by amount (INV-1)
+    pub fn withdraw_prediction(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: Option<i128>,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        user.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::flush_stake_delta(&env, pool_id, &mut pool);
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= Self::lock_time(&env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+
+        let pred_key = DataKey::Prediction(user.clone(), pool_id);
+        let mut prediction: Prediction = env
+            .storage()
+            .persistent()
+            .get(&pred_key)
+            .ok_or(PredifiError::PredictionNotFound)?;
+        if Self::bet_legs(&env, &user, pool_id).is_some() {
+            return Err(PredifiError::PositionConflict);
+        }
+        let amount = amount.unwrap_or(prediction.amount);
+        let remaining = prediction.amount - amount;
+        if amount <= 0 || remaining < 0 || (remaining > 0 && remaining < pool.min_stake) {
+            return Err(PredifiError::InvalidAmount);
+        }
+
+        let fee_bps: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        Self::extend_instance(env);
-        paused
-    }
+            .get(&DataKey::EarlyExitFeeBps)
+            .unwrap_or(0);
+        let fee = amount
+            .checked_mul(fee_bps as i128)
+            .ok_or(PredifiError::InvalidAmount)?
+            / 10_000;
+        let refund = amount - fee;
 
-    fn require_not_paused(env: &Env) {
-        if Self::is_paused(env) {
-            panic!("Contract is paused");
-        }
-    }
+        Self::enter_reentrancy_guard(&env);
 
-    fn enter_reentrancy_guard(env: &Env) {
-        let key = DataKey::ReentrancyGuard;
-        if env.storage().temporary().has(&key) {
-            panic!("Reentrancy detected");
+        // --- EFFECTS ---
+
+        if remaining == 0 {
+            env.storage().persistent().remove(&pred_key);
+            Self::remove_pool_bettor(&env, pool_id, &user);
+            Self::remove_user_prediction_index(&env, &user, pool_id);
+        } else {
+            prediction.amount = remaining;
+            env.storage().persistent().set(&pred_key, &prediction);
+            Self::extend_persistent(&env, &pred_key);
         }
-        env.storage().temporary().set(&key, &true);
-    }
 
-    fn exit_reentrancy_guard(env: &Env) {
-        env.storage().temporary().remove(&DataKey::ReentrancyGuard);
-    }
+        pool.total_stake = pool.total_stake.checked_sub(amount).expect("underflow");
+        env.storage().persistent().set(&pool_key, &pool);
+        Self::extend_persistent(&env, &pool_key);
+        Self::debit_pool_escrow(&env, pool_id, &pool, refund, fee);
 
-    /// Returns true if the token is on the allowed betting whitelist.
-    fn is_token_whitelisted(env: &Env, token: &Address) -> bool {
-        let key = DataKey::TokenWhitelist(token.clone());
-        let allowed = env.storage().persistent().get(&key).unwrap_or(false);
-        if env.storage().persistent().has(&key) {
-            Self::extend_persistent(env, &key);
-        }
-        allowed
-    }
+        Self::update_outcome_stake(
+            &env,
+            pool_id,
+            prediction.outcome,
+            -amount,
+            pool.options_count,
+        );
 
-    // ── Public interface ──────────────────────────────────────────────────────
+        // --- INTERACTIONS ---
 
-    /// Initialize the contract. Idempotent — safe to call multiple times.
-    pub fn init(
-        env: Env,
-        access_control: Address,
-        treasury: Address,
-        fee_bps: u32,
-        resolution_delay: u64,
-    ) {
-        if !env.storage().instance().has(&DataKey::Config) {
-            let config = Config {
-                fee_bps,
-                treasury: treasury.clone(),
-                access_control: access_control.clone(),
-                resolution_delay,
-            };
-            env.storage().instance().set(&DataKey::Config, &config);
-            env.storage().instance().set(&DataKey::PoolIdCounter, &0u64);
-            Self::extend_instance(&env);
+        // A smaller pool may no longer cover its deposit within the share cap.
+        Self::recall_yield_deposit(&env, pool_id, &pool);
 
-            InitEvent {
-                access_control,
-                treasury,
-                fee_bps,
-                resolution_delay,
-            }
-            .publish(&env);
-        }
-    }
+        let token_client = token::Client::new(&env, &pool.token);
+        Self::accrue_protocol_fee(&env, pool_id, &pool.token, fee);
+        token_client.transfer(&env.current_contract_address(), &user, &refund);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &pool.token,
+            LedgerDirection::Out,
+            refund,
+            "refund",
+        );
 
-    /// Pause the contract. Only callable by Admin (role 0).
-    pub fn pause(env: Env, admin: Address) {
-        admin.require_auth();
-        if Self::require_role(&env, &admin, 0).is_err() {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "pause"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            panic!("Unauthorized: missing required role");
-        }
-        env.storage().instance().set(&DataKey::Paused, &true);
-        Self::extend_instance(&env);
+        Self::exit_reentrancy_guard(&env);
 
-        // Emit dedicated pause-alert event so monitors can apply zero-tolerance
-        // rules independently of the generic PauseEvent.
-        ContractPausedAlertEvent {
-            admin: admin.clone(),
-            timestamp: env.ledger().timestamp(),
+        PredictionWithdrawnEvent {
+            pool_id,
+            user,
+            outcome: prediction.outcome,
+            amount,
+            fee,
+            remaining,
         }
         .publish(&env);
-        PauseEvent { admin }.publish(&env);
+
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(refund)
     }
 
-    /// Unpause the contract. Only callable by Admin (role 0).
-    pub fn unpause(env: Env, admin: Address) {
-        admin.require_auth();
-        if Self::require_role(&env, &admin, 0).is_err() {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "unpause"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            panic!("Unauthorized: missing required role");
-        }
-        env.storage().instance().set(&DataKey::Paused, &false);
-        Self::extend_instance(&env);
+    /// Recover the initial liquidity of a settled pool (see
+    /// `calculate_liquidity_return` for the formula). On resolved pools the
+    /// protocol fee applies to the part funded by bettors, as for winnings.
+    /// On handicap pools this also pays out what the outcome weights withheld
+    /// from the winners, on LMSR pools the trading surplus. Canceled pools
+    /// return the liquidity on cancellation.
+    /// Only the pool creator may claim, once. Returns the amount transferred.
+    /// PRE: pool.state ≠ Active, caller = pool.creator
+    pub fn claim_initial_liquidity(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        creator.require_auth();
 
-        UnpauseEvent { admin }.publish(&env);
+        Self::claim_liquidity_unchecked(env, creator, pool_id)
     }
 
-    /// Set fee in basis points. Caller must have Admin role (0).
-    /// PRE: admin has role 0
-    /// POST: Config.fee_bps ≤ 10_000 (INV-6)
-    pub fn set_fee_bps(env: Env, admin: Address, fee_bps: u32) -> Result<(), PredifiError> {
+    /// Register reusable settings for recurring markets (weekly fixtures,
+    /// daily price questions). The category, token and options count are
+    /// checked now; everything else is validated on each pool created from
+    /// the template. Templates registered by an admin are public.
+    /// Returns the template id.
+    pub fn create_template(
+        env: Env,
+        owner: Address,
+        params: CreatePoolParams,
+    ) -> Result<u32, PredifiError> {
         Self::require_not_paused(&env);
-        admin.require_auth();
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "set_fee_bps"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
+        owner.require_auth();
+
+        if !Self::validate_category(&env, &params.category) {
+            return Err(PredifiError::InvalidCategory);
         }
-        assert!(Self::is_valid_fee_bps(fee_bps), "fee_bps exceeds 10000");
-        let mut config = Self::get_config(&env);
-        config.fee_bps = fee_bps;
-        env.storage().instance().set(&DataKey::Config, &config);
+        if !Self::is_token_whitelisted(&env, &params.token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+        if !(2..=MAX_OPTIONS_COUNT).contains(&params.options_count) {
+            return Err(PredifiError::InvalidOutcome);
+        }
+
+        let public = Self::require_role(&env, &owner, 0).is_ok();
+        let count_key = DataKey::Ext(ExtKey::TemplateCount);
+        let template_id: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
+        env.storage().instance().set(&count_key, &(template_id + 1));
         Self::extend_instance(&env);
 
-        FeeUpdateEvent { admin, fee_bps }.publish(&env);
-        Ok(())
-    }
+        let key = DataKey::Ext(ExtKey::Template(template_id));
+        env.storage().persistent().set(
+            &key,
+            &PoolTemplate {
+                owner: owner.clone(),
+                public,
+                params,
+            },
+        );
+        Self::extend_persistent(&env, &key);
 
-    /// Set treasury address. Caller must have Admin role (0).
-    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), PredifiError> {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "set_treasury"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
+        TemplateCreatedEvent {
+            template_id,
+            owner,
+            public,
         }
-        let mut config = Self::get_config(&env);
-        config.treasury = treasury.clone();
-        env.storage().instance().set(&DataKey::Config, &config);
-        Self::extend_instance(&env);
-
-        TreasuryUpdateEvent { admin, treasury }.publish(&env);
-        Ok(())
+        .publish(&env);
+        Ok(template_id)
     }
 
-    /// Set resolution delay in seconds. Caller must have Admin role (0).
-    pub fn set_resolution_delay(env: Env, admin: Address, delay: u64) -> Result<(), PredifiError> {
+    /// Create a pool from a template, ending at `end_time`. Same rules as
+    /// `create_pool_with_params`; `creator` must own the template unless it
+    /// is public. Returns the new pool id.
+    pub fn create_pool_from_template(
+        env: Env,
+        creator: Address,
+        template_id: u32,
+        end_time: u64,
+    ) -> Result<u64, PredifiError> {
         Self::require_not_paused(&env);
-        admin.require_auth();
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "set_resolution_delay"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
+        creator.require_auth();
+
+        let template =
+            Self::get_template(env.clone(), template_id).ok_or(PredifiError::TemplateNotFound)?;
+        if !template.public && template.owner != creator {
+            return Err(PredifiError::Unauthorized);
         }
-        let mut config = Self::get_config(&env);
-        config.resolution_delay = delay;
-        env.storage().instance().set(&DataKey::Config, &config);
-        Self::extend_instance(&env);
+        let params = CreatePoolParams {
+            end_time,
+            lock_time: 0,
+            ..template.params
+        };
+        Ok(Self::create_pool_unchecked(env, creator, params))
+    }
 
-        ResolutionDelayUpdateEvent { admin, delay }.publish(&env);
-        Ok(())
+    /// Returns a market template, if registered.
+    pub fn get_template(env: Env, template_id: u32) -> Option<PoolTemplate> {
+        let key = DataKey::Ext(ExtKey::Template(template_id));
+        let template = env.storage().persistent().get(&key);
+        if template.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        template
     }
 
-    /// Add a token to the allowed betting whitelist. Caller must have Admin role (0).
-    pub fn add_token_to_whitelist(
+    /// Recover the initial liquidity of a settled pool and seed a new pool
+    /// with it in one transaction, for recurring market series. If
+    /// `new_pool_params.initial_liquidity` is 0 the new pool is seeded with
+    /// the full amount recovered; otherwise the given amount is used and the
+    /// creator keeps or tops up the difference.
+    /// Returns the new pool id.
+    pub fn claim_and_create(
         env: Env,
-        admin: Address,
-        token: Address,
-    ) -> Result<(), PredifiError> {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "add_token_to_whitelist"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
-        }
-        let key = DataKey::TokenWhitelist(token.clone());
-        env.storage().persistent().set(&key, &true);
-        Self::extend_persistent(&env, &key);
+        creator: Address,
+        from_pool: u64,
+        new_pool_params: CreatePoolParams,
+    ) -> Result<u64, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        creator.require_auth();
 
-        TokenWhitelistAddedEvent {
-            admin: admin.clone(),
-            token: token.clone(),
+        let recovered = Self::claim_liquidity_unchecked(env.clone(), creator.clone(), from_pool)?;
+        let mut params = new_pool_params;
+        if params.initial_liquidity == 0 {
+            params.initial_liquidity = recovered;
         }
-        .publish(&env);
-        Ok(())
+        Ok(Self::create_pool_unchecked(env, creator, params))
     }
 
-    /// Remove a token from the allowed betting whitelist. Caller must have Admin role (0).
-    pub fn remove_token_from_whitelist(
+    /// Body of `claim_initial_liquidity`, after the pause and auth checks.
+    fn claim_liquidity_unchecked(
         env: Env,
-        admin: Address,
-        token: Address,
-    ) -> Result<(), PredifiError> {
-        Self::require_not_paused(&env);
-        admin.require_auth();
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "remove_token_from_whitelist"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
+        creator: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state == MarketState::Active || pool.state == MarketState::Disputed {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        if Self::is_pool_swept(&env, pool_id) {
+            return Err(PredifiError::PoolSwept);
+        }
+        if Self::is_pool_archived(&env, pool_id) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if pool.state == MarketState::Resolved
+            && env.ledger().timestamp() < Self::claims_open_at(&env, pool_id)
+        {
+            return Err(PredifiError::ClaimsNotOpen);
+        }
+        let claimed_key = DataKey::LiquidityClaimed(pool_id);
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(PredifiError::AlreadyClaimed);
         }
-        let key = DataKey::TokenWhitelist(token.clone());
-        env.storage().persistent().remove(&key);
 
-        TokenWhitelistRemovedEvent {
-            admin: admin.clone(),
-            token: token.clone(),
+        Self::enter_reentrancy_guard(&env);
+
+        // --- EFFECTS ---
+
+        let gross = Self::calculate_liquidity_return(&env, pool_id, &pool);
+        let fee = if pool.state == MarketState::Resolved {
+            Self::calculate_protocol_fee(
+                gross,
+                pool.total_stake,
+                Self::bettor_stake(&env, pool_id, &pool),
+                Self::pool_fee_bps(&env, pool_id, &pool),
+            )
+        } else {
+            0
+        };
+        let amount = gross - fee + Self::lmsr_trading_surplus(&env, pool_id, &pool);
+
+        env.storage().persistent().set(&claimed_key, &true);
+        Self::extend_persistent(&env, &claimed_key);
+        if gross > 0 {
+            let disbursed_key = DataKey::PoolDisbursed(pool_id);
+            let disbursed: i128 = env.storage().persistent().get(&disbursed_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&disbursed_key, &(disbursed + gross));
+            Self::extend_persistent(&env, &disbursed_key);
+            Self::debit_pool_escrow(&env, pool_id, &pool, gross - fee, fee);
         }
-        .publish(&env);
-        Ok(())
-    }
 
-    /// Upgrade the contract Wasm code. Only callable by Admin (role 0).
-    pub fn upgrade_contract(
-        env: Env,
-        admin: Address,
-        new_wasm_hash: BytesN<32>,
-    ) -> Result<(), PredifiError> {
-        admin.require_auth();
-        Self::require_role(&env, &admin, 0)?;
+        // --- INTERACTIONS ---
 
-        env.deployer()
-            .update_current_contract_wasm(new_wasm_hash.clone());
+        let token_client = token::Client::new(&env, &pool.token);
+        Self::accrue_protocol_fee(&env, pool_id, &pool.token, fee);
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &creator, &amount);
+            Self::record_ledger_entry(
+                &env,
+                Some(pool_id),
+                &creator,
+                &pool.token,
+                LedgerDirection::Out,
+                amount,
+                "liquidity_return",
+            );
+        }
 
-        UpgradeEvent {
-            admin: admin.clone(),
-            new_wasm_hash,
+        Self::exit_reentrancy_guard(&env);
+
+        LiquidityReturnedEvent {
+            pool_id,
+            creator,
+            amount,
+            fee,
         }
         .publish(&env);
 
-        Ok(())
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(amount)
     }
 
-    /// Placeholder for post-upgrade migration logic.
-    pub fn migrate_state(env: Env, admin: Address) -> Result<(), PredifiError> {
-        admin.require_auth();
-        Self::require_role(&env, &admin, 0)?;
-        // Initial implementation has no state migration needed.
-        Ok(())
+    /// Withdraw the creator fees accrued in `token` across the caller's pools.
+    /// Returns the amount transferred.
+    pub fn claim_creator_fees(env: Env, creator: Address, token: Address) -> i128 {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        creator.require_auth();
+
+        let key = DataKey::CreatorFeesAccrued(creator.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
+        }
+        env.storage().persistent().remove(&key);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &creator, &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &creator,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "creator_fee",
+        );
+
+        CreatorFeeClaimedEvent {
+            creator,
+            token,
+            amount,
+        }
+        .publish(&env);
+        amount
     }
 
-    /// Returns true if the given token is on the allowed betting whitelist.
-    pub fn is_token_allowed(env: Env, token: Address) -> bool {
-        Self::is_token_whitelisted(&env, &token)
+    /// Returns the creator fees accrued in `token` and not yet claimed.
+    pub fn get_creator_fees_accrued(env: Env, creator: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreatorFeesAccrued(creator, token))
+            .unwrap_or(0)
     }
 
-    /// Withdraw accumulated protocol fees or unused liquidity from the contract.
-    /// Only callable by Admin (role 0).
-    ///
-    /// # Arguments
-    /// * `admin` - Address with Admin role (must provide auth)
-    /// * `token` - The token contract address to withdraw
-    /// * `amount` - Amount to withdraw (must be > 0)
-    /// * `recipient` - Address to receive the withdrawn funds (typically treasury)
-    ///
-    /// # Returns
-    /// Result indicating success or error
-    ///
-    /// # Security
-    /// - Requires Admin role (0)
-    /// - Emits TreasuryWithdrawnEvent for audit trail
-    /// - Validates amount > 0
-    /// - Checks contract has sufficient balance
-    pub fn withdraw_treasury(
+    /// Direct `share_bps` of the pool's creator fee to a registered
+    /// public-goods recipient (0 = keep the whole fee). Only the pool creator
+    /// may call this, and only while the pool is Active; fees accrued before
+    /// the change stay with the creator.
+    pub fn set_pool_public_goods(
         env: Env,
-        admin: Address,
-        token: Address,
-        amount: i128,
+        creator: Address,
+        pool_id: u64,
         recipient: Address,
+        share_bps: u32,
     ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env);
-        admin.require_auth();
+        creator.require_auth();
 
-        // Verify admin role
-        if let Err(e) = Self::require_role(&env, &admin, 0) {
-            UnauthorizedAdminAttemptEvent {
-                caller: admin,
-                operation: Symbol::new(&env, "withdraw_treasury"),
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
         }
-
-        // Validate amount
-        if amount <= 0 {
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if share_bps > 10_000 {
             return Err(PredifiError::InvalidAmount);
         }
-
-        // Get token client and check balance
-        let token_client = token::Client::new(&env, &token);
-        let contract_balance = token_client.balance(&env.current_contract_address());
-
-        if contract_balance < amount {
-            return Err(PredifiError::InsufficientBalance);
+        let key = DataKey::Ext(ExtKey::PoolPublicGoods(pool_id));
+        if share_bps == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            if !Self::is_public_goods_recipient(env.clone(), recipient.clone()) {
+                return Err(PredifiError::Unauthorized);
+            }
+            let total_accrued = env
+                .storage()
+                .persistent()
+                .get::<_, PublicGoodsShare>(&key)
+                .map(|s| s.total_accrued)
+                .unwrap_or(0);
+            env.storage().persistent().set(
+                &key,
+                &PublicGoodsShare {
+                    recipient: recipient.clone(),
+                    share_bps,
+                    total_accrued,
+                },
+            );
+            Self::extend_persistent(&env, &key);
         }
 
-        // Transfer tokens to recipient
-        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
-
-        // Emit audit event
-        TreasuryWithdrawnEvent {
-            admin: admin.clone(),
-            token: token.clone(),
-            amount,
-            recipient: recipient.clone(),
-            timestamp: env.ledger().timestamp(),
+        PoolPublicGoodsSetEvent {
+            pool_id,
+            creator,
+            recipient,
+            share_bps,
         }
         .publish(&env);
-
         Ok(())
     }
 
-    /// Create a new prediction pool. Returns the new pool ID.
-    ///
-    /// PRE: end_time > current_time (INV-8)
-    /// POST: Pool.state = Active, Pool.total_stake = initial_liquidity (if provided)
-    ///
-    /// # Arguments
-    /// * `creator`           - Address of the pool creator (must provide auth).
-    /// * `end_time`          - Unix timestamp after which no more predictions are accepted.
-    /// * `token`             - The Stellar token contract address used for staking.
-    /// * `options_count`     - Number of possible outcomes (must be >= 2 and <= MAX_OPTIONS_COUNT).
-    /// * `description`       - Short human-readable description of the event (max 256 bytes).
-    /// * `metadata_url`      - URL pointing to extended metadata, e.g. an IPFS link (max 512 bytes).
-    /// * `min_stake`         - Minimum stake amount per prediction (must be > 0).
-    /// * `max_stake`         - Maximum stake amount per prediction (0 = no limit, else must be >= min_stake).
-    /// * `initial_liquidity` - Optional initial liquidity to provide (house money). Must be > 0 if provided.
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_pool(
-        env: Env,
-        creator: Address,
-        end_time: u64,
-        token: Address,
-        options_count: u32,
-        description: String,
-        metadata_url: String,
-        min_stake: i128,
-        max_stake: i128,
-        initial_liquidity: i128,
-        category: Symbol,
-    ) -> u64 {
-        Self::require_not_paused(&env);
-        creator.require_auth();
+    /// Returns the public-goods share of a pool's creator fee, if any.
+    pub fn get_pool_public_goods(env: Env, pool_id: u64) -> Option<PublicGoodsShare> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PoolPublicGoods(pool_id)))
+    }
 
-        // Validate: category must be in the allowed list
-        assert!(
-            Self::validate_category(&env, &category),
-            "category must be one of the allowed categories"
-        );
+    /// Withdraw the public-goods fees accrued in `token` across all pools
+    /// directing fees to the caller. Returns the amount transferred.
+    pub fn claim_public_goods_fees(env: Env, recipient: Address, token: Address) -> i128 {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        recipient.require_auth();
 
-        // Validate: token must be on the allowed betting whitelist
-        if !Self::is_token_whitelisted(&env, &token) {
-            soroban_sdk::panic_with_error!(&env, PredifiError::TokenNotWhitelisted);
+        let key = DataKey::Ext(ExtKey::PublicGoodsAccrued(recipient.clone(), token.clone()));
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
         }
+        env.storage().persistent().remove(&key);
 
-        let current_time = env.ledger().timestamp();
-
-        // Validate: end_time must be in the future
-        assert!(end_time > current_time, "end_time must be in the future");
-
-        // Validate: minimum pool duration (1 hour)
-        assert!(
-            end_time >= current_time + MIN_POOL_DURATION,
-            "end_time must be at least 1 hour in the future"
-        );
-
-        // Validate: options_count must be at least 2 (binary or more outcomes)
-        assert!(options_count >= 2, "options_count must be at least 2");
-
-        // Validate: options_count must not exceed maximum limit
-        assert!(
-            options_count <= MAX_OPTIONS_COUNT,
-            "options_count exceeds maximum allowed value"
-        );
-
-        // Validate: initial_liquidity must be non-negative if provided
-        assert!(
-            initial_liquidity >= 0,
-            "initial_liquidity must be non-negative"
-        );
-
-        // Validate: initial_liquidity must not exceed maximum limit
-        assert!(
-            initial_liquidity <= MAX_INITIAL_LIQUIDITY,
-            "initial_liquidity exceeds maximum allowed value"
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &recipient,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "public_goods_fee",
         );
 
-        // Note: Token address validation is deferred to when the token is actually used.
-        // This is the standard pattern in Soroban - invalid tokens will fail when
-        // transfers are attempted during place_prediction.
-
-        assert!(description.len() <= 256, "description exceeds 256 bytes");
-        assert!(metadata_url.len() <= 512, "metadata_url exceeds 512 bytes");
-
-        // Validate stake limits
-        assert!(min_stake > 0, "min_stake must be greater than zero");
-        assert!(
-            max_stake == 0 || max_stake >= min_stake,
-            "max_stake must be zero (unlimited) or >= min_stake"
-        );
+        PublicGoodsFeeClaimedEvent {
+            recipient,
+            token,
+            amount,
+        }
+        .publish(&env);
+        amount
+    }
 
-        let pool_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PoolIdCounter)
-            .unwrap_or(0);
-        Self::extend_instance(&env);
+    /// Returns the public-goods fees accrued in `token` and not yet withdrawn.
+    pub fn get_public_goods_accrued(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PublicGoodsAccrued(recipient, token)))
+            .unwrap_or(0)
+    }
 
-        let pool = Pool {
-            end_time,
-            resolved: false,
-            canceled: false,
-            state: MarketState::Active,
-            outcome: 0,
-            token: token.clone(),
-            total_stake: initial_liquidity, // Initial liquidity is part of total stake
-            description,
-            metadata_url: metadata_url.clone(),
-            options_count,
-            min_stake,
-            max_stake,
-            initial_liquidity,
-            creator: creator.clone(),
-            category: category.clone(),
-        };
+    /// Claim winnings from a resolved pool. Returns the amount paid out (0 for losers).
+    /// If part of the entitlement was already taken via `claim_partial`, only the
+    /// remainder is paid.
+    /// PRE: pool.state ≠ Active
+    /// POST: HasClaimed(user, pool) = true (INV-3), payout ≤ pool.total_stake (INV-4)
+    pub fn claim_winnings(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        user.require_auth();
 
-        let pool_key = DataKey::Pool(pool_id);
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::extend_persistent(&env, &pool_key);
+        Self::settle_claim(&env, &user, pool_id, None, None)
+    }
 
-        let pc_key = DataKey::ParticipantsCount(pool_id);
-        env.storage().persistent().set(&pc_key, &0u32);
-        Self::extend_persistent(&env, &pc_key);
+    /// Claim from several settled pools in one invocation. Returns
+    /// `(pool_id, amount)` for each processed pool, in order; at most
+    /// `MAX_BATCH_SIZE` pools are processed, so a shorter result means the
+    /// rest should be resubmitted. Pools that are missing, still active or
+    /// already claimed (HasClaimed, INV-3) yield 0 instead of aborting the batch.
+    pub fn claim_all(env: Env, user: Address, pool_ids: Vec<u64>) -> Vec<(u64, i128)> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        user.require_auth();
 
-        // Transfer initial liquidity from creator to contract if provided
-        if initial_liquidity > 0 {
-            let token_client = token::Client::new(&env, &token);
-            token_client.transfer(&creator, env.current_contract_address(), &initial_liquidity);
+        let processed = core::cmp::min(pool_ids.len(), MAX_BATCH_SIZE);
+        let mut results = Vec::new(&env);
+        for i in 0..processed {
+            let pool_id = pool_ids.get(i).unwrap();
+            let amount = if env.storage().persistent().has(&DataKey::Pool(pool_id)) {
+                Self::settle_claim(&env, &user, pool_id, None, None).unwrap_or(0)
+            } else {
+                0
+            };
+            results.push_back((pool_id, amount));
         }
+        results
+    }
 
-        // Update category index
-        let category_count_key = DataKey::CategoryPoolCount(category.clone());
-        let category_count: u32 = env
-            .storage()
-            .persistent()
-            .get(&category_count_key)
-            .unwrap_or(0);
-
-        let category_index_key = DataKey::CategoryPoolIndex(category.clone(), category_count);
-        env.storage()
-            .persistent()
-            .set(&category_index_key, &pool_id);
-        Self::extend_persistent(&env, &category_index_key);
+    /// Claim only `amount` of the caller's entitlement now, leaving the rest
+    /// claimable later. Once the cumulative amount reaches the full entitlement
+    /// the position is marked claimed exactly like `claim_winnings`.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if `amount` is not positive or exceeds what is left.
+    /// - `AlreadyClaimed` if the entitlement has been fully paid out.
+    pub fn claim_partial(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        user.require_auth();
 
-        env.storage()
-            .persistent()
-            .set(&category_count_key, &(category_count + 1));
-        Self::extend_persistent(&env, &category_count_key);
+        Self::settle_claim(&env, &user, pool_id, Some(amount), None)
+    }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::PoolIdCounter, &(pool_id + 1));
-        Self::extend_instance(&env);
+    /// Claim on behalf of `beneficiary`, e.g. from a relayer or custodial
+    /// service. The payout goes to the beneficiary who placed the prediction;
+    /// the caller receives the beneficiary's claim tip (see `set_claim_tip`)
+    /// out of it. No tip is paid when the payout is deferred (see
+    /// `retry_payout`). Returns the amount claimed, tip included.
+    pub fn claim_for(
+        env: Env,
+        caller: Address,
+        beneficiary: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        caller.require_auth();
 
-        PoolCreatedEvent {
-            pool_id,
-            end_time,
-            token,
-            options_count,
-            metadata_url,
-            initial_liquidity,
-            category,
+        Self::settle_claim(&env, &beneficiary, pool_id, None, Some(&caller))
+    }
+
+    /// Set the tip, in bps of the winnings, paid to whoever claims for `user`
+    /// via `claim_for` (at most `MAX_CLAIM_TIP_BPS`, 0 = no tip).
+    pub fn set_claim_tip(env: Env, user: Address, tip_bps: u32) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        user.require_auth();
+        if tip_bps > MAX_CLAIM_TIP_BPS {
+            return Err(PredifiError::InvalidAmount);
         }
-        .publish(&env);
 
-        // Emit initial liquidity event if liquidity was provided
-        if initial_liquidity > 0 {
-            InitialLiquidityProvidedEvent {
-                pool_id,
-                creator,
-                amount: initial_liquidity,
-            }
-            .publish(&env);
+        let key = DataKey::Ext(ExtKey::ClaimTipBps(user.clone()));
+        if tip_bps > 0 {
+            env.storage().persistent().set(&key, &tip_bps);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
         }
 
-        pool_id
+        ClaimTipUpdateEvent { user, tip_bps }.publish(&env);
+        Ok(())
     }
 
-    /// Resolve a pool with a winning outcome. Caller must have Operator role (1).
-    /// Cannot resolve a canceled pool.
+    /// Returns the claim tip `user` grants to relayers, in bps.
+    pub fn get_claim_tip(env: Env, user: Address) -> u32 {
+        Self::claim_tip_bps(&env, &user)
+    }
+
+    /// Returns how much of the user's entitlement in a pool has been paid so far.
+    pub fn get_claimed_amount(env: Env, user: Address, pool_id: u64) -> i128 {
+        let key = DataKey::ClaimedAmount(user, pool_id);
+        let claimed = env.storage().persistent().get(&key).unwrap_or(0);
+        if env.storage().persistent().has(&key) {
+            Self::extend_persistent(&env, &key);
+        }
+        claimed
+    }
+
+    /// Update the stake limits for an active pool. Caller must have Operator role (1).
     /// PRE: pool.state = Active, operator has role 1
-    /// POST: pool.state = Resolved, state transition valid (INV-2)
-    pub fn resolve_pool(
+    /// POST: pool.min_stake and pool.max_stake updated
+    pub fn set_stake_limits(
         env: Env,
         operator: Address,
         pool_id: u64,
-        outcome: u32,
+        min_stake: i128,
+        max_stake: i128,
     ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env);
         operator.require_auth();
-        if let Err(e) = Self::require_role(&env, &operator, 1) {
-            // 🔴 HIGH ALERT: unauthorized attempt to resolve a pool.
-            UnauthorizedResolveAttemptEvent {
-                caller: operator,
-                pool_id,
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            return Err(e);
-        }
+        Self::require_role(&env, &operator, 1)?;
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: Pool = env
@@ -1223,61 +9785,160 @@ impl PredifiContract {
             .get(&pool_key)
             .expect("Pool not found");
 
-        assert!(!pool.resolved, "Pool already resolved");
-        assert!(!pool.canceled, "Cannot resolve a canceled pool");
         if pool.state != MarketState::Active {
             return Err(PredifiError::InvalidPoolState);
         }
 
-        let current_time = env.ledger().timestamp();
-        let config = Self::get_config(&env);
-
-        if current_time < pool.end_time.saturating_add(config.resolution_delay) {
-            return Err(PredifiError::ResolutionDelayNotMet);
-        }
-
-        // Validate: outcome must be within the valid options range
-        // Verify state transition validity (INV-2)
+        assert!(min_stake > 0, "min_stake must be greater than zero");
         assert!(
-            outcome < pool.options_count
-                && Self::is_valid_state_transition(pool.state, MarketState::Resolved),
-            "outcome exceeds options_count or invalid state transition"
+            max_stake == 0 || max_stake >= min_stake,
+            "max_stake must be zero (unlimited) or >= min_stake"
         );
 
-        pool.state = MarketState::Resolved;
-        pool.resolved = true;
-        pool.outcome = outcome;
+        pool.min_stake = min_stake;
+        pool.max_stake = max_stake;
 
         env.storage().persistent().set(&pool_key, &pool);
         Self::extend_persistent(&env, &pool_key);
 
-        // Retrieve winning-outcome stake for the diagnostic event using optimized batch storage
-        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
-        let winning_stake: i128 = stakes.get(outcome).unwrap_or(0);
-
-        PoolResolvedEvent {
+        StakeLimitsUpdatedEvent {
             pool_id,
             operator,
-            outcome,
+            min_stake,
+            max_stake,
         }
         .publish(&env);
 
-        // 🟢 INFO: enriched diagnostics alongside the standard resolved event.
-        PoolResolvedDiagEvent {
+        Ok(())
+    }
+
+    /// Returns the operator note recorded when the pool was settled, if any.
+    pub fn get_operator_note(env: Env, pool_id: u64) -> Option<OperatorNote> {
+        let key = DataKey::OperatorNote(pool_id);
+        let note = env.storage().persistent().get(&key);
+        if note.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        note
+    }
+
+    /// Anchor a status update (e.g. "match postponed") to a pool by posting
+    /// the sha256 hash of the off-chain message. At most `MAX_POOL_NOTES`
+    /// per pool. Caller must be the pool creator or have Operator role (1).
+    /// Returns the note's index in the feed.
+    pub fn post_pool_note(
+        env: Env,
+        author: Address,
+        pool_id: u64,
+        hash: BytesN<32>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        author.require_auth();
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != author {
+            Self::require_role(&env, &author, 1)?;
+        }
+
+        let key = DataKey::Ext(ExtKey::PoolNotes(pool_id));
+        let mut notes = Self::get_pool_notes(env.clone(), pool_id);
+        if notes.len() >= MAX_POOL_NOTES {
+            return Err(PredifiError::CapacityReached);
+        }
+        let index = notes.len();
+        notes.push_back(PoolNote {
+            author: author.clone(),
+            hash: hash.clone(),
+            posted_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &notes);
+        Self::extend_persistent(&env, &key);
+
+        PoolNotePostedEvent {
             pool_id,
-            outcome,
-            total_stake: pool.total_stake,
-            winning_stake,
-            timestamp: env.ledger().timestamp(),
+            author,
+            index,
+            hash,
         }
         .publish(&env);
+        Ok(index)
+    }
+
+    /// Returns the status update anchors of a pool, oldest first.
+    pub fn get_pool_notes(env: Env, pool_id: u64) -> Vec<PoolNote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PoolNotes(pool_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set (or with `None` remove) the sponsor branding of a pool. Branding
+    /// is locked once the first bet is placed, so bettors know what they
+    /// staked under. Caller must be the pool creator.
+    pub fn set_pool_sponsor(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        sponsor: Option<PoolSponsor>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        let participants: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ParticipantsCount(pool_id))
+            .unwrap_or(0);
+        if pool.state != MarketState::Active || participants > 0 {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = DataKey::Ext(ExtKey::PoolSponsor(pool_id));
+        match &sponsor {
+            Some(sponsor) => {
+                env.storage().persistent().set(&key, sponsor);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
 
+        PoolSponsorSetEvent { pool_id, sponsor }.publish(&env);
         Ok(())
     }
 
-    /// Mark a pool as ready for resolution and emit an event.
-    /// Can be called by anyone once the resolution delay has passed.
-    pub fn mark_pool_ready(env: Env, pool_id: u64) -> Result<(), PredifiError> {
+    /// Returns the sponsor branding of a pool, if any.
+    pub fn get_pool_sponsor(env: Env, pool_id: u64) -> Option<PoolSponsor> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::PoolSponsor(pool_id)))
+    }
+
+    /// Turn a freshly created binary pool into a derivative market on another
+    /// pool's outcome. Only the creator may link, once, before any external
+    /// bets are placed. The reference must be an older pool (which rules out
+    /// cycles). Outcome 1 means "the reference resolves to `reference_outcome`
+    /// by this pool's end_time"; see `resolve_derivative_pool`.
+    pub fn set_derivative_reference(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        reference_pool_id: u64,
+        reference_outcome: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+
         let pool_key = DataKey::Pool(pool_id);
         let pool: Pool = env
             .storage()
@@ -1285,357 +9946,857 @@ impl PredifiContract {
             .get(&pool_key)
             .expect("Pool not found");
 
-        if pool.state != MarketState::Active {
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state != MarketState::Active || pool.total_stake != pool.initial_liquidity {
             return Err(PredifiError::InvalidPoolState);
         }
 
-        let config = Self::get_config(&env);
-        let current_time = env.ledger().timestamp();
+        let link_key = DataKey::DerivativeLink(pool_id);
+        if pool.options_count != 2 || env.storage().persistent().has(&link_key) {
+            return Err(PredifiError::InvalidPoolReference);
+        }
 
-        if current_time >= pool.end_time.saturating_add(config.resolution_delay) {
-            PoolReadyForResolutionEvent {
-                pool_id,
-                timestamp: current_time,
-            }
-            .publish(&env);
-            Ok(())
-        } else {
-            Err(PredifiError::ResolutionDelayNotMet)
+        let reference: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(reference_pool_id))
+            .ok_or(PredifiError::InvalidPoolReference)?;
+        if reference_pool_id >= pool_id || reference_outcome >= reference.options_count {
+            return Err(PredifiError::InvalidPoolReference);
         }
+
+        let link = DerivativeLink {
+            reference_pool_id,
+            reference_outcome,
+        };
+        env.storage().persistent().set(&link_key, &link);
+        Self::extend_persistent(&env, &link_key);
+
+        DerivativeLinkedEvent {
+            pool_id,
+            reference_pool_id,
+            reference_outcome,
+        }
+        .publish(&env);
+
+        Ok(())
     }
 
-    /// Cancel an active pool. Caller must have Operator role (1).
-    /// Cancel a pool, freezing all betting and enabling refund process.
-    /// Only callable by Admin (role 0) - can cancel any pool for any reason.
-    ///
-    /// # Arguments
-    /// * `caller`  - The address requesting the cancellation (must be admin).
-    /// * `pool_id` - The ID of the pool to cancel.
-    /// * `reason`  - A short description of why the pool is being canceled.
-    ///
-    /// # Errors
-    /// - `Unauthorized` if caller is not admin.
-    /// - `PoolNotResolved` error (code 22) is returned if trying to cancel an already resolved pool.
-    /// PRE: pool.state = Active, operator has role 1
-    /// POST: pool.state = Canceled, state transition valid (INV-2)
-    pub fn cancel_pool(env: Env, operator: Address, pool_id: u64) -> Result<(), PredifiError> {
+    /// Make a freshly created pool conditional on another pool's outcome: it
+    /// can only resolve once `parent_pool_id` resolves to `parent_outcome`,
+    /// and is canceled (refunding every bettor) if the parent resolves to
+    /// anything else or is canceled. Only the creator may link, once, before
+    /// any external bets; the parent must be an older, still-active pool.
+    pub fn set_parent_condition(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        parent_pool_id: u64,
+        parent_outcome: u32,
+    ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env);
-        operator.require_auth();
+        creator.require_auth();
 
-        // Check authorization: operator must have role 1
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state != MarketState::Active || pool.total_stake != pool.initial_liquidity {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let condition_key = DataKey::Ext(ExtKey::ParentCondition(pool_id));
+        if env.storage().persistent().has(&condition_key) {
+            return Err(PredifiError::InvalidPoolReference);
+        }
+        let parent: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(parent_pool_id))
+            .ok_or(PredifiError::InvalidPoolReference)?;
+        if parent_pool_id >= pool_id
+            || parent_outcome >= parent.options_count
+            || parent.state != MarketState::Active
+        {
+            return Err(PredifiError::InvalidPoolReference);
+        }
+
+        let children_key = DataKey::Ext(ExtKey::ChildPools(parent_pool_id));
+        let mut children: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&children_key)
+            .unwrap_or(Vec::new(&env));
+        if children.len() >= MAX_CHILD_POOLS {
+            return Err(PredifiError::CapacityReached);
+        }
+        children.push_back(pool_id);
+        env.storage().persistent().set(&children_key, &children);
+        Self::extend_persistent(&env, &children_key);
+        env.storage().persistent().set(
+            &condition_key,
+            &ParentCondition {
+                parent_pool_id,
+                parent_outcome,
+            },
+        );
+        Self::extend_persistent(&env, &condition_key);
+
+        ParentConditionSetEvent {
+            pool_id,
+            parent_pool_id,
+            parent_outcome,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the number of claims paid out of a pool so far, i.e. the
+    /// `sequence` of its latest `WinningsClaimedEvent`.
+    pub fn get_claim_sequence(env: Env, pool_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ClaimSequence(pool_id)))
+            .unwrap_or(0)
+    }
+
+    /// Returns the parent condition of a conditional pool, if any.
+    pub fn get_parent_condition(env: Env, pool_id: u64) -> Option<ParentCondition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::ParentCondition(pool_id)))
+    }
+
+    /// Hide a pool from the discovery views (`get_pools`,
+    /// `get_pools_by_category`) or list it again, e.g. to de-list a market
+    /// for legal reasons. Betting, resolution and claims are unaffected.
+    /// Operator only.
+    pub fn set_pool_hidden(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        hidden: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
         Self::require_role(&env, &operator, 1)?;
+        if !env.storage().persistent().has(&DataKey::Pool(pool_id)) {
+            return Err(PredifiError::PoolNotFound);
+        }
+
+        let key = DataKey::Ext(ExtKey::Hidden(pool_id));
+        if hidden {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        PoolVisibilityEvent {
+            pool_id,
+            operator,
+            hidden,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns true if a pool is hidden from the discovery views.
+    pub fn is_pool_hidden(env: Env, pool_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Ext(ExtKey::Hidden(pool_id)))
+    }
+
+    /// Fix the description and metadata URL of a pool, e.g. a typo or a wrong
+    /// IPFS link. Only the creator may edit, and only while no external bets
+    /// have been placed (total_stake = initial_liquidity).
+    pub fn update_pool_metadata(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        description: String,
+        metadata_url: String,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: Pool = env
             .storage()
             .persistent()
             .get(&pool_key)
-            .expect("Pool not found");
-        Self::extend_persistent(&env, &pool_key);
+            .ok_or(PredifiError::PoolNotFound)?;
 
-        // Ensure resolved pools cannot be canceled
-        if pool.resolved {
-            return Err(PredifiError::PoolNotResolved);
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
         }
+        if pool.state != MarketState::Active || pool.total_stake != pool.initial_liquidity {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        assert!(description.len() <= 256, "description exceeds 256 bytes");
+        assert!(metadata_url.len() <= 512, "metadata_url exceeds 512 bytes");
 
-        // Prevent double cancellation
-        assert!(!pool.canceled, "Pool already canceled");
-        // Verify state transition validity (INV-2)
-        assert!(
-            Self::is_valid_state_transition(pool.state, MarketState::Canceled),
-            "Invalid state transition"
-        );
-
-        pool.state = MarketState::Canceled;
-
-        // Mark pool as canceled
-        pool.canceled = true;
+        pool.description = description.clone();
+        pool.metadata_url = metadata_url.clone();
         env.storage().persistent().set(&pool_key, &pool);
         Self::extend_persistent(&env, &pool_key);
 
-        PoolCanceledEvent {
+        PoolMetadataUpdatedEvent {
             pool_id,
-            caller: operator.clone(),
-            reason: String::from_str(&env, ""),
-            operator,
+            creator,
+            description,
+            metadata_url,
         }
         .publish(&env);
-
         Ok(())
     }
 
-    /// Place a prediction on a pool. Cannot predict on canceled or resolved pools.
-    /// PRE: amount > 0 (INV-7), pool.state = Active, current_time < pool.end_time
-    /// PRE: pool.min_stake <= amount <= pool.max_stake (unless max_stake == 0)
-    /// POST: pool.total_stake increases by amount, OutcomeStake increases by amount (INV-1)
-    #[allow(clippy::needless_borrows_for_generic_args)]
-    pub fn place_prediction(env: Env, user: Address, pool_id: u64, amount: i128, outcome: u32) {
+    /// Configure the maker/taker fees charged by the outcome-share trading
+    /// mode of a pool. Only the pool creator may call this, and only before any
+    /// prediction is placed, so traders never see fees change under them.
+    /// Makers may not pay more than takers.
+    pub fn set_pool_amm_fees(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        maker_fee_bps: u32,
+        taker_fee_bps: u32,
+    ) -> Result<(), PredifiError> {
         Self::require_not_paused(&env);
-        user.require_auth();
-        assert!(amount > 0, "amount must be positive");
-
-        Self::enter_reentrancy_guard(&env);
-
-        let pool_key = DataKey::Pool(pool_id);
-        let mut pool: Pool = env
-            .storage()
-            .persistent()
-            .get(&pool_key)
-            .expect("Pool not found");
+        creator.require_auth();
 
-        assert!(!pool.resolved, "Pool already resolved");
-        assert!(!pool.canceled, "Cannot place prediction on canceled pool");
-        assert!(pool.state == MarketState::Active, "Pool is not active");
-        assert!(env.ledger().timestamp() < pool.end_time, "Pool has ended");
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state != MarketState::Active || pool.total_stake != pool.initial_liquidity {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if let Some(state) = Self::lmsr_state(&env, pool_id) {
+            if state.shares.iter().any(|q| q != 0) {
+                return Err(PredifiError::InvalidPoolState);
+            }
+        }
+        if !Self::is_valid_fee_bps(taker_fee_bps) || maker_fee_bps > taker_fee_bps {
+            return Err(PredifiError::InvalidFeeSchedule);
+        }
 
-        // Validate: outcome must be within the valid options range
-        assert!(
-            outcome < pool.options_count,
-            "outcome exceeds options_count"
+        let key = DataKey::AmmFees(pool_id);
+        env.storage().persistent().set(
+            &key,
+            &AmmFeeConfig {
+                maker_fee_bps,
+                taker_fee_bps,
+            },
         );
+        Self::extend_persistent(&env, &key);
 
-        // --- INTERNAL CHECKS & EFFECTS ---
-        // Validate: per-pool stake limits
-        assert!(
-            amount >= pool.min_stake,
-            "amount is below the pool minimum stake"
-        );
-        if pool.max_stake > 0 {
-            assert!(
-                amount <= pool.max_stake,
-                "amount exceeds the pool maximum stake"
-            );
+        AmmFeesSetEvent {
+            pool_id,
+            creator,
+            maker_fee_bps,
+            taker_fee_bps,
         }
+        .publish(&env);
+        Ok(())
+    }
 
-        let pred_key = DataKey::Prediction(user.clone(), pool_id);
-        if !env.storage().persistent().has(&pred_key) {
-            let pc_key = DataKey::ParticipantsCount(pool_id);
-            let pc: u32 = env.storage().persistent().get(&pc_key).unwrap_or(0);
-            env.storage().persistent().set(&pc_key, &(pc + 1));
-            Self::extend_persistent(&env, &pc_key);
+    /// Returns the maker/taker fees of a pool's trading mode, if configured.
+    pub fn get_pool_amm_fees(env: Env, pool_id: u64) -> Option<AmmFeeConfig> {
+        let key = DataKey::AmmFees(pool_id);
+        let fees = env.storage().persistent().get(&key);
+        if fees.is_some() {
+            Self::extend_persistent(&env, &key);
         }
-        env.storage()
-            .persistent()
-            .set(&pred_key, &Prediction { amount, outcome });
-        Self::extend_persistent(&env, &pred_key);
+        fees
+    }
 
-        // Update total stake (INV-1)
-        pool.total_stake = pool.total_stake.checked_add(amount).expect("overflow");
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::extend_persistent(&env, &pool_key);
+    /// Designate or revoke a market maker on a pool. Market makers may post
+    /// quotes and trade at the pool's maker fee. Revoking clears their quotes.
+    /// Caller must have Operator role (1).
+    pub fn set_market_maker(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        market_maker: Address,
+        enabled: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+        if !env.storage().persistent().has(&DataKey::Pool(pool_id)) {
+            return Err(PredifiError::PoolNotFound);
+        }
 
-        // Update outcome stake (INV-1) - using optimized batch storage
-        let _stakes =
-            Self::update_outcome_stake(&env, pool_id, outcome, amount, pool.options_count);
+        let key = DataKey::Ext(ExtKey::MarketMaker(pool_id, market_maker.clone()));
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Ext(ExtKey::Quotes(pool_id, market_maker.clone())));
+        }
 
-        let count_key = DataKey::UserPredictionCount(user.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        MarketMakerSetEvent {
+            pool_id,
+            operator,
+            market_maker,
+            enabled,
+        }
+        .publish(&env);
+        Ok(())
+    }
 
-        let index_key = DataKey::UserPredictionIndex(user.clone(), count);
-        env.storage().persistent().set(&index_key, &pool_id);
-        Self::extend_persistent(&env, &index_key);
+    /// Returns true if `market_maker` is a designated market maker of a pool.
+    pub fn is_market_maker(env: Env, pool_id: u64, market_maker: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Ext(ExtKey::MarketMaker(pool_id, market_maker)))
+    }
 
-        env.storage().persistent().set(&count_key, &(count + 1));
-        Self::extend_persistent(&env, &count_key);
+    /// Post or replace many quotes in one call. Each quote replaces the
+    /// maker's quote on the same outcome; a `size` of 0 withdraws it. Only
+    /// designated market makers may quote, and only until betting locks.
+    /// Returns the number of live quotes.
+    pub fn refresh_quotes(
+        env: Env,
+        market_maker: Address,
+        pool_id: u64,
+        quotes: Vec<Quote>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        market_maker.require_auth();
+        if !Self::is_market_maker(env.clone(), pool_id, market_maker.clone()) {
+            return Err(PredifiError::Unauthorized);
+        }
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if env.ledger().timestamp() >= Self::lock_time(&env, pool_id, &pool) {
+            return Err(PredifiError::PredictionTooLate);
+        }
+        if quotes.len() > pool.options_count {
+            return Err(PredifiError::InvalidOutcome);
+        }
 
-        // --- INTERACTIONS ---
+        let key = DataKey::Ext(ExtKey::Quotes(pool_id, market_maker.clone()));
+        let mut book: Vec<Quote> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let mut seen: Vec<u32> = Vec::new(&env);
+        for quote in quotes.iter() {
+            if quote.outcome >= pool.options_count || seen.contains(quote.outcome) {
+                return Err(PredifiError::InvalidOutcome);
+            }
+            if quote.size < 0 || (quote.size > 0 && quote.odds_bps <= 10_000) {
+                return Err(PredifiError::InvalidAmount);
+            }
+            seen.push_back(quote.outcome);
 
-        let token_client = token::Client::new(&env, &pool.token);
-        token_client.transfer(&user, &env.current_contract_address(), &amount);
+            let existing = book.iter().position(|q| q.outcome == quote.outcome);
+            match (existing, quote.size > 0) {
+                (Some(i), true) => book.set(i as u32, quote),
+                (Some(i), false) => {
+                    book.remove(i as u32);
+                }
+                (None, true) => book.push_back(quote),
+                (None, false) => {}
+            }
+        }
 
-        Self::exit_reentrancy_guard(&env);
+        if book.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &book);
+            Self::extend_persistent(&env, &key);
+        }
 
-        PredictionPlacedEvent {
+        QuotesRefreshedEvent {
             pool_id,
-            user: user.clone(),
-            amount,
-            outcome,
+            market_maker,
+            updated: quotes.len(),
+            live: book.len(),
         }
         .publish(&env);
+        Ok(book.len())
+    }
 
-        // 🟡 MEDIUM ALERT: large stake detected — emit supplementary event.
-        if amount >= HIGH_VALUE_THRESHOLD {
-            HighValuePredictionEvent {
-                pool_id,
-                user,
-                amount,
-                outcome,
-                threshold: HIGH_VALUE_THRESHOLD,
-            }
-            .publish(&env);
-        }
+    /// Returns the live quotes of a market maker on a pool.
+    pub fn get_quotes(env: Env, pool_id: u64, market_maker: Address) -> Vec<Quote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::Quotes(pool_id, market_maker)))
+            .unwrap_or(Vec::new(&env))
+    }
 
-        // 🟢 INFO: For markets with many outcomes (16+), emit batch stake update event
-        // to avoid emitting individual events per outcome which would be impractical
-        // for large tournaments (e.g., 32-team bracket).
-        if pool.options_count >= 16 {
-            OutcomeStakesUpdatedEvent {
-                pool_id,
-                options_count: pool.options_count,
-                total_stake: pool.total_stake,
-            }
-            .publish(&env);
+    /// Returns the trading fee `trader` pays in a pool's trading mode:
+    /// the maker fee for designated market makers, the taker fee otherwise
+    /// (0 when the pool has no trading fees).
+    pub fn get_trading_fee_bps(env: Env, pool_id: u64, trader: Address) -> u32 {
+        let Some(fees) = Self::get_pool_amm_fees(env.clone(), pool_id) else {
+            return 0;
+        };
+        if Self::is_market_maker(env, pool_id, trader) {
+            fees.maker_fee_bps
+        } else {
+            fees.taker_fee_bps
         }
     }
 
-    /// Claim winnings from a resolved pool. Returns the amount paid out (0 for losers).
-    /// PRE: pool.state ≠ Active
-    /// POST: HasClaimed(user, pool) = true (INV-3), payout ≤ pool.total_stake (INV-4)
-    #[allow(clippy::needless_borrows_for_generic_args)]
-    pub fn claim_winnings(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError> {
-        Self::require_not_paused(&env);
-        user.require_auth();
+    /// Buy `shares` of `outcome` in an LMSR pool. The price is the change in
+    /// the LMSR cost function, plus the trader's trading fee (accrued to the
+    /// creator). Fails with `SlippageExceeded` if the total exceeds
+    /// `max_cost`. Returns the total paid.
+    pub fn buy_shares(
+        env: Env,
+        trader: Address,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+        max_cost: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        trader.require_auth();
+        if shares <= 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        if !Self::is_bettor_allowed(&env, pool_id, &trader) {
+            return Err(PredifiError::Unauthorized);
+        }
+        Self::require_not_blocked(&env, &trader, "buy_shares")?;
+        if Self::is_draft(&env, pool_id) {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let (pool, state, position, cost) =
+            Self::lmsr_trade(&env, &trader, pool_id, outcome, shares)?;
+        let fee_bps = Self::get_trading_fee_bps(env.clone(), pool_id, trader.clone());
+        let fee = cost * fee_bps as i128 / 10_000;
+        if cost + fee > max_cost {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::SlippageExceeded,
+                Some(pool_id),
+                max_cost,
+                cost + fee,
+            ));
+        }
 
         Self::enter_reentrancy_guard(&env);
+        Self::commit_lmsr_trade(
+            &env, &trader, pool_id, &pool, state, position, outcome, shares, cost, fee,
+        );
+        let token_client = token::Client::new(&env, &pool.token);
+        token_client.transfer(&trader, &env.current_contract_address(), &(cost + fee));
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &trader,
+            &pool.token,
+            LedgerDirection::In,
+            cost + fee,
+            "trade",
+        );
+        Self::exit_reentrancy_guard(&env);
 
-        let pool_key = DataKey::Pool(pool_id);
-        let pool: Pool = env
-            .storage()
-            .persistent()
-            .get(&pool_key)
-            .expect("Pool not found");
-        Self::extend_persistent(&env, &pool_key);
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(cost + fee)
+    }
 
-        if pool.state == MarketState::Active {
-            Self::exit_reentrancy_guard(&env);
-            return Err(PredifiError::PoolNotResolved);
+    /// Sell `shares` of `outcome` back to an LMSR pool for the drop in the
+    /// LMSR cost function, net of the trader's trading fee. Fails with
+    /// `SlippageExceeded` if the net proceeds fall below `min_proceeds`.
+    /// Returns the amount received.
+    pub fn sell_shares(
+        env: Env,
+        trader: Address,
+        pool_id: u64,
+        outcome: u32,
+        shares: i128,
+        min_proceeds: i128,
+    ) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_BETTING);
+        trader.require_auth();
+        if shares <= 0 {
+            return Err(PredifiError::InvalidAmount);
         }
 
-        let claimed_key = DataKey::HasClaimed(user.clone(), pool_id);
-        if env.storage().persistent().has(&claimed_key) {
-            // 🔴 HIGH ALERT: repeated claim attempt on an already-claimed pool.
-            SuspiciousDoubleClaimEvent {
-                user: user.clone(),
-                pool_id,
-                timestamp: env.ledger().timestamp(),
-            }
-            .publish(&env);
-            Self::exit_reentrancy_guard(&env);
-            return Err(PredifiError::AlreadyClaimed);
+        let (pool, state, position, cost) =
+            Self::lmsr_trade(&env, &trader, pool_id, outcome, -shares)?;
+        let proceeds = (-cost).max(0);
+        let fee_bps = Self::get_trading_fee_bps(env.clone(), pool_id, trader.clone());
+        let fee = proceeds * fee_bps as i128 / 10_000;
+        if proceeds - fee < min_proceeds {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::SlippageExceeded,
+                Some(pool_id),
+                min_proceeds,
+                proceeds - fee,
+            ));
         }
 
-        // --- CHECKS ---
+        Self::enter_reentrancy_guard(&env);
+        Self::commit_lmsr_trade(
+            &env, &trader, pool_id, &pool, state, position, outcome, -shares, -proceeds, fee,
+        );
+        if proceeds - fee > 0 {
+            let token_client = token::Client::new(&env, &pool.token);
+            token_client.transfer(&env.current_contract_address(), &trader, &(proceeds - fee));
+            Self::record_ledger_entry(
+                &env,
+                Some(pool_id),
+                &trader,
+                &pool.token,
+                LedgerDirection::Out,
+                proceeds - fee,
+                "trade",
+            );
+        }
+        Self::exit_reentrancy_guard(&env);
 
-        let pred_key = DataKey::Prediction(user.clone(), pool_id);
-        let prediction: Option<Prediction> = env.storage().persistent().get(&pred_key);
+        Self::check_pool_invariants(&env, pool_id);
+        Ok(proceeds - fee)
+    }
 
-        if env.storage().persistent().has(&pred_key) {
-            Self::extend_persistent(&env, &pred_key);
-        }
+    /// Redeem an LMSR position once the pool settles: one token unit per
+    /// winning share on resolved pools, the positive cost basis on canceled
+    /// ones. Returns the amount paid.
+    pub fn redeem_shares(env: Env, trader: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        trader.require_auth();
 
-        let prediction = match prediction {
-            Some(p) => p,
-            None => {
-                Self::exit_reentrancy_guard(&env);
-                return Ok(0);
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        match pool.state {
+            MarketState::Resolved => {
+                if env.ledger().timestamp() < Self::claims_open_at(&env, pool_id) {
+                    return Err(PredifiError::ClaimsNotOpen);
+                }
             }
-        };
-
-        // --- EFFECTS ---
+            MarketState::Canceled => {}
+            _ => return Err(PredifiError::PoolNotResolved),
+        }
+        let position_key = DataKey::Ext(ExtKey::LmsrPosition(pool_id, trader.clone()));
+        let position: LmsrPosition = env
+            .storage()
+            .persistent()
+            .get(&position_key)
+            .ok_or(PredifiError::PredictionNotFound)?;
 
-        // Mark as claimed immediately to prevent re-entrancy (INV-3)
-        env.storage().persistent().set(&claimed_key, &true);
-        Self::extend_persistent(&env, &claimed_key);
+        let amount = if pool.state == MarketState::Resolved {
+            position.shares.get(pool.outcome).unwrap_or(0)
+        } else {
+            position.basis.max(0)
+        };
 
-        if pool.state == MarketState::Canceled {
-            // --- INTERACTIONS (Refund) ---
+        Self::enter_reentrancy_guard(&env);
+        env.storage().persistent().remove(&position_key);
+        if amount > 0 {
             let token_client = token::Client::new(&env, &pool.token);
-            token_client.transfer(&env.current_contract_address(), &user, &prediction.amount);
-
-            Self::exit_reentrancy_guard(&env);
-
-            WinningsClaimedEvent {
-                pool_id,
-                user: user.clone(),
-                amount: prediction.amount,
-            }
-            .publish(&env);
+            token_client.transfer(&env.current_contract_address(), &trader, &amount);
+            Self::record_ledger_entry(
+                &env,
+                Some(pool_id),
+                &trader,
+                &pool.token,
+                LedgerDirection::Out,
+                amount,
+                "redeem",
+            );
+        }
+        Self::exit_reentrancy_guard(&env);
 
-            return Ok(prediction.amount);
+        SharesRedeemedEvent {
+            pool_id,
+            trader,
+            amount,
         }
+        .publish(&env);
+        Ok(amount)
+    }
 
-        if prediction.outcome != pool.outcome {
-            Self::exit_reentrancy_guard(&env);
-            return Ok(0);
+    /// Returns how a pool prices positions.
+    pub fn get_pool_mechanism(env: Env, pool_id: u64) -> Mechanism {
+        if Self::lmsr_state(&env, pool_id).is_some() {
+            Mechanism::Lmsr
+        } else {
+            Mechanism::Parimutuel
         }
+    }
 
-        // Get winning stake using optimized batch storage
-        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
-        let winning_stake: i128 = stakes.get(pool.outcome).unwrap_or(0);
+    /// Returns the market state of an LMSR pool, if it is one.
+    pub fn get_lmsr_state(env: Env, pool_id: u64) -> Option<LmsrState> {
+        Self::lmsr_state(&env, pool_id)
+    }
 
-        if winning_stake == 0 {
-            Self::exit_reentrancy_guard(&env);
-            return Ok(0);
+    /// Returns a trader's unredeemed holdings in an LMSR pool.
+    pub fn get_lmsr_position(env: Env, pool_id: u64, trader: Address) -> Option<LmsrPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ext(ExtKey::LmsrPosition(pool_id, trader)))
+    }
+
+    /// Returns the reference of a derivative pool, if it is one.
+    pub fn get_derivative_reference(env: Env, pool_id: u64) -> Option<DerivativeLink> {
+        let key = DataKey::DerivativeLink(pool_id);
+        let link = env.storage().persistent().get(&key);
+        if link.is_some() {
+            Self::extend_persistent(&env, &key);
         }
+        link
+    }
 
-        // Use pure function for winnings calculation (verifiable)
-        let winnings = Self::calculate_winnings(prediction.amount, winning_stake, pool.total_stake);
+    /// Settle a derivative pool from its reference pool's final state.
+    /// Permissionless: anyone may call once the resolution delay has passed.
+    /// The derivative's `end_time` is its deadline: it resolves to yes (1)
+    /// only if the reference resolved to `reference_outcome` by then, and to
+    /// no (0) otherwise, including when the reference is still unsettled. A
+    /// reference canceled by the deadline cancels the derivative.
+    /// Returns the derivative's winning outcome (0 when canceled).
+    ///
+    /// # Errors
+    /// - `InvalidPoolReference` if the pool is not a derivative market.
+    /// - Any error of `check_settleable`.
+    pub fn resolve_derivative_pool(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
 
-        // Verify invariant: winnings ≤ total_stake (INV-4)
-        assert!(winnings <= pool.total_stake, "Winnings exceed total stake");
+        let link: DerivativeLink = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DerivativeLink(pool_id))
+            .ok_or(PredifiError::InvalidPoolReference)?;
+        let pool = Self::check_settleable(&env, pool_id)?;
 
-        // --- INTERACTIONS (Winnings Payout) ---
-        let token_client = token::Client::new(&env, &pool.token);
-        token_client.transfer(&env.current_contract_address(), &user, &winnings);
+        let reference: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(link.reference_pool_id))
+            .ok_or(PredifiError::InvalidPoolReference)?;
+
+        let resolver = env.current_contract_address();
+        let outcome = match reference.state {
+            MarketState::Canceled => {
+                let canceled_at: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::CanceledAt(link.reference_pool_id))
+                    .unwrap_or(0);
+                if canceled_at <= pool.end_time {
+                    let reason = String::from_str(&env, "reference pool canceled");
+                    Self::finalize_cancellation(&env, pool_id, pool, resolver, reason);
+                    return Ok(0);
+                }
+                0
+            }
+            MarketState::Resolved
+                if reference.outcome == link.reference_outcome
+                    && Self::resolved_at(&env, link.reference_pool_id) <= pool.end_time =>
+            {
+                1
+            }
+            _ => 0,
+        };
+        let note = String::from_str(&env, "");
+        Self::finalize_resolution(&env, pool_id, pool, outcome, resolver, note);
+        Ok(outcome)
+    }
 
-        Self::exit_reentrancy_guard(&env);
+    /// Push a chunk of winners of a resolved pool towards its merkle summary.
+    /// Each address must hold a winning prediction and the chunk must keep the
+    /// leaves in strictly increasing order. Caller must have Operator role (1).
+    /// Returns the number of leaves pushed so far.
+    pub fn push_winner_entries(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        winners: Vec<Address>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
 
-        WinningsClaimedEvent {
-            pool_id,
-            user,
-            amount: winnings,
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.state != MarketState::Resolved {
+            return Err(PredifiError::PoolNotResolved);
         }
-        .publish(&env);
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::WinnersSummary(pool_id))
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let draft_key = DataKey::WinnersDraft(pool_id);
+        let mut draft: WinnersDraft =
+            env.storage()
+                .persistent()
+                .get(&draft_key)
+                .unwrap_or(WinnersDraft {
+                    leaves: Vec::new(&env),
+                    stake_total: 0,
+                    entitlement_total: 0,
+                });
+
+        for user in winners.iter() {
+            let prediction: Prediction = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Prediction(user.clone(), pool_id))
+                .ok_or(PredifiError::InvalidWinnerEntry)?;
+            let prediction = Self::settled_prediction(&env, &user, pool_id, &pool, prediction);
+            if prediction.outcome != pool.outcome {
+                return Err(PredifiError::InvalidWinnerEntry);
+            }
+
+            let entitlement = Self::calculate_entitlement(&env, pool_id, &pool, &prediction);
+            let leaf = Self::winner_leaf(&env, &user, entitlement);
+            if let Some(last) = draft.leaves.last() {
+                if leaf <= last {
+                    return Err(PredifiError::InvalidWinnerEntry);
+                }
+            }
+            draft.leaves.push_back(leaf);
+            draft.stake_total = draft
+                .stake_total
+                .checked_add(prediction.amount)
+                .expect("overflow");
+            draft.entitlement_total = draft
+                .entitlement_total
+                .checked_add(entitlement)
+                .expect("overflow");
+        }
+
+        env.storage().persistent().set(&draft_key, &draft);
+        Self::extend_persistent(&env, &draft_key);
 
-        Ok(winnings)
+        Ok(draft.leaves.len())
     }
 
-    /// Update the stake limits for an active pool. Caller must have Operator role (1).
-    /// PRE: pool.state = Active, operator has role 1
-    /// POST: pool.min_stake and pool.max_stake updated
-    pub fn set_stake_limits(
+    /// Finalize the winners merkle root once every winner has been pushed.
+    /// The pushed stakes must add up exactly to the winning outcome's stake.
+    /// Caller must have Operator role (1).
+    pub fn finalize_winners_root(
         env: Env,
         operator: Address,
         pool_id: u64,
-        min_stake: i128,
-        max_stake: i128,
-    ) -> Result<(), PredifiError> {
+    ) -> Result<BytesN<32>, PredifiError> {
         Self::require_not_paused(&env);
         operator.require_auth();
         Self::require_role(&env, &operator, 1)?;
 
-        let pool_key = DataKey::Pool(pool_id);
-        let mut pool: Pool = env
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.state != MarketState::Resolved {
+            return Err(PredifiError::PoolNotResolved);
+        }
+
+        let draft_key = DataKey::WinnersDraft(pool_id);
+        let draft: WinnersDraft = env
             .storage()
             .persistent()
-            .get(&pool_key)
-            .expect("Pool not found");
+            .get(&draft_key)
+            .ok_or(PredifiError::WinnersSummaryMismatch)?;
 
-        if pool.state != MarketState::Active {
-            return Err(PredifiError::InvalidPoolState);
+        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+        let winning_stake = stakes.get(pool.outcome).unwrap_or(0);
+        if draft.leaves.is_empty()
+            || draft.stake_total != winning_stake
+            || draft.entitlement_total > pool.total_stake
+        {
+            return Err(PredifiError::WinnersSummaryMismatch);
         }
 
-        assert!(min_stake > 0, "min_stake must be greater than zero");
-        assert!(
-            max_stake == 0 || max_stake >= min_stake,
-            "max_stake must be zero (unlimited) or >= min_stake"
-        );
-
-        pool.min_stake = min_stake;
-        pool.max_stake = max_stake;
-
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::extend_persistent(&env, &pool_key);
+        let summary = WinnersSummary {
+            root: Self::merkle_root(&env, &draft.leaves),
+            leaf_count: draft.leaves.len(),
+            total_entitlement: draft.entitlement_total,
+        };
+        let summary_key = DataKey::WinnersSummary(pool_id);
+        env.storage().persistent().set(&summary_key, &summary);
+        Self::extend_persistent(&env, &summary_key);
+        env.storage().persistent().remove(&draft_key);
 
-        StakeLimitsUpdatedEvent {
+        WinnersRootPublishedEvent {
             pool_id,
-            operator,
-            min_stake,
-            max_stake,
+            root: summary.root.clone(),
+            leaf_count: summary.leaf_count,
+            total_entitlement: summary.total_entitlement,
         }
         .publish(&env);
 
-        Ok(())
+        let attestation = Self::build_settlement_attestation(&env, pool_id, &pool);
+        Self::publish_settlement_attestation(&env, attestation);
+
+        Ok(summary.root)
+    }
+
+    /// Returns the settlement attestation of a resolved pool for relayers.
+    pub fn get_settlement_attestation(
+        env: Env,
+        pool_id: u64,
+    ) -> Result<SettlementAttestation, PredifiError> {
+        let pool = Self::get_pool(env.clone(), pool_id);
+        if pool.state != MarketState::Resolved {
+            return Err(PredifiError::PoolNotResolved);
+        }
+        Ok(Self::build_settlement_attestation(&env, pool_id, &pool))
+    }
+
+    /// Re-emit the settlement attestation of a resolved pool. Permissionless,
+    /// so relayers that missed the original event can recover it.
+    pub fn attest_settlement(env: Env, pool_id: u64) -> Result<BytesN<32>, PredifiError> {
+        let attestation = Self::get_settlement_attestation(env.clone(), pool_id)?;
+        let digest = attestation.digest.clone();
+        Self::publish_settlement_attestation(&env, attestation);
+        Ok(digest)
+    }
+
+    /// Returns the finalized winners merkle summary of a pool, if published.
+    pub fn get_winners_summary(env: Env, pool_id: u64) -> Option<WinnersSummary> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WinnersSummary(pool_id))
+    }
+
+    /// Verify a merkle proof that `user` is entitled to `entitlement` in the
+    /// pool's published winners summary.
+    pub fn verify_winner_proof(
+        env: Env,
+        pool_id: u64,
+        user: Address,
+        entitlement: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let summary: Option<WinnersSummary> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WinnersSummary(pool_id));
+        let summary = match summary {
+            Some(s) => s,
+            None => return false,
+        };
+
+        let mut node = Self::winner_leaf(&env, &user, entitlement);
+        for sibling in proof.iter() {
+            node = Self::merkle_parent(&env, &node, &sibling);
+        }
+        node == summary.root
     }
 
     /// Get a paginated list of a user's predictions.
@@ -1697,22 +10858,76 @@ impl PredifiContract {
         results
     }
 
-    /// This function is optimized for markets with many outcomes (e.g., 32+ teams).
-    /// Instead of making N storage reads (one per outcome), it makes a single read.
-    ///
-    /// Returns a Vec of stakes where index corresponds to outcome index.
-    /// For example, stake[0] is the total amount bet on outcome 0.
+    /// Returns the full pool, including its description and metadata URL.
+    /// Panics if the pool does not exist.
     pub fn get_pool(env: Env, pool_id: u64) -> Pool {
         let pool_key = DataKey::Pool(pool_id);
-        let pool: Pool = env
+        let mut pool: Pool = env
             .storage()
             .persistent()
             .get(&pool_key)
             .expect("Pool not found");
         Self::extend_persistent(&env, &pool_key);
+        pool.total_stake += Self::stake_delta(&env, pool_id);
         pool
     }
 
+    /// Returns the fixed-size fields of a pool, without its description and
+    /// metadata URL, for cheaper reads by frontends and other contracts.
+    pub fn get_pool_summary(env: Env, pool_id: u64) -> Result<PoolSummary, PredifiError> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::extend_persistent(&env, &pool_key);
+        pool.total_stake += Self::stake_delta(&env, pool_id);
+        Ok(Self::summarize_pool(pool_id, pool))
+    }
+
+    /// Returns the number of pools ever created; pool ids run from 0 to
+    /// `get_pool_count() - 1`.
+    pub fn get_pool_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PoolIdCounter)
+            .unwrap_or(0)
+    }
+
+    /// Returns a page of pool summaries in creation order, starting at pool id
+    /// `offset`. At most `MAX_POOLS_PAGE_SIZE` pools are returned per call;
+    /// hidden pools are skipped, so a page may hold fewer.
+    pub fn get_pools(env: Env, offset: u64, limit: u32) -> Vec<PoolSummary> {
+        let count = Self::get_pool_count(env.clone());
+        let mut results = Vec::new(&env);
+        if offset >= count || limit == 0 {
+            return results;
+        }
+
+        let num_to_take = core::cmp::min(
+            core::cmp::min(limit, MAX_POOLS_PAGE_SIZE) as u64,
+            count - offset,
+        );
+        for pool_id in offset..offset + num_to_take {
+            if Self::is_pool_hidden(env.clone(), pool_id) {
+                continue;
+            }
+            let pool_key = DataKey::Pool(pool_id);
+            if let Some(mut pool) = env.storage().persistent().get::<_, Pool>(&pool_key) {
+                Self::extend_persistent(&env, &pool_key);
+                pool.total_stake += Self::stake_delta(&env, pool_id);
+                results.push_back(Self::summarize_pool(pool_id, pool));
+            }
+        }
+        results
+    }
+
+    /// This function is optimized for markets with many outcomes (e.g., 32+ teams).
+    /// Instead of making N storage reads (one per outcome), it makes a single read.
+    ///
+    /// Returns a Vec of stakes where index corresponds to outcome index.
+    /// For example, stake[0] is the total amount bet on outcome 0.
     pub fn get_pool_outcome_stakes(env: Env, pool_id: u64) -> Vec<i128> {
         let pool_key = DataKey::Pool(pool_id);
         let pool: Pool = env
@@ -1725,6 +10940,42 @@ impl PredifiContract {
         Self::get_outcome_stakes(&env, pool_id, pool.options_count)
     }
 
+    /// Implied probability of each outcome in bps: its stake over the pool's
+    /// total_stake, rounded down (the inverse of `PoolStats::current_odds`).
+    /// House liquidity counts toward total_stake only, so the entries sum to
+    /// less than 10_000 on seeded pools. LMSR pools report their share prices.
+    pub fn get_implied_odds(env: Env, pool_id: u64) -> Vec<u32> {
+        let pool = Self::get_pool(env.clone(), pool_id);
+        let mut odds = Vec::new(&env);
+
+        if let Some(state) = Self::lmsr_state(&env, pool_id) {
+            let max = state.shares.iter().max().unwrap_or(0);
+            let mut weights: Vec<i128> = Vec::new(&env);
+            let mut sum: i128 = 0;
+            for q in state.shares.iter() {
+                let weight = SafeMath::exp_wad((q - max).saturating_mul(safe_math::WAD) / state.b)
+                    .unwrap_or(0);
+                weights.push_back(weight);
+                sum += weight;
+            }
+            for weight in weights.iter() {
+                odds.push_back((weight * 10_000 / sum) as u32);
+            }
+            return odds;
+        }
+
+        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
+        for stake in stakes.iter() {
+            let bps = if pool.total_stake > 0 {
+                stake * 10_000 / pool.total_stake
+            } else {
+                0
+            };
+            odds.push_back(bps as u32);
+        }
+        odds
+    }
+
     /// Get a specific outcome's stake (backward compatible).
     /// For markets with many outcomes, consider using get_pool_outcome_stakes() instead.
     pub fn get_outcome_stake(env: Env, pool_id: u64, outcome: u32) -> i128 {
@@ -1748,7 +10999,8 @@ impl PredifiContract {
         stakes.get(outcome).unwrap_or(0)
     }
 
-    /// Get a paginated list of pool IDs by category.
+    /// Get a paginated list of pool IDs by category, newest first. Hidden
+    /// pools are skipped, so a page may hold fewer than `limit` ids.
     pub fn get_pools_by_category(env: Env, category: Symbol, offset: u32, limit: u32) -> Vec<u64> {
         let count_key = DataKey::CategoryPoolCount(category.clone());
         let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
@@ -1775,12 +11027,91 @@ impl PredifiContract {
                 .expect("index not found");
             Self::extend_persistent(&env, &index_key);
 
-            results.push_back(pool_id);
+            if !Self::is_pool_hidden(env.clone(), pool_id) {
+                results.push_back(pool_id);
+            }
+        }
+
+        results
+    }
+
+    /// Largest individual positions in a pool, for concentration monitoring.
+    /// Scans one page of bettor slots, `[offset, offset + limit)` (with
+    /// `limit` capped at `MAX_TOP_POSITIONS`), and returns those positions
+    /// grouped by outcome and sorted by amount descending. Callers merge pages
+    /// for a pool-wide ranking.
+    pub fn get_top_positions(env: Env, pool_id: u64, offset: u32, limit: u32) -> Vec<PositionView> {
+        let pool = Self::get_pool(env.clone(), pool_id);
+
+        let mut results = Vec::new(&env);
+        let participants: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ParticipantsCount(pool_id))
+            .unwrap_or(0);
+        if offset >= participants || limit == 0 {
+            return results;
+        }
+        let end = offset.saturating_add(core::cmp::min(limit, MAX_TOP_POSITIONS));
+        let end = core::cmp::min(end, participants);
+
+        let mut positions: Vec<PositionView> = Vec::new(&env);
+        for i in offset..end {
+            let user: Address = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::PoolBettor(pool_id, i))
+            {
+                Some(u) => u,
+                None => continue,
+            };
+            let prediction: Option<Prediction> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Prediction(user.clone(), pool_id));
+            if let Some(p) = prediction {
+                positions.push_back(PositionView {
+                    user,
+                    outcome: p.outcome,
+                    amount: p.amount,
+                });
+            }
+        }
+
+        for outcome in 0..pool.options_count {
+            // Insertion sort; the page is bounded by `MAX_TOP_POSITIONS`.
+            let mut bucket: Vec<PositionView> = Vec::new(&env);
+            for position in positions.iter() {
+                if position.outcome != outcome {
+                    continue;
+                }
+                let mut idx = bucket.len();
+                while idx > 0 && bucket.get_unchecked(idx - 1).amount < position.amount {
+                    idx -= 1;
+                }
+                bucket.insert(idx, position);
+            }
+            results.append(&bucket);
         }
 
         results
     }
 
+    /// Returns how a pool's escrow splits between house liquidity and bettor
+    /// stakes.
+    pub fn get_pool_liquidity(env: Env, pool_id: u64) -> Result<PoolLiquidity, PredifiError> {
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        Ok(PoolLiquidity {
+            house_liquidity: pool.initial_liquidity,
+            bettor_stake: Self::bettor_stake(&env, pool_id, &pool),
+            seed_per_outcome: Self::liquidity_seed(&pool),
+        })
+    }
+
     /// Get comprehensive stats for a pool.
     pub fn get_pool_stats(env: Env, pool_id: u64) -> PoolStats {
         let pool_key = DataKey::Pool(pool_id);
@@ -1835,6 +11166,16 @@ impl OracleCallback for PredifiContract {
         outcome: u32,
         proof: String,
     ) -> Result<(), PredifiError> {
+        Self::submit_oracle_result(env, oracle, pool_id, outcome, proof).map(|_| ())
+    }
+
+    fn submit_oracle_result(
+        env: Env,
+        oracle: Address,
+        pool_id: u64,
+        outcome: u32,
+        proof: String,
+    ) -> Result<bool, PredifiError> {
         Self::require_not_paused(&env);
         oracle.require_auth();
 
@@ -1850,72 +11191,62 @@ impl OracleCallback for PredifiContract {
             return Err(e);
         }
 
-        let pool_key = DataKey::Pool(pool_id);
-        let mut pool: Pool = env
+        // Oracle results are authoritative: a zero-stake outcome needs no
+        // confirmation.
+        Self::assert_not_settled(&env, pool_id);
+        let pool = Self::check_resolvable(&env, pool_id, outcome, true)?;
+
+        let quorum = Self::oracle_quorum(&env, pool_id);
+        let submissions_key = DataKey::OracleSubmissions(pool_id);
+        let mut submissions: Map<Address, u32> = env
             .storage()
             .persistent()
-            .get(&pool_key)
-            .expect("Pool not found");
-
-        assert!(!pool.resolved, "Pool already resolved");
-        assert!(!pool.canceled, "Cannot resolve a canceled pool");
-        if pool.state != MarketState::Active {
-            return Err(PredifiError::InvalidPoolState);
+            .get(&submissions_key)
+            .unwrap_or(Map::new(&env));
+        if submissions.contains_key(oracle.clone()) {
+            return Err(PredifiError::OracleAlreadySubmitted);
         }
-
-        let current_time = env.ledger().timestamp();
-        let config = Self::get_config(&env);
-
-        if current_time < pool.end_time.saturating_add(config.resolution_delay) {
-            return Err(PredifiError::ResolutionDelayNotMet);
+        let mut votes: u32 = 1;
+        for (_, submitted) in submissions.iter() {
+            if submitted == outcome {
+                votes += 1;
+            }
         }
 
-        // Validate: outcome must be within the valid options range
-        // Verify state transition validity (INV-2)
-        assert!(
-            outcome < pool.options_count
-                && Self::is_valid_state_transition(pool.state, MarketState::Resolved),
-            "outcome exceeds options_count or invalid state transition"
-        );
-
-        pool.state = MarketState::Resolved;
-        pool.resolved = true;
-        pool.outcome = outcome;
-
-        env.storage().persistent().set(&pool_key, &pool);
-        Self::extend_persistent(&env, &pool_key);
-
-        // Retrieve winning-outcome stake for the diagnostic event using optimized batch storage
-        let stakes = Self::get_outcome_stakes(&env, pool_id, pool.options_count);
-        let winning_stake: i128 = stakes.get(outcome).unwrap_or(0);
-
-        OracleResolvedEvent {
+        OracleResultSubmittedEvent {
             pool_id,
             oracle: oracle.clone(),
             outcome,
-            proof,
+            votes,
+            quorum,
         }
         .publish(&env);
 
-        // Emit standard resolved event to maintain compatibility
-        PoolResolvedEvent {
-            pool_id,
-            operator: oracle,
-            outcome,
+        if votes < quorum {
+            submissions.set(oracle, outcome);
+            env.storage()
+                .persistent()
+                .set(&submissions_key, &submissions);
+            Self::extend_persistent(&env, &submissions_key);
+            return Ok(false);
         }
-        .publish(&env);
+        env.storage().persistent().remove(&submissions_key);
 
-        // 🟢 INFO: enriched diagnostics alongside the standard resolved event.
-        PoolResolvedDiagEvent {
+        OracleResolvedEvent {
             pool_id,
+            oracle: oracle.clone(),
             outcome,
-            total_stake: pool.total_stake,
-            winning_stake,
-            timestamp: env.ledger().timestamp(),
+            proof,
         }
         .publish(&env);
 
-        Ok(())
+        // Emits the standard resolved event to maintain compatibility. The
+        // proof is unbounded, so it stays in `OracleResolvedEvent` and is not
+        // recorded as the settlement note.
+        let note = String::from_str(&env, "");
+        Self::finalize_resolution(&env, pool_id, pool, outcome, oracle, note);
+
+        Ok(true)
     }
 }
 
@@ -0,0 +1,266 @@
+//! # Pool Migration
+//!
+//! Positions of a pool can be moved to a replacement pool, but only for
+//! users who consented to the move.
+
+use super::*;
+
+/// Storage keys of pool migration.
+#[contracttype]
+#[derive(Clone)]
+pub enum MigrationKey {
+    /// Pool a user agreed to have their position moved to when the source
+    /// pool is migrated: MigrationConsent(user, from_pool) -> to_pool.
+    MigrationConsent(Address, u64),
+}
+
+#[contractevent(topics = ["migration_consent"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationConsentEvent {
+    pub user: Address,
+    pub from_pool: u64,
+    pub to_pool: Option<u64>,
+}
+
+#[contractevent(topics = ["position_migrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionMigratedEvent {
+    pub from_pool: u64,
+    pub to_pool: u64,
+    pub user: Address,
+    pub outcome: u32,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["pool_migrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolMigratedEvent {
+    pub from_pool: u64,
+    pub to_pool: u64,
+    pub operator: Address,
+    /// Creator liquidity moved.
+    pub liquidity: i128,
+    /// Positions moved in this call.
+    pub positions: u32,
+    /// Total stake moved, liquidity included.
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Agree to have the caller's position in `from_pool` moved to `to_pool`
+    /// if an operator migrates the market (see `migrate_pool`). `None`
+    /// withdraws the consent.
+    pub fn set_migration_consent(
+        env: Env,
+        user: Address,
+        from_pool: u64,
+        to_pool: Option<u64>,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        user.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Prediction(user.clone(), from_pool))
+        {
+            return Err(PredifiError::PredictionNotFound);
+        }
+
+        let key = MigrationKey::MigrationConsent(user.clone(), from_pool);
+        match to_pool {
+            Some(to_pool) => {
+                if to_pool == from_pool {
+                    return Err(PredifiError::InvalidPoolState);
+                }
+                env.storage().persistent().set(&key, &to_pool);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        MigrationConsentEvent {
+            user,
+            from_pool,
+            to_pool,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the pool a user agreed to have their `from_pool` position
+    /// moved to, if any.
+    pub fn get_migration_consent(env: Env, user: Address, from_pool: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&MigrationKey::MigrationConsent(user, from_pool))
+    }
+
+    /// Move a re-listed market's stake from `from_pool` to `to_pool` in one
+    /// transaction. The creator liquidity of `from_pool` moves on the first
+    /// call; then each of `users` whose consent names `to_pool` has their
+    /// position moved on the same outcome. Users without consent, with a
+    /// multi-outcome bet, already holding a position in `to_pool` or not
+    /// allowed to bet on it are skipped. Stake caps of `to_pool` are not re-checked.
+    /// Both pools must be Active, not awaiting resolution, share the token,
+    /// options count and creator, and not be LMSR pools.
+    /// Caller must have Operator role (1). At most `MAX_BATCH_SIZE` users
+    /// are processed per call; returns the number of positions moved.
+    /// POST: INV-1 holds on both pools; their escrows move by the same amount
+    pub fn migrate_pool(
+        env: Env,
+        operator: Address,
+        from_pool: u64,
+        to_pool: u64,
+        users: Vec<Address>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let from_key = DataKey::Pool(from_pool);
+        let mut from: Pool = env
+            .storage()
+            .persistent()
+            .get(&from_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        let to_key = DataKey::Pool(to_pool);
+        let mut to: Pool = env
+            .storage()
+            .persistent()
+            .get(&to_key)
+            .ok_or(PredifiError::PoolNotFound)?;
+        Self::flush_stake_delta(&env, from_pool, &mut from);
+        Self::flush_stake_delta(&env, to_pool, &mut to);
+        if from_pool == to_pool
+            || from.state != MarketState::Active
+            || to.state != MarketState::Active
+            || from.token != to.token
+            || from.options_count != to.options_count
+            || Self::lmsr_state(&env, from_pool).is_some()
+            || Self::lmsr_state(&env, to_pool).is_some()
+            || Self::is_resolution_locked(&env, from_pool)
+            || Self::is_resolution_locked(&env, to_pool)
+            || Self::is_draft(&env, to_pool)
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+        if from.creator != to.creator {
+            return Err(PredifiError::Unauthorized);
+        }
+
+        Self::enter_reentrancy_guard(&env);
+
+        // --- EFFECTS ---
+
+        let liquidity = from.initial_liquidity;
+        from.initial_liquidity = 0;
+        to.initial_liquidity = to
+            .initial_liquidity
+            .checked_add(liquidity)
+            .expect("overflow");
+        let mut moved = liquidity;
+
+        let mut positions: u32 = 0;
+        let processed = core::cmp::min(users.len(), MAX_BATCH_SIZE);
+        for i in 0..processed {
+            let user = users.get(i).unwrap();
+            let consent_key = MigrationKey::MigrationConsent(user.clone(), from_pool);
+            let consent: Option<u64> = env.storage().persistent().get(&consent_key);
+            if consent != Some(to_pool) {
+                continue;
+            }
+            let from_pred_key = DataKey::Prediction(user.clone(), from_pool);
+            let to_pred_key = DataKey::Prediction(user.clone(), to_pool);
+            let prediction: Option<Prediction> = env.storage().persistent().get(&from_pred_key);
+            let Some(prediction) = prediction else {
+                continue;
+            };
+            if Self::bet_legs(&env, &user, from_pool).is_some()
+                || env.storage().persistent().has(&to_pred_key)
+                || !Self::is_bettor_allowed(&env, to_pool, &user)
+            {
+                continue;
+            }
+
+            env.storage().persistent().remove(&consent_key);
+            env.storage().persistent().remove(&from_pred_key);
+            Self::remove_pool_bettor(&env, from_pool, &user);
+            Self::remove_user_prediction_index(&env, &user, from_pool);
+            Self::update_outcome_stake(
+                &env,
+                from_pool,
+                prediction.outcome,
+                -prediction.amount,
+                from.options_count,
+            );
+
+            env.storage().persistent().set(&to_pred_key, &prediction);
+            Self::extend_persistent(&env, &to_pred_key);
+            let pc_key = DataKey::ParticipantsCount(to_pool);
+            let pc: u32 = env.storage().persistent().get(&pc_key).unwrap_or(0);
+            env.storage().persistent().set(&pc_key, &(pc + 1));
+            Self::extend_persistent(&env, &pc_key);
+            let bettor_key = DataKey::PoolBettor(to_pool, pc);
+            env.storage().persistent().set(&bettor_key, &user);
+            Self::extend_persistent(&env, &bettor_key);
+            let count_key = DataKey::UserPredictionCount(user.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            let index_key = DataKey::UserPredictionIndex(user.clone(), count);
+            env.storage().persistent().set(&index_key, &to_pool);
+            Self::extend_persistent(&env, &index_key);
+            env.storage().persistent().set(&count_key, &(count + 1));
+            Self::extend_persistent(&env, &count_key);
+            Self::update_outcome_stake(
+                &env,
+                to_pool,
+                prediction.outcome,
+                prediction.amount,
+                to.options_count,
+            );
+
+            moved += prediction.amount;
+            positions += 1;
+            PositionMigratedEvent {
+                from_pool,
+                to_pool,
+                user,
+                outcome: prediction.outcome,
+                amount: prediction.amount,
+            }
+            .publish(&env);
+        }
+
+        from.total_stake = from.total_stake.checked_sub(moved).expect("underflow");
+        to.total_stake = to.total_stake.checked_add(moved).expect("overflow");
+        env.storage().persistent().set(&from_key, &from);
+        Self::extend_persistent(&env, &from_key);
+        env.storage().persistent().set(&to_key, &to);
+        Self::extend_persistent(&env, &to_key);
+        if moved > 0 {
+            Self::debit_pool_escrow(&env, from_pool, &from, moved, 0);
+            Self::credit_pool_escrow(&env, to_pool, moved);
+        }
+
+        // --- INTERACTIONS ---
+
+        // The source pool may no longer cover its deposit within the share cap.
+        Self::recall_yield_deposit(&env, from_pool, &from);
+
+        Self::exit_reentrancy_guard(&env);
+
+        PoolMigratedEvent {
+            from_pool,
+            to_pool,
+            operator,
+            liquidity,
+            positions,
+            amount: moved,
+        }
+        .publish(&env);
+
+        Self::check_pool_invariants(&env, from_pool);
+        Self::check_pool_invariants(&env, to_pool);
+        Ok(positions)
+    }
+}
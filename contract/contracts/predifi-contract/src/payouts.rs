@@ -0,0 +1,297 @@
+//! # Held Payouts
+//!
+//! Claims always settle, but a payout is not always delivered: the compliance
+//! gate holds a flagged claimant's payout for admin review, blocked addresses
+//! are not paid, and clawback-enabled assets can refuse the transfer. All
+//! three land in one ledger entry per (user, pool), tagged with a
+//! `HoldReason`. Admins release or forfeit any entry; entries not awaiting
+//! compliance review can also be retried by anyone with `retry_payout`.
+
+use super::*;
+
+/// Storage keys of the held payout ledger.
+#[contracttype]
+#[derive(Clone)]
+pub enum PayoutKey {
+    /// Payout settled but not delivered: Held(user, pool_id) -> `HeldPayout`.
+    Held(Address, u64),
+}
+
+/// Why a payout was not delivered, from least to most restrictive. An entry
+/// keeps the most restrictive reason of the claims it accumulates.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HoldReason {
+    /// The token refused the transfer (frozen or deauthorized trustline).
+    TransferRefused = 0,
+    /// The claimant was on the global blocklist.
+    Blocked = 1,
+    /// The claimant is flagged at the compliance gate; only an admin may
+    /// release or forfeit the payout.
+    Compliance = 2,
+}
+
+/// A settled payout that has not been delivered to the claimant.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldPayout {
+    pub token: Address,
+    pub amount: i128,
+    /// Timestamp of the (first) claim that was held.
+    pub held_at: u64,
+    pub reason: HoldReason,
+}
+
+/// 🟡 MEDIUM ALERT — a flagged claimant's payout was moved to escrow instead
+/// of being transferred.  Requires compliance review.
+#[contractevent(topics = ["payout_held"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutHeldEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    /// Total held for (user, pool) after this claim.
+    pub total_held: i128,
+}
+
+/// 🟡 MEDIUM — a payout was not delivered because the claimant is blocked or
+/// the token refused the transfer (clawback-enabled asset with a frozen or
+/// deauthorized trustline); see `retry_payout`.
+#[contractevent(topics = ["payout_deferred"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutDeferredEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    /// Total held for (user, pool) after this claim.
+    pub total_pending: i128,
+    pub reason: HoldReason,
+}
+
+#[contractevent(topics = ["pending_payout_released"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPayoutReleasedEvent {
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["held_payout_released"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldPayoutReleasedEvent {
+    pub admin: Address,
+    pub pool_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["held_payout_forfeited"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldPayoutForfeitedEvent {
+    pub admin: Address,
+    pub pool_id: u64,
+    pub user: Address,
+    pub amount: i128,
+    pub recipient: Address,
+}
+
+impl PredifiContract {
+    /// Add an undelivered payout to the (user, pool) ledger entry. Returns
+    /// the total now held for (user, pool).
+    pub(crate) fn hold_payout(
+        env: &Env,
+        user: &Address,
+        pool_id: u64,
+        token: &Address,
+        amount: i128,
+        reason: HoldReason,
+    ) -> i128 {
+        let key = PayoutKey::Held(user.clone(), pool_id);
+        let held = match env.storage().persistent().get::<_, HeldPayout>(&key) {
+            Some(mut h) => {
+                h.amount = h.amount.checked_add(amount).expect("overflow");
+                if reason as u32 > h.reason as u32 {
+                    h.reason = reason;
+                }
+                h
+            }
+            None => HeldPayout {
+                token: token.clone(),
+                amount,
+                held_at: env.ledger().timestamp(),
+                reason,
+            },
+        };
+        env.storage().persistent().set(&key, &held);
+        Self::extend_persistent(env, &key);
+        held.amount
+    }
+
+    /// Remove and return the ledger entry of (user, pool).
+    fn take_held_payout(env: &Env, user: &Address, pool_id: u64) -> Option<HeldPayout> {
+        let key = PayoutKey::Held(user.clone(), pool_id);
+        let held = env.storage().persistent().get(&key)?;
+        env.storage().persistent().remove(&key);
+        Some(held)
+    }
+
+    /// Admin gate shared by `release_held_payout` and `forfeit_held_payout`.
+    fn require_payout_admin(
+        env: &Env,
+        admin: &Address,
+        operation: &str,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(env, admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin.clone(),
+                operation: Symbol::new(env, operation),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(env);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Returns the payout settled but not delivered to (user, pool), if any.
+    pub fn get_held_payout(env: Env, user: Address, pool_id: u64) -> Option<HeldPayout> {
+        env.storage()
+            .persistent()
+            .get(&PayoutKey::Held(user, pool_id))
+    }
+
+    /// Retry delivering a payout that was not delivered at claim time, e.g.
+    /// once the user's trustline is authorized again or the user is
+    /// unblocked. Callable by anyone; funds only go to `user`. Returns the
+    /// amount delivered.
+    ///
+    /// # Errors
+    /// - `PayoutNotHeld` if nothing is held for (user, pool).
+    /// - `Unauthorized` while `user` is blocked, or if the payout awaits
+    ///   compliance review.
+    /// - `PayoutTransferFailed` if the token still refuses the transfer.
+    pub fn retry_payout(env: Env, user: Address, pool_id: u64) -> Result<i128, PredifiError> {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        Self::require_not_blocked(&env, &user, "retry_payout")?;
+        let pending = Self::get_held_payout(env.clone(), user.clone(), pool_id)
+            .ok_or(PredifiError::PayoutNotHeld)?;
+        if pending.reason == HoldReason::Compliance {
+            return Err(PredifiError::Unauthorized);
+        }
+
+        Self::enter_reentrancy_guard(&env);
+        env.storage()
+            .persistent()
+            .remove(&PayoutKey::Held(user.clone(), pool_id));
+
+        let token_client = token::Client::new(&env, &pending.token);
+        if !matches!(
+            token_client.try_transfer(&env.current_contract_address(), &user, &pending.amount),
+            Ok(Ok(()))
+        ) {
+            Self::exit_reentrancy_guard(&env);
+            return Err(PredifiError::PayoutTransferFailed);
+        }
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &pending.token,
+            LedgerDirection::Out,
+            pending.amount,
+            "payout_retry",
+        );
+        Self::exit_reentrancy_guard(&env);
+
+        PendingPayoutReleasedEvent {
+            pool_id,
+            user,
+            amount: pending.amount,
+        }
+        .publish(&env);
+        Ok(pending.amount)
+    }
+
+    /// Release a held payout to the claimant after review.
+    /// Caller must have Admin role (0).
+    pub fn release_held_payout(
+        env: Env,
+        admin: Address,
+        user: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        Self::require_payout_admin(&env, &admin, "release_held_payout")?;
+        let held =
+            Self::take_held_payout(&env, &user, pool_id).ok_or(PredifiError::PayoutNotHeld)?;
+
+        let token_client = token::Client::new(&env, &held.token);
+        token_client.transfer(&env.current_contract_address(), &user, &held.amount);
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &user,
+            &held.token,
+            LedgerDirection::Out,
+            held.amount,
+            "held_release",
+        );
+
+        HeldPayoutReleasedEvent {
+            admin,
+            pool_id,
+            user,
+            amount: held.amount,
+        }
+        .publish(&env);
+        Ok(held.amount)
+    }
+
+    /// Forfeit a held payout to the treasury after review.
+    /// Caller must have Admin role (0).
+    pub fn forfeit_held_payout(
+        env: Env,
+        admin: Address,
+        user: Address,
+        pool_id: u64,
+    ) -> Result<i128, PredifiError> {
+        Self::require_payout_admin(&env, &admin, "forfeit_held_payout")?;
+        let held =
+            Self::take_held_payout(&env, &user, pool_id).ok_or(PredifiError::PayoutNotHeld)?;
+
+        let config = Self::load_config(&env);
+        let token_client = token::Client::new(&env, &held.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &config.treasury,
+            &held.amount,
+        );
+        Self::record_ledger_entry(
+            &env,
+            Some(pool_id),
+            &config.treasury,
+            &held.token,
+            LedgerDirection::Out,
+            held.amount,
+            "held_forfeit",
+        );
+
+        HeldPayoutForfeitedEvent {
+            admin,
+            pool_id,
+            user,
+            amount: held.amount,
+            recipient: config.treasury,
+        }
+        .publish(&env);
+        Ok(held.amount)
+    }
+}
@@ -0,0 +1,132 @@
+//! # Price Markets
+//!
+//! Scalar pools tagged with a Reflector price feed resolve permissionlessly
+//! from the feed's round at `end_time + delay` (see `reflector`).
+
+use super::*;
+
+/// Storage keys of price markets.
+#[contracttype]
+#[derive(Clone)]
+pub enum PriceMarketKey {
+    /// Reflector price feed a scalar pool resolves from (`PriceMarket`).
+    PriceMarket(u64),
+}
+
+#[contractevent(topics = ["price_market_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceMarketSetEvent {
+    pub pool_id: u64,
+    pub feed: Address,
+    pub asset: ReflectorAsset,
+    pub delay: u64,
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Tag a scalar pool as a price market resolved from a Reflector-compatible
+    /// feed: once `end_time + delay` has passed, anyone may call
+    /// `resolve_from_price_feed`. The scalar buckets must be expressed in the
+    /// feed's fixed-point units. Must be set before the pool ends. Caller
+    /// must have Operator role (1).
+    pub fn set_price_market(
+        env: Env,
+        operator: Address,
+        pool_id: u64,
+        feed: Address,
+        asset: ReflectorAsset,
+        delay: u64,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+        Self::require_role(&env, &operator, 1)?;
+
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.state != MarketState::Active
+            || env.ledger().timestamp() >= pool.end_time
+            || Self::get_scalar_buckets(env.clone(), pool_id).is_empty()
+        {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = PriceMarketKey::PriceMarket(pool_id);
+        env.storage().persistent().set(
+            &key,
+            &PriceMarket {
+                feed: feed.clone(),
+                asset: asset.clone(),
+                delay,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        PriceMarketSetEvent {
+            pool_id,
+            feed,
+            asset,
+            delay,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the price feed a pool resolves from, if it is a price market.
+    pub fn get_price_market(env: Env, pool_id: u64) -> Option<PriceMarket> {
+        env.storage()
+            .persistent()
+            .get(&PriceMarketKey::PriceMarket(pool_id))
+    }
+
+    /// Resolve a price market from the feed round at `end_time + delay`: the
+    /// scalar bucket containing the price wins, even if nobody staked on it.
+    /// Callable by anyone. Emits `OracleResolvedEvent` with the round as
+    /// proof and returns the outcome.
+    ///
+    /// # Errors
+    /// - `PriceConditionNotSet` if the pool is not a price market.
+    /// - `ResolutionDelayNotMet` before `end_time + delay`.
+    /// - `PriceFeedNotFound` / `PriceDataInvalid` if the feed has no usable
+    ///   round; `InvalidOutcome` if no bucket contains the price.
+    pub fn resolve_from_price_feed(env: Env, pool_id: u64) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        let market = Self::get_price_market(env.clone(), pool_id)
+            .ok_or(PredifiError::PriceConditionNotSet)?;
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+
+        let read_at = pool.end_time.saturating_add(market.delay);
+        let now = env.ledger().timestamp();
+        if now < read_at {
+            return Err(Self::error_detail(
+                &env,
+                PredifiError::ResolutionDelayNotMet,
+                Some(pool_id),
+                read_at as i128,
+                now as i128,
+            ));
+        }
+        let round = reflector::read_round(&env, &market, read_at)?;
+        let buckets = Self::get_scalar_buckets(env.clone(), pool_id);
+        let outcome =
+            reflector::bucket_for(&buckets, round.price).ok_or(PredifiError::InvalidOutcome)?;
+        let pool = Self::check_resolvable(&env, pool_id, outcome, true)?;
+
+        OracleResolvedEvent {
+            pool_id,
+            oracle: market.feed.clone(),
+            outcome,
+            proof: reflector::round_proof(&env, &round),
+        }
+        .publish(&env);
+        let note = String::from_str(&env, "");
+        Self::finalize_resolution(&env, pool_id, pool, outcome, market.feed, note);
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,150 @@
+//! # Private Pools
+//!
+//! A pool creator can restrict betting on their pool to an allowlist.
+
+use super::*;
+
+/// Storage keys of private pools.
+#[contracttype]
+#[derive(Clone)]
+pub enum PrivatePoolKey {
+    /// Set while a pool only accepts bets from its allowlist.
+    PrivatePool(u64),
+    /// Bettor allowlist of a private pool: AllowedBettor(pool_id, user).
+    AllowedBettor(u64, Address),
+}
+
+#[contractevent(topics = ["pool_privacy_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolPrivacySetEvent {
+    pub pool_id: u64,
+    pub private: bool,
+}
+
+#[contractevent(topics = ["allowed_bettor_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowedBettorUpdateEvent {
+    pub pool_id: u64,
+    pub user: Address,
+    pub allowed: bool,
+}
+
+impl PredifiContract {
+    /// True unless the pool is private and `user` is not on its allowlist.
+    pub(crate) fn is_bettor_allowed(env: &Env, pool_id: u64, user: &Address) -> bool {
+        !env.storage()
+            .persistent()
+            .has(&PrivatePoolKey::PrivatePool(pool_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&PrivatePoolKey::AllowedBettor(pool_id, user.clone()))
+    }
+
+    pub(crate) fn set_allowed_bettor(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        user: Address,
+        allowed: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+
+        let key = PrivatePoolKey::AllowedBettor(pool_id, user.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        AllowedBettorUpdateEvent {
+            pool_id,
+            user,
+            allowed,
+        }
+        .publish(&env);
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Restrict betting on a pool to its allowlist (see `add_allowed_bettor`),
+    /// e.g. for invite-only or compliance-restricted markets. Positions
+    /// already placed are unaffected. Only the pool creator may call.
+    pub fn set_pool_private(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        private: bool,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        creator.require_auth();
+        let pool: Pool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(PredifiError::PoolNotFound)?;
+        if pool.creator != creator {
+            return Err(PredifiError::Unauthorized);
+        }
+        if pool.state != MarketState::Active {
+            return Err(PredifiError::InvalidPoolState);
+        }
+
+        let key = PrivatePoolKey::PrivatePool(pool_id);
+        if private {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        PoolPrivacySetEvent { pool_id, private }.publish(&env);
+        Ok(())
+    }
+
+    /// Returns true if betting on a pool is restricted to its allowlist.
+    pub fn is_pool_private(env: Env, pool_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&PrivatePoolKey::PrivatePool(pool_id))
+    }
+
+    /// Allow `user` to bet on a private pool. Only the pool creator may call.
+    pub fn add_allowed_bettor(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        user: Address,
+    ) -> Result<(), PredifiError> {
+        Self::set_allowed_bettor(env, creator, pool_id, user, true)
+    }
+
+    /// Remove `user` from a private pool's allowlist; their existing position
+    /// stays. Only the pool creator may call.
+    pub fn remove_allowed_bettor(
+        env: Env,
+        creator: Address,
+        pool_id: u64,
+        user: Address,
+    ) -> Result<(), PredifiError> {
+        Self::set_allowed_bettor(env, creator, pool_id, user, false)
+    }
+
+    /// Returns true if `user` may bet on the pool: always for public pools,
+    /// only if allowlisted for private ones.
+    pub fn is_allowed_bettor(env: Env, pool_id: u64, user: Address) -> bool {
+        Self::is_bettor_allowed(&env, pool_id, &user)
+    }
+}
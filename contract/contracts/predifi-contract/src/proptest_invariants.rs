@@ -0,0 +1,225 @@
+//! # Property-Based Invariant Harness
+//!
+//! Drives random sequences of create / bet / resolve / cancel / claim
+//! operations against a fresh market and checks the protocol invariants
+//! (INV-1..INV-8, see `lib.rs`) after every step. Failing sequences are
+//! shrunk to a minimal reproduction by proptest.
+//!
+//! The runner uses a fixed RNG so results are reproducible across machines.
+//! Gated behind the `proptest` feature and never part of the wasm build:
+//!
+//! ```text
+//! cargo test -p predifi-contract --features proptest proptest_invariants
+//! ```
+
+extern crate std;
+
+use crate::test_utils::MarketTestContext;
+use crate::MarketState;
+use proptest::prelude::*;
+use proptest::test_runner::{Config, RngAlgorithm, TestCaseError, TestRng, TestRunner};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use std::vec::Vec;
+
+const USERS: usize = 3;
+const MAX_POOLS: usize = 4;
+const MAX_OPTIONS: u32 = 4;
+const CASES: u32 = 64;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Create {
+        options: u32,
+        duration: u64,
+        liquidity: i128,
+    },
+    Bet {
+        user: usize,
+        pool: usize,
+        outcome: u32,
+        amount: i128,
+    },
+    Advance {
+        secs: u64,
+    },
+    Resolve {
+        pool: usize,
+        outcome: u32,
+    },
+    Cancel {
+        pool: usize,
+    },
+    Claim {
+        user: usize,
+        pool: usize,
+    },
+    SetFee {
+        bps: u32,
+    },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (2u32..=MAX_OPTIONS, 0u64..10_000, 0i128..500).prop_map(
+            |(options, duration, liquidity)| Op::Create {
+                options,
+                duration,
+                liquidity,
+            }
+        ),
+        (0..USERS, 0..MAX_POOLS, 0..MAX_OPTIONS, -10i128..500).prop_map(
+            |(user, pool, outcome, amount)| Op::Bet {
+                user,
+                pool,
+                outcome,
+                amount,
+            }
+        ),
+        (0u64..5_000).prop_map(|secs| Op::Advance { secs }),
+        (0..MAX_POOLS, 0..MAX_OPTIONS).prop_map(|(pool, outcome)| Op::Resolve { pool, outcome }),
+        (0..MAX_POOLS).prop_map(|pool| Op::Cancel { pool }),
+        (0..USERS, 0..MAX_POOLS).prop_map(|(user, pool)| Op::Claim { user, pool }),
+        (0u32..20_000).prop_map(|bps| Op::SetFee { bps }),
+    ]
+}
+
+/// Run one operation sequence, checking every invariant after each step.
+fn run_sequence(ops: &[Op]) -> Result<(), TestCaseError> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let market = MarketTestContext::setup(&env);
+    let client = &market.client;
+    let creator = Address::generate(&env);
+    let users: Vec<Address> = (0..USERS).map(|_| Address::generate(&env)).collect();
+    let mut pools: Vec<(u64, MarketState)> = Vec::new();
+
+    for op in ops {
+        match *op {
+            Op::Create {
+                options,
+                duration,
+                liquidity,
+            } => {
+                if pools.len() >= MAX_POOLS {
+                    continue;
+                }
+                if liquidity > 0 {
+                    market.token.mint(&creator, liquidity);
+                }
+                let end_time = env.ledger().timestamp() + duration;
+                let res = client.try_create_pool(
+                    &creator,
+                    &end_time,
+                    &market.token.token_address,
+                    &options,
+                    &String::from_str(&env, "Property Pool"),
+                    &String::from_str(&env, "ipfs://property"),
+                    &1i128,
+                    &0i128,
+                    &liquidity,
+                    &symbol_short!("Tech"),
+                );
+                // INV-8: pools must end in the future (at least MIN_POOL_DURATION).
+                prop_assert_eq!(res.is_ok(), duration >= 3_600);
+                if let Ok(Ok(pool_id)) = res {
+                    pools.push((pool_id, MarketState::Active));
+                }
+            }
+            Op::Bet {
+                user,
+                pool,
+                outcome,
+                amount,
+            } => {
+                let Some(&(pool_id, _)) = pools.get(pool) else {
+                    continue;
+                };
+                if amount > 0 {
+                    market.token.mint(&users[user], amount);
+                }
+                let res = client.try_place_prediction(&users[user], &pool_id, &amount, &outcome);
+                // INV-7: zero or negative stakes are never accepted.
+                if amount <= 0 {
+                    prop_assert!(res.is_err());
+                }
+            }
+            Op::Advance { secs } => {
+                env.ledger().with_mut(|li| li.timestamp += secs);
+            }
+            Op::Resolve { pool, outcome } => {
+                if let Some(&(pool_id, _)) = pools.get(pool) {
+                    let _ = client.try_resolve_pool(&market.operator, &pool_id, &outcome);
+                }
+            }
+            Op::Cancel { pool } => {
+                if let Some(&(pool_id, _)) = pools.get(pool) {
+                    let _ = client.try_cancel_pool(&market.operator, &pool_id);
+                }
+            }
+            Op::Claim { user, pool } => {
+                let Some(&(pool_id, _)) = pools.get(pool) else {
+                    continue;
+                };
+                if let Ok(Ok(amount)) = client.try_claim_winnings(&users[user], &pool_id) {
+                    // INV-3: a completed claim cannot be repeated.
+                    if amount > 0 {
+                        prop_assert!(client.try_claim_winnings(&users[user], &pool_id).is_err());
+                    }
+                }
+            }
+            Op::SetFee { bps } => {
+                let res = client.try_set_fee_bps(&market.admin, &bps);
+                // INV-6: fee_bps never exceeds 100%.
+                if bps > 10_000 {
+                    prop_assert!(res.is_err());
+                }
+            }
+        }
+
+        let mut unclaimed_total: i128 = 0;
+        for (pool_id, last_state) in pools.iter_mut() {
+            let pool_id = *pool_id;
+            let pool = client.get_pool(&pool_id);
+            let stakes = client.get_pool_outcome_stakes(&pool_id);
+            let mut bettor_stake: i128 = 0;
+            for stake in stakes.iter() {
+                prop_assert!(stake >= 0);
+                bettor_stake += stake;
+            }
+            // INV-1: escrow = house liquidity + Σ outcome stakes.
+            prop_assert_eq!(pool.total_stake, pool.initial_liquidity + bettor_stake);
+
+            // INV-2: settled pools never change state again.
+            if *last_state != MarketState::Active {
+                prop_assert_eq!(pool.state, *last_state);
+            }
+            *last_state = pool.state;
+
+            // INV-4 / INV-5: payouts never exceed the pool's escrow.
+            let unclaimed = client.get_pool_unclaimed(&pool_id);
+            prop_assert!(unclaimed >= 0 && unclaimed <= pool.total_stake);
+            unclaimed_total += unclaimed;
+        }
+        // Solvency: the contract holds exactly what is still owed.
+        prop_assert_eq!(market.token.token.balance(&client.address), unclaimed_total);
+    }
+    Ok(())
+}
+
+#[test]
+fn invariants_hold_for_random_operation_sequences() {
+    let config = Config {
+        cases: CASES,
+        ..Config::default()
+    };
+    let mut runner =
+        TestRunner::new_with_rng(config, TestRng::deterministic_rng(RngAlgorithm::ChaCha));
+    let strategy = proptest::collection::vec(op_strategy(), 1..40);
+    if let Err(e) = runner.run(&strategy, |ops| run_sequence(&ops)) {
+        panic!("{}", e);
+    }
+}
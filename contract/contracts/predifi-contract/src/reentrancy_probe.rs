@@ -0,0 +1,179 @@
+//! # Reentrancy Probe
+//!
+//! A hostile token whose `transfer` calls back into PrediFi while a payout is
+//! in flight. Every token-moving path writes its storage effects before the
+//! transfer (effects-then-interactions) and holds the reentrancy guard across
+//! it, so the re-entrant call must fail and the victim must be paid exactly
+//! once — with or without `HasClaimed` being written first.
+//!
+//! Gated behind the `reentrancy-probe` feature and never part of the wasm
+//! build:
+//!
+//! ```text
+//! cargo test -p predifi-contract --features reentrancy-probe reentrancy_probe
+//! ```
+
+use crate::test_utils::MarketTestContext;
+use crate::{CreatePoolParams, Mechanism, PredifiError};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, IntoVal, MuxedAddress, String, Symbol, Val, Vec,
+};
+
+/// Token that re-enters `claim_winnings(victim, pool_id)` whenever the
+/// armed PrediFi contract pays out, and counts how often that succeeded.
+#[contract]
+pub struct ProbeToken;
+
+#[contractimpl]
+impl ProbeToken {
+    pub fn arm(env: Env, predifi: Address, victim: Address, pool_id: u64) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("target"), &(predifi, victim, pool_id));
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    /// Read by PrediFi when the token is whitelisted.
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        let to = to.address();
+        Self::mint(env.clone(), from.clone(), -amount);
+        Self::mint(env.clone(), to, amount);
+
+        let target: Option<(Address, Address, u64)> =
+            env.storage().instance().get(&symbol_short!("target"));
+        let Some((predifi, victim, pool_id)) = target else {
+            return;
+        };
+        if from != predifi {
+            return;
+        }
+        let attempts: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("attempts"))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("attempts"), &(attempts + 1));
+        let result = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &predifi,
+            &Symbol::new(&env, "claim_winnings"),
+            soroban_sdk::vec![&env, victim.into_val(&env), pool_id.into_val(&env)],
+        );
+        if matches!(result, Ok(Ok(_))) {
+            let reentered: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("reentered"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("reentered"), &(reentered + 1));
+        }
+    }
+
+    /// Returns (re-entry attempts, successful re-entries).
+    pub fn probe_stats(env: Env) -> (u32, u32) {
+        let instance = env.storage().instance();
+        (
+            instance.get(&symbol_short!("attempts")).unwrap_or(0),
+            instance.get(&symbol_short!("reentered")).unwrap_or(0),
+        )
+    }
+}
+
+fn probe_pool(env: &Env, market: &MarketTestContext) -> (ProbeTokenClient<'static>, u64) {
+    let probe_id = env.register(ProbeToken, ());
+    let probe = ProbeTokenClient::new(env, &probe_id);
+    market
+        .client
+        .add_token_to_whitelist(&market.admin, &probe_id);
+
+    let creator = Address::generate(env);
+    let pool_id = market.client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            end_time: 100_000,
+            token: probe_id,
+            options_count: 2,
+            description: String::from_str(env, "Probe Pool"),
+            metadata_url: String::from_str(env, "ipfs://probe"),
+            min_stake: 1,
+            max_stake: 0,
+            initial_liquidity: 0,
+            category: symbol_short!("Tech"),
+            creator_fee_bps: 0,
+            oracle_quorum: 0,
+            lock_time: 0,
+            max_total_stake: 0,
+            truncate_at_cap: false,
+            outcome_weights: Vec::new(env),
+            scalar_buckets: Vec::new(env),
+            outcome_labels: Vec::new(env),
+            mechanism: Mechanism::Parimutuel,
+        },
+    );
+    (probe, pool_id)
+}
+
+#[test]
+fn test_reentrant_claim_cannot_pay_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let market = MarketTestContext::setup(&env);
+    let (probe, pool_id) = probe_pool(&env, &market);
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    probe.mint(&winner, &100);
+    probe.mint(&loser, &100);
+    market.client.place_prediction(&winner, &pool_id, &100, &0);
+    market.client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100_001);
+    market
+        .client
+        .resolve_pool(&market.operator, &pool_id, &0u32);
+    probe.arm(&market.client.address, &winner, &pool_id);
+
+    assert_eq!(market.client.claim_winnings(&winner, &pool_id), 200);
+    assert_eq!(probe.probe_stats(), (1, 0));
+    assert_eq!(probe.balance(&winner), 200);
+    assert_eq!(
+        market.client.try_claim_winnings(&winner, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_reentrant_refund_cannot_pay_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let market = MarketTestContext::setup(&env);
+    let (probe, pool_id) = probe_pool(&env, &market);
+
+    let user = Address::generate(&env);
+    probe.mint(&user, &100);
+    market.client.place_prediction(&user, &pool_id, &100, &0);
+    market.client.cancel_pool(&market.operator, &pool_id);
+    probe.arm(&market.client.address, &user, &pool_id);
+
+    assert_eq!(market.client.claim_winnings(&user, &pool_id), 100);
+    assert_eq!(probe.probe_stats(), (1, 0));
+    assert_eq!(probe.balance(&user), 100);
+    assert_eq!(market.client.get_pool_unclaimed(&pool_id), 0);
+}
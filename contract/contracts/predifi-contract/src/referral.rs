@@ -0,0 +1,348 @@
+//! # Referrals
+//!
+//! Users can be referred by an address or a registered referral code. The
+//! referrer earns a configurable share of the protocol fee charged on the
+//! user's claims.
+
+use super::*;
+
+/// Storage keys of referrals.
+#[contracttype]
+#[derive(Clone)]
+pub enum ReferralKey {
+    /// Referral code registry: RefCode(code) -> owner.
+    RefCode(Symbol),
+    /// Referrer a user was first referred by.
+    Referrer(Address),
+    /// Share of the protocol fee paid to referrers, in basis points.
+    ReferralShareBps,
+    /// Referral rewards accrued and not yet claimed:
+    /// ReferralRewards(referrer, token).
+    ReferralRewards(Address, Address),
+}
+
+#[contractevent(topics = ["ref_code_registered"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefCodeRegisteredEvent {
+    pub code: Symbol,
+    pub owner: Address,
+}
+
+#[contractevent(topics = ["ref_code_transferred"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefCodeTransferredEvent {
+    pub code: Symbol,
+    pub from: Address,
+    pub to: Address,
+}
+
+#[contractevent(topics = ["ref_code_revoked"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefCodeRevokedEvent {
+    pub code: Symbol,
+    pub owner: Address,
+    pub revoked_by: Address,
+}
+
+#[contractevent(topics = ["referral_recorded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRecordedEvent {
+    pub user: Address,
+    pub referrer: Address,
+    /// Code the bettor used, if the referrer was given by code.
+    pub code: Option<Symbol>,
+}
+
+#[contractevent(topics = ["referral_share_update"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralShareUpdateEvent {
+    pub admin: Address,
+    pub share_bps: u32,
+}
+
+#[contractevent(topics = ["referral_reward_accrued"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRewardAccruedEvent {
+    pub pool_id: u64,
+    pub referrer: Address,
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["referral_rewards_claimed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralRewardsClaimedEvent {
+    #[topic]
+    pub referrer: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+impl PredifiContract {
+    /// Record `referrer` as the user's referrer unless one is already
+    /// recorded or the user refers themselves.
+    pub(crate) fn record_referrer(
+        env: &Env,
+        user: &Address,
+        referrer: &Address,
+        code: Option<Symbol>,
+    ) {
+        let key = ReferralKey::Referrer(user.clone());
+        if user == referrer || env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().set(&key, referrer);
+        Self::extend_persistent(env, &key);
+
+        ReferralRecordedEvent {
+            user: user.clone(),
+            referrer: referrer.clone(),
+            code,
+        }
+        .publish(env);
+    }
+
+    /// Credit the referrer of `user`, if any, with the configured share of a
+    /// claim's protocol fee. Returns the amount accrued, which the caller
+    /// withholds from the protocol fee ledger.
+    pub(crate) fn accrue_referral_fee(
+        env: &Env,
+        pool_id: u64,
+        token: &Address,
+        user: &Address,
+        fee: i128,
+    ) -> i128 {
+        let share_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&ReferralKey::ReferralShareBps)
+            .unwrap_or(0);
+        let amount = fee * share_bps as i128 / 10_000;
+        if amount <= 0 {
+            return 0;
+        }
+        let Some(referrer) = Self::get_referrer(env.clone(), user.clone()) else {
+            return 0;
+        };
+        let key = ReferralKey::ReferralRewards(referrer.clone(), token.clone());
+        let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(accrued + amount));
+        Self::extend_persistent(env, &key);
+        ReferralRewardAccruedEvent {
+            pool_id,
+            referrer,
+            user: user.clone(),
+            token: token.clone(),
+            amount,
+        }
+        .publish(env);
+        amount
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Register `code` as a referral code of `owner`, so bettors can name the
+    /// referrer by code (see `place_prediction_with_code`). An address may
+    /// hold several codes. Fails with `ReferralCodeTaken` if the code is
+    /// registered.
+    pub fn register_ref_code(env: Env, owner: Address, code: Symbol) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        owner.require_auth();
+        let key = ReferralKey::RefCode(code.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(PredifiError::ReferralCodeTaken);
+        }
+        env.storage().persistent().set(&key, &owner);
+        Self::extend_persistent(&env, &key);
+
+        RefCodeRegisteredEvent { code, owner }.publish(&env);
+        Ok(())
+    }
+
+    /// Hand a referral code over to `new_owner`. Only the current owner may
+    /// transfer it; referrals already recorded stay with the old owner.
+    pub fn transfer_ref_code(
+        env: Env,
+        owner: Address,
+        code: Symbol,
+        new_owner: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        owner.require_auth();
+        let key = ReferralKey::RefCode(code.clone());
+        let current: Address = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::Unauthorized)?;
+        if current != owner {
+            return Err(PredifiError::Unauthorized);
+        }
+        env.storage().persistent().set(&key, &new_owner);
+        Self::extend_persistent(&env, &key);
+
+        RefCodeTransferredEvent {
+            code,
+            from: owner,
+            to: new_owner,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Revoke a referral code, freeing it for registration. The owner may
+    /// revoke their own codes; an Operator (1) may revoke any code, e.g. an
+    /// impersonating one.
+    pub fn revoke_ref_code(env: Env, caller: Address, code: Symbol) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        let key = ReferralKey::RefCode(code.clone());
+        let owner: Address = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(PredifiError::Unauthorized)?;
+        if owner != caller {
+            Self::require_role(&env, &caller, 1)?;
+        }
+        env.storage().persistent().remove(&key);
+
+        RefCodeRevokedEvent {
+            code,
+            owner,
+            revoked_by: caller,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the owner of a referral code, if registered.
+    pub fn get_ref_code_owner(env: Env, code: Symbol) -> Option<Address> {
+        env.storage().persistent().get(&ReferralKey::RefCode(code))
+    }
+
+    /// Returns the referrer recorded for a user, if any.
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent().get(&ReferralKey::Referrer(user))
+    }
+
+    /// `place_prediction`, naming the user's referrer. The first referrer
+    /// recorded for a user sticks and earns the referral share of the
+    /// protocol fee on the user's claims (see `set_referral_share_bps`).
+    /// Self-referrals are ignored.
+    pub fn place_prediction_with_referrer(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+        outcome: u32,
+        referrer: Option<Address>,
+    ) {
+        Self::place_prediction(env.clone(), user.clone(), pool_id, amount, outcome);
+        if let Some(referrer) = referrer {
+            Self::record_referrer(&env, &user, &referrer, None);
+        }
+    }
+
+    /// `place_prediction`, naming the referrer by referral code. The first
+    /// referrer recorded for a user sticks; unknown codes and self-referrals
+    /// place the bet without recording one.
+    pub fn place_prediction_with_code(
+        env: Env,
+        user: Address,
+        pool_id: u64,
+        amount: i128,
+        outcome: u32,
+        code: Symbol,
+    ) {
+        Self::place_prediction(env.clone(), user.clone(), pool_id, amount, outcome);
+        let owner: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&ReferralKey::RefCode(code.clone()));
+        if let Some(referrer) = owner {
+            Self::record_referrer(&env, &user, &referrer, Some(code));
+        }
+    }
+
+    /// Set the share of the protocol fee paid to referrers, in basis points
+    /// (0 = no referral rewards). Caller must have Admin role (0).
+    pub fn set_referral_share_bps(
+        env: Env,
+        admin: Address,
+        share_bps: u32,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_referral_share_bps"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if share_bps > 10_000 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = ReferralKey::ReferralShareBps;
+        let old: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        Self::record_config_change(
+            &env,
+            &admin,
+            "referral_share_bps",
+            ConfigValue::U32(old),
+            ConfigValue::U32(share_bps),
+        );
+        env.storage().instance().set(&key, &share_bps);
+        Self::extend_instance(&env);
+
+        ReferralShareUpdateEvent { admin, share_bps }.publish(&env);
+        Ok(())
+    }
+
+    /// Transfer the referral rewards accrued in `token` to the referrer.
+    /// Returns the amount transferred (0 if nothing was accrued).
+    pub fn claim_referral_rewards(env: Env, referrer: Address, token: Address) -> i128 {
+        Self::require_scope_not_paused(&env, PAUSE_CLAIMS);
+        referrer.require_auth();
+
+        let key = ReferralKey::ReferralRewards(referrer.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
+        }
+        env.storage().persistent().remove(&key);
+
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &referrer, &amount);
+        Self::record_ledger_entry(
+            &env,
+            None,
+            &referrer,
+            &token,
+            LedgerDirection::Out,
+            amount,
+            "referral_reward",
+        );
+
+        ReferralRewardsClaimedEvent {
+            referrer,
+            token,
+            amount,
+        }
+        .publish(&env);
+        amount
+    }
+
+    /// Returns the referral rewards accrued in `token` and not yet claimed.
+    pub fn get_referral_rewards(env: Env, referrer: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReferralKey::ReferralRewards(referrer, token))
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,121 @@
+//! # Reflector Price Oracle Adapter
+//!
+//! Resolves scalar pools tagged as price markets from a Reflector-compatible
+//! (SEP-40) price feed, so no off-chain operator is needed once the market
+//! has ended. The pool's scalar buckets are expressed in the feed's
+//! fixed-point units (see the feed's `decimals`); the round recorded for
+//! `end_time + delay` picks the winning bucket.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! let btc = ReflectorAsset::Other(symbol_short!("BTC"));
+//! client.set_price_market(&operator, &pool_id, &feed, &btc, &300);
+//! // after end_time + 300:
+//! let outcome = client.resolve_from_price_feed(&pool_id);
+//! ```
+
+use crate::PredifiError;
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Symbol, Vec};
+
+/// Asset identifier of a SEP-40 price feed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReflectorAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// One price round of a SEP-40 price feed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 oracle interface implemented by Reflector feeds.
+#[allow(dead_code)]
+#[contractclient(name = "ReflectorClient")]
+pub trait ReflectorOracle {
+    /// Number of decimals of the fixed-point prices.
+    fn decimals(env: Env) -> u32;
+
+    /// Price round of `asset` at `timestamp`, if one was recorded.
+    fn price(env: Env, asset: ReflectorAsset, timestamp: u64) -> Option<PriceData>;
+}
+
+/// Price feed a pool resolves from: the round at `end_time + delay` of
+/// `asset` on `feed`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceMarket {
+    pub feed: Address,
+    pub asset: ReflectorAsset,
+    pub delay: u64,
+}
+
+/// Read the round of `market` at `timestamp`. A feed that traps, returns an
+/// unexpected value or has no round yields `PriceFeedNotFound`; a round from
+/// after `timestamp` yields `PriceDataInvalid`.
+pub(crate) fn read_round(
+    env: &Env,
+    market: &PriceMarket,
+    timestamp: u64,
+) -> Result<PriceData, PredifiError> {
+    let client = ReflectorClient::new(env, &market.feed);
+    let round = match client.try_price(&market.asset, &timestamp) {
+        Ok(Ok(Some(round))) => round,
+        _ => return Err(PredifiError::PriceFeedNotFound),
+    };
+    if round.timestamp > timestamp {
+        return Err(PredifiError::PriceDataInvalid);
+    }
+    Ok(round)
+}
+
+/// Index of the bucket `[low, high)` containing `price`, if any.
+pub(crate) fn bucket_for(buckets: &Vec<(i128, i128)>, price: i128) -> Option<u32> {
+    buckets
+        .iter()
+        .position(|(low, high)| low <= price && price < high)
+        .map(|i| i as u32)
+}
+
+/// `"reflector:<round timestamp>:<price>"`, recorded as the resolution proof.
+pub(crate) fn round_proof(env: &Env, round: &PriceData) -> String {
+    let mut buf = [0u8; 80];
+    let mut len = 0;
+    for &b in b"reflector:" {
+        buf[len] = b;
+        len += 1;
+    }
+    push_decimal(&mut buf, &mut len, round.timestamp as i128);
+    buf[len] = b':';
+    len += 1;
+    push_decimal(&mut buf, &mut len, round.price);
+    String::from_bytes(env, &buf[..len])
+}
+
+fn push_decimal(buf: &mut [u8], len: &mut usize, value: i128) {
+    if value < 0 {
+        buf[*len] = b'-';
+        *len += 1;
+    }
+    let mut digits = [0u8; 39];
+    let mut count = 0;
+    let mut rest = value.unsigned_abs();
+    loop {
+        digits[count] = b'0' + (rest % 10) as u8;
+        count += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    while count > 0 {
+        count -= 1;
+        buf[*len] = digits[count];
+        *len += 1;
+    }
+}
@@ -40,6 +40,18 @@ const PRECISION: i128 = 10_000;
 /// Maximum basis points (100% = 10,000 bps)
 const MAX_BPS: i128 = 10_000;
 
+/// Scale of the fixed-point `exp_wad` / `ln_wad` helpers (1.0 = 1e18)
+pub const WAD: i128 = 1_000_000_000_000_000_000;
+
+/// ln(2) at WAD scale
+const LN2_WAD: i128 = 693_147_180_559_945_309;
+
+/// Below this argument e^x rounds to 0 at WAD scale
+const MIN_EXP_WAD: i128 = -42 * WAD;
+
+/// Above this argument e^x no longer fits an i128 at WAD scale
+const MAX_EXP_WAD: i128 = 46 * WAD;
+
 /// Rounding mode for calculations
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RoundingMode {
@@ -267,6 +279,87 @@ impl SafeMath {
     pub fn safe_mul(a: i128, b: i128) -> Result<i128, PrediFiError> {
         a.checked_mul(b).ok_or(PrediFiError::ArithmeticError)
     }
+
+    /// Fixed-point e^x, with `x` and the result scaled by [`WAD`].
+    ///
+    /// Reduces `x = k·ln2 + r` with |r| ≤ ln2/2, sums the Taylor series of
+    /// e^r and shifts by 2^k. Accurate to a few units of 1e-18 relative.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // e^1 ≈ 2.718281828459045235
+    /// let e = SafeMath::exp_wad(WAD)?;
+    /// ```
+    pub fn exp_wad(x: i128) -> Result<i128, PrediFiError> {
+        if x < MIN_EXP_WAD {
+            return Ok(0);
+        }
+        if x > MAX_EXP_WAD {
+            return Err(PrediFiError::ArithmeticError);
+        }
+
+        // Round k to nearest so the series argument stays small
+        let half = if x >= 0 { LN2_WAD / 2 } else { -LN2_WAD / 2 };
+        let k = (x + half) / LN2_WAD;
+        let r = x - k * LN2_WAD;
+
+        let mut term = WAD;
+        let mut sum = WAD;
+        let mut i: i128 = 1;
+        while term != 0 {
+            term = term * r / WAD / i;
+            sum += term;
+            i += 1;
+        }
+
+        if k >= 0 {
+            sum.checked_mul(1i128 << k)
+                .ok_or(PrediFiError::ArithmeticError)
+        } else {
+            Ok(sum >> -k)
+        }
+    }
+
+    /// Fixed-point natural logarithm, with `x` and the result scaled by
+    /// [`WAD`]. Errors for `x <= 0`.
+    ///
+    /// Normalizes `x = 2^k·y` with y in [1, 2) and sums
+    /// ln(y) = 2·atanh((y − 1) / (y + 1)).
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // ln(2) ≈ 0.693147180559945309
+    /// let ln2 = SafeMath::ln_wad(2 * WAD)?;
+    /// ```
+    pub fn ln_wad(x: i128) -> Result<i128, PrediFiError> {
+        if x <= 0 {
+            return Err(PrediFiError::ArithmeticError);
+        }
+
+        let mut k: i128 = 0;
+        let mut y = x;
+        while y >= 2 * WAD {
+            y /= 2;
+            k += 1;
+        }
+        while y < WAD {
+            y *= 2;
+            k -= 1;
+        }
+
+        let z = (y - WAD) * WAD / (y + WAD);
+        let z2 = z * z / WAD;
+        let mut term = z;
+        let mut sum: i128 = 0;
+        let mut i: i128 = 1;
+        while term != 0 {
+            sum += term / i;
+            term = term * z2 / WAD;
+            i += 2;
+        }
+
+        Ok(k * LN2_WAD + 2 * sum)
+    }
 }
 
 #[cfg(test)]
@@ -561,6 +654,69 @@ mod tests {
         );
     }
 
+    fn assert_close(actual: i128, expected: i128, tolerance: i128) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{} not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_exp_wad() {
+        assert_eq!(SafeMath::exp_wad(0).unwrap(), WAD);
+        // e ≈ 2.718281828459045235, 1/e ≈ 0.367879441171442321
+        assert_close(
+            SafeMath::exp_wad(WAD).unwrap(),
+            2_718_281_828_459_045_235,
+            100,
+        );
+        assert_close(
+            SafeMath::exp_wad(-WAD).unwrap(),
+            367_879_441_171_442_321,
+            100,
+        );
+        // e^10 ≈ 22026.465794806716517
+        assert_close(
+            SafeMath::exp_wad(10 * WAD).unwrap(),
+            22_026_465_794_806_716_517_000,
+            10_000_000,
+        );
+
+        // Underflows to zero, overflow is an error
+        assert_eq!(SafeMath::exp_wad(-50 * WAD).unwrap(), 0);
+        assert_eq!(
+            SafeMath::exp_wad(50 * WAD),
+            Err(PrediFiError::ArithmeticError)
+        );
+    }
+
+    #[test]
+    fn test_ln_wad() {
+        assert_eq!(SafeMath::ln_wad(WAD).unwrap(), 0);
+        assert_close(SafeMath::ln_wad(2 * WAD).unwrap(), LN2_WAD, 100);
+        assert_close(SafeMath::ln_wad(WAD / 2).unwrap(), -LN2_WAD, 100);
+        // ln(10) ≈ 2.302585092994045684
+        assert_close(
+            SafeMath::ln_wad(10 * WAD).unwrap(),
+            2_302_585_092_994_045_684,
+            100,
+        );
+
+        assert_eq!(SafeMath::ln_wad(0), Err(PrediFiError::ArithmeticError));
+        assert_eq!(SafeMath::ln_wad(-WAD), Err(PrediFiError::ArithmeticError));
+    }
+
+    #[test]
+    fn test_exp_ln_roundtrip() {
+        for x in [-5 * WAD, -3 * WAD / 2, WAD / 3, 7 * WAD] {
+            let y = SafeMath::exp_wad(x).unwrap();
+            assert_close(SafeMath::ln_wad(y).unwrap(), x, 1_000_000);
+        }
+    }
+
     #[test]
     fn test_large_numbers() {
         // Test with realistic token amounts (e.g., 7 decimal places)
@@ -2,30 +2,12 @@ use crate::{PredifiContract, PredifiContractClient};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    token, Address, Env, String, Symbol,
+    token, Address, Env, String,
 };
 
 extern crate alloc;
 
-mod dummy_access_control {
-    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
-
-    #[contract]
-    pub struct DummyAccessControl;
-
-    #[contractimpl]
-    impl DummyAccessControl {
-        pub fn grant_role(env: Env, user: Address, role: u32) {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().set(&key, &true);
-        }
-
-        pub fn has_role(env: Env, user: Address, role: u32) -> bool {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().get(&key).unwrap_or(false)
-        }
-    }
-}
+use crate::test_utils::dummy_access_control;
 
 const ROLE_ADMIN: u32 = 0;
 const ROLE_OPERATOR: u32 = 1;
@@ -4,29 +4,11 @@
 use super::*;
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Ledger},
-    token, Address, BytesN, Env, String, Symbol,
+    testutils::{Address as _, Events as _, Ledger},
+    token, vec, Address, BytesN, Env, Event, Map, String, Symbol,
 };
 
-mod dummy_access_control {
-    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
-
-    #[contract]
-    pub struct DummyAccessControl;
-
-    #[contractimpl]
-    impl DummyAccessControl {
-        pub fn grant_role(env: Env, user: Address, role: u32) {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().set(&key, &true);
-        }
-
-        pub fn has_role(env: Env, user: Address, role: u32) -> bool {
-            let key = (Symbol::new(&env, "role"), user, role);
-            env.storage().instance().get(&key).unwrap_or(false)
-        }
-    }
-}
+use crate::test_utils::dummy_access_control;
 
 const ROLE_ADMIN: u32 = 0;
 const ROLE_OPERATOR: u32 = 1;
@@ -78,6 +60,38 @@ fn setup(
     )
 }
 
+/// True if the last contract invocation published `event`.
+fn emitted(env: &Env, contract: &Address, event: &impl Event) -> bool {
+    env.events()
+        .all()
+        .events()
+        .contains(&event.to_xdr(env, contract))
+}
+
+/// True if the last contract invocation published `event` before failing;
+/// the event was rolled back with the call but is kept in the host's log.
+fn emitted_before_failure(env: &Env, contract: &Address, event: &impl Event) -> bool {
+    let event = event.to_xdr(env, contract);
+    env.host()
+        .get_events()
+        .unwrap()
+        .0
+        .iter()
+        .any(|e| e.failed_call && e.event == event)
+}
+
+/// True if the last contract invocation published an event whose first topic
+/// is `name` and whose topics include `user`.
+fn published_with_user_topic(env: &Env, name: &str, user: &Address) -> bool {
+    use soroban_sdk::xdr::{ContractEventBody, ScVal};
+    let name = ScVal::try_from_val(env, &Symbol::new(env, name).to_val()).unwrap();
+    let user = ScVal::try_from_val(env, &user.to_val()).unwrap();
+    env.events().all().events().iter().any(|e| {
+        let ContractEventBody::V0(body) = &e.body;
+        body.topics.first() == Some(&name) && body.topics.contains(&user)
+    })
+}
+
 // ── Core prediction tests ────────────────────────────────────────────────────
 
 #[test]
@@ -242,7 +256,7 @@ fn test_multiple_pools_independent() {
     env.ledger().with_mut(|li| li.timestamp = 100001);
 
     client.resolve_pool(&operator, &pool_a, &1u32);
-    client.resolve_pool(&operator, &pool_b, &2u32);
+    client.force_resolve_pool(&operator, &pool_b, &2u32, &String::from_str(&env, ""));
 
     let w1 = client.claim_winnings(&user1, &pool_a);
     assert_eq!(w1, 100);
@@ -516,6 +530,20 @@ fn test_admin_can_migrate() {
     client.migrate_state(&admin);
 }
 
+#[test]
+fn test_version_getters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, client, _, _, _, _, _, _) = setup(&env);
+
+    assert_eq!(
+        client.version(),
+        String::from_str(&env, env!("CARGO_PKG_VERSION"))
+    );
+    // Fresh deployments start at the current schema revision.
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #10)")]
 fn test_non_admin_cannot_migrate() {
@@ -603,7 +631,9 @@ fn test_paused_blocks_create_pool() {
 
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
-    let token = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
     ac_client.grant_role(&admin, &ROLE_ADMIN);
     client.init(&ac_id, &treasury, &0u32, &0u64);
     client.add_token_to_whitelist(&admin, &token);
@@ -977,7 +1007,9 @@ fn test_token_whitelist_add_remove_and_is_allowed() {
 
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
-    let token = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
     ac_client.grant_role(&admin, &ROLE_ADMIN);
     client.init(&ac_id, &treasury, &0u32, &0u64);
 
@@ -1360,7 +1392,9 @@ fn test_resolve_pool_before_delay() {
     let admin = Address::generate(&env);
     let operator = Address::generate(&env);
     let treasury = Address::generate(&env);
-    let token = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
     ac_client.grant_role(&admin, &ROLE_ADMIN);
     ac_client.grant_role(&operator, &ROLE_OPERATOR);
 
@@ -1407,7 +1441,9 @@ fn test_resolve_pool_after_delay() {
     let admin = Address::generate(&env);
     let operator = Address::generate(&env);
     let treasury = Address::generate(&env);
-    let token = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
     ac_client.grant_role(&admin, &ROLE_ADMIN);
     ac_client.grant_role(&operator, &ROLE_OPERATOR);
 
@@ -1449,7 +1485,9 @@ fn test_mark_pool_ready() {
 
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
-    let token = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
     ac_client.grant_role(&admin, &ROLE_ADMIN);
     client.init(&ac_id, &treasury, &0u32, &3600u64);
     client.add_token_to_whitelist(&admin, &token);
@@ -1704,7 +1742,7 @@ fn test_admin_can_withdraw_treasury() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (ac_client, client, token_address, token, token_admin_client, treasury, _, creator) =
+    let (ac_client, client, token_address, token, token_admin_client, treasury, _, _creator) =
         setup(&env);
     let contract_addr = client.address.clone();
     let admin = Address::generate(&env);
@@ -1727,7 +1765,7 @@ fn test_non_admin_cannot_withdraw_treasury() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (_, client, token_address, token, token_admin_client, treasury, _, _) = setup(&env);
+    let (_, client, token_address, _token, token_admin_client, treasury, _, _) = setup(&env);
     let contract_addr = client.address.clone();
     let non_admin = Address::generate(&env);
 
@@ -1743,7 +1781,8 @@ fn test_withdraw_treasury_rejects_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (ac_client, client, token_address, token, token_admin_client, treasury, _, _) = setup(&env);
+    let (ac_client, client, token_address, _token, token_admin_client, treasury, _, _) =
+        setup(&env);
     let contract_addr = client.address.clone();
     let admin = Address::generate(&env);
     ac_client.grant_role(&admin, &ROLE_ADMIN);
@@ -1760,7 +1799,8 @@ fn test_withdraw_treasury_rejects_insufficient_balance() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (ac_client, client, token_address, token, token_admin_client, treasury, _, _) = setup(&env);
+    let (ac_client, client, token_address, _token, token_admin_client, treasury, _, _) =
+        setup(&env);
     let contract_addr = client.address.clone();
     let admin = Address::generate(&env);
     ac_client.grant_role(&admin, &ROLE_ADMIN);
@@ -1809,7 +1849,8 @@ fn test_paused_blocks_withdraw_treasury() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (ac_client, client, token_address, token, token_admin_client, treasury, _, _) = setup(&env);
+    let (ac_client, client, token_address, _token, token_admin_client, treasury, _, _) =
+        setup(&env);
     let contract_addr = client.address.clone();
     let admin = Address::generate(&env);
     ac_client.grant_role(&admin, &ROLE_ADMIN);
@@ -2708,9 +2749,9 @@ fn test_state_consistency_across_many_pools() {
     env.ledger().with_mut(|li| li.timestamp = 200_000);
 
     // Even-indexed pools → outcome 0 wins; odd-indexed → outcome 1 wins.
-    for i in 0..5usize {
+    for (i, pool) in pools.iter().enumerate() {
         let winning_outcome: u32 = if i % 2 == 0 { 0 } else { 1 };
-        client.resolve_pool(&operator, &pools[i], &winning_outcome);
+        client.resolve_pool(&operator, pool, &winning_outcome);
     }
 
     let mut total_paid: i128 = 0;
@@ -2881,10 +2922,6958 @@ fn test_no_bettor_on_winning_side() {
     client.place_prediction(&user2, &pool_id, &200, &1);
 
     env.ledger().with_mut(|li| li.timestamp = 100_001);
-    client.resolve_pool(&operator, &pool_id, &2u32); // outcome 2 – no bettors
+    client.force_resolve_pool(&operator, &pool_id, &2u32, &String::from_str(&env, "")); // outcome 2 – no bettors
 
     let w1 = client.claim_winnings(&user1, &pool_id);
     let w2 = client.claim_winnings(&user2, &pool_id);
     assert_eq!(w1, 0);
     assert_eq!(w2, 0);
 }
+
+// ── Pool lifecycle callback tests ────────────────────────────────────────────
+
+mod recording_callback {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct RecordingCallback;
+
+    #[contractimpl]
+    impl RecordingCallback {
+        pub fn on_pool_resolved(env: Env, pool_id: u64, outcome: u32) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("resolved"), &(pool_id, outcome));
+        }
+
+        pub fn on_pool_canceled(env: Env, pool_id: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("canceled"), &pool_id);
+        }
+
+        pub fn last_resolved(env: Env) -> Option<(u64, u32)> {
+            env.storage().instance().get(&symbol_short!("resolved"))
+        }
+
+        pub fn last_canceled(env: Env) -> Option<u64> {
+            env.storage().instance().get(&symbol_short!("canceled"))
+        }
+    }
+}
+
+mod failing_callback {
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct FailingCallback;
+
+    #[contractimpl]
+    impl FailingCallback {
+        pub fn on_pool_resolved(_env: Env, _pool_id: u64, _outcome: u32) {
+            panic!("callback failure");
+        }
+    }
+}
+
+#[test]
+fn test_pool_callback_notified_on_resolution_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let cb_id = env.register(recording_callback::RecordingCallback, ());
+    let cb_client = recording_callback::RecordingCallbackClient::new(&env, &cb_id);
+
+    let pool_a = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Callback Pool A"),
+        &String::from_str(&env, "ipfs://callback-a"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let pool_b = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Callback Pool B"),
+        &String::from_str(&env, "ipfs://callback-b"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    client.set_pool_callback(&creator, &pool_a, &Some(cb_id.clone()));
+    client.set_pool_callback(&creator, &pool_b, &Some(cb_id.clone()));
+    assert_eq!(client.get_pool_callback(&pool_a), Some(cb_id.clone()));
+
+    client.cancel_pool(&operator, &pool_b);
+    // Settlement only queues the hook.
+    assert_eq!(cb_client.last_canceled(), None);
+    assert_eq!(
+        client.get_pending_pool_callback(&pool_b),
+        Some(PoolHook::Canceled)
+    );
+    assert!(client.dispatch_pool_callback(&pool_b));
+    assert_eq!(cb_client.last_canceled(), Some(pool_b));
+    assert_eq!(client.get_pending_pool_callback(&pool_b), None);
+    assert_eq!(
+        client.try_dispatch_pool_callback(&pool_b),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_a, &1u32);
+    assert!(client.dispatch_pool_callback(&pool_a));
+    assert_eq!(cb_client.last_resolved(), Some((pool_a, 1u32)));
+}
+
+#[test]
+fn test_failing_pool_callback_does_not_block_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let cb_id = env.register(failing_callback::FailingCallback, ());
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Failing Callback Pool"),
+        &String::from_str(&env, "ipfs://callback-fail"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    client.set_pool_callback(&creator, &pool_id, &Some(cb_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Resolved);
+    assert!(!client.dispatch_pool_callback(&pool_id));
+    assert_eq!(client.get_pending_pool_callback(&pool_id), None);
+}
+
+#[test]
+fn test_operator_clears_pool_callback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let cb_id = env.register(failing_callback::FailingCallback, ());
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Cleared Callback Pool"),
+        &String::from_str(&env, "ipfs://callback-clear"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    client.set_pool_callback(&creator, &pool_id, &Some(cb_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_clear_pool_callback(&stranger, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.clear_pool_callback(&operator, &pool_id);
+    assert_eq!(client.get_pool_callback(&pool_id), None);
+    assert_eq!(client.get_pending_pool_callback(&pool_id), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_non_creator_cannot_set_pool_callback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let stranger = Address::generate(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Callback Auth Pool"),
+        &String::from_str(&env, "ipfs://callback-auth"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    client.set_pool_callback(&stranger, &pool_id, &Some(stranger.clone()));
+}
+
+// ── Derivative market tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_derivative_pool_resolves_from_reference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+
+    let reference = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &3u32,
+        &String::from_str(&env, "Reference Pool"),
+        &String::from_str(&env, "ipfs://reference"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let derivative = client.create_pool(
+        &creator,
+        &200000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Will reference resolve to 2?"),
+        &String::from_str(&env, "ipfs://derivative"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    client.set_derivative_reference(&creator, &derivative, &reference, &2u32);
+    assert_eq!(
+        client.get_derivative_reference(&derivative),
+        Some(DerivativeLink {
+            reference_pool_id: reference,
+            reference_outcome: 2,
+        })
+    );
+
+    let yes = Address::generate(&env);
+    let no = Address::generate(&env);
+    token_admin_client.mint(&yes, &100);
+    token_admin_client.mint(&no, &100);
+    client.place_prediction(&yes, &derivative, &100, &1);
+    client.place_prediction(&no, &derivative, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &reference, &2u32);
+
+    // The derivative settles at its own deadline, never by hand.
+    let res = client.try_resolve_derivative_pool(&derivative);
+    assert_eq!(res, Err(Ok(PredifiError::ResolutionDelayNotMet)));
+    env.ledger().with_mut(|li| li.timestamp = 200001);
+    let res = client.try_resolve_pool(&operator, &derivative, &0u32);
+    assert_eq!(res, Err(Ok(PredifiError::InvalidPoolState)));
+
+    assert_eq!(client.resolve_derivative_pool(&derivative), 1);
+
+    assert_eq!(client.claim_winnings(&yes, &derivative), 200);
+    assert_eq!(token.balance(&yes), 200);
+}
+
+#[test]
+fn test_derivative_pool_canceled_with_reference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+
+    let reference = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Reference Pool"),
+        &String::from_str(&env, "ipfs://reference"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let derivative = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Will reference resolve to 1?"),
+        &String::from_str(&env, "ipfs://derivative"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    client.set_derivative_reference(&creator, &derivative, &reference, &1u32);
+
+    client.cancel_pool(&operator, &reference);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_derivative_pool(&derivative);
+
+    assert_eq!(client.get_pool(&derivative).state, MarketState::Canceled);
+}
+
+#[test]
+fn test_derivative_pool_resolves_no_when_reference_settles_late() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+
+    let reference = client.create_pool(
+        &creator,
+        &200000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Reference Pool"),
+        &String::from_str(&env, "ipfs://reference"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let derivative = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Will reference resolve to 1 by 100000?"),
+        &String::from_str(&env, "ipfs://derivative"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let late = client.create_pool(
+        &creator,
+        &150000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Will reference resolve to 1 by 150000?"),
+        &String::from_str(&env, "ipfs://derivative-late"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    client.set_derivative_reference(&creator, &derivative, &reference, &1u32);
+    client.set_derivative_reference(&creator, &late, &reference, &1u32);
+
+    // Reference still open past the deadline: the answer is already no.
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(client.resolve_derivative_pool(&derivative), 0);
+
+    // Resolved to the referenced outcome, but after the deadline.
+    env.ledger().with_mut(|li| li.timestamp = 200001);
+    client.resolve_pool(&operator, &reference, &1u32);
+    assert_eq!(client.resolve_derivative_pool(&late), 0);
+}
+
+#[test]
+fn test_derivative_reference_must_be_older_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+
+    let derivative = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Derivative"),
+        &String::from_str(&env, "ipfs://derivative"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let newer = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Newer Pool"),
+        &String::from_str(&env, "ipfs://newer"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+
+    let res = client.try_set_derivative_reference(&creator, &derivative, &newer, &0u32);
+    assert_eq!(res, Err(Ok(PredifiError::InvalidPoolReference)));
+}
+
+// ── Partial claim tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_partial_claims_then_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &1000);
+    token_admin_client.mint(&user2, &1000);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Partial Claim Pool"),
+        &String::from_str(&env, "ipfs://partial"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    client.place_prediction(&user1, &pool_id, &100, &0);
+    client.place_prediction(&user2, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.claim_partial(&user1, &pool_id, &50), 50);
+    assert_eq!(client.claim_partial(&user1, &pool_id, &30), 30);
+    assert_eq!(client.get_claimed_amount(&user1, &pool_id), 80);
+    assert_eq!(token.balance(&user1), 980);
+
+    // Cannot take more than what is left.
+    let res = client.try_claim_partial(&user1, &pool_id, &121);
+    assert_eq!(res, Err(Ok(PredifiError::InvalidAmount)));
+
+    // The remainder is paid by a regular claim.
+    assert_eq!(client.claim_winnings(&user1, &pool_id), 120);
+    assert_eq!(client.get_claimed_amount(&user1, &pool_id), 200);
+    assert_eq!(token.balance(&user1), 1100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #60)")]
+fn test_partial_claim_exhausting_entitlement_blocks_further_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    token_admin_client.mint(&user1, &1000);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Partial Claim Pool"),
+        &String::from_str(&env, "ipfs://partial"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    client.place_prediction(&user1, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    client.claim_partial(&user1, &pool_id, &100);
+    client.claim_winnings(&user1, &pool_id);
+}
+
+// ── Position concentration tests ─────────────────────────────────────────────
+
+#[test]
+fn test_get_top_positions_sorted_per_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Concentration Pool"),
+        &String::from_str(&env, "ipfs://concentration"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let small = Address::generate(&env);
+    let large = Address::generate(&env);
+    let medium = Address::generate(&env);
+    let other = Address::generate(&env);
+    for (user, amount, outcome) in [
+        (&small, 100i128, 0u32),
+        (&large, 300, 0),
+        (&medium, 200, 0),
+        (&other, 50, 1),
+    ] {
+        token_admin_client.mint(user, &amount);
+        client.place_prediction(user, &pool_id, &amount, &outcome);
+    }
+
+    let top = client.get_top_positions(&pool_id, &0u32, &10u32);
+    assert_eq!(top.len(), 4);
+    assert_eq!(top.get(0).unwrap().user, large);
+    assert_eq!(top.get(0).unwrap().amount, 300);
+    assert_eq!(top.get(1).unwrap().user, medium);
+    assert_eq!(top.get(2).unwrap().user, small);
+    assert_eq!(top.get(3).unwrap().user, other);
+    assert_eq!(top.get(3).unwrap().outcome, 1);
+
+    // Pages cover bettor slots in placement order: slots 1..3 hold
+    // `large` and `medium`.
+    let page = client.get_top_positions(&pool_id, &1u32, &2u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().user, large);
+    assert_eq!(page.get(1).unwrap().user, medium);
+    assert!(client.get_top_positions(&pool_id, &4u32, &10u32).is_empty());
+}
+
+// ── Active pool cap tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_max_active_pools_enforced_with_operator_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_max_active_pools(&admin, &1u32);
+
+    let first = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Capped Pool 1"),
+        &String::from_str(&env, "ipfs://cap1"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    assert_eq!(client.get_active_pool_count(), 1);
+
+    let res = client.try_create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Capped Pool 2"),
+        &String::from_str(&env, "ipfs://cap2"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    assert_eq!(
+        res,
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::ActivePoolLimitReached as u32
+        )))
+    );
+
+    // Operators may exceed the cap.
+    let operator_pool = client.create_pool(
+        &operator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Operator Pool"),
+        &String::from_str(&env, "ipfs://cap-op"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    assert_eq!(client.get_active_pool_count(), 2);
+
+    // Settling pools frees capacity again.
+    client.cancel_pool(&operator, &first);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &operator_pool, &0u32);
+    assert_eq!(client.get_active_pool_count(), 0);
+}
+
+#[test]
+fn test_seed_active_pool_count_once_after_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    // Fresh deployments count from `init` and cannot be re-seeded.
+    assert_eq!(
+        client.try_seed_active_pool_count(&admin, &5u32),
+        Err(Ok(PredifiError::ActivePoolCountSeeded))
+    );
+
+    // An instance upgraded from a release without the counter.
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&ActivePoolsKey::Seeded);
+        env.storage().instance().remove(&DataKey::ActivePoolCount);
+    });
+    assert_eq!(client.get_active_pool_count(), 0);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_seed_active_pool_count(&stranger, &3u32),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    client.seed_active_pool_count(&admin, &3u32);
+    assert_eq!(client.get_active_pool_count(), 3);
+    assert_eq!(
+        client.try_seed_active_pool_count(&admin, &4u32),
+        Err(Ok(PredifiError::ActivePoolCountSeeded))
+    );
+}
+
+// ── Operator note tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_operator_notes_persisted_on_resolve_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+
+    let resolved = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Noted Pool"),
+        &String::from_str(&env, "ipfs://noted"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let canceled = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Voided Pool"),
+        &String::from_str(&env, "ipfs://voided"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    assert_eq!(client.get_operator_note(&resolved), None);
+
+    let reason = String::from_str(&env, "match abandoned: https://example.org/report");
+    client.cancel_pool_with_note(&operator, &canceled, &reason);
+    let note = client.get_operator_note(&canceled).unwrap();
+    assert_eq!(note.note, reason);
+    assert_eq!(note.author, operator);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    let source = String::from_str(&env, "https://example.org/final-score");
+    client.resolve_pool_with_note(&operator, &resolved, &1u32, &source);
+    let note = client.get_operator_note(&resolved).unwrap();
+    assert_eq!(note.note, source);
+    assert_eq!(note.timestamp, 100001);
+}
+
+#[test]
+fn test_long_notes_rejected_and_oracle_proof_not_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let oracle = Address::generate(&env);
+    ac_client.grant_role(&oracle, &ROLE_ORACLE);
+
+    let mut pools = [0u64; 2];
+    for pool in pools.iter_mut() {
+        *pool = client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Long Note Pool"),
+            &String::from_str(&env, "ipfs://long-note"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Sports"),
+        );
+    }
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+
+    let long = String::from_bytes(&env, &[b'x'; 300]);
+    assert_eq!(
+        client.try_resolve_pool_with_note(&operator, &pools[0], &0u32, &long),
+        Err(Ok(PredifiError::NoteTooLong))
+    );
+    assert_eq!(
+        client.try_cancel_pool_with_note(&operator, &pools[0], &long),
+        Err(Ok(PredifiError::NoteTooLong))
+    );
+    assert_eq!(client.get_pool(&pools[0]).state, MarketState::Active);
+
+    // A long oracle proof still resolves the pool; it is not kept as a note.
+    client.oracle_resolve(&oracle, &pools[1], &1u32, &long);
+    assert!(client.get_pool(&pools[1]).resolved);
+    assert_eq!(client.get_operator_note(&pools[1]), None);
+}
+
+// ── Cancellation compensation tests ──────────────────────────────────────────
+
+#[test]
+fn test_late_cancellation_refund_pays_capped_compensation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    // 10 bps per day after a 1-day grace period, capped at 50 bps.
+    client.set_compensation_config(&admin, &10u32, &50u32, &86_400u64);
+    token_admin_client.mint(&treasury, &100);
+    client.fund_compensation_reserve(&treasury, &token_address, &100);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &1000);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Late Cancel Pool"),
+        &String::from_str(&env, "ipfs://late-cancel"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    client.place_prediction(&user, &pool_id, &1000, &0);
+
+    // Canceled 10 days after end_time → 9 compensated days = 90 bps, capped at 50.
+    env.ledger()
+        .with_mut(|li| li.timestamp = 100000 + 10 * 86_400);
+    client.cancel_pool(&operator, &pool_id);
+
+    assert_eq!(client.claim_winnings(&user, &pool_id), 1000);
+    assert_eq!(token.balance(&user), 1005);
+    assert_eq!(client.get_compensation_reserve(&token_address), 95);
+}
+
+#[test]
+fn test_timely_cancellation_refund_has_no_compensation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    client.set_compensation_config(&admin, &10u32, &50u32, &86_400u64);
+    token_admin_client.mint(&treasury, &100);
+    client.fund_compensation_reserve(&treasury, &token_address, &100);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &1000);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Timely Cancel Pool"),
+        &String::from_str(&env, "ipfs://timely-cancel"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    client.place_prediction(&user, &pool_id, &1000, &0);
+    client.cancel_pool(&operator, &pool_id);
+
+    assert_eq!(client.claim_winnings(&user, &pool_id), 1000);
+    assert_eq!(token.balance(&user), 1000);
+    assert_eq!(client.get_compensation_reserve(&token_address), 100);
+}
+
+// ── Compliance hold tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_flagged_claimant_payout_held_then_released() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let flagged = Address::generate(&env);
+    let clean = Address::generate(&env);
+    token_admin_client.mint(&flagged, &100);
+    token_admin_client.mint(&clean, &100);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Compliance Pool"),
+        &String::from_str(&env, "ipfs://compliance"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Finance"),
+    );
+    client.place_prediction(&flagged, &pool_id, &100, &0);
+    client.place_prediction(&clean, &pool_id, &100, &0);
+
+    client.set_compliance_flag(&admin, &flagged, &true);
+    assert!(client.is_flagged(&flagged));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Flagged claim settles into escrow; others are unaffected.
+    assert_eq!(client.claim_winnings(&flagged, &pool_id), 100);
+    assert_eq!(token.balance(&flagged), 0);
+    let held = client.get_held_payout(&flagged, &pool_id).unwrap();
+    assert_eq!(held.amount, 100);
+    assert_eq!(held.reason, HoldReason::Compliance);
+    assert_eq!(client.claim_winnings(&clean, &pool_id), 100);
+
+    // Compliance holds are released by an admin, not by `retry_payout`.
+    assert_eq!(
+        client.try_retry_payout(&flagged, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    assert_eq!(client.release_held_payout(&admin, &flagged, &pool_id), 100);
+    assert_eq!(token.balance(&flagged), 100);
+    assert_eq!(client.get_held_payout(&flagged, &pool_id), None);
+}
+
+#[test]
+fn test_held_payout_can_be_forfeited_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let flagged = Address::generate(&env);
+    token_admin_client.mint(&flagged, &100);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Compliance Pool"),
+        &String::from_str(&env, "ipfs://compliance"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Finance"),
+    );
+    client.place_prediction(&flagged, &pool_id, &100, &1);
+    client.set_compliance_flag(&admin, &flagged, &true);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &1u32);
+    client.claim_winnings(&flagged, &pool_id);
+
+    client.pause(&admin);
+    assert!(client
+        .try_forfeit_held_payout(&admin, &flagged, &pool_id)
+        .is_err());
+    client.unpause(&admin);
+
+    assert_eq!(client.forfeit_held_payout(&admin, &flagged, &pool_id), 100);
+    assert_eq!(token.balance(&treasury), 100);
+
+    let res = client.try_release_held_payout(&admin, &flagged, &pool_id);
+    assert_eq!(res, Err(Ok(PredifiError::PayoutNotHeld)));
+}
+
+// ── Winners merkle summary tests ─────────────────────────────────────────────
+
+fn winner_leaf(env: &Env, user: &Address, entitlement: i128) -> BytesN<32> {
+    use soroban_sdk::xdr::ToXdr;
+    let mut buf = user.clone().to_xdr(env);
+    buf.extend_from_array(&entitlement.to_be_bytes());
+    env.crypto().sha256(&buf).into()
+}
+
+#[test]
+fn test_winners_merkle_summary_and_proofs() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Merkle Pool"),
+        &String::from_str(&env, "ipfs://merkle"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Crypto"),
+    );
+
+    let u1 = Address::generate(&env);
+    let u2 = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&u1, &100);
+    token_admin_client.mint(&u2, &300);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&u1, &pool_id, &100, &0);
+    client.place_prediction(&u2, &pool_id, &300, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Entitlements: 100/400 * 500 = 125 and 300/400 * 500 = 375.
+    let leaf1 = winner_leaf(&env, &u1, 125);
+    let leaf2 = winner_leaf(&env, &u2, 375);
+    let (first, second) = if leaf1 < leaf2 {
+        (u1.clone(), u2.clone())
+    } else {
+        (u2.clone(), u1.clone())
+    };
+
+    // Losers are rejected.
+    let res = client.try_push_winner_entries(&operator, &pool_id, &vec![&env, loser.clone()]);
+    assert_eq!(res, Err(Ok(PredifiError::InvalidWinnerEntry)));
+
+    client.push_winner_entries(&operator, &pool_id, &vec![&env, first.clone()]);
+    // Out-of-order (or duplicate) leaves are rejected.
+    let res = client.try_push_winner_entries(&operator, &pool_id, &vec![&env, first.clone()]);
+    assert_eq!(res, Err(Ok(PredifiError::InvalidWinnerEntry)));
+
+    // Incomplete pushes cannot be finalized.
+    let res = client.try_finalize_winners_root(&operator, &pool_id);
+    assert_eq!(res, Err(Ok(PredifiError::WinnersSummaryMismatch)));
+
+    assert_eq!(
+        client.push_winner_entries(&operator, &pool_id, &vec![&env, second]),
+        2
+    );
+    let root = client.finalize_winners_root(&operator, &pool_id);
+
+    let summary = client.get_winners_summary(&pool_id).unwrap();
+    assert_eq!(summary.root, root);
+    assert_eq!(summary.leaf_count, 2);
+    assert_eq!(summary.total_entitlement, 500);
+
+    assert!(client.verify_winner_proof(&pool_id, &u1, &125, &vec![&env, leaf2.clone()]));
+    assert!(client.verify_winner_proof(&pool_id, &u2, &375, &vec![&env, leaf1.clone()]));
+    assert!(!client.verify_winner_proof(&pool_id, &u1, &126, &vec![&env, leaf2]));
+}
+
+// ── Settlement attestation tests ─────────────────────────────────────────────
+
+#[test]
+fn test_settlement_attestation_tracks_winners_root() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Attested Pool"),
+        &String::from_str(&env, "ipfs://attest"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Crypto"),
+    );
+
+    let user = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    token_admin_client.mint(&loser, &50);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &50, &1);
+
+    // Unresolved pools cannot be attested.
+    assert_eq!(
+        client.try_get_settlement_attestation(&pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    let before = client.get_settlement_attestation(&pool_id);
+    assert_eq!(before.contract, client.address);
+    assert_eq!(before.outcome, 0);
+    assert_eq!(before.total_stake, 150);
+    assert_eq!(before.winning_stake, 100);
+    assert_eq!(before.winners_root, BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(client.attest_settlement(&pool_id), before.digest);
+
+    client.push_winner_entries(&operator, &pool_id, &vec![&env, user.clone()]);
+    let root = client.finalize_winners_root(&operator, &pool_id);
+
+    let after = client.get_settlement_attestation(&pool_id);
+    assert_eq!(after.winners_root, root);
+    assert_ne!(after.digest, before.digest);
+}
+
+// ── Error detail tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_error_detail_reports_expected_and_actual_values() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_resolution_delay(&admin, &3_600u64);
+
+    // Stake cap: the room left under the cap against the requested amount.
+    let params = CreatePoolParams {
+        max_total_stake: 300,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &400);
+    client.place_prediction(&user, &pool_id, &200, &0);
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &150),
+        Err(Ok(PredifiError::PoolStakeCapReached))
+    );
+    assert!(emitted_before_failure(
+        &env,
+        &client.address,
+        &ErrorDetailEvent {
+            code: PredifiError::PoolStakeCapReached as u32,
+            pool_id: Some(pool_id),
+            expected: 100,
+            actual: 150,
+        }
+    ));
+
+    // Timing: the earliest resolution time against the ledger time.
+    let end_time = client.get_pool(&pool_id).end_time;
+    env.ledger().with_mut(|li| li.timestamp = end_time + 1);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &0u32),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+    assert!(emitted_before_failure(
+        &env,
+        &client.address,
+        &ErrorDetailEvent {
+            code: PredifiError::ResolutionDelayNotMet as u32,
+            pool_id: Some(pool_id),
+            expected: (end_time + 3_600) as i128,
+            actual: (end_time + 1) as i128,
+        }
+    ));
+
+    // Slippage: the caller's limit against the actual cost.
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let lmsr_id = lmsr_pool(&env, &client, &token_admin_client, &token_address, &creator);
+    client.set_pool_amm_fees(&creator, &lmsr_id, &0, &100);
+    let trader = Address::generate(&env);
+    token_admin_client.mint(&trader, &1_000);
+    assert_eq!(
+        client.try_buy_shares(&trader, &lmsr_id, &0, &1_000, &514),
+        Err(Ok(PredifiError::SlippageExceeded))
+    );
+    assert!(emitted_before_failure(
+        &env,
+        &client.address,
+        &ErrorDetailEvent {
+            code: PredifiError::SlippageExceeded as u32,
+            pool_id: Some(lmsr_id),
+            expected: 514,
+            actual: 515,
+        }
+    ));
+}
+
+// ── User topic tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_bet_and_claim_events_carry_user_topic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Topic Pool"),
+        &String::from_str(&env, "ipfs://topic"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    token_admin_client.mint(&other, &100);
+
+    client.place_prediction(&user, &pool_id, &100, &0);
+    assert!(published_with_user_topic(&env, "prediction_placed", &user));
+    assert!(!published_with_user_topic(
+        &env,
+        "prediction_placed",
+        &other
+    ));
+
+    client.place_prediction(&other, &pool_id, &100, &1);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    client.claim_winnings(&user, &pool_id);
+    assert!(published_with_user_topic(&env, "winnings_claimed", &user));
+    assert!(!published_with_user_topic(&env, "winnings_claimed", &other));
+}
+
+// ── Outcome stake event tests ────────────────────────────────────────────────
+
+#[test]
+fn test_outcome_stake_event_switches_at_batch_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+
+    let pool_with = |options_count: u32| {
+        client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &options_count,
+            &String::from_str(&env, "Threshold Pool"),
+            &String::from_str(&env, "ipfs://threshold"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        )
+    };
+
+    let small = pool_with(BATCH_STAKE_EVENT_MIN_OPTIONS - 1);
+    client.place_prediction(&user, &small, &100, &0);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &OutcomeStakeDeltaEvent {
+            pool_id: small,
+            outcome: 0,
+            delta: 100,
+            new_total: 100,
+        }
+    ));
+    assert!(!emitted(
+        &env,
+        &client.address,
+        &OutcomeStakesUpdatedEvent {
+            pool_id: small,
+            options_count: BATCH_STAKE_EVENT_MIN_OPTIONS - 1,
+            total_stake: 100,
+        }
+    ));
+
+    let large = pool_with(BATCH_STAKE_EVENT_MIN_OPTIONS);
+    client.place_prediction(&user, &large, &100, &0);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &OutcomeStakesUpdatedEvent {
+            pool_id: large,
+            options_count: BATCH_STAKE_EVENT_MIN_OPTIONS,
+            total_stake: 100,
+        }
+    ));
+    assert!(!emitted(
+        &env,
+        &client.address,
+        &OutcomeStakeDeltaEvent {
+            pool_id: large,
+            outcome: 0,
+            delta: 100,
+            new_total: 100,
+        }
+    ));
+}
+
+// ── Ledger entry tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_ledger_entries_track_every_token_movement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1000u32);
+    client.set_claim_period(&admin, &86_400u64);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Ledger Pool"),
+        &String::from_str(&env, "ipfs://ledger"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let entry = |account: &Address, direction: LedgerDirection, amount: i128, reason: &str| {
+        LedgerEntryEvent {
+            pool_id: Some(pool_id),
+            account: account.clone(),
+            token: token_address.clone(),
+            direction,
+            amount,
+            reason: Symbol::new(&env, reason),
+        }
+    };
+
+    let winner = Address::generate(&env);
+    let idle = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let leaver = Address::generate(&env);
+    for user in [&winner, &idle, &loser, &leaver] {
+        token_admin_client.mint(user, &100);
+    }
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &entry(&winner, LedgerDirection::In, 100, "stake")
+    ));
+    client.place_prediction(&idle, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+    client.place_prediction(&leaver, &pool_id, &100, &1);
+
+    let refund = client.withdraw_prediction(&leaver, &pool_id, &None);
+    assert_eq!(refund, 100);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &entry(&leaver, LedgerDirection::Out, refund, "refund")
+    ));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    let payout = client.claim_winnings(&winner, &pool_id);
+    assert_eq!(payout, 135);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &entry(&winner, LedgerDirection::Out, payout, "payout")
+    ));
+
+    let fees = client.withdraw_fees(&admin, &token_address);
+    assert_eq!(fees, 15);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &LedgerEntryEvent {
+            pool_id: None,
+            account: treasury.clone(),
+            token: token_address.clone(),
+            direction: LedgerDirection::Out,
+            amount: fees,
+            reason: Symbol::new(&env, "fee"),
+        }
+    ));
+
+    env.ledger().with_mut(|li| li.timestamp = 100000 + 86_400);
+    let swept = client.sweep_unclaimed(&admin, &pool_id);
+    assert_eq!(swept, 150);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &entry(&treasury, LedgerDirection::Out, swept, "sweep")
+    ));
+}
+
+// ── Read interface tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_read_interface_client_matches_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Interface Pool"),
+        &String::from_str(&env, "ipfs://interface"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &1);
+
+    let reader = crate::interface::PredifiReadClient::new(&env, &client.address);
+    let pool = reader.get_pool(&pool_id);
+    assert_eq!(pool.total_stake, client.get_pool(&pool_id).total_stake);
+    assert_eq!(pool.state, MarketState::Active);
+    assert_eq!(reader.get_outcome_stake(&pool_id, &1u32), 100);
+    assert_eq!(reader.get_active_pool_count(), 1);
+    assert!(reader.is_token_allowed(&token_address));
+    assert_eq!(
+        reader.try_get_settlement_attestation(&pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &1u32);
+    assert_eq!(
+        reader.get_settlement_attestation(&pool_id),
+        client.get_settlement_attestation(&pool_id)
+    );
+}
+
+#[test]
+fn test_read_interface_covers_every_view() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Interface Pool"),
+        &String::from_str(&env, "ipfs://interface"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &1u32);
+
+    // Every `PredifiRead` method, dispatched through the read client, must
+    // decode to what the contract client returns.
+    let reader = crate::interface::PredifiReadClient::new(&env, &client.address);
+    let pool = reader.get_pool(&pool_id);
+    assert_eq!(pool.total_stake, client.get_pool(&pool_id).total_stake);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(
+        reader.try_get_pool_summary(&pool_id),
+        client.try_get_pool_summary(&pool_id)
+    );
+    assert_eq!(reader.get_pool_count(), client.get_pool_count());
+    assert_eq!(
+        reader.get_pools(&0u64, &10u32),
+        client.get_pools(&0u64, &10u32)
+    );
+    assert_eq!(
+        reader.is_pool_hidden(&pool_id),
+        client.is_pool_hidden(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_outcome_stakes(&pool_id),
+        client.get_pool_outcome_stakes(&pool_id)
+    );
+    assert_eq!(
+        reader.get_implied_odds(&pool_id),
+        client.get_implied_odds(&pool_id)
+    );
+    assert_eq!(
+        reader.get_outcome_stake(&pool_id, &1u32),
+        client.get_outcome_stake(&pool_id, &1u32)
+    );
+    assert_eq!(
+        reader.get_pool_stats(&pool_id),
+        client.get_pool_stats(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pools_by_category(&symbol_short!("Tech"), &0u32, &10u32),
+        client.get_pools_by_category(&symbol_short!("Tech"), &0u32, &10u32)
+    );
+    assert_eq!(
+        reader.get_top_positions(&pool_id, &0u32, &10u32),
+        client.get_top_positions(&pool_id, &0u32, &10u32)
+    );
+    assert_eq!(
+        reader.get_user_predictions(&user, &0u32, &10u32).len(),
+        client.get_user_predictions(&user, &0u32, &10u32).len()
+    );
+    assert_eq!(
+        reader.get_claimed_amount(&user, &pool_id),
+        client.get_claimed_amount(&user, &pool_id)
+    );
+    assert_eq!(
+        reader.get_active_pool_count(),
+        client.get_active_pool_count()
+    );
+    assert_eq!(reader.get_capabilities(), client.get_capabilities());
+    assert_eq!(
+        reader.is_token_allowed(&token_address),
+        client.is_token_allowed(&token_address)
+    );
+    assert_eq!(
+        reader.get_operator_note(&pool_id),
+        client.get_operator_note(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_notes(&pool_id),
+        client.get_pool_notes(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_sponsor(&pool_id),
+        client.get_pool_sponsor(&pool_id)
+    );
+    assert_eq!(
+        reader.get_display_key(&symbol_short!("Tech")),
+        client.get_display_key(&symbol_short!("Tech"))
+    );
+    assert_eq!(
+        reader.get_display_keys(&vec![&env, symbol_short!("Tech")]),
+        client.get_display_keys(&vec![&env, symbol_short!("Tech")])
+    );
+    assert_eq!(
+        reader.get_derivative_reference(&pool_id),
+        client.get_derivative_reference(&pool_id)
+    );
+    assert_eq!(
+        reader.get_held_payout(&user, &pool_id),
+        client.get_held_payout(&user, &pool_id)
+    );
+    assert_eq!(
+        reader.get_winners_summary(&pool_id),
+        client.get_winners_summary(&pool_id)
+    );
+    assert_eq!(
+        reader.verify_winner_proof(&pool_id, &user, &100i128, &Vec::new(&env)),
+        client.verify_winner_proof(&pool_id, &user, &100i128, &Vec::new(&env))
+    );
+    assert_eq!(
+        reader.try_get_settlement_attestation(&pool_id),
+        client.try_get_settlement_attestation(&pool_id)
+    );
+    assert_eq!(reader.get_pause_scopes(), client.get_pause_scopes());
+    assert_eq!(reader.get_dead_man_switch(), client.get_dead_man_switch());
+    assert_eq!(
+        reader.get_last_admin_action(),
+        client.get_last_admin_action()
+    );
+    assert_eq!(
+        reader.get_dead_man_triggered_at(),
+        client.get_dead_man_triggered_at()
+    );
+    assert_eq!(reader.get_creator_bond(), client.get_creator_bond());
+    assert_eq!(
+        reader.get_pool_bond(&pool_id),
+        client.get_pool_bond(&pool_id)
+    );
+    assert_eq!(
+        reader.get_max_pool_duration(),
+        client.get_max_pool_duration()
+    );
+    assert_eq!(reader.get_fee_tiers(), client.get_fee_tiers());
+    assert_eq!(
+        reader.get_operator_sla_stats(&operator),
+        client.get_operator_sla_stats(&operator)
+    );
+    assert_eq!(
+        reader.get_oracle_quorum(&pool_id),
+        client.get_oracle_quorum(&pool_id)
+    );
+    assert_eq!(
+        reader.get_oracle_submissions(&pool_id),
+        client.get_oracle_submissions(&pool_id)
+    );
+    assert_eq!(
+        reader.get_claims_open_at(&pool_id),
+        client.get_claims_open_at(&pool_id)
+    );
+    assert_eq!(reader.get_config().treasury, client.get_config().treasury);
+    assert_eq!(reader.is_paused(), client.is_paused());
+    assert_eq!(
+        reader.get_config_history(&0u32, &10u32),
+        client.get_config_history(&0u32, &10u32)
+    );
+    assert_eq!(
+        reader.get_compensation_reserve(&token_address),
+        client.get_compensation_reserve(&token_address)
+    );
+    assert_eq!(
+        reader.get_yield_adapter(&token_address),
+        client.get_yield_adapter(&token_address)
+    );
+    assert_eq!(
+        reader.get_yield_deposit(&pool_id),
+        client.get_yield_deposit(&pool_id)
+    );
+    assert_eq!(reader.is_flagged(&user), client.is_flagged(&user));
+    assert_eq!(
+        reader.get_claim_deadline(&pool_id),
+        client.get_claim_deadline(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_analytics(&pool_id),
+        client.get_pool_analytics(&pool_id)
+    );
+    assert_eq!(
+        reader.get_lock_time(&pool_id),
+        client.get_lock_time(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_unclaimed(&pool_id),
+        client.get_pool_unclaimed(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_escrow(&pool_id),
+        client.get_pool_escrow(&pool_id)
+    );
+    assert_eq!(
+        reader.get_sweep_schedule(&pool_id),
+        client.get_sweep_schedule(&pool_id)
+    );
+    assert_eq!(
+        reader.try_get_claim_progress(&pool_id),
+        client.try_get_claim_progress(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_archived_at(&pool_id),
+        client.get_pool_archived_at(&pool_id)
+    );
+    assert_eq!(
+        reader.is_public_goods_recipient(&user),
+        client.is_public_goods_recipient(&user)
+    );
+    assert_eq!(
+        reader.get_airdrop_epoch(&0u32),
+        client.get_airdrop_epoch(&0u32)
+    );
+    assert_eq!(
+        reader.get_airdrop_snapshot(&0u32, &0u32, &10u32),
+        client.get_airdrop_snapshot(&0u32, &0u32, &10u32)
+    );
+    assert_eq!(
+        reader.get_user_activity(&user),
+        client.get_user_activity(&user)
+    );
+    assert_eq!(reader.version(), client.version());
+    assert_eq!(reader.storage_version(), client.storage_version());
+    assert_eq!(
+        reader.get_resolution_commitment(&pool_id),
+        client.get_resolution_commitment(&pool_id)
+    );
+    assert_eq!(
+        reader.get_optimistic_config(),
+        client.get_optimistic_config()
+    );
+    assert_eq!(
+        reader.get_outcome_assertion(&pool_id),
+        client.get_outcome_assertion(&pool_id)
+    );
+    assert_eq!(
+        reader.simulate_batch(&Vec::new(&env)),
+        client.simulate_batch(&Vec::new(&env))
+    );
+    assert_eq!(
+        reader.try_quote_prediction(&pool_id, &1u32, &100i128),
+        client.try_quote_prediction(&pool_id, &1u32, &100i128)
+    );
+    assert_eq!(
+        reader.try_simulate_claim(&user, &pool_id),
+        client.try_simulate_claim(&user, &pool_id)
+    );
+    assert_eq!(
+        reader.get_outcome_weights(&pool_id),
+        client.get_outcome_weights(&pool_id)
+    );
+    assert_eq!(
+        reader.get_outcome_labels(&pool_id),
+        client.get_outcome_labels(&pool_id)
+    );
+    assert_eq!(
+        reader.get_scalar_buckets(&pool_id),
+        client.get_scalar_buckets(&pool_id)
+    );
+    assert_eq!(
+        reader.get_cancel_request(&pool_id),
+        client.get_cancel_request(&pool_id)
+    );
+    assert_eq!(
+        reader.get_odds_alerts(&pool_id),
+        client.get_odds_alerts(&pool_id)
+    );
+    assert_eq!(
+        reader.get_bet_legs(&user, &pool_id),
+        client.get_bet_legs(&user, &pool_id)
+    );
+    assert_eq!(reader.get_template(&0u32), client.get_template(&0u32));
+    assert_eq!(
+        reader.get_creator_fees_accrued(&creator, &token_address),
+        client.get_creator_fees_accrued(&creator, &token_address)
+    );
+    assert_eq!(
+        reader.get_pool_public_goods(&pool_id),
+        client.get_pool_public_goods(&pool_id)
+    );
+    assert_eq!(
+        reader.get_public_goods_accrued(&user, &token_address),
+        client.get_public_goods_accrued(&user, &token_address)
+    );
+    assert_eq!(reader.get_claim_tip(&user), client.get_claim_tip(&user));
+    assert_eq!(
+        reader.get_claim_sequence(&pool_id),
+        client.get_claim_sequence(&pool_id)
+    );
+    assert_eq!(
+        reader.get_parent_condition(&pool_id),
+        client.get_parent_condition(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_amm_fees(&pool_id),
+        client.get_pool_amm_fees(&pool_id)
+    );
+    assert_eq!(
+        reader.is_market_maker(&pool_id, &user),
+        client.is_market_maker(&pool_id, &user)
+    );
+    assert_eq!(
+        reader.get_quotes(&pool_id, &user),
+        client.get_quotes(&pool_id, &user)
+    );
+    assert_eq!(
+        reader.get_trading_fee_bps(&pool_id, &user),
+        client.get_trading_fee_bps(&pool_id, &user)
+    );
+    assert_eq!(
+        reader.get_pool_mechanism(&pool_id),
+        client.get_pool_mechanism(&pool_id)
+    );
+    assert_eq!(
+        reader.get_lmsr_state(&pool_id),
+        client.get_lmsr_state(&pool_id)
+    );
+    assert_eq!(
+        reader.get_lmsr_position(&pool_id, &user),
+        client.get_lmsr_position(&pool_id, &user)
+    );
+    assert_eq!(
+        reader.try_get_pool_liquidity(&pool_id),
+        client.try_get_pool_liquidity(&pool_id)
+    );
+    assert_eq!(reader.is_blocked(&user), client.is_blocked(&user));
+    assert_eq!(
+        reader.get_pool_callback(&pool_id),
+        client.get_pool_callback(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pending_pool_callback(&pool_id),
+        client.get_pending_pool_callback(&pool_id)
+    );
+    assert_eq!(reader.get_curation_config(), client.get_curation_config());
+    assert_eq!(
+        reader.is_pool_draft(&pool_id),
+        client.is_pool_draft(&pool_id)
+    );
+    assert_eq!(
+        reader.get_fees_accrued(&token_address),
+        client.get_fees_accrued(&token_address)
+    );
+    assert_eq!(reader.is_hot_pool(&pool_id), client.is_hot_pool(&pool_id));
+    assert_eq!(reader.get_keeper_config(), client.get_keeper_config());
+    assert_eq!(
+        reader.get_authorized_outcome(&pool_id),
+        client.get_authorized_outcome(&pool_id)
+    );
+    assert_eq!(
+        reader.get_pool_keeper(&pool_id),
+        client.get_pool_keeper(&pool_id)
+    );
+    assert_eq!(
+        reader.get_migration_consent(&user, &pool_id),
+        client.get_migration_consent(&user, &pool_id)
+    );
+    assert_eq!(
+        reader.get_price_market(&pool_id),
+        client.get_price_market(&pool_id)
+    );
+    assert_eq!(
+        reader.is_pool_private(&pool_id),
+        client.is_pool_private(&pool_id)
+    );
+    assert_eq!(
+        reader.is_allowed_bettor(&pool_id, &user),
+        client.is_allowed_bettor(&pool_id, &user)
+    );
+    assert_eq!(
+        reader.get_ref_code_owner(&symbol_short!("ref")),
+        client.get_ref_code_owner(&symbol_short!("ref"))
+    );
+    assert_eq!(reader.get_referrer(&user), client.get_referrer(&user));
+    assert_eq!(
+        reader.get_referral_rewards(&user, &token_address),
+        client.get_referral_rewards(&user, &token_address)
+    );
+    assert_eq!(
+        reader.get_token_params(&token_address),
+        client.get_token_params(&token_address)
+    );
+}
+
+// ── Storage layout compatibility tests ───────────────────────────────────────
+
+#[test]
+fn test_config_v1_storage_is_readable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, treasury, _, creator) = setup(&env);
+
+    // Simulate an entry written before `max_active_pools` existed.
+    env.as_contract(&client.address, || {
+        let v1 = ConfigV1 {
+            fee_bps: 0,
+            treasury: treasury.clone(),
+            access_control: ac_client.address.clone(),
+            resolution_delay: 0,
+        };
+        env.storage().instance().set(&DataKey::Config, &v1);
+    });
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Legacy Config Pool"),
+        &String::from_str(&env, "ipfs://legacy"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    assert_eq!(client.get_pool(&pool_id).creator, creator);
+
+    // Admin updates rewrite the entry in the current layout.
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_max_active_pools(&admin, &5u32);
+    env.as_contract(&client.address, || {
+        let config: ConfigV3 = env.storage().instance().get(&DataKey::Config).unwrap();
+        assert_eq!(config.max_active_pools, 5);
+        assert_eq!(config.treasury, treasury);
+    });
+}
+
+#[test]
+fn test_migrate_state_upgrades_unversioned_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, treasury, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    // Simulate an instance deployed before storage versioning existed.
+    env.as_contract(&client.address, || {
+        let v1 = ConfigV1 {
+            fee_bps: 0,
+            treasury: treasury.clone(),
+            access_control: ac_client.address.clone(),
+            resolution_delay: 0,
+        };
+        env.storage().instance().set(&DataKey::Config, &v1);
+        env.storage()
+            .instance()
+            .remove(&DataKey::Ext(ExtKey::StorageVersion));
+    });
+
+    assert_eq!(client.storage_version(), 0);
+
+    client.migrate_state(&admin);
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    env.as_contract(&client.address, || {
+        let config: ConfigV3 = env.storage().instance().get(&DataKey::Config).unwrap();
+        assert_eq!(config.treasury, treasury);
+    });
+
+    // Already current: a second run changes nothing.
+    client.migrate_state(&admin);
+}
+
+#[test]
+fn test_config_v2_storage_is_readable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, treasury, _, _) = setup(&env);
+
+    // Simulate an entry written before the settlement hold settings existed.
+    env.as_contract(&client.address, || {
+        let v2 = ConfigV2 {
+            fee_bps: 0,
+            treasury: treasury.clone(),
+            access_control: ac_client.address.clone(),
+            resolution_delay: 0,
+            max_active_pools: 7,
+        };
+        env.storage().instance().set(&DataKey::Config, &v2);
+    });
+
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_settlement_hold(&admin, &1_000i128, &3600u64);
+    env.as_contract(&client.address, || {
+        let config: ConfigV3 = env.storage().instance().get(&DataKey::Config).unwrap();
+        assert_eq!(config.max_active_pools, 7);
+        assert_eq!(config.settlement_hold_threshold, 1_000);
+        assert_eq!(config.settlement_hold_delay, 3600);
+    });
+}
+
+#[test]
+fn test_frozen_layout_field_names() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Layout Pool"),
+        &String::from_str(&env, "ipfs://layout"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &10);
+    client.place_prediction(&user, &pool_id, &10, &0);
+
+    let keys_of = |key: DataKey, instance: bool| -> Vec<Symbol> {
+        env.as_contract(&client.address, || {
+            let raw: Map<Symbol, Val> = if instance {
+                env.storage().instance().get(&key).unwrap()
+            } else {
+                env.storage().persistent().get(&key).unwrap()
+            };
+            raw.keys()
+        })
+    };
+    let expected = |names: &[&str]| -> Vec<Symbol> {
+        let mut v = Vec::new(&env);
+        for name in names {
+            v.push_back(Symbol::new(&env, name));
+        }
+        v
+    };
+
+    assert_eq!(POOL_LAYOUT_VERSION, 1);
+    assert_eq!(
+        keys_of(DataKey::Pool(pool_id), false),
+        expected(&[
+            "canceled",
+            "category",
+            "creator",
+            "description",
+            "end_time",
+            "initial_liquidity",
+            "max_stake",
+            "metadata_url",
+            "min_stake",
+            "options_count",
+            "outcome",
+            "resolved",
+            "state",
+            "token",
+            "total_stake",
+        ])
+    );
+
+    assert_eq!(PREDICTION_LAYOUT_VERSION, 1);
+    assert_eq!(
+        keys_of(DataKey::Prediction(user, pool_id), false),
+        expected(&["amount", "outcome"])
+    );
+
+    assert_eq!(CONFIG_LAYOUT_VERSION, 3);
+    assert_eq!(
+        keys_of(DataKey::Config, true),
+        expected(&[
+            "access_control",
+            "fee_bps",
+            "max_active_pools",
+            "resolution_delay",
+            "settlement_hold_delay",
+            "settlement_hold_threshold",
+            "treasury",
+        ])
+    );
+}
+
+// ── Batch resolution tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_pools_batch_reports_per_item_results() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+
+    let mut pools = Vec::new(&env);
+    for _ in 0..3 {
+        pools.push_back(client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Match Day Pool"),
+            &String::from_str(&env, "ipfs://matchday"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Sports"),
+        ));
+    }
+    let (p1, p2, p3) = (
+        pools.get(0).unwrap(),
+        pools.get(1).unwrap(),
+        pools.get(2).unwrap(),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    let result = client.resolve_pools_batch(
+        &operator,
+        &vec![&env, (p1, 0u32), (p2, 5u32), (999u64, 0u32), (p3, 1u32)],
+    );
+
+    assert_eq!(result.processed, 4);
+    assert_eq!(
+        result.results,
+        vec![
+            &env,
+            BatchItemResult {
+                pool_id: p1,
+                error: 0
+            },
+            BatchItemResult {
+                pool_id: p2,
+                error: PredifiError::InvalidOutcome as u32
+            },
+            BatchItemResult {
+                pool_id: 999,
+                error: PredifiError::PoolNotFound as u32
+            },
+            BatchItemResult {
+                pool_id: p3,
+                error: 0
+            },
+        ]
+    );
+    assert_eq!(client.get_pool(&p1).state, MarketState::Resolved);
+    assert_eq!(client.get_pool(&p2).state, MarketState::Active);
+    assert_eq!(client.get_pool(&p3).outcome, 1);
+
+    // Already-resolved pools are reported, not re-resolved.
+    let result = client.resolve_pools_batch(&operator, &vec![&env, (p1, 1u32)]);
+    assert_eq!(
+        result.results.get(0).unwrap().error,
+        PredifiError::InvalidPoolState as u32
+    );
+    assert_eq!(client.get_pool(&p1).outcome, 0);
+}
+
+#[test]
+fn test_resolve_pools_batch_caps_processed_items() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, _, _, _, _, operator, _) = setup(&env);
+
+    let mut items = Vec::new(&env);
+    for pool_id in 0..30u64 {
+        items.push_back((pool_id + 1000, 0u32));
+    }
+    let result = client.resolve_pools_batch(&operator, &items);
+    assert_eq!(result.processed, 25);
+    assert_eq!(result.results.len(), 25);
+}
+
+#[test]
+fn test_resolve_pools_batch_requires_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, _, _, _, _, _, _) = setup(&env);
+    let stranger = Address::generate(&env);
+    let res = client.try_resolve_pools_batch(&stranger, &vec![&env, (0u64, 0u32)]);
+    assert_eq!(res, Err(Ok(PredifiError::Unauthorized)));
+}
+
+#[test]
+fn test_cancel_pools_batch_with_shared_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+
+    let mut pools = Vec::new(&env);
+    for _ in 0..3 {
+        pools.push_back(client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Round Pool"),
+            &String::from_str(&env, "ipfs://round"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Sports"),
+        ));
+    }
+    let (p1, p2, p3) = (
+        pools.get(0).unwrap(),
+        pools.get(1).unwrap(),
+        pools.get(2).unwrap(),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &p2, &0u32);
+
+    let reason = String::from_str(&env, "round postponed");
+    let result = client.cancel_pools_batch(&operator, &vec![&env, p1, p2, 999u64, p3], &reason);
+
+    assert_eq!(result.processed, 4);
+    let mut errors = Vec::new(&env);
+    for item in result.results.iter() {
+        errors.push_back(item.error);
+    }
+    assert_eq!(
+        errors,
+        vec![
+            &env,
+            0u32,
+            PredifiError::PoolNotResolved as u32,
+            PredifiError::PoolNotFound as u32,
+            0
+        ]
+    );
+    assert_eq!(client.get_pool(&p1).state, MarketState::Canceled);
+    assert_eq!(client.get_pool(&p2).state, MarketState::Resolved);
+    assert_eq!(client.get_pool(&p3).state, MarketState::Canceled);
+    assert_eq!(client.get_operator_note(&p3).unwrap().note, reason);
+
+    // Canceling again is reported per item.
+    let result = client.cancel_pools_batch(&operator, &vec![&env, p1], &reason);
+    assert_eq!(
+        result.results.get(0).unwrap().error,
+        PredifiError::InvalidPoolState as u32
+    );
+}
+
+// ── Batch whitelist tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_set_token_whitelist_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let t1 = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let t2 = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let processed = client.set_token_whitelist_batch(
+        &admin,
+        &vec![
+            &env,
+            (t1.clone(), Some(0i128)),
+            (t2.clone(), Some(25i128)),
+            (token_address.clone(), None),
+        ],
+    );
+    assert_eq!(processed, 3);
+    assert!(client.is_token_allowed(&t1));
+    assert!(client.is_token_allowed(&t2));
+    assert_eq!(client.get_token_params(&t2).min_stake, 25);
+    assert!(!client.is_token_allowed(&token_address));
+
+    // Every whitelisted token must report acceptable decimals.
+    let wide = env.register(crate::test_utils::dummy_wide_token::DummyWideToken, ());
+    assert_eq!(
+        client.try_set_token_whitelist_batch(&admin, &vec![&env, (wide.clone(), Some(0i128))]),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert!(!client.is_token_allowed(&wide));
+
+    let stranger = Address::generate(&env);
+    let res = client.try_set_token_whitelist_batch(&stranger, &vec![&env, (t1.clone(), None)]);
+    assert_eq!(res, Err(Ok(PredifiError::Unauthorized)));
+    assert!(client.is_token_allowed(&t1));
+}
+
+#[test]
+fn test_set_token_whitelist_batch_caps_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let mut entries = Vec::new(&env);
+    for _ in 0..25 {
+        let token = env.register_stellar_asset_contract_v2(admin.clone());
+        entries.push_back((token.address(), Some(10i128)));
+    }
+    assert_eq!(client.set_token_whitelist_batch(&admin, &entries), 15);
+    let last = entries.get(14).unwrap().0;
+    assert!(client.is_token_allowed(&last));
+    assert_eq!(
+        client.get_token_params(&last),
+        TokenParams {
+            decimals: 7,
+            min_stake: 10
+        }
+    );
+    assert!(!client.is_token_allowed(&entries.get(15).unwrap().0));
+
+    let removals = vec![&env, (last.clone(), None)];
+    assert_eq!(client.set_token_whitelist_batch(&admin, &removals), 1);
+    assert!(!client.is_token_allowed(&last));
+}
+
+// ── Protocol fee tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_claim_deducts_protocol_fee_excluding_initial_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+
+    token_admin_client.mint(&creator, &100);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Fee Pool"),
+        &String::from_str(&env, "ipfs://fee"),
+        &1i128,
+        &0i128,
+        &100i128,
+        &symbol_short!("Tech"),
+    );
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // The creator's liquidity seeds 50 on each outcome, so the winner gets
+    // 100 * 300 / 150 = 200 gross, a third of it funded by initial liquidity:
+    // fee = 10% of 200 * 200 / 300 = 13.
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 187);
+    assert_eq!(token.balance(&winner), 187);
+    assert_eq!(client.get_fees_accrued(&token_address), 13);
+    assert_eq!(client.claim_winnings(&loser, &pool_id), 0);
+    assert_eq!(client.withdraw_fees(&admin, &token_address), 13);
+    assert_eq!(token.balance(&treasury), 13);
+}
+
+#[test]
+fn test_withdraw_fees_is_admin_only_and_kept_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+
+    let other_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let other = token::Client::new(&env, &other_address);
+    let other_admin_client = token::StellarAssetClient::new(&env, &other_address);
+    client.add_token_to_whitelist(&admin, &other_address);
+
+    // Nothing accrued yet: no transfer, no event.
+    assert_eq!(client.withdraw_fees(&admin, &token_address), 0);
+    assert!(env.events().all().events().is_empty());
+    assert_eq!(token.balance(&treasury), 0);
+
+    // Pools in two tokens; each winner collects the whole 200 or 400 pot.
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    other_admin_client.mint(&winner, &200);
+    other_admin_client.mint(&loser, &200);
+    let mut pools = Vec::new(&env);
+    for (pool_token, stake) in [(&token_address, 100i128), (&other_address, 200i128)] {
+        let pool_id = client.create_pool(
+            &creator,
+            &100000u64,
+            pool_token,
+            &2u32,
+            &String::from_str(&env, "Fee Ledger Pool"),
+            &String::from_str(&env, "ipfs://feeledger"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        );
+        client.place_prediction(&winner, &pool_id, &stake, &0);
+        client.place_prediction(&loser, &pool_id, &stake, &1);
+        pools.push_back(pool_id);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    for pool_id in pools.iter() {
+        client.resolve_pool(&operator, &pool_id, &0u32);
+        client.claim_winnings(&winner, &pool_id);
+    }
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+    assert_eq!(client.get_fees_accrued(&other_address), 40);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_withdraw_fees(&stranger, &token_address),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    assert!(emitted_before_failure(
+        &env,
+        &client.address,
+        &UnauthorizedAdminAttemptEvent {
+            caller: stranger,
+            operation: Symbol::new(&env, "withdraw_fees"),
+            timestamp: 100001,
+        }
+    ));
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+
+    // Withdrawing one token clears its balance and leaves the other untouched.
+    assert_eq!(client.withdraw_fees(&admin, &token_address), 20);
+    assert_eq!(token.balance(&treasury), 20);
+    assert_eq!(client.get_fees_accrued(&token_address), 0);
+    assert_eq!(client.get_fees_accrued(&other_address), 40);
+    assert_eq!(other.balance(&treasury), 0);
+    assert_eq!(client.withdraw_fees(&admin, &token_address), 0);
+    assert_eq!(token.balance(&treasury), 20);
+
+    assert_eq!(client.withdraw_fees(&admin, &other_address), 40);
+    assert_eq!(other.balance(&treasury), 40);
+    assert_eq!(client.get_fees_accrued(&other_address), 0);
+}
+
+#[test]
+fn test_partial_claim_collects_fee_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &500u32);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Partial Fee Pool"),
+        &String::from_str(&env, "ipfs://partialfee"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &200);
+    token_admin_client.mint(&loser, &200);
+    client.place_prediction(&winner, &pool_id, &200, &0);
+    client.place_prediction(&loser, &pool_id, &200, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Gross 400, fee 20, net 380.
+    client.claim_partial(&winner, &pool_id, &100);
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 280);
+    assert_eq!(token.balance(&winner), 380);
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+}
+
+#[test]
+fn test_fee_rate_locked_at_resolution_across_partial_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &500u32);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Locked Fee Pool"),
+        &String::from_str(&env, "ipfs://lockedfee"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &200);
+    token_admin_client.mint(&loser, &200);
+    client.place_prediction(&winner, &pool_id, &200, &0);
+    client.place_prediction(&loser, &pool_id, &200, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Gross 400, fee 20 at the 5% rate locked in at resolution.
+    client.claim_partial(&winner, &pool_id, &100);
+
+    // Neither a new base rate nor a matching tier changes the entitlement.
+    client.set_fee_bps(&admin, &0u32);
+    client.set_fee_tiers(
+        &admin,
+        &vec![
+            &env,
+            FeeTier {
+                min_total_stake: 0,
+                fee_bps: 1000,
+            },
+        ],
+    );
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 280);
+    assert_eq!(token.balance(&winner), 380);
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+}
+
+#[test]
+fn test_canceled_refunds_are_fee_free() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Refund Pool"),
+        &String::from_str(&env, "ipfs://refund"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    client.cancel_pool(&operator, &pool_id);
+
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+    assert_eq!(token.balance(&user), 100);
+    assert_eq!(token.balance(&treasury), 0);
+}
+
+// ── Sweep scheduling tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_sweep_requires_announcement_and_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Sweep Pool"),
+        &String::from_str(&env, "ipfs://sweep"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let u1 = Address::generate(&env);
+    let u2 = Address::generate(&env);
+    let loser = Address::generate(&env);
+    for user in [&u1, &u2, &loser] {
+        token_admin_client.mint(user, &100);
+    }
+    client.place_prediction(&u1, &pool_id, &100, &0);
+    client.place_prediction(&u2, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    // Active pools cannot be swept.
+    assert_eq!(
+        client.try_schedule_sweep(&admin, &pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&u1, &pool_id), 150);
+
+    // Nothing can be swept without an announcement.
+    assert_eq!(
+        client.try_execute_sweep(&pool_id),
+        Err(Ok(PredifiError::SweepNotScheduled))
+    );
+
+    let executable_at = client.schedule_sweep(&admin, &pool_id);
+    assert_eq!(executable_at, 100001 + 7 * 24 * 60 * 60);
+    assert_eq!(
+        client.try_schedule_sweep(&admin, &pool_id),
+        Err(Ok(PredifiError::SweepAlreadyScheduled))
+    );
+    assert_eq!(
+        client.try_execute_sweep(&pool_id),
+        Err(Ok(PredifiError::SweepGracePeriodActive))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = executable_at);
+    assert_eq!(client.execute_sweep(&pool_id), 150);
+    assert_eq!(token.balance(&treasury), 150);
+    assert!(client.get_sweep_schedule(&pool_id).unwrap().executed);
+
+    assert_eq!(
+        client.try_claim_winnings(&u2, &pool_id),
+        Err(Ok(PredifiError::PoolSwept))
+    );
+    assert_eq!(
+        client.try_execute_sweep(&pool_id),
+        Err(Ok(PredifiError::PoolSwept))
+    );
+}
+
+#[test]
+fn test_claims_allowed_during_sweep_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Grace Pool"),
+        &String::from_str(&env, "ipfs://grace"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    let executable_at = client.schedule_sweep(&admin, &pool_id);
+
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = executable_at);
+    assert_eq!(client.execute_sweep(&pool_id), 0);
+    assert_eq!(token.balance(&treasury), 0);
+    assert_eq!(token.balance(&user), 100);
+}
+
+// ── Increase stake tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_increase_stake_tops_up_existing_prediction() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Top Up Pool"),
+        &String::from_str(&env, "ipfs://topup"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    assert_eq!(client.increase_stake(&user, &pool_id, &200), 300);
+    assert_eq!(token.balance(&user), 0);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 400);
+    assert_eq!(client.get_outcome_stake(&pool_id, &0u32), 300);
+    assert_eq!(client.get_outcome_stake(&pool_id, &1u32), 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 400);
+}
+
+#[test]
+fn test_increase_stake_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Capped Pool"),
+        &String::from_str(&env, "ipfs://capped"),
+        &1i128,
+        &150i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    assert_eq!(
+        client.try_increase_stake(&stranger, &pool_id, &10),
+        Err(Ok(PredifiError::PredictionNotFound))
+    );
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &0),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    // The topped-up position may not exceed max_stake.
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &51),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert_eq!(client.increase_stake(&user, &pool_id, &50), 150);
+
+    env.ledger().with_mut(|li| li.timestamp = 100000);
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &1),
+        Err(Ok(PredifiError::PredictionTooLate))
+    );
+}
+
+#[test]
+fn test_repeat_place_prediction_keeps_both_stakes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Repeat Bet Pool"),
+        &String::from_str(&env, "ipfs://repeat"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    client.place_prediction(&user, &pool_id, &200, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    // A bet on the other outcome would orphan the first stake.
+    token_admin_client.mint(&user, &50);
+    assert_eq!(
+        client.try_place_prediction(&user, &pool_id, &50, &1),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::PositionConflict as u32
+        )))
+    );
+
+    assert_eq!(client.get_pool(&pool_id).total_stake, 400);
+    assert_eq!(client.get_outcome_stake(&pool_id, &0u32), 300);
+    assert_eq!(client.get_user_predictions(&user, &0u32, &10u32).len(), 1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 400);
+    assert_eq!(token.balance(&user), 450);
+}
+
+// ── Config history tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_config_history_records_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, treasury, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.set_fee_bps(&admin, &250u32);
+    let new_treasury = Address::generate(&env);
+    client.set_treasury(&admin, &new_treasury);
+    client.set_resolution_delay(&admin, &3600u64);
+
+    let history = client.get_config_history(&0u32, &10u32);
+    assert_eq!(history.len(), 3);
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.seq, 0);
+    assert_eq!(first.field, Symbol::new(&env, "fee_bps"));
+    assert_eq!(first.old_value, ConfigValue::U32(0));
+    assert_eq!(first.new_value, ConfigValue::U32(250));
+    assert_eq!(first.admin, admin);
+    assert_eq!(first.timestamp, 500);
+
+    let second = history.get(1).unwrap();
+    assert_eq!(second.old_value, ConfigValue::Address(treasury));
+    assert_eq!(second.new_value, ConfigValue::Address(new_treasury));
+    assert_eq!(history.get(2).unwrap().new_value, ConfigValue::U64(3600));
+
+    // Paging.
+    let page = client.get_config_history(&1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().seq, 1);
+    assert_eq!(client.get_config_history(&3u32, &10u32).len(), 0);
+}
+
+#[test]
+fn test_config_history_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    for fee in 1..=70u32 {
+        client.set_fee_bps(&admin, &fee);
+    }
+
+    let history = client.get_config_history(&0u32, &100u32);
+    assert_eq!(history.len(), 64);
+    // The six oldest changes were overwritten.
+    let oldest = history.get(0).unwrap();
+    assert_eq!(oldest.seq, 6);
+    assert_eq!(oldest.new_value, ConfigValue::U32(7));
+    assert_eq!(history.get(63).unwrap().new_value, ConfigValue::U32(70));
+}
+
+// ── Withdraw prediction tests ────────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_prediction_rolls_back_stake_and_indexes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_early_exit_fee_bps(&admin, &500u32);
+
+    let pool_a = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Exit Pool A"),
+        &String::from_str(&env, "ipfs://exita"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let pool_b = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Exit Pool B"),
+        &String::from_str(&env, "ipfs://exitb"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    token_admin_client.mint(&other, &50);
+    client.place_prediction(&user, &pool_a, &200, &0);
+    client.place_prediction(&other, &pool_a, &50, &0);
+    client.place_prediction(&user, &pool_b, &100, &1);
+
+    // Partial withdrawal: 5% fee on 100.
+    assert_eq!(
+        client.withdraw_prediction(&user, &pool_a, &Some(100i128)),
+        95
+    );
+    assert_eq!(client.get_fees_accrued(&token_address), 5);
+    assert_eq!(client.get_outcome_stake(&pool_a, &0u32), 150);
+    assert_eq!(client.get_pool(&pool_a).total_stake, 150);
+
+    // Full withdrawal removes the position and its index entries.
+    assert_eq!(client.withdraw_prediction(&user, &pool_a, &None), 95);
+    assert_eq!(token.balance(&user), 190);
+    assert_eq!(client.get_outcome_stake(&pool_a, &0u32), 50);
+    assert_eq!(client.get_pool_stats(&pool_a).participants_count, 1);
+    let positions = client.get_top_positions(&pool_a, &0u32, &10u32);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().user, other);
+
+    let predictions = client.get_user_predictions(&user, &0u32, &10u32);
+    assert_eq!(predictions.len(), 1);
+    assert_eq!(predictions.get(0).unwrap().pool_id, pool_b);
+
+    assert_eq!(
+        client.try_withdraw_prediction(&user, &pool_a, &None),
+        Err(Ok(PredifiError::PredictionNotFound))
+    );
+}
+
+#[test]
+fn test_withdraw_prediction_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Exit Rules Pool"),
+        &String::from_str(&env, "ipfs://exitrules"),
+        &50i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    // Remainder below min_stake, zero and excessive amounts are rejected.
+    for amount in [51i128, 0, 101] {
+        assert_eq!(
+            client.try_withdraw_prediction(&user, &pool_id, &Some(amount)),
+            Err(Ok(PredifiError::InvalidAmount))
+        );
+    }
+    // Without an early-exit fee the whole stake is refunded.
+    assert_eq!(
+        client.withdraw_prediction(&user, &pool_id, &Some(50i128)),
+        50
+    );
+    assert_eq!(token.balance(&user), 50);
+
+    env.ledger().with_mut(|li| li.timestamp = 100000);
+    assert_eq!(
+        client.try_withdraw_prediction(&user, &pool_id, &None),
+        Err(Ok(PredifiError::PredictionTooLate))
+    );
+}
+
+// ── Fee tier tests ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_fee_tiers_apply_by_final_pool_size() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+    client.set_fee_tiers(
+        &admin,
+        &vec![
+            &env,
+            FeeTier {
+                min_total_stake: 1_000,
+                fee_bps: 500,
+            },
+            FeeTier {
+                min_total_stake: 10_000,
+                fee_bps: 100,
+            },
+        ],
+    );
+
+    let mut pools = Vec::new(&env);
+    for stake in [200i128, 1_000] {
+        let pool_id = client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Tiered Pool"),
+            &String::from_str(&env, "ipfs://tiered"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        );
+        let winner = Address::generate(&env);
+        let loser = Address::generate(&env);
+        token_admin_client.mint(&winner, &stake);
+        token_admin_client.mint(&loser, &stake);
+        client.place_prediction(&winner, &pool_id, &stake, &0);
+        client.place_prediction(&loser, &pool_id, &stake, &1);
+        pools.push_back((pool_id, winner));
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    let (small, small_winner) = pools.get(0).unwrap();
+    let (large, large_winner) = pools.get(1).unwrap();
+    client.resolve_pool(&operator, &small, &0u32);
+    client.resolve_pool(&operator, &large, &0u32);
+
+    // Below the first tier: flat 10% of 400.
+    assert_eq!(client.claim_winnings(&small_winner, &small), 360);
+    // Final size 2_000 reaches the 5% tier.
+    assert_eq!(client.claim_winnings(&large_winner, &large), 1_900);
+    assert_eq!(client.get_fees_accrued(&token_address), 140);
+}
+
+#[test]
+fn test_fee_tiers_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let tier = |min_total_stake: i128, fee_bps: u32| FeeTier {
+        min_total_stake,
+        fee_bps,
+    };
+    // Unsorted thresholds, increasing fees and out-of-range fees are rejected.
+    for tiers in [
+        vec![&env, tier(1_000, 100), tier(500, 50)],
+        vec![&env, tier(500, 50), tier(1_000, 100)],
+        vec![&env, tier(500, 10_001)],
+    ] {
+        assert_eq!(
+            client.try_set_fee_tiers(&admin, &tiers),
+            Err(Ok(PredifiError::InvalidFeeSchedule))
+        );
+    }
+
+    let tiers = vec![&env, tier(500, 100), tier(1_000, 100)];
+    client.set_fee_tiers(&admin, &tiers);
+    assert_eq!(client.get_fee_tiers(), tiers);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_fee_tiers(&stranger, &Vec::new(&env)),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+}
+
+// ── Claim all tests ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_claim_all_across_pools() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+
+    let mut pools = Vec::new(&env);
+    for _ in 0..4 {
+        pools.push_back(client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Claim All Pool"),
+            &String::from_str(&env, "ipfs://claimall"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        ));
+    }
+    let (won, lost, refunded, active) = (
+        pools.get(0).unwrap(),
+        pools.get(1).unwrap(),
+        pools.get(2).unwrap(),
+        pools.get(3).unwrap(),
+    );
+
+    let user = Address::generate(&env);
+    let rival = Address::generate(&env);
+    token_admin_client.mint(&user, &400);
+    token_admin_client.mint(&rival, &200);
+    for pool_id in pools.iter() {
+        client.place_prediction(&user, &pool_id, &100, &0);
+    }
+    client.place_prediction(&rival, &won, &100, &1);
+    client.place_prediction(&rival, &lost, &100, &1);
+
+    client.cancel_pool(&operator, &refunded);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &won, &0u32);
+    client.resolve_pool(&operator, &lost, &1u32);
+
+    let results = client.claim_all(&user, &vec![&env, won, lost, refunded, active, 999u64]);
+    assert_eq!(
+        results,
+        vec![
+            &env,
+            (won, 200i128),
+            (lost, 0i128),
+            (refunded, 100i128),
+            (active, 0i128),
+            (999u64, 0i128),
+        ]
+    );
+    assert_eq!(token.balance(&user), 300);
+
+    // HasClaimed is respected on a second pass.
+    let results = client.claim_all(&user, &vec![&env, won, refunded]);
+    assert_eq!(results, vec![&env, (won, 0i128), (refunded, 0i128)]);
+    assert_eq!(token.balance(&user), 300);
+}
+
+// ── Maker/taker fee tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_set_pool_amm_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Maker Taker Pool"),
+        &String::from_str(&env, "ipfs://makertaker"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Crypto"),
+    );
+    assert_eq!(client.get_pool_amm_fees(&pool_id), None);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_pool_amm_fees(&stranger, &pool_id, &10u32, &30u32),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    // Makers may not pay more than takers.
+    assert_eq!(
+        client.try_set_pool_amm_fees(&creator, &pool_id, &30u32, &10u32),
+        Err(Ok(PredifiError::InvalidFeeSchedule))
+    );
+
+    client.set_pool_amm_fees(&creator, &pool_id, &10u32, &30u32);
+    assert_eq!(
+        client.get_pool_amm_fees(&pool_id),
+        Some(AmmFeeConfig {
+            maker_fee_bps: 10,
+            taker_fee_bps: 30,
+        })
+    );
+
+    // Fees are frozen once trading has started.
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &10);
+    client.place_prediction(&user, &pool_id, &10, &0);
+    assert_eq!(
+        client.try_set_pool_amm_fees(&creator, &pool_id, &0u32, &0u32),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+}
+
+// ── Creator fee tests ────────────────────────────────────────────────────────
+
+fn creator_fee_params(env: &Env, token: &Address, creator_fee_bps: u32) -> CreatePoolParams {
+    CreatePoolParams {
+        end_time: 100000,
+        token: token.clone(),
+        options_count: 2,
+        description: String::from_str(env, "Creator Fee Pool"),
+        metadata_url: String::from_str(env, "ipfs://creatorfee"),
+        min_stake: 1,
+        max_stake: 0,
+        initial_liquidity: 0,
+        category: symbol_short!("Tech"),
+        creator_fee_bps,
+        oracle_quorum: 0,
+        lock_time: 0,
+        max_total_stake: 0,
+        truncate_at_cap: false,
+        outcome_weights: Vec::new(env),
+        scalar_buckets: Vec::new(env),
+        outcome_labels: Vec::new(env),
+        mechanism: Mechanism::Parimutuel,
+    }
+}
+
+#[test]
+fn test_creator_fee_accrues_and_is_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+    client.set_max_creator_fee_bps(&admin, &200u32);
+
+    assert_eq!(
+        client
+            .try_create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 300)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::CreatorFeeTooHigh as u32
+        )))
+    );
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 200));
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Gross 200: protocol fee 20 (10%), creator fee 4 (2%).
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 176);
+    assert_eq!(client.get_fees_accrued(&token_address), 20);
+    assert_eq!(client.get_creator_fees_accrued(&creator, &token_address), 4);
+
+    assert_eq!(client.claim_creator_fees(&creator, &token_address), 4);
+    assert_eq!(token.balance(&creator), 4);
+    assert_eq!(client.get_creator_fees_accrued(&creator, &token_address), 0);
+    assert_eq!(client.claim_creator_fees(&creator, &token_address), 0);
+}
+
+#[test]
+fn test_creator_fee_disabled_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    assert_eq!(
+        client.try_create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 1)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::CreatorFeeTooHigh as u32
+        )))
+    );
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(client.get_pool(&pool_id).creator, creator);
+}
+
+// ── Settlement hold tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_settlement_hold_delays_claims_on_high_value_pools() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_settlement_hold(&admin, &1_000i128, &86_400u64);
+
+    let mut pools = Vec::new(&env);
+    for stake in [999i128, 1_000] {
+        let pool_id = client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Held Pool"),
+            &String::from_str(&env, "ipfs://held"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        );
+        let user = Address::generate(&env);
+        token_admin_client.mint(&user, &stake);
+        client.place_prediction(&user, &pool_id, &stake, &0);
+        pools.push_back((pool_id, user));
+    }
+    let (small, small_user) = pools.get(0).unwrap();
+    let (large, large_user) = pools.get(1).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &small, &0u32);
+    client.resolve_pool(&operator, &large, &0u32);
+
+    // Below the threshold: claims open immediately.
+    assert_eq!(client.get_claims_open_at(&small), 0);
+    assert_eq!(client.claim_winnings(&small_user, &small), 999);
+
+    assert_eq!(client.get_claims_open_at(&large), 100001 + 86_400);
+    assert_eq!(
+        client.try_claim_winnings(&large_user, &large),
+        Err(Ok(PredifiError::ClaimsNotOpen))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001 + 86_400);
+    assert_eq!(client.claim_winnings(&large_user, &large), 1_000);
+}
+
+// ── Dispute window tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_dispute_resolution_corrects_outcome_within_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_dispute_window(&admin, &3600u64);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Disputed Pool"),
+        &String::from_str(&env, "ipfs://disputed"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &100);
+    token_admin_client.mint(&user2, &100);
+    client.place_prediction(&user1, &pool_id, &100, &0);
+    client.place_prediction(&user2, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.get_claims_open_at(&pool_id), 100001 + 3600);
+    assert_eq!(
+        client.try_claim_winnings(&user1, &pool_id),
+        Err(Ok(PredifiError::ClaimsNotOpen))
+    );
+
+    // Same outcome or out of range is rejected.
+    assert_eq!(
+        client.try_dispute_resolution(&operator, &pool_id, &0u32),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_dispute_resolution(&operator, &pool_id, &2u32),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 102000);
+    client.dispute_resolution(&operator, &pool_id, &1u32);
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 1);
+    // The window restarts from the correction.
+    assert_eq!(client.get_claims_open_at(&pool_id), 102000 + 3600);
+
+    env.ledger().with_mut(|li| li.timestamp = 102000 + 3600);
+    assert_eq!(client.claim_winnings(&user1, &pool_id), 0);
+    assert_eq!(client.claim_winnings(&user2, &pool_id), 200);
+    assert_eq!(
+        client.try_dispute_resolution(&operator, &pool_id, &0u32),
+        Err(Ok(PredifiError::DisputeWindowClosed))
+    );
+}
+
+#[test]
+fn test_dispute_resolution_requires_open_window_and_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "No Window"),
+        &String::from_str(&env, "ipfs://nowindow"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    assert_eq!(
+        client.try_dispute_resolution(&operator, &pool_id, &1u32),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_dispute_resolution(&stranger, &pool_id, &1u32),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    // No dispute window configured: claims are already open.
+    assert_eq!(
+        client.try_dispute_resolution(&operator, &pool_id, &1u32),
+        Err(Ok(PredifiError::DisputeWindowClosed))
+    );
+}
+
+// ── Zero-stake resolution guard tests ────────────────────────────────────────
+
+#[test]
+fn test_resolve_to_zero_stake_outcome_requires_confirmation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &3u32,
+        &String::from_str(&env, "Zero Stake Outcome"),
+        &String::from_str(&env, "ipfs://zerostake"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &2u32),
+        Err(Ok(PredifiError::ZeroStakeOutcome))
+    );
+    let mut items = Vec::new(&env);
+    items.push_back((pool_id, 2u32));
+    let result = client.resolve_pools_batch(&operator, &items);
+    assert_eq!(
+        result.results.get(0).unwrap().error,
+        PredifiError::ZeroStakeOutcome as u32
+    );
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Active);
+
+    client.force_resolve_pool(
+        &operator,
+        &pool_id,
+        &2u32,
+        &String::from_str(&env, "confirmed: no bettor on outcome 2"),
+    );
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 2);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 0);
+}
+
+#[test]
+fn test_resolve_pool_without_bets_needs_no_confirmation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Empty Pool"),
+        &String::from_str(&env, "ipfs://empty"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &1u32);
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Resolved);
+}
+
+// ── Oracle quorum tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_oracle_quorum_resolves_when_reached() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, _, creator) = setup(&env);
+    let mut oracles = Vec::new(&env);
+    for _ in 0..3 {
+        let oracle = Address::generate(&env);
+        ac_client.grant_role(&oracle, &ROLE_ORACLE);
+        oracles.push_back(oracle);
+    }
+    let params = CreatePoolParams {
+        oracle_quorum: 2,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+    assert_eq!(client.get_oracle_quorum(&pool_id), 2);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    let proof = String::from_str(&env, "proof");
+    assert!(!client.submit_oracle_result(&oracles.get(0).unwrap(), &pool_id, &0u32, &proof));
+    assert_eq!(
+        client.try_submit_oracle_result(&oracles.get(0).unwrap(), &pool_id, &1u32, &proof),
+        Err(Ok(PredifiError::OracleAlreadySubmitted))
+    );
+    // A dissenting result does not count towards outcome 0.
+    client.oracle_resolve(&oracles.get(1).unwrap(), &pool_id, &1u32, &proof);
+    assert_eq!(client.get_oracle_submissions(&pool_id).len(), 2);
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Active);
+
+    assert!(client.submit_oracle_result(&oracles.get(2).unwrap(), &pool_id, &0u32, &proof));
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 0);
+    assert_eq!(client.get_oracle_submissions(&pool_id).len(), 0);
+}
+
+#[test]
+fn test_oracle_quorum_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        oracle_quorum: 11,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    assert_eq!(
+        client.try_create_pool_with_params(&creator, &params),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::InvalidOracleQuorum as u32
+        )))
+    );
+
+    // Pools created without a quorum resolve on the first oracle result.
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(client.get_oracle_quorum(&pool_id), 1);
+}
+
+// ── Batch readiness tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_mark_pools_ready_batch_reports_per_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let mut pool_ids = Vec::new(&env);
+    for end_time in [100000u64, 200000] {
+        let pool_id = client.create_pool(
+            &creator,
+            &end_time,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Ready Batch"),
+            &String::from_str(&env, "ipfs://ready"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        );
+        pool_ids.push_back(pool_id);
+    }
+    let pending = pool_ids.get(1).unwrap();
+    let resolved = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Ready Batch"),
+        &String::from_str(&env, "ipfs://ready"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &resolved, &0u32);
+    pool_ids.push_back(resolved);
+    pool_ids.push_back(999);
+
+    let result = client.mark_pools_ready_batch(&pool_ids);
+    assert_eq!(result.processed, 4);
+    let errors: [u32; 4] = core::array::from_fn(|i| result.results.get(i as u32).unwrap().error);
+    assert_eq!(
+        errors,
+        [
+            0,
+            PredifiError::ResolutionDelayNotMet as u32,
+            PredifiError::InvalidPoolState as u32,
+            PredifiError::PoolNotFound as u32,
+        ]
+    );
+    assert_eq!(result.results.get(1).unwrap().pool_id, pending);
+}
+
+// ── Initial liquidity return tests ───────────────────────────────────────────
+
+fn liquidity_pool(
+    env: &Env,
+    client: &PredifiContractClient<'_>,
+    creator: &Address,
+    token: &Address,
+) -> u64 {
+    client.create_pool(
+        creator,
+        &100000u64,
+        token,
+        &2u32,
+        &String::from_str(env, "Liquidity Pool"),
+        &String::from_str(env, "ipfs://liquidity"),
+        &1i128,
+        &0i128,
+        &100i128,
+        &symbol_short!("Tech"),
+    )
+}
+
+#[test]
+fn test_creator_recovers_winning_share_of_initial_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &50);
+    token_admin_client.mint(&loser, &150);
+    client.place_prediction(&winner, &pool_id, &50, &0);
+    client.place_prediction(&loser, &pool_id, &150, &1);
+
+    assert_eq!(
+        client.try_claim_initial_liquidity(&creator, &pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Seed 50 per outcome; total 300 is shared by the winner (50) and the
+    // creator's seed on the winning outcome (50).
+    assert_eq!(
+        client.try_claim_initial_liquidity(&winner, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    assert_eq!(client.claim_initial_liquidity(&creator, &pool_id), 150);
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 150);
+    assert_eq!(token.balance(&creator), 150);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(
+        client.try_claim_initial_liquidity(&creator, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_initial_liquidity_refunded_on_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    client.cancel_pool(&operator, &pool_id);
+
+    assert_eq!(token.balance(&creator), 100);
+    assert_eq!(
+        client.try_claim_initial_liquidity(&creator, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_canceled_pool_with_liquidity_drains_to_zero() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &40);
+    token_admin_client.mint(&user2, &60);
+    client.place_prediction(&user1, &pool_id, &40, &0);
+    client.place_prediction(&user2, &pool_id, &60, &1);
+    assert_eq!(token.balance(&client.address), 200);
+
+    client.cancel_pool(&operator, &pool_id);
+    assert_eq!(token.balance(&creator), 100);
+    assert_eq!(client.claim_winnings(&user1, &pool_id), 40);
+    assert_eq!(client.claim_winnings(&user2, &pool_id), 60);
+    assert_eq!(client.get_pool_unclaimed(&pool_id), 0);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+// ── House liquidity accounting tests ─────────────────────────────────────────
+
+#[test]
+fn test_mixed_pool_fees_exclude_house_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    assert_eq!(
+        client.get_pool_liquidity(&pool_id),
+        PoolLiquidity {
+            house_liquidity: 100,
+            bettor_stake: 200,
+            seed_per_outcome: 50,
+        }
+    );
+    assert_eq!(client.get_pool(&pool_id).total_stake, 300);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Winner: gross 200, fee 10% of 200 * 200 / 300 = 13.
+    // Creator: gross 100, fee 10% of 100 * 200 / 300 = 6.
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 187);
+    assert_eq!(client.claim_initial_liquidity(&creator, &pool_id), 94);
+    assert_eq!(client.get_fees_accrued(&token_address), 19);
+    assert_eq!(token.balance(&client.address), 19);
+}
+
+#[test]
+fn test_house_only_pool_charges_no_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    assert_eq!(client.get_pool_liquidity(&pool_id).bettor_stake, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &1u32);
+
+    assert_eq!(client.claim_initial_liquidity(&creator, &pool_id), 100);
+    assert_eq!(token.balance(&treasury), 0);
+}
+
+// ── Claim deadline tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_sweep_unclaimed_after_claim_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_claim_period(&admin, &(30 * 86_400u64));
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Deadline Pool"),
+        &String::from_str(&env, "ipfs://deadline"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let deadline = 100000 + 30 * 86_400;
+    assert_eq!(client.get_claim_deadline(&pool_id), Some(deadline));
+
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    let loser = Address::generate(&env);
+    for user in [&early, &late, &loser] {
+        token_admin_client.mint(user, &100);
+    }
+    client.place_prediction(&early, &pool_id, &100, &0);
+    client.place_prediction(&late, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&early, &pool_id), 150);
+    assert_eq!(client.get_pool_unclaimed(&pool_id), 150);
+
+    assert_eq!(
+        client.try_sweep_unclaimed(&admin, &pool_id),
+        Err(Ok(PredifiError::ClaimDeadlineNotReached))
+    );
+    assert_eq!(
+        client.try_sweep_unclaimed(&operator, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = deadline);
+    assert_eq!(client.sweep_unclaimed(&admin, &pool_id), 150);
+    assert_eq!(token.balance(&treasury), 150);
+    assert_eq!(client.get_pool_unclaimed(&pool_id), 0);
+    assert_eq!(
+        client.try_claim_winnings(&late, &pool_id),
+        Err(Ok(PredifiError::PoolSwept))
+    );
+    assert_eq!(
+        client.try_sweep_unclaimed(&admin, &pool_id),
+        Err(Ok(PredifiError::PoolSwept))
+    );
+}
+
+#[test]
+fn test_pools_without_claim_deadline_cannot_be_swept_unclaimed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "No Deadline"),
+        &String::from_str(&env, "ipfs://nodeadline"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    // Setting the period later does not move existing pools' deadlines.
+    client.set_claim_period(&admin, &86_400u64);
+    assert_eq!(client.get_claim_deadline(&pool_id), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 10_000_000);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(
+        client.try_sweep_unclaimed(&admin, &pool_id),
+        Err(Ok(PredifiError::ClaimDeadlineNotReached))
+    );
+}
+
+// ── Pool summary tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_get_pool_summary_matches_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &3u32,
+        &String::from_str(&env, "Summary Pool"),
+        &String::from_str(&env, "ipfs://summary"),
+        &5i128,
+        &500i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    );
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &2);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &2u32);
+
+    assert_eq!(
+        client.get_pool_summary(&pool_id),
+        PoolSummary {
+            pool_id,
+            end_time: 100000,
+            state: MarketState::Resolved,
+            outcome: 2,
+            token: token_address,
+            total_stake: 100,
+            category: symbol_short!("Sports"),
+            options_count: 3,
+            min_stake: 5,
+            max_stake: 500,
+            initial_liquidity: 0,
+            creator,
+        }
+    );
+    assert_eq!(
+        client.try_get_pool_summary(&999),
+        Err(Ok(PredifiError::PoolNotFound))
+    );
+}
+
+// ── Global pool listing tests ────────────────────────────────────────────────
+
+#[test]
+fn test_get_pools_paginates_in_creation_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    assert_eq!(client.get_pool_count(), 0);
+    assert_eq!(client.get_pools(&0u64, &10u32).len(), 0);
+
+    for category in [
+        symbol_short!("Tech"),
+        symbol_short!("Sports"),
+        symbol_short!("Tech"),
+    ] {
+        client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "Listed Pool"),
+            &String::from_str(&env, "ipfs://listed"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &category,
+        );
+    }
+    assert_eq!(client.get_pool_count(), 3);
+
+    let page = client.get_pools(&1u64, &10u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().pool_id, 1);
+    assert_eq!(page.get(0).unwrap().category, symbol_short!("Sports"));
+    assert_eq!(page.get(1).unwrap(), client.get_pool_summary(&2));
+
+    assert_eq!(client.get_pools(&0u64, &1u32).get(0).unwrap().pool_id, 0);
+    assert_eq!(client.get_pools(&3u64, &10u32).len(), 0);
+    assert_eq!(client.get_pools(&0u64, &0u32).len(), 0);
+}
+
+// ── Pool metadata update tests ───────────────────────────────────────────────
+
+#[test]
+fn test_update_pool_metadata_before_bets_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Typo Pool"),
+        &String::from_str(&env, "ipfs://wrong"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_update_pool_metadata(
+            &stranger,
+            &pool_id,
+            &String::from_str(&env, "Hijacked"),
+            &String::from_str(&env, "ipfs://hijacked"),
+        ),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    client.update_pool_metadata(
+        &creator,
+        &pool_id,
+        &String::from_str(&env, "Fixed Pool"),
+        &String::from_str(&env, "ipfs://fixed"),
+    );
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.description, String::from_str(&env, "Fixed Pool"));
+    assert_eq!(pool.metadata_url, String::from_str(&env, "ipfs://fixed"));
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &10);
+    client.place_prediction(&user, &pool_id, &10, &0);
+    assert_eq!(
+        client.try_update_pool_metadata(
+            &creator,
+            &pool_id,
+            &String::from_str(&env, "Too Late"),
+            &String::from_str(&env, "ipfs://late"),
+        ),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+}
+
+// ── Debug invariant hook tests ───────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "INV-1")]
+fn test_debug_invariant_hook_detects_stake_mismatch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Corrupted Pool"),
+        &String::from_str(&env, "ipfs://corrupted"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &110);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.as_contract(&client.address, || {
+        let key = DataKey::Pool(pool_id);
+        let mut pool: Pool = env.storage().persistent().get(&key).unwrap();
+        pool.total_stake += 1;
+        env.storage().persistent().set(&key, &pool);
+    });
+    client.increase_stake(&user, &pool_id, &10);
+}
+
+// ── Resolution SLA tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_resolution_sla_tracks_operator_latency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_resolution_sla(&admin, &3600u64);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+    let mut pool_ids = Vec::new(&env);
+    for _ in 0..2 {
+        let pool_id = client.create_pool(
+            &creator,
+            &100000u64,
+            &token_address,
+            &2u32,
+            &String::from_str(&env, "SLA Pool"),
+            &String::from_str(&env, "ipfs://sla"),
+            &1i128,
+            &0i128,
+            &0i128,
+            &symbol_short!("Tech"),
+        );
+        client.place_prediction(&user, &pool_id, &100, &0);
+        pool_ids.push_back(pool_id);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 100000 + 600);
+    client.resolve_pool(&operator, &pool_ids.get(0).unwrap(), &0u32);
+    let stats = client.get_operator_sla_stats(&operator);
+    assert_eq!(stats.resolutions, 1);
+    assert_eq!(stats.late_resolutions, 0);
+    assert_eq!(stats.max_latency, 600);
+
+    env.ledger().with_mut(|li| li.timestamp = 100000 + 7200);
+    client.resolve_pool(&operator, &pool_ids.get(1).unwrap(), &0u32);
+    assert_eq!(
+        client.get_operator_sla_stats(&operator),
+        OperatorSlaStats {
+            resolutions: 2,
+            late_resolutions: 1,
+            total_latency: 600 + 7200,
+            max_latency: 7200,
+        }
+    );
+}
+
+// ── Betting lock time tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_lock_time_closes_betting_before_end_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let params = CreatePoolParams {
+        lock_time: 90000,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+    assert_eq!(client.get_lock_time(&pool_id), 90000);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 90000);
+    assert!(client
+        .try_place_prediction(&user, &pool_id, &100, &1)
+        .is_err());
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &100),
+        Err(Ok(PredifiError::PredictionTooLate))
+    );
+    assert_eq!(
+        client.try_withdraw_prediction(&user, &pool_id, &None),
+        Err(Ok(PredifiError::PredictionTooLate))
+    );
+
+    // Resolution still waits for end_time.
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &0u32),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "lock_time must be in the future and not after end_time")]
+fn test_lock_time_after_end_time_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        lock_time: 100001,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    client.create_pool_with_params(&creator, &params);
+}
+
+#[test]
+fn test_lock_time_defaults_to_end_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(client.get_lock_time(&pool_id), 100000);
+}
+
+// ── Pool analytics tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_pool_analytics_checkpointed_at_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Analytics Pool"),
+        &String::from_str(&env, "ipfs://analytics"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    token_admin_client.mint(&user1, &5);
+    token_admin_client.mint(&user2, &50);
+    token_admin_client.mint(&user3, &600);
+
+    client.place_prediction(&user1, &pool_id, &5, &0);
+    client.place_prediction(&user2, &pool_id, &50, &1);
+    env.ledger().with_mut(|li| li.timestamp = 50000);
+    client.place_prediction(&user3, &pool_id, &500, &0);
+    env.ledger().with_mut(|li| li.timestamp = 95000);
+    client.increase_stake(&user3, &pool_id, &100);
+    assert_eq!(client.get_pool_analytics(&pool_id), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    let analytics = client.get_pool_analytics(&pool_id).unwrap();
+    assert_eq!(analytics.bet_count, 4);
+    assert_eq!(analytics.total_bet_stake, 655);
+    assert_eq!(analytics.mean_stake, 163);
+    assert_eq!(analytics.median_stake_floor, 10);
+    assert_eq!(analytics.size_buckets, vec![&env, 1u32, 1, 2]);
+    assert_eq!(
+        analytics.time_buckets,
+        vec![&env, 55i128, 0, 0, 0, 0, 500, 0, 0, 0, 100]
+    );
+    assert_eq!(analytics.resolved_at, 100001);
+}
+
+// ── Pool stake cap tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_stake_cap_rejects_bets_beyond_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        max_total_stake: 300,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &300);
+    token_admin_client.mint(&user2, &300);
+    client.place_prediction(&user1, &pool_id, &200, &0);
+
+    assert!(client
+        .try_place_prediction(&user2, &pool_id, &150, &1)
+        .is_err());
+    assert_eq!(
+        client.try_increase_stake(&user1, &pool_id, &150),
+        Err(Ok(PredifiError::PoolStakeCapReached))
+    );
+
+    client.place_prediction(&user2, &pool_id, &100, &1);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 300);
+    assert_eq!(token.balance(&user2), 200);
+    assert_eq!(
+        client.try_increase_stake(&user1, &pool_id, &1),
+        Err(Ok(PredifiError::PoolStakeCapReached))
+    );
+}
+
+#[test]
+fn test_stake_cap_truncates_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        max_total_stake: 300,
+        truncate_at_cap: true,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &300);
+    token_admin_client.mint(&user2, &300);
+    client.place_prediction(&user1, &pool_id, &200, &0);
+    client.place_prediction(&user2, &pool_id, &250, &1);
+
+    assert_eq!(client.get_pool(&pool_id).total_stake, 300);
+    assert_eq!(client.get_outcome_stake(&pool_id, &1), 100);
+    assert_eq!(token.balance(&user2), 200);
+    assert_eq!(
+        client.try_increase_stake(&user1, &pool_id, &10),
+        Err(Ok(PredifiError::PoolStakeCapReached))
+    );
+}
+
+// ── Insufficient balance tests ───────────────────────────────────────────────
+
+#[test]
+fn test_bet_exceeding_balance_returns_typed_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &150);
+
+    assert_eq!(
+        client.try_place_prediction(&user, &pool_id, &200, &0),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::InsufficientBalance as u32
+        )))
+    );
+    client.place_prediction(&user, &pool_id, &100, &0);
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &100),
+        Err(Ok(PredifiError::InsufficientBalance))
+    );
+    assert_eq!(client.increase_stake(&user, &pool_id, &50), 150);
+}
+
+#[test]
+fn test_create_pool_with_unfunded_liquidity_returns_typed_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        initial_liquidity: 100,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    assert_eq!(
+        client.try_create_pool_with_params(&creator, &params),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::InsufficientBalance as u32
+        )))
+    );
+}
+
+// ── Delegated claim tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_claim_for_pays_beneficiary_and_relayer_tip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    assert_eq!(
+        client.try_set_claim_tip(&winner, &1_001u32),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    client.set_claim_tip(&winner, &500u32);
+    assert_eq!(client.get_claim_tip(&winner), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.claim_for(&relayer, &winner, &pool_id), 200);
+    assert_eq!(token.balance(&winner), 190);
+    assert_eq!(token.balance(&relayer), 10);
+    assert_eq!(
+        client.try_claim_winnings(&winner, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_claim_for_without_tip_pays_everything_to_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let winner = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.claim_for(&relayer, &winner, &pool_id), 100);
+    assert_eq!(token.balance(&winner), 100);
+    assert_eq!(token.balance(&relayer), 0);
+}
+
+// ── Multi-leg bet tests ──────────────────────────────────────────────────────
+
+fn multi_leg_pool(env: &Env, client: &PredifiContractClient<'_>, token: &Address) -> u64 {
+    client.create_pool(
+        &Address::generate(env),
+        &100000u64,
+        token,
+        &4u32,
+        &String::from_str(env, "Multi-leg Pool"),
+        &String::from_str(env, "ipfs://multileg"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Sports"),
+    )
+}
+
+#[test]
+fn test_multi_leg_bet_splits_stake_and_pays_winning_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, _) = setup(&env);
+    let pool_id = multi_leg_pool(&env, &client, &token_address);
+    let multi = Address::generate(&env);
+    let single = Address::generate(&env);
+    let rival = Address::generate(&env);
+    token_admin_client.mint(&multi, &100);
+    token_admin_client.mint(&single, &100);
+    token_admin_client.mint(&rival, &100);
+
+    let legs = client.place_multi_prediction(&multi, &pool_id, &100, &vec![&env, 1u32, 2, 3]);
+    assert_eq!(
+        legs,
+        vec![
+            &env,
+            BetLeg {
+                outcome: 1,
+                amount: 34
+            },
+            BetLeg {
+                outcome: 2,
+                amount: 33
+            },
+            BetLeg {
+                outcome: 3,
+                amount: 33
+            },
+        ]
+    );
+    assert_eq!(client.get_bet_legs(&multi, &pool_id), Some(legs));
+    client.place_prediction(&single, &pool_id, &100, &2);
+    client.place_prediction(&rival, &pool_id, &100, &0);
+    assert_eq!(
+        client.get_pool_outcome_stakes(&pool_id),
+        vec![&env, 100i128, 34, 133, 33]
+    );
+    assert_eq!(
+        client.try_increase_stake(&multi, &pool_id, &10),
+        Err(Ok(PredifiError::PositionConflict))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &2u32);
+
+    // 300 * 33 / 133 = 74, 300 * 100 / 133 = 225
+    assert_eq!(client.claim_winnings(&multi, &pool_id), 74);
+    assert_eq!(client.claim_winnings(&single, &pool_id), 225);
+    assert_eq!(client.claim_winnings(&rival, &pool_id), 0);
+    assert_eq!(token.balance(&multi), 74);
+}
+
+#[test]
+fn test_multi_leg_bet_validation_and_cancel_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, _) = setup(&env);
+    let pool_id = multi_leg_pool(&env, &client, &token_address);
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+
+    assert_eq!(
+        client.try_place_multi_prediction(&user, &pool_id, &100, &vec![&env, 1u32]),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_place_multi_prediction(&user, &pool_id, &100, &vec![&env, 1u32, 1]),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_place_multi_prediction(&user, &pool_id, &100, &vec![&env, 1u32, 4]),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+
+    client.place_multi_prediction(&user, &pool_id, &100, &vec![&env, 0u32, 3]);
+    assert_eq!(
+        client.try_place_multi_prediction(&user, &pool_id, &100, &vec![&env, 1u32, 2]),
+        Err(Ok(PredifiError::PositionConflict))
+    );
+
+    client.cancel_pool(&operator, &pool_id);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+    assert_eq!(token.balance(&user), 100);
+}
+
+// ── Handicap pool tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_handicap_weights_scale_payouts_and_return_remainder_to_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let params = CreatePoolParams {
+        outcome_weights: vec![&env, 5_000u32, 10_000],
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    let pool_id = client.create_pool_with_params(&creator, &params);
+    assert_eq!(
+        client.get_outcome_weights(&pool_id),
+        vec![&env, 5_000u32, 10_000]
+    );
+
+    let favorite = Address::generate(&env);
+    let longshot = Address::generate(&env);
+    token_admin_client.mint(&favorite, &300);
+    token_admin_client.mint(&longshot, &100);
+    client.place_prediction(&favorite, &pool_id, &300, &0);
+    client.place_prediction(&longshot, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.claim_winnings(&favorite, &pool_id), 200);
+    assert_eq!(client.claim_initial_liquidity(&creator, &pool_id), 200);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic(
+    expected = "outcome_weights must be empty or one weight in 1..=10000 bps per outcome"
+)]
+fn test_handicap_weights_must_cover_every_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let params = CreatePoolParams {
+        outcome_weights: vec![&env, 5_000u32],
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    client.create_pool_with_params(&creator, &params);
+}
+
+// ── Commit-reveal resolution tests ───────────────────────────────────────────
+
+fn outcome_commitment(env: &Env, pool_id: u64, outcome: u32, salt: &BytesN<32>) -> BytesN<32> {
+    let mut buf = soroban_sdk::Bytes::new(env);
+    buf.extend_from_array(&pool_id.to_be_bytes());
+    buf.extend_from_array(&outcome.to_be_bytes());
+    buf.append(&salt.clone().into());
+    env.crypto().sha256(&buf).into()
+}
+
+#[test]
+fn test_commit_reveal_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &1);
+
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let commitment = outcome_commitment(&env, pool_id, 1, &salt);
+    assert_eq!(
+        client.try_commit_resolution(&operator, &pool_id, &commitment),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100000);
+    client.commit_resolution(&operator, &pool_id, &commitment);
+    assert_eq!(
+        client
+            .get_resolution_commitment(&pool_id)
+            .unwrap()
+            .commitment,
+        commitment
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &1u32),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    let note = String::from_str(&env, "revealed");
+    assert_eq!(
+        client.try_reveal_resolution(&operator, &pool_id, &0u32, &salt, &note),
+        Err(Ok(PredifiError::CommitmentMismatch))
+    );
+
+    client.reveal_resolution(&operator, &pool_id, &1u32, &salt, &note);
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.state, MarketState::Resolved);
+    assert_eq!(pool.outcome, 1);
+    assert_eq!(client.get_resolution_commitment(&pool_id), None);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+}
+
+// ── Odds alert tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_odds_alerts_are_registered_replaced_and_removed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let subscriber = Address::generate(&env);
+
+    client.set_odds_alert(&subscriber, &pool_id, &0u32, &20_000u64);
+    client.set_odds_alert(&subscriber, &pool_id, &0u32, &30_000u64);
+    client.set_odds_alert(&subscriber, &pool_id, &1u32, &15_000u64);
+    assert_eq!(
+        client.get_odds_alerts(&pool_id),
+        vec![
+            &env,
+            OddsAlert {
+                subscriber: subscriber.clone(),
+                outcome: 0,
+                threshold_bps: 30_000,
+            },
+            OddsAlert {
+                subscriber: subscriber.clone(),
+                outcome: 1,
+                threshold_bps: 15_000,
+            },
+        ]
+    );
+    assert_eq!(
+        client.try_set_odds_alert(&subscriber, &pool_id, &2u32, &10_000u64),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+
+    // Bets crossing the thresholds still go through.
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &100);
+    token_admin_client.mint(&user2, &300);
+    client.place_prediction(&user1, &pool_id, &100, &0);
+    client.place_prediction(&user2, &pool_id, &300, &1);
+    assert_eq!(
+        client.get_pool_stats(&pool_id).current_odds.get(0),
+        Some(40_000)
+    );
+
+    client.set_odds_alert(&subscriber, &pool_id, &0u32, &0u64);
+    client.set_odds_alert(&subscriber, &pool_id, &1u32, &0u64);
+    assert_eq!(client.get_odds_alerts(&pool_id).len(), 0);
+}
+
+#[test]
+fn test_odds_alerts_per_pool_are_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    for _ in 0..20 {
+        client.set_odds_alert(&Address::generate(&env), &pool_id, &0u32, &20_000u64);
+    }
+    assert_eq!(
+        client.try_set_odds_alert(&Address::generate(&env), &pool_id, &0u32, &20_000u64),
+        Err(Ok(PredifiError::CapacityReached))
+    );
+}
+
+// ── Escrow yield routing tests ───────────────────────────────────────────────
+
+mod mock_yield_adapter {
+    use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+    #[contract]
+    pub struct MockYieldAdapter;
+
+    #[contractimpl]
+    impl MockYieldAdapter {
+        pub fn deposit(env: Env, owner: Address, token: Address, amount: i128) {
+            owner.require_auth();
+            let balance = Self::balance(env.clone(), owner.clone(), token.clone());
+            env.storage()
+                .instance()
+                .set(&(owner, token), &(balance + amount));
+        }
+
+        pub fn withdraw(env: Env, owner: Address, token: Address, amount: i128) {
+            owner.require_auth();
+            let balance = Self::balance(env.clone(), owner.clone(), token.clone());
+            env.storage()
+                .instance()
+                .set(&(owner.clone(), token.clone()), &(balance - amount));
+            token::Client::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &amount,
+            );
+        }
+
+        pub fn balance(env: Env, owner: Address, token: Address) -> i128 {
+            env.storage().instance().get(&(owner, token)).unwrap_or(0)
+        }
+
+        /// Test hook: credit yield already minted to the adapter.
+        pub fn accrue(env: Env, owner: Address, token: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), owner.clone(), token.clone());
+            env.storage()
+                .instance()
+                .set(&(owner, token), &(balance + amount));
+        }
+    }
+}
+
+#[test]
+fn test_idle_escrow_earns_yield_for_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let adapter_id = env.register(mock_yield_adapter::MockYieldAdapter, ());
+    let adapter = mock_yield_adapter::MockYieldAdapterClient::new(&env, &adapter_id);
+
+    assert_eq!(
+        client.try_set_yield_adapter(&admin, &token_address, &Some(adapter_id.clone()), &6000u32),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    client.set_yield_adapter(&admin, &token_address, &Some(adapter_id.clone()), &5000u32);
+
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &2u32,
+        &String::from_str(&env, "Yield Pool"),
+        &String::from_str(&env, "ipfs://yield"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &500);
+    token_admin_client.mint(&user2, &500);
+    client.place_prediction(&user1, &pool_id, &500, &0);
+    client.place_prediction(&user2, &pool_id, &500, &1);
+
+    // At most 50% of the 1000 escrow may leave the contract.
+    assert_eq!(
+        client.try_deposit_idle_escrow(&operator, &pool_id, &600),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert_eq!(client.deposit_idle_escrow(&operator, &pool_id, &500), 500);
+    assert_eq!(token.balance(&client.address), 500);
+
+    // The adapter can't be swapped while escrow is deposited.
+    assert_eq!(
+        client.try_set_yield_adapter(&admin, &token_address, &None, &0u32),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    token_admin_client.mint(&adapter_id, &40);
+    adapter.accrue(&client.address, &token_address, &40);
+    assert_eq!(client.harvest_yield(&token_address), 40);
+    assert_eq!(token.balance(&treasury), 40);
+    assert_eq!(client.harvest_yield(&token_address), 0);
+
+    // Resolution recalls the principal before claims open.
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.get_yield_deposit(&pool_id), 0);
+    assert_eq!(token.balance(&client.address), 1000);
+    assert_eq!(client.claim_winnings(&user1, &pool_id), 1000);
+
+    client.set_yield_adapter(&admin, &token_address, &None, &0u32);
+    assert_eq!(client.get_yield_adapter(&token_address), None);
+}
+
+#[test]
+fn test_idle_escrow_recalled_by_keeper_after_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let adapter_id = env.register(mock_yield_adapter::MockYieldAdapter, ());
+    client.set_yield_adapter(&admin, &token_address, &Some(adapter_id), &5000u32);
+
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    client.deposit_idle_escrow(&operator, &pool_id, &100);
+
+    let keeper = Address::generate(&env);
+    assert_eq!(
+        client.try_withdraw_idle_escrow(&keeper, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100000);
+    assert_eq!(
+        client.try_deposit_idle_escrow(&operator, &pool_id, &1),
+        Err(Ok(PredifiError::PredictionTooLate))
+    );
+    assert_eq!(client.withdraw_idle_escrow(&keeper, &pool_id), 100);
+    assert_eq!(token.balance(&client.address), 200);
+}
+
+// ── Outcome stake migration tests ────────────────────────────────────────────
+
+#[test]
+fn test_migrate_outcome_stakes_removes_legacy_keys() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id = client.create_pool(
+        &creator,
+        &100000u64,
+        &token_address,
+        &3u32,
+        &String::from_str(&env, "Legacy Stakes Pool"),
+        &String::from_str(&env, "ipfs://legacy-stakes"),
+        &1i128,
+        &0i128,
+        &0i128,
+        &symbol_short!("Tech"),
+    );
+    // Simulate a pool created before the migration existed.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Ext(ExtKey::OutcomeStakesMigrated(pool_id)));
+    });
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &100);
+    token_admin_client.mint(&user2, &200);
+    client.place_prediction(&user1, &pool_id, &100, &0);
+    client.place_prediction(&user2, &pool_id, &200, &2);
+
+    assert_eq!(client.migrate_outcome_stakes(&pool_id), 2);
+    assert_eq!(client.migrate_outcome_stakes(&pool_id), 0);
+    assert_eq!(
+        client.get_pool_outcome_stakes(&pool_id),
+        vec![&env, 100, 0, 200]
+    );
+
+    // Later bets only update the batch vector.
+    let user3 = Address::generate(&env);
+    token_admin_client.mint(&user3, &50);
+    client.place_prediction(&user3, &pool_id, &50, &1);
+    env.as_contract(&client.address, || {
+        for outcome in 0..3u32 {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&DataKey::OutcomeStake(pool_id, outcome)));
+        }
+    });
+    assert_eq!(
+        client.get_pool_outcome_stakes(&pool_id),
+        vec![&env, 100, 50, 200]
+    );
+}
+
+// ── Escrow segregation tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_pool_escrow_tracks_locked_and_claimable_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    token_admin_client.mint(&user1, &100);
+    token_admin_client.mint(&user2, &50);
+    client.place_prediction(&user1, &pool_id, &100, &0);
+    client.place_prediction(&user2, &pool_id, &50, &1);
+
+    let escrow = client.get_pool_escrow(&pool_id).unwrap();
+    assert_eq!((escrow.locked, escrow.claimable), (250, 0));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    let escrow = client.get_pool_escrow(&pool_id).unwrap();
+    assert_eq!((escrow.locked, escrow.claimable), (0, 250));
+
+    let won = client.claim_winnings(&user1, &pool_id);
+    let escrow = client.get_pool_escrow(&pool_id).unwrap();
+    assert_eq!(escrow.claimable, 250 - won);
+    assert_eq!(escrow.claimable, client.get_pool_unclaimed(&pool_id));
+    assert!(client.audit_pool_escrow(&pool_id));
+}
+
+#[test]
+fn test_audit_pool_escrow_detects_contamination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    assert!(client.audit_pool_escrow(&pool_id));
+
+    // Simulate funds booked to the wrong pool.
+    env.as_contract(&client.address, || {
+        let escrow = PoolEscrow {
+            locked: 150,
+            ..PoolEscrow::default()
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Ext(ExtKey::PoolEscrow(pool_id)), &escrow);
+    });
+    assert!(!client.audit_pool_escrow(&pool_id));
+}
+
+// ── Market maker tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_market_maker_refreshes_quotes_in_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let pool_id = client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            options_count: 3,
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    client.set_pool_amm_fees(&creator, &pool_id, &10u32, &30u32);
+    let mm = Address::generate(&env);
+
+    let quote = |outcome: u32, odds_bps: u64, size: i128| Quote {
+        outcome,
+        odds_bps,
+        size,
+    };
+    assert_eq!(
+        client.try_refresh_quotes(&mm, &pool_id, &vec![&env, quote(0, 20_000, 100)]),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    assert_eq!(client.get_trading_fee_bps(&pool_id, &mm), 30);
+
+    client.set_market_maker(&operator, &pool_id, &mm, &true);
+    assert_eq!(client.get_trading_fee_bps(&pool_id, &mm), 10);
+    assert_eq!(
+        client.refresh_quotes(
+            &mm,
+            &pool_id,
+            &vec![&env, quote(0, 20_000, 100), quote(2, 35_000, 50)]
+        ),
+        2
+    );
+
+    // Replace one quote, withdraw another, add a third.
+    assert_eq!(
+        client.refresh_quotes(
+            &mm,
+            &pool_id,
+            &vec![
+                &env,
+                quote(0, 21_000, 80),
+                quote(2, 0, 0),
+                quote(1, 15_000, 40)
+            ]
+        ),
+        2
+    );
+    assert_eq!(
+        client.get_quotes(&pool_id, &mm),
+        vec![&env, quote(0, 21_000, 80), quote(1, 15_000, 40)]
+    );
+
+    assert_eq!(
+        client.try_refresh_quotes(
+            &mm,
+            &pool_id,
+            &vec![&env, quote(1, 15_000, 10), quote(1, 16_000, 10)]
+        ),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_refresh_quotes(&mm, &pool_id, &vec![&env, quote(1, 9_000, 10)]),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+
+    client.set_market_maker(&operator, &pool_id, &mm, &false);
+    assert_eq!(client.get_quotes(&pool_id, &mm).len(), 0);
+}
+
+// ── Scalar market tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_resolve_scalar_maps_value_into_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let buckets = vec![
+        &env,
+        (0i128, 50_000i128),
+        (50_000, 60_000),
+        (60_000, i128::MAX),
+    ];
+    let pool_id = client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            options_count: 3,
+            scalar_buckets: buckets.clone(),
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    assert_eq!(client.get_scalar_buckets(&pool_id), buckets);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_resolve_scalar(&operator, &pool_id, &-1),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    // Bucket bounds are inclusive below, exclusive above.
+    assert_eq!(client.resolve_scalar(&operator, &pool_id, &50_000), 1);
+    assert_eq!(client.get_pool(&pool_id).outcome, 1);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+}
+
+#[test]
+#[should_panic(
+    expected = "scalar_buckets must be empty or one ascending, non-overlapping range per outcome"
+)]
+fn test_scalar_buckets_must_not_overlap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            scalar_buckets: vec![&env, (0i128, 100i128), (50, 150)],
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+}
+
+// ── Batch simulation tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_simulate_batch_chains_hypothetical_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let bet = |outcome: u32, amount: i128| SimAction {
+        pool_id,
+        outcome,
+        amount,
+    };
+    let results = client.simulate_batch(&vec![
+        &env,
+        bet(0, 100),
+        bet(1, 300),
+        bet(5, 100),
+        bet(0, 100),
+        SimAction {
+            pool_id: 99,
+            outcome: 0,
+            amount: 100,
+        },
+    ]);
+
+    let sim = |error: u32, accepted: i128, odds_bps: u64, payout: i128| SimResult {
+        pool_id,
+        error,
+        accepted,
+        odds_bps,
+        payout,
+    };
+    assert_eq!(results.get(0).unwrap(), sim(0, 100, 10_000, 100));
+    assert_eq!(results.get(1).unwrap(), sim(0, 300, 13_333, 400));
+    assert_eq!(
+        results.get(2).unwrap(),
+        sim(PredifiError::InvalidOutcome as u32, 0, 0, 0)
+    );
+    assert_eq!(results.get(3).unwrap(), sim(0, 100, 25_000, 250));
+    assert_eq!(
+        results.get(4).unwrap().error,
+        PredifiError::PoolNotFound as u32
+    );
+    // Nothing was written.
+    assert_eq!(client.get_pool(&pool_id).total_stake, 0);
+}
+
+// ── Outcome label tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_outcome_labels_stored_on_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let labels = vec![
+        &env,
+        String::from_str(&env, "Home"),
+        String::from_str(&env, "Away"),
+    ];
+    let pool_id = client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            outcome_labels: labels.clone(),
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    assert_eq!(client.get_outcome_labels(&pool_id), labels);
+
+    let unlabeled =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(client.get_outcome_labels(&unlabeled).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "outcome_labels must be empty or one label of 1..=64 bytes per outcome")]
+fn test_outcome_labels_must_match_options_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            outcome_labels: vec![&env, String::from_str(&env, "Yes")],
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+}
+
+// ── Bettor cancellation tests ────────────────────────────────────────────────
+
+fn bettor_cancel_pool(
+    env: &Env,
+    client: &PredifiContractClient<'_>,
+    ac_client: &dummy_access_control::DummyAccessControlClient<'_>,
+    token_admin_client: &token::StellarAssetClient<'_>,
+    token_address: &Address,
+    creator: &Address,
+) -> (u64, Address, Address) {
+    let admin = Address::generate(env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_bettor_cancel_params(&admin, &6_667u32, &3_600u64);
+
+    let pool_id =
+        client.create_pool_with_params(creator, &creator_fee_params(env, token_address, 0));
+    let whale = Address::generate(env);
+    let small = Address::generate(env);
+    let other = Address::generate(env);
+    token_admin_client.mint(&whale, &300);
+    token_admin_client.mint(&small, &100);
+    token_admin_client.mint(&other, &100);
+    client.place_prediction(&whale, &pool_id, &300, &0);
+    client.place_prediction(&small, &pool_id, &100, &1);
+    client.place_prediction(&other, &pool_id, &100, &0);
+    (pool_id, whale, small)
+}
+
+#[test]
+fn test_bettor_supermajority_cancels_pool_after_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+    let (pool_id, whale, small) = bettor_cancel_pool(
+        &env,
+        &client,
+        &ac_client,
+        &token_admin_client,
+        &token_address,
+        &creator,
+    );
+
+    // 300 of 500 is below the two-thirds threshold.
+    assert_eq!(client.support_cancellation(&whale, &pool_id), 300);
+    assert_eq!(
+        client.get_cancel_request(&pool_id).unwrap().executable_at,
+        0
+    );
+    assert_eq!(
+        client.try_support_cancellation(&Address::generate(&env), &pool_id),
+        Err(Ok(PredifiError::PredictionNotFound))
+    );
+
+    assert_eq!(client.support_cancellation(&small, &pool_id), 400);
+    assert_eq!(
+        client.get_cancel_request(&pool_id).unwrap().executable_at,
+        3_600
+    );
+    assert_eq!(
+        client.try_execute_bettor_cancellation(&pool_id),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    client.execute_bettor_cancellation(&pool_id);
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Canceled);
+    assert_eq!(client.claim_winnings(&whale, &pool_id), 300);
+    assert_eq!(token.balance(&whale), 300);
+}
+
+#[test]
+fn test_operator_vetoes_bettor_cancellation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let (pool_id, whale, small) = bettor_cancel_pool(
+        &env,
+        &client,
+        &ac_client,
+        &token_admin_client,
+        &token_address,
+        &creator,
+    );
+    client.support_cancellation(&whale, &pool_id);
+    client.support_cancellation(&small, &pool_id);
+
+    client.veto_cancellation(&operator, &pool_id);
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    assert_eq!(
+        client.try_execute_bettor_cancellation(&pool_id),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    assert_eq!(client.get_pool(&pool_id).state, MarketState::Active);
+}
+
+// ── LMSR tests ───────────────────────────────────────────────────────────────
+
+fn lmsr_pool(
+    env: &Env,
+    client: &PredifiContractClient<'_>,
+    token_admin_client: &token::StellarAssetClient<'_>,
+    token: &Address,
+    creator: &Address,
+) -> u64 {
+    token_admin_client.mint(creator, &10_000);
+    client.create_pool_with_params(
+        creator,
+        &CreatePoolParams {
+            initial_liquidity: 10_000,
+            mechanism: Mechanism::Lmsr,
+            ..creator_fee_params(env, token, 0)
+        },
+    )
+}
+
+#[test]
+fn test_lmsr_trades_resolve_and_redeem() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id = lmsr_pool(&env, &client, &token_admin_client, &token_address, &creator);
+    assert_eq!(client.get_pool_mechanism(&pool_id), Mechanism::Lmsr);
+    // b = 10_000 / ln 2
+    assert_eq!(client.get_lmsr_state(&pool_id).unwrap().b, 14_426);
+    client.set_pool_amm_fees(&creator, &pool_id, &0, &100);
+
+    let trader = Address::generate(&env);
+    token_admin_client.mint(&trader, &1_000);
+    assert_eq!(
+        client.try_buy_shares(&trader, &pool_id, &0, &1_000, &514),
+        Err(Ok(PredifiError::SlippageExceeded))
+    );
+    // 510 for 1_000 shares at ~0.5, plus the 1% taker fee
+    assert_eq!(client.buy_shares(&trader, &pool_id, &0, &1_000, &515), 515);
+    assert_eq!(
+        client.try_sell_shares(&trader, &pool_id, &0, &1_001, &0),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert_eq!(client.sell_shares(&trader, &pool_id, &0, &400, &0), 203);
+
+    let position = client.get_lmsr_position(&pool_id, &trader).unwrap();
+    assert_eq!(position.shares, vec![&env, 600i128, 0]);
+    assert_eq!(position.basis, 305);
+    assert_eq!(
+        client.try_redeem_shares(&trader, &pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    assert_eq!(client.redeem_shares(&trader, &pool_id), 600);
+    assert_eq!(token.balance(&trader), 1_000 - 515 + 203 + 600);
+    assert_eq!(
+        client.try_redeem_shares(&trader, &pool_id),
+        Err(Ok(PredifiError::PredictionNotFound))
+    );
+
+    // The subsidy covers the 295 traders won beyond what they paid in.
+    assert_eq!(client.claim_initial_liquidity(&creator, &pool_id), 9_705);
+    assert_eq!(client.claim_creator_fees(&creator, &token_address), 7);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_lmsr_cancellation_refunds_cost_basis() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id = lmsr_pool(&env, &client, &token_admin_client, &token_address, &creator);
+
+    let trader = Address::generate(&env);
+    token_admin_client.mint(&trader, &1_000);
+    client.buy_shares(&trader, &pool_id, &1, &1_000, &1_000);
+    client.sell_shares(&trader, &pool_id, &1, &400, &0);
+
+    client.cancel_pool(&operator, &pool_id);
+    assert_eq!(token.balance(&creator), 10_000);
+    assert_eq!(client.redeem_shares(&trader, &pool_id), 305);
+    assert_eq!(token.balance(&trader), 1_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_lmsr_pool_rejects_parimutuel_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id = lmsr_pool(&env, &client, &token_admin_client, &token_address, &creator);
+    let parimutuel =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(
+        client.get_pool_mechanism(&parimutuel),
+        Mechanism::Parimutuel
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    assert_eq!(
+        client.try_place_multi_prediction(&user, &pool_id, &100, &vec![&env, 0u32, 1u32]),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    assert_eq!(
+        client.try_buy_shares(&user, &parimutuel, &0, &10, &100),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+}
+
+// ── Hidden pool tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_hidden_pool_leaves_discovery_but_still_settles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let hidden =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let visible =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &hidden, &100, &0);
+
+    assert_eq!(
+        client.try_set_pool_hidden(&user, &hidden, &true),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_pool_hidden(&operator, &hidden, &true);
+    assert!(client.is_pool_hidden(&hidden));
+
+    let pools = client.get_pools(&0, &10);
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools.get(0).unwrap().pool_id, visible);
+    assert_eq!(
+        client.get_pools_by_category(&symbol_short!("Tech"), &0, &10),
+        vec![&env, visible]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &hidden, &0u32);
+    assert_eq!(client.claim_winnings(&user, &hidden), 100);
+    assert_eq!(token.balance(&user), 100);
+
+    client.set_pool_hidden(&operator, &hidden, &false);
+    assert_eq!(client.get_pools(&0, &10).len(), 2);
+}
+
+// ── Implied odds tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_implied_odds_per_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(client.get_implied_odds(&pool_id), vec![&env, 0u32, 0]);
+
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    token_admin_client.mint(&a, &200);
+    token_admin_client.mint(&b, &100);
+    client.place_prediction(&a, &pool_id, &200, &0);
+    client.place_prediction(&b, &pool_id, &100, &1);
+    // 200/300 and 100/300, rounded down
+    assert_eq!(
+        client.get_implied_odds(&pool_id),
+        vec![&env, 6_666u32, 3_333]
+    );
+
+    let lmsr = lmsr_pool(&env, &client, &token_admin_client, &token_address, &creator);
+    assert_eq!(client.get_implied_odds(&lmsr), vec![&env, 5_000u32, 5_000]);
+}
+
+// ── Claim-and-create tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_claim_and_create_rolls_liquidity_into_next_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &100);
+    let pool_id = liquidity_pool(&env, &client, &creator, &token_address);
+
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&loser, &50);
+    client.place_prediction(&loser, &pool_id, &50, &1);
+
+    assert_eq!(
+        client.try_claim_and_create(
+            &creator,
+            &pool_id,
+            &creator_fee_params(&env, &token_address, 0)
+        ),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.force_resolve_pool(&operator, &pool_id, &0u32, &String::from_str(&env, ""));
+
+    // Nobody backed outcome 0, so the whole 150 returns to the creator.
+    let next = client.claim_and_create(
+        &creator,
+        &pool_id,
+        &CreatePoolParams {
+            end_time: 200000,
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    let pool = client.get_pool(&next);
+    assert_eq!(pool.initial_liquidity, 150);
+    assert_eq!(pool.total_stake, 150);
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(token.balance(&client.address), 150);
+    assert_eq!(
+        client.try_claim_initial_liquidity(&creator, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+// ── Payout quoting tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_quote_prediction_and_simulate_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    token_admin_client.mint(&a, &100);
+    token_admin_client.mint(&b, &300);
+    client.place_prediction(&a, &pool_id, &100, &0);
+    client.place_prediction(&b, &pool_id, &300, &1);
+
+    // Another 100 on outcome 0: 100 of 200 winning stake share a 500 pool.
+    assert_eq!(client.quote_prediction(&pool_id, &0, &100), 250);
+    assert_eq!(
+        client.try_quote_prediction(&pool_id, &2, &100),
+        Err(Ok(PredifiError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_simulate_claim(&a, &pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(
+        client.try_quote_prediction(&pool_id, &0, &100),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    assert_eq!(client.simulate_claim(&a, &pool_id), 400);
+    assert_eq!(client.simulate_claim(&b, &pool_id), 0);
+    assert_eq!(client.simulate_claim(&Address::generate(&env), &pool_id), 0);
+    assert_eq!(client.claim_winnings(&a, &pool_id), 400);
+    assert_eq!(
+        client.try_simulate_claim(&a, &pool_id),
+        Err(Ok(PredifiError::AlreadyClaimed))
+    );
+}
+
+// ── Conditional market tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_conditional_pools_follow_parent_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let parent =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let if_yes =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let if_no =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    assert_eq!(
+        client.try_set_parent_condition(&creator, &parent, &if_yes, &0),
+        Err(Ok(PredifiError::InvalidPoolReference))
+    );
+    client.set_parent_condition(&creator, &if_yes, &parent, &0);
+    client.set_parent_condition(&creator, &if_no, &parent, &1);
+    assert_eq!(
+        client.get_parent_condition(&if_no),
+        Some(ParentCondition {
+            parent_pool_id: parent,
+            parent_outcome: 1,
+        })
+    );
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+    client.place_prediction(&user, &parent, &50, &0);
+    client.place_prediction(&user, &if_yes, &50, &0);
+    client.place_prediction(&user, &if_no, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &if_yes, &0u32),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+
+    client.resolve_pool(&operator, &parent, &0u32);
+    assert_eq!(client.get_pool(&if_no).state, MarketState::Canceled);
+    assert_eq!(client.claim_winnings(&user, &if_no), 100);
+
+    client.resolve_pool(&operator, &if_yes, &0u32);
+    assert_eq!(client.claim_winnings(&user, &if_yes), 50);
+    assert_eq!(token.balance(&user), 150);
+}
+
+// ── Claim sequence tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_claims_take_consecutive_sequence_numbers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let (a, b, loser) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    for user in [&a, &b, &loser] {
+        token_admin_client.mint(user, &100);
+    }
+    client.place_prediction(&a, &pool_id, &100, &0);
+    client.place_prediction(&b, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.get_claim_sequence(&pool_id), 0);
+
+    client.claim_partial(&a, &pool_id, &50);
+    client.claim_winnings(&loser, &pool_id);
+    client.claim_winnings(&b, &pool_id);
+    client.claim_winnings(&a, &pool_id);
+    // Only paying claims are numbered.
+    assert_eq!(client.get_claim_sequence(&pool_id), 3);
+}
+
+// ── Market template tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_pools_from_templates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, _, creator) = setup(&env);
+    let labels = vec![
+        &env,
+        String::from_str(&env, "Home"),
+        String::from_str(&env, "Away"),
+    ];
+    let template_id = client.create_template(
+        &creator,
+        &CreatePoolParams {
+            outcome_labels: labels.clone(),
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    assert!(!client.get_template(&template_id).unwrap().public);
+
+    let week1 = client.create_pool_from_template(&creator, &template_id, &7_200);
+    let week2 = client.create_pool_from_template(&creator, &template_id, &14_400);
+    assert_eq!(client.get_pool(&week1).end_time, 7_200);
+    assert_eq!(client.get_pool(&week2).end_time, 14_400);
+    assert_eq!(client.get_outcome_labels(&week2), labels);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_create_pool_from_template(&stranger, &template_id, &7_200),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_create_pool_from_template(&creator, &99, &7_200),
+        Err(Ok(PredifiError::TemplateNotFound))
+    );
+    assert_eq!(
+        client.try_create_template(
+            &creator,
+            &CreatePoolParams {
+                category: symbol_short!("Nope"),
+                ..creator_fee_params(&env, &token_address, 0)
+            }
+        ),
+        Err(Ok(PredifiError::InvalidCategory))
+    );
+
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let public = client.create_template(&admin, &creator_fee_params(&env, &token_address, 0));
+    assert!(client.get_template(&public).unwrap().public);
+    client.create_pool_from_template(&stranger, &public, &7_200);
+}
+
+// ── Max pool duration tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_max_pool_duration_with_operator_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    assert_eq!(
+        client.try_set_max_pool_duration(&creator, &86_400),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_max_pool_duration(&admin, &86_400);
+    assert_eq!(client.get_max_pool_duration(), 86_400);
+
+    // creator_fee_params ends at 100_000, beyond the one-day cap.
+    assert_eq!(
+        client.try_create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0)),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::PoolDurationTooLong as u32
+        )))
+    );
+    client.create_pool_with_params(
+        &creator,
+        &CreatePoolParams {
+            end_time: 86_400,
+            ..creator_fee_params(&env, &token_address, 0)
+        },
+    );
+    client.create_pool_with_params(&operator, &creator_fee_params(&env, &token_address, 0));
+}
+
+// ── Dead-man switch tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_dead_man_switch_halts_creation_after_admin_inactivity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let guardian = Address::generate(&env);
+    let year = 365 * 24 * 60 * 60;
+
+    assert_eq!(
+        client.try_set_dead_man_switch(&admin, &guardian, &3_600),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_dead_man_switch(&admin, &guardian, &year);
+    assert_eq!(client.get_last_admin_action(), 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + year - 1);
+    assert_eq!(
+        client.try_trigger_dead_man_switch(&guardian),
+        Err(Ok(PredifiError::AdminStillActive))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + year);
+    assert_eq!(
+        client.try_trigger_dead_man_switch(&creator),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    let claims_until = client.trigger_dead_man_switch(&guardian);
+    assert_eq!(claims_until, 1_000 + 2 * year);
+    assert_eq!(client.get_dead_man_triggered_at(), Some(1_000 + year));
+
+    let params = CreatePoolParams {
+        end_time: 1_000 + year + 100_000,
+        ..creator_fee_params(&env, &token_address, 0)
+    };
+    assert_eq!(
+        client.try_create_pool_with_params(&creator, &params),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            PredifiError::DeadManSwitchTriggered as u32
+        )))
+    );
+
+    // A returning admin re-arms the switch, which lifts the freeze.
+    client.set_dead_man_switch(&admin, &guardian, &year);
+    assert_eq!(client.get_dead_man_triggered_at(), None);
+    client.create_pool_with_params(&creator, &params);
+}
+
+// ── Pool archival tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_close_pool_deletes_settled_bettor_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    for user in [&winner, &loser] {
+        token_admin_client.mint(user, &100);
+    }
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    assert_eq!(
+        client.try_close_pool(&pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(
+        client.try_close_pool(&pool_id),
+        Err(Ok(PredifiError::ClaimDeadlineNotReached))
+    );
+
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 200);
+    assert_eq!(client.close_pool(&pool_id), 0);
+    assert!(client.get_pool_archived_at(&pool_id).is_some());
+    assert_eq!(client.get_outcome_stake(&pool_id, &0), 0);
+    assert_eq!(client.get_user_predictions(&loser, &0, &10).len(), 0);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 200);
+    assert_eq!(
+        client.try_close_pool(&pool_id),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+}
+
+// ── TTL keeper tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_extend_pool_ttl_is_public() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let live_until = client.extend_pool_ttl(&pool_id);
+    assert_eq!(live_until, env.ledger().sequence() + 30 * 17280);
+    assert_eq!(
+        client.try_extend_pool_ttl(&99),
+        Err(Ok(PredifiError::PoolNotFound))
+    );
+}
+
+// ── Public goods fee tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_public_goods_share_of_creator_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_max_creator_fee_bps(&admin, &1_000u32);
+    let charity = Address::generate(&env);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 1_000));
+    assert_eq!(
+        client.try_set_pool_public_goods(&creator, &pool_id, &charity, &2_500),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.add_public_goods_recipient(&admin, &charity);
+    assert!(client.is_public_goods_recipient(&charity));
+    assert_eq!(
+        client.try_set_pool_public_goods(&operator, &pool_id, &charity, &2_500),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_pool_public_goods(&creator, &pool_id, &charity, &2_500);
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    // Gross 200: creator fee 20 (10%), a quarter of which goes to the charity.
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 180);
+    assert_eq!(
+        client.get_creator_fees_accrued(&creator, &token_address),
+        15
+    );
+    assert_eq!(client.get_public_goods_accrued(&charity, &token_address), 5);
+    assert_eq!(
+        client
+            .get_pool_public_goods(&pool_id)
+            .unwrap()
+            .total_accrued,
+        5
+    );
+
+    assert_eq!(client.claim_public_goods_fees(&charity, &token_address), 5);
+    assert_eq!(token.balance(&charity), 5);
+    assert_eq!(client.get_public_goods_accrued(&charity, &token_address), 0);
+}
+
+// ── Pause scope tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_pause_betting_still_allows_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    client.set_pause_scopes(&admin, &PAUSE_BETTING);
+    assert_eq!(client.get_pause_scopes(), PAUSE_BETTING);
+    assert!(client
+        .try_place_prediction(&user, &pool_id, &100, &0)
+        .is_err());
+    assert!(client
+        .try_withdraw_prediction(&user, &pool_id, &None)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&user, &pool_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "Operation is paused")]
+fn test_pause_creation_blocks_new_pools() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, _, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    client.set_pause_scopes(&admin, &(PAUSE_CREATION | PAUSE_CLAIMS));
+    client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+}
+
+// ── Airdrop snapshot tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_airdrop_snapshot_freezes_user_activity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let idle = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &60, &0);
+    client.increase_stake(&winner, &pool_id, &40);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    client.claim_winnings(&winner, &pool_id);
+
+    let users = Vec::from_array(&env, [winner.clone(), loser.clone(), idle]);
+    assert_eq!(client.snapshot_airdrop(&admin, &1, &users), 2);
+    assert_eq!(client.freeze_airdrop_snapshot(&admin, &1), 2);
+    assert_eq!(
+        client.try_snapshot_airdrop(&admin, &1, &users),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    let page = client.get_airdrop_snapshot(&1, &0, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(
+        page.get(0).unwrap(),
+        AirdropEntry {
+            user: winner,
+            volume: 100,
+            wins: 1,
+            pools: 1,
+        }
+    );
+    assert_eq!(page.get(1).unwrap().wins, 0);
+    assert_eq!(client.get_airdrop_snapshot(&1, &1, &10).len(), 1);
+}
+
+// ── Capability tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_capabilities_follow_deployment_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, _, _) = setup(&env);
+    let caps = client.get_capabilities();
+    assert!(caps.amm && caps.parlays);
+    assert!(!caps.disputes && !caps.claim_deadlines && !caps.internal_balances);
+
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_dispute_window(&admin, &3_600u64);
+    client.set_claim_period(&admin, &(30 * 86_400u64));
+    let caps = client.get_capabilities();
+    assert!(caps.disputes && caps.claim_deadlines);
+}
+
+// ── Claim progress tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_claim_progress_tracks_winning_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let small = Address::generate(&env);
+    let large = Address::generate(&env);
+    let loser = Address::generate(&env);
+    for user in [&small, &large, &loser] {
+        token_admin_client.mint(user, &300);
+    }
+    client.place_prediction(&small, &pool_id, &100, &0);
+    client.place_prediction(&large, &pool_id, &300, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    assert_eq!(
+        client.try_get_claim_progress(&pool_id),
+        Err(Ok(PredifiError::PoolNotResolved))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.get_claim_progress(&pool_id).claimed_bps, 0);
+
+    assert_eq!(client.claim_winnings(&small, &pool_id), 125);
+    let progress = client.get_claim_progress(&pool_id);
+    assert_eq!(progress.claimed_bps, 2_500);
+    assert_eq!(progress.stats.claims.get(0), Some(1));
+    assert_eq!(progress.stats.paid.get(0), Some(125));
+
+    client.claim_winnings(&large, &pool_id);
+    assert_eq!(client.get_claim_progress(&pool_id).claimed_bps, 10_000);
+}
+
+// ── Config view tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_get_config_and_is_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, treasury, _, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &250u32);
+
+    let config = client.get_config();
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.treasury, treasury);
+    assert_eq!(config.resolution_delay, 0);
+
+    assert!(!client.is_paused());
+    client.pause(&admin);
+    assert!(client.is_paused());
+}
+
+// ── Access-control failure tests ─────────────────────────────────────────────
+
+#[test]
+fn test_broken_access_control_is_reported_distinctly() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // A contract without `has_role` stands in for a broken access-control
+    // deployment.
+    let not_access_control = env.register(PredifiContract, ());
+    let contract_id = env.register(PredifiContract, ());
+    let client = PredifiContractClient::new(&env, &contract_id);
+    let treasury = Address::generate(&env);
+    client.init(&not_access_control, &treasury, &0u32, &0u64);
+
+    let admin = Address::generate(&env);
+    assert_eq!(
+        client.try_set_fee_bps(&admin, &100u32),
+        Err(Ok(PredifiError::AccessControlUnavailable))
+    );
+}
+
+// ── Creator bond tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_creator_bond_returned_on_resolution_and_forfeited_on_violation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, treasury, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_creator_bond(&admin, &token_address, &500);
+    token_admin_client.mint(&creator, &1_000);
+
+    let honest =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let spam =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_pool_bond(&honest).unwrap().amount, 500);
+
+    client.cancel_pool_for_violation(&operator, &spam, &String::from_str(&env, "spam"));
+    assert_eq!(token.balance(&treasury), 500);
+    assert_eq!(client.get_pool_bond(&spam), None);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &honest, &0u32);
+    assert_eq!(token.balance(&creator), 500);
+    assert_eq!(client.get_pool_bond(&honest), None);
+}
+
+// ── Optimistic resolution tests ──────────────────────────────────────────────
+
+#[test]
+fn test_unchallenged_assertion_settles_after_liveness() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_optimistic_config(&admin, &100, &7_200u64);
+    assert!(client.get_capabilities().optimistic_resolution);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let asserter = Address::generate(&env);
+    token_admin_client.mint(&asserter, &100);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+
+    assert_eq!(
+        client.try_assert_outcome(&asserter, &pool_id, &1u32, &99),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert_eq!(
+        client.assert_outcome(&asserter, &pool_id, &1u32, &100),
+        107_201
+    );
+    assert_eq!(token.balance(&asserter), 0);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &0u32),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    assert_eq!(
+        client.try_settle_assertion(&pool_id),
+        Err(Ok(PredifiError::AssertionLivenessActive))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 107_201);
+    assert_eq!(client.settle_assertion(&pool_id), 1);
+    let pool = client.get_pool(&pool_id);
+    assert!(pool.resolved);
+    assert_eq!(pool.outcome, 1);
+    assert_eq!(token.balance(&asserter), 100);
+    assert_eq!(client.get_outcome_assertion(&pool_id), None);
+}
+
+#[test]
+fn test_challenged_assertion_pays_both_bonds_to_winner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_optimistic_config(&admin, &100, &7_200u64);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let asserter = Address::generate(&env);
+    let challenger = Address::generate(&env);
+    let late = Address::generate(&env);
+    token_admin_client.mint(&asserter, &250);
+    token_admin_client.mint(&challenger, &250);
+    token_admin_client.mint(&late, &250);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+
+    client.assert_outcome(&asserter, &pool_id, &1u32, &250);
+    client.challenge_assertion(&challenger, &pool_id);
+    assert_eq!(
+        client.try_challenge_assertion(&late, &pool_id),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 200_000);
+    assert_eq!(
+        client.try_settle_assertion(&pool_id),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    assert_eq!(
+        client.try_resolve_assertion(&late, &pool_id, &0u32),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    assert_eq!(
+        client.resolve_assertion(&operator, &pool_id, &0u32),
+        challenger
+    );
+    assert_eq!(client.get_pool(&pool_id).outcome, 0);
+    assert_eq!(token.balance(&challenger), 500);
+    assert_eq!(token.balance(&asserter), 0);
+}
+
+#[test]
+fn test_assertion_cannot_be_challenged_after_liveness() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let asserter = Address::generate(&env);
+    let challenger = Address::generate(&env);
+    token_admin_client.mint(&asserter, &100);
+    token_admin_client.mint(&challenger, &100);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_assert_outcome(&asserter, &pool_id, &0u32, &100),
+        Err(Ok(PredifiError::OptimisticResolutionDisabled))
+    );
+
+    client.set_optimistic_config(&admin, &100, &60u64);
+    client.assert_outcome(&asserter, &pool_id, &0u32, &100);
+    env.ledger().with_mut(|li| li.timestamp = 100061);
+    assert_eq!(
+        client.try_challenge_assertion(&challenger, &pool_id),
+        Err(Ok(PredifiError::AssertionLivenessExpired))
+    );
+}
+
+// ── Pool notes tests ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_pool_notes_feed_is_bounded_and_restricted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, _, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let postponed = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(client.post_pool_note(&creator, &pool_id, &postponed), 0);
+    assert_eq!(
+        client.try_post_pool_note(&Address::generate(&env), &pool_id, &postponed),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    for i in 1..MAX_POOL_NOTES {
+        let hash = BytesN::from_array(&env, &[i as u8; 32]);
+        assert_eq!(client.post_pool_note(&operator, &pool_id, &hash), i);
+    }
+    assert_eq!(
+        client.try_post_pool_note(&operator, &pool_id, &postponed),
+        Err(Ok(PredifiError::CapacityReached))
+    );
+
+    let notes = client.get_pool_notes(&pool_id);
+    assert_eq!(notes.len(), MAX_POOL_NOTES);
+    let first = notes.get(0).unwrap();
+    assert_eq!(first.author, creator);
+    assert_eq!(first.hash, postponed);
+    assert_eq!(first.posted_at, 500);
+}
+
+// ── Sponsor branding tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_pool_sponsor_locks_after_first_bet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let sponsor = PoolSponsor {
+        name: symbol_short!("Acme"),
+        logo_uri_hash: BytesN::from_array(&env, &[1u8; 32]),
+        link_hash: BytesN::from_array(&env, &[2u8; 32]),
+    };
+
+    assert_eq!(
+        client.try_set_pool_sponsor(&Address::generate(&env), &pool_id, &Some(sponsor.clone())),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_pool_sponsor(&creator, &pool_id, &Some(sponsor.clone()));
+    assert_eq!(client.get_pool_sponsor(&pool_id), Some(sponsor));
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+    assert_eq!(
+        client.try_set_pool_sponsor(&creator, &pool_id, &None),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+}
+
+// ── Reflector price market tests ─────────────────────────────────────────────
+
+#[test]
+fn test_price_market_resolves_from_reflector_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let feed_id = env.register(crate::test_utils::dummy_reflector::DummyReflector, ());
+    let feed = crate::test_utils::dummy_reflector::DummyReflectorClient::new(&env, &feed_id);
+    let btc = ReflectorAsset::Other(symbol_short!("BTC"));
+
+    let mut params = creator_fee_params(&env, &token_address, 0);
+    params.scalar_buckets = vec![&env, (0, 50_000), (50_000, i128::MAX)];
+    let pool_id = client.create_pool_with_params(&creator, &params);
+    client.set_price_market(&operator, &pool_id, &feed_id, &btc, &300u64);
+
+    // Only the low bucket has stake; the feed decides regardless.
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100_200);
+    assert_eq!(
+        client.try_resolve_from_price_feed(&pool_id),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100_400);
+    assert_eq!(
+        client.try_resolve_from_price_feed(&pool_id),
+        Err(Ok(PredifiError::PriceFeedNotFound))
+    );
+
+    feed.set_price(&btc, &100_300u64, &64_250);
+    assert_eq!(client.resolve_from_price_feed(&pool_id), 1);
+    let pool = client.get_pool(&pool_id);
+    assert!(pool.resolved);
+    assert_eq!(pool.outcome, 1);
+    // The round proof is only published in `OracleResolvedEvent`.
+    assert_eq!(client.get_operator_note(&pool_id), None);
+}
+
+// ── Deferred payout tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_refused_payout_is_deferred_and_retried() {
+    use soroban_sdk::testutils::IssuerFlags;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, operator, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    // Clawback-style asset whose issuer can deauthorize trustlines.
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = token::Client::new(&env, &asset.address());
+    let token_admin_client = token::StellarAssetClient::new(&env, &asset.address());
+    client.add_token_to_whitelist(&admin, &asset.address());
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &asset.address(), 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    token_admin_client.set_authorized(&winner, &false);
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 200);
+    let pending = client.get_held_payout(&winner, &pool_id).unwrap();
+    assert_eq!(pending.amount, 200);
+    assert_eq!(pending.reason, HoldReason::TransferRefused);
+    assert_eq!(
+        client.try_retry_payout(&winner, &pool_id),
+        Err(Ok(PredifiError::PayoutTransferFailed))
+    );
+
+    token_admin_client.set_authorized(&winner, &true);
+    assert_eq!(client.retry_payout(&winner, &pool_id), 200);
+    assert_eq!(token.balance(&winner), 200);
+    assert_eq!(client.get_held_payout(&winner, &pool_id), None);
+    assert_eq!(
+        client.try_retry_payout(&winner, &pool_id),
+        Err(Ok(PredifiError::PayoutNotHeld))
+    );
+}
+
+#[test]
+fn test_relayed_claim_deferred_pays_no_tip() {
+    use soroban_sdk::testutils::IssuerFlags;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, operator, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = token::Client::new(&env, &asset.address());
+    let token_admin_client = token::StellarAssetClient::new(&env, &asset.address());
+    client.add_token_to_whitelist(&admin, &asset.address());
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &asset.address(), 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+    client.set_claim_tip(&winner, &500u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+
+    token_admin_client.set_authorized(&winner, &false);
+    assert_eq!(client.claim_for(&relayer, &winner, &pool_id), 200);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &ClaimRelayedEvent {
+            pool_id,
+            beneficiary: winner.clone(),
+            relayer: relayer.clone(),
+            tip: 0,
+        }
+    ));
+    assert_eq!(token.balance(&relayer), 0);
+    assert_eq!(
+        client.get_held_payout(&winner, &pool_id).unwrap().amount,
+        200
+    );
+
+    // The user receives the whole payout once the transfer goes through.
+    token_admin_client.set_authorized(&winner, &true);
+    assert_eq!(client.retry_payout(&winner, &pool_id), 200);
+    assert_eq!(token.balance(&winner), 200);
+    assert_eq!(token.balance(&relayer), 0);
+}
+
+// ── Keeper finalization tests ────────────────────────────────────────────────
+
+#[test]
+fn test_keeper_finalizes_authorized_outcome_for_a_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_keeper_config(&admin, &100u32, &0u64);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    let keeper = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    assert_eq!(
+        client.try_finalize_pool(&keeper, &pool_id),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+    client.authorize_outcome(&operator, &pool_id, &0u32);
+    assert_eq!(
+        client.try_resolve_pool(&operator, &pool_id, &1u32),
+        Err(Ok(PredifiError::InvalidPoolState))
+    );
+
+    assert_eq!(
+        client.finalize_pool(&keeper, &pool_id),
+        MarketState::Resolved
+    );
+    assert_eq!(client.get_pool(&pool_id).outcome, 0);
+    assert_eq!(client.get_pool_keeper(&pool_id).unwrap().keeper, keeper);
+
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 198);
+    assert_eq!(token.balance(&winner), 198);
+    assert_eq!(token.balance(&keeper), 2);
+}
+
+#[test]
+fn test_claim_event_gross_is_net_plus_all_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+    client.set_max_creator_fee_bps(&admin, &200u32);
+    client.set_keeper_config(&admin, &100u32, &0u64);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 200));
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.authorize_outcome(&operator, &pool_id, &0u32);
+    client.finalize_pool(&Address::generate(&env), &pool_id);
+
+    // The first payout carries every fee of the position: 200 gross less a
+    // 20 protocol fee, a 4 creator fee and a 2 keeper reward.
+    assert_eq!(client.claim_partial(&winner, &pool_id, &100), 100);
+    let first = WinningsClaimedEvent {
+        pool_id,
+        user: winner.clone(),
+        amount: 100,
+        gross: 126,
+        fee: 20,
+        creator_fee: 4,
+        keeper_fee: 2,
+        net: 100,
+        sequence: 1,
+    };
+    assert_eq!(
+        first.gross,
+        first.net + first.fee + first.creator_fee + first.keeper_fee
+    );
+    assert!(emitted(&env, &client.address, &first));
+
+    // The rest of the position is paid without further fees.
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 74);
+    assert!(emitted(
+        &env,
+        &client.address,
+        &WinningsClaimedEvent {
+            pool_id,
+            user: winner.clone(),
+            amount: 74,
+            gross: 74,
+            fee: 0,
+            creator_fee: 0,
+            keeper_fee: 0,
+            net: 74,
+            sequence: 2,
+        }
+    ));
+}
+
+#[test]
+fn test_keeper_auto_cancels_stale_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_keeper_config(&admin, &100u32, &86_400u64);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    let keeper = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 100_000 + 86_399);
+    assert_eq!(
+        client.try_finalize_pool(&keeper, &pool_id),
+        Err(Ok(PredifiError::ResolutionDelayNotMet))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100_000 + 86_400);
+    assert_eq!(
+        client.finalize_pool(&keeper, &pool_id),
+        MarketState::Canceled
+    );
+
+    assert_eq!(client.claim_winnings(&user, &pool_id), 99);
+    assert_eq!(token.balance(&keeper), 1);
+}
+
+// ── Hot pool tests ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_hot_pool_defers_pool_writes_until_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    client.set_hot_pool(&creator, &pool_id, &true);
+    assert!(client.is_hot_pool(&pool_id));
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &100);
+    token_admin_client.mint(&loser, &300);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&loser, &pool_id, &300, &1);
+
+    // The stored Pool is untouched; views fold in the pending delta.
+    let stored: Pool = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .unwrap()
+    });
+    assert_eq!(stored.total_stake, 0);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 400);
+    assert_eq!(client.get_pool_summary(&pool_id).total_stake, 400);
+    assert_eq!(
+        client.get_pools(&pool_id, &1).get(0).unwrap().total_stake,
+        400
+    );
+    assert!(client.audit_pool_escrow(&pool_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&winner, &pool_id), 400);
+    assert_eq!(token.balance(&winner), 400);
+}
+
+#[test]
+fn test_hot_pool_bettor_finds_and_claims_position() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    client.set_hot_pool(&creator, &pool_id, &true);
+
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &150);
+    token_admin_client.mint(&loser, &100);
+    client.place_prediction(&winner, &pool_id, &100, &0);
+    client.place_prediction(&winner, &pool_id, &50, &0);
+    client.place_prediction(&loser, &pool_id, &100, &1);
+
+    // Indexed once per bettor, with the whole position.
+    let predictions = client.get_user_predictions(&winner, &0, &10);
+    assert_eq!(predictions.len(), 1);
+    let position = predictions.get(0).unwrap();
+    assert_eq!(position.pool_id, pool_id);
+    assert_eq!(position.amount, 150);
+    assert_eq!(position.user_outcome, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    assert_eq!(client.claim_winnings(&winner, &position.pool_id), 250);
+    assert_eq!(token.balance(&winner), 250);
+}
+
+// ── Pool migration tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_migrate_pool_moves_liquidity_and_consenting_positions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, token, token_admin_client, _, operator, creator) = setup(&env);
+    token_admin_client.mint(&creator, &1_000);
+    let mut params = creator_fee_params(&env, &token_address, 0);
+    params.initial_liquidity = 1_000;
+    let old_pool = client.create_pool_with_params(&creator, &params);
+    let new_pool =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+
+    let mover = Address::generate(&env);
+    let stayer = Address::generate(&env);
+    token_admin_client.mint(&mover, &100);
+    token_admin_client.mint(&stayer, &50);
+    client.place_prediction(&mover, &old_pool, &100, &0);
+    client.place_prediction(&stayer, &old_pool, &50, &1);
+    client.set_migration_consent(&mover, &old_pool, &Some(new_pool));
+    assert_eq!(
+        client.get_migration_consent(&mover, &old_pool),
+        Some(new_pool)
+    );
+
+    let users = vec![&env, mover.clone(), stayer.clone()];
+    assert_eq!(
+        client.migrate_pool(&operator, &old_pool, &new_pool, &users),
+        1
+    );
+    assert_eq!(client.get_migration_consent(&mover, &old_pool), None);
+
+    let old = client.get_pool(&old_pool);
+    let new = client.get_pool(&new_pool);
+    assert_eq!((old.total_stake, old.initial_liquidity), (50, 0));
+    assert_eq!((new.total_stake, new.initial_liquidity), (1_100, 1_000));
+    let details = client.get_user_predictions(&mover, &0, &10);
+    assert_eq!(details.len(), 1);
+    assert_eq!(details.get(0).unwrap().pool_id, new_pool);
+    assert_eq!(client.get_outcome_stake(&old_pool, &0u32), 0);
+    assert_eq!(client.get_outcome_stake(&new_pool, &0u32), 100);
+    assert_eq!(token.balance(&client.address), 1_150);
+}
+
+// ── Token params tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_token_min_stake_enforced_on_place_prediction() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_token_min_stake(&admin, &token_address, &50);
+    // Decimals come from the token itself; listing it again keeps the minimum.
+    client.add_token_to_whitelist(&admin, &token_address);
+    assert_eq!(
+        client.get_token_params(&token_address),
+        TokenParams {
+            decimals: 7,
+            min_stake: 50
+        }
+    );
+    let wide = env.register(crate::test_utils::dummy_wide_token::DummyWideToken, ());
+    assert_eq!(
+        client.try_add_token_to_whitelist(&admin, &wide),
+        Err(Ok(PredifiError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_token_min_stake(&admin, &wide, &1),
+        Err(Ok(PredifiError::TokenNotWhitelisted))
+    );
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &100);
+    assert!(client
+        .try_place_prediction(&user, &pool_id, &49, &0)
+        .is_err());
+    client.place_prediction(&user, &pool_id, &50, &0);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 50);
+}
+
+// ── Referral code tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_ref_code_registry_and_referral_binding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, operator, creator) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let code = symbol_short!("ALICE");
+    client.register_ref_code(&alice, &code);
+    assert_eq!(
+        client.try_register_ref_code(&bob, &code),
+        Err(Ok(PredifiError::ReferralCodeTaken))
+    );
+    assert_eq!(
+        client.try_transfer_ref_code(&bob, &code, &bob),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+    client.place_prediction_with_code(&user, &pool_id, &100, &0, &code);
+    assert_eq!(client.get_referrer(&user), Some(alice.clone()));
+
+    // Transfers keep recorded referrals; the first referrer sticks.
+    client.transfer_ref_code(&alice, &code, &bob);
+    assert_eq!(client.get_ref_code_owner(&code), Some(bob.clone()));
+    let next_pool =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    client.place_prediction_with_code(&user, &next_pool, &100, &0, &code);
+    assert_eq!(client.get_referrer(&user), Some(alice));
+
+    client.revoke_ref_code(&operator, &code);
+    assert_eq!(client.get_ref_code_owner(&code), None);
+}
+
+// ── Private pool tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_private_pool_rejects_bettors_off_the_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, token_address, _, token_admin_client, _, _, creator) = setup(&env);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let invited = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    token_admin_client.mint(&invited, &100);
+    token_admin_client.mint(&outsider, &100);
+
+    assert_eq!(
+        client.try_set_pool_private(&outsider, &pool_id, &true),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_pool_private(&creator, &pool_id, &true);
+    client.add_allowed_bettor(&creator, &pool_id, &invited);
+    assert!(client.is_pool_private(&pool_id));
+    assert!(!client.is_allowed_bettor(&pool_id, &outsider));
+
+    client.place_prediction(&invited, &pool_id, &100, &0);
+    assert!(client
+        .try_place_prediction(&outsider, &pool_id, &100, &1)
+        .is_err());
+    let outcomes = vec![&env, 0u32, 1u32];
+    assert_eq!(
+        client.try_place_multi_prediction(&outsider, &pool_id, &100, &outcomes),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    client.set_pool_private(&creator, &pool_id, &false);
+    client.place_prediction(&outsider, &pool_id, &100, &1);
+    assert_eq!(client.get_pool(&pool_id).total_stake, 200);
+}
+
+// ── Blocklist tests ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_blocked_address_cannot_bet_and_claim_waits_for_unblock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &200);
+    client.place_prediction(&user, &pool_id, &100, &0);
+
+    client.block_address(&admin, &user);
+    assert!(client.is_blocked(&user));
+    assert!(client
+        .try_place_prediction(&user, &pool_id, &100, &0)
+        .is_err());
+    assert_eq!(
+        client.try_increase_stake(&user, &pool_id, &100),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    client.claim_winnings(&user, &pool_id);
+    assert_eq!(token.balance(&user), 100);
+    let pending = client.get_held_payout(&user, &pool_id).unwrap();
+    assert_eq!(pending.amount, 100);
+    assert_eq!(pending.reason, HoldReason::Blocked);
+    assert_eq!(
+        client.try_retry_payout(&user, &pool_id),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+
+    client.unblock_address(&admin, &user);
+    assert_eq!(client.retry_payout(&user, &pool_id), 100);
+    assert_eq!(token.balance(&user), 200);
+}
+
+// ── Curation queue tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_curation_queue_drafts_until_approved_or_timed_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, _, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_curation_config(&admin, &true, &3_600u64);
+
+    let user = Address::generate(&env);
+    token_admin_client.mint(&user, &300);
+    let params = creator_fee_params(&env, &token_address, 0);
+
+    // Approved by an operator.
+    let approved = client.create_pool_with_params(&creator, &params);
+    assert!(client.is_pool_draft(&approved));
+    assert!(client
+        .try_place_prediction(&user, &approved, &100, &0)
+        .is_err());
+    client.approve_pool(&operator, &approved);
+    client.place_prediction(&user, &approved, &100, &0);
+
+    // Rejected with a reason.
+    let rejected = client.create_pool_with_params(&creator, &params);
+    client.reject_pool(&operator, &rejected, &String::from_str(&env, "duplicate"));
+    assert_eq!(client.get_pool(&rejected).state, MarketState::Canceled);
+
+    // Approved automatically after the timeout.
+    let timed_out = client.create_pool_with_params(&creator, &params);
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert!(!client.is_pool_draft(&timed_out));
+    client.place_prediction(&user, &timed_out, &100, &0);
+
+    // Operators skip the queue.
+    let curated = client.create_pool_with_params(&operator, &params);
+    assert!(!client.is_pool_draft(&curated));
+}
+
+// ── Referral reward tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_referrer_earns_share_of_protocol_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, token_address, token, token_admin_client, _, operator, creator) =
+        setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    client.set_fee_bps(&admin, &1_000u32);
+    client.set_referral_share_bps(&admin, &5_000u32);
+
+    let pool_id =
+        client.create_pool_with_params(&creator, &creator_fee_params(&env, &token_address, 0));
+    let referrer = Address::generate(&env);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    token_admin_client.mint(&winner, &1_000);
+    token_admin_client.mint(&loser, &1_000);
+    client.place_prediction_with_referrer(&winner, &pool_id, &1_000, &0, &Some(referrer.clone()));
+    client.place_prediction(&loser, &pool_id, &1_000, &1);
+    assert_eq!(client.get_referrer(&winner), Some(referrer.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp = 100001);
+    client.resolve_pool(&operator, &pool_id, &0u32);
+    let paid = client.claim_winnings(&winner, &pool_id);
+
+    let rewards = client.get_referral_rewards(&referrer, &token_address);
+    assert!(rewards > 0);
+    assert_eq!(
+        client.get_fees_accrued(&token_address) + rewards,
+        2_000 - paid
+    );
+    assert_eq!(
+        client.claim_referral_rewards(&referrer, &token_address),
+        rewards
+    );
+    assert_eq!(token.balance(&referrer), rewards);
+    assert_eq!(client.get_referral_rewards(&referrer, &token_address), 0);
+}
+
+// ── Display key registry tests ───────────────────────────────────────────────
+
+#[test]
+fn test_display_key_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (ac_client, client, _, _, _, _, operator, _) = setup(&env);
+    let admin = Address::generate(&env);
+    ac_client.grant_role(&admin, &ROLE_ADMIN);
+    let tech = symbol_short!("Tech");
+    let sports = symbol_short!("Sports");
+
+    assert_eq!(
+        client.try_set_display_key(
+            &operator,
+            &tech,
+            &Some(String::from_str(&env, "category.technology"))
+        ),
+        Err(Ok(PredifiError::Unauthorized))
+    );
+    client.set_display_key(
+        &admin,
+        &tech,
+        &Some(String::from_str(&env, "category.technology")),
+    );
+    assert_eq!(
+        client.get_display_key(&tech),
+        Some(String::from_str(&env, "category.technology"))
+    );
+
+    let keys = client.get_display_keys(&vec![&env, tech.clone(), sports]);
+    assert_eq!(keys.len(), 1);
+    assert_eq!(
+        keys.get(tech.clone()),
+        Some(String::from_str(&env, "category.technology"))
+    );
+
+    client.set_display_key(&admin, &tech, &None);
+    assert_eq!(client.get_display_key(&tech), None);
+}
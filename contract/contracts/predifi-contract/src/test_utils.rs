@@ -1,4 +1,93 @@
-use soroban_sdk::{token, Address, Env};
+//! # Test Harness
+//!
+//! Fixtures for spinning up a complete PrediFi market environment in tests.
+//! Compiled for this crate's own tests and, behind the `testutils` feature,
+//! for downstream contracts integrating with PrediFi. Never enable the
+//! feature in wasm builds.
+//!
+//! ## Usage Example
+//!
+//! ```rust,ignore
+//! use predifi_contract::test_utils::MarketTestContext;
+//!
+//! let env = Env::default();
+//! env.mock_all_auths();
+//! let market = MarketTestContext::setup(&env);
+//! market.token.mint(&user, 1_000);
+//! ```
+
+use crate::PredifiContractClient;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+/// Minimal access-control contract: roles are granted explicitly and
+/// `has_role` answers from storage.
+pub mod dummy_access_control {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct DummyAccessControl;
+
+    #[contractimpl]
+    impl DummyAccessControl {
+        pub fn grant_role(env: Env, user: Address, role: u32) {
+            let key = (Symbol::new(&env, "role"), user, role);
+            env.storage().instance().set(&key, &true);
+        }
+
+        pub fn has_role(env: Env, user: Address, role: u32) -> bool {
+            let key = (Symbol::new(&env, "role"), user, role);
+            env.storage().instance().get(&key).unwrap_or(false)
+        }
+    }
+}
+
+/// Minimal SEP-40 price feed: rounds are recorded explicitly and `price`
+/// answers from storage.
+pub mod dummy_reflector {
+    use crate::{PriceData, ReflectorAsset};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct DummyReflector;
+
+    #[contractimpl]
+    impl DummyReflector {
+        pub fn set_price(env: Env, asset: ReflectorAsset, timestamp: u64, price: i128) {
+            env.storage()
+                .instance()
+                .set(&(asset, timestamp), &PriceData { price, timestamp });
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            14
+        }
+
+        pub fn price(env: Env, asset: ReflectorAsset, timestamp: u64) -> Option<PriceData> {
+            env.storage().instance().get(&(asset, timestamp))
+        }
+    }
+}
+
+/// Token stub reporting more decimals than PrediFi accepts.
+pub mod dummy_wide_token {
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct DummyWideToken;
+
+    #[contractimpl]
+    impl DummyWideToken {
+        pub fn decimals(_env: Env) -> u32 {
+            19
+        }
+    }
+}
+
+use dummy_access_control::{DummyAccessControl, DummyAccessControlClient};
+
+pub const ROLE_ADMIN: u32 = 0;
+pub const ROLE_OPERATOR: u32 = 1;
+pub const ROLE_ORACLE: u32 = 3;
 
 pub struct TokenTestContext {
     pub token_address: Address,
@@ -24,3 +113,49 @@ impl TokenTestContext {
         self.admin.mint(to, &amount);
     }
 }
+
+/// A deployed and initialized PrediFi contract with a whitelisted betting
+/// token and one account per role.
+pub struct MarketTestContext {
+    pub client: PredifiContractClient<'static>,
+    pub access_control: DummyAccessControlClient<'static>,
+    pub token: TokenTestContext,
+    pub admin: Address,
+    pub operator: Address,
+    pub oracle: Address,
+    pub treasury: Address,
+}
+
+impl MarketTestContext {
+    /// Deploy the market with a 0 fee and no resolution delay.
+    /// Requires `env.mock_all_auths()` (or equivalent auth mocks).
+    pub fn setup(env: &Env) -> Self {
+        let admin = Address::generate(env);
+        let operator = Address::generate(env);
+        let oracle = Address::generate(env);
+        let treasury = Address::generate(env);
+
+        let ac_id = env.register(DummyAccessControl, ());
+        let access_control = DummyAccessControlClient::new(env, &ac_id);
+        access_control.grant_role(&admin, &ROLE_ADMIN);
+        access_control.grant_role(&operator, &ROLE_OPERATOR);
+        access_control.grant_role(&oracle, &ROLE_ORACLE);
+
+        let contract_id = env.register(crate::PredifiContract, ());
+        let client = PredifiContractClient::new(env, &contract_id);
+        client.init(&ac_id, &treasury, &0u32, &0u64);
+
+        let token = TokenTestContext::deploy(env, &admin);
+        client.add_token_to_whitelist(&admin, &token.token_address);
+
+        Self {
+            client,
+            access_control,
+            token,
+            admin,
+            operator,
+            oracle,
+            treasury,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! # Token Whitelist
+//!
+//! Bets are only accepted in whitelisted tokens. Each whitelisted token
+//! records its decimals, read from the token itself, and a minimum stake.
+
+use super::*;
+
+/// Storage keys of the token whitelist.
+#[contracttype]
+#[derive(Clone)]
+pub enum TokenKey {
+    /// Decimals and minimum stake of a whitelisted token (`TokenParams`).
+    TokenParams(Address),
+}
+
+/// Decimals and minimum stake of a whitelisted token, set by
+/// `add_token_to_whitelist` and `set_token_min_stake`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenParams {
+    pub decimals: u32,
+    /// Smallest bet accepted on any pool in this token, on top of the pool's
+    /// own `min_stake`.
+    pub min_stake: i128,
+}
+
+#[contractevent(topics = ["token_whitelist_added"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenWhitelistAddedEvent {
+    pub admin: Address,
+    pub token: Address,
+}
+
+#[contractevent(topics = ["token_whitelist_removed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenWhitelistRemovedEvent {
+    pub admin: Address,
+    pub token: Address,
+}
+
+#[contractevent(topics = ["token_params_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenParamsSetEvent {
+    pub token: Address,
+    pub decimals: u32,
+    pub min_stake: i128,
+}
+
+impl PredifiContract {
+    /// Returns true if the token is on the allowed betting whitelist.
+    pub(crate) fn is_token_whitelisted(env: &Env, token: &Address) -> bool {
+        let key = DataKey::TokenWhitelist(token.clone());
+        let allowed = env.storage().persistent().get(&key).unwrap_or(false);
+        if env.storage().persistent().has(&key) {
+            Self::extend_persistent(env, &key);
+        }
+        allowed
+    }
+
+    /// Decimals and minimum stake of `token`. Tokens whitelisted without them
+    /// are treated as 7-decimal assets with no minimum.
+    pub(crate) fn token_params(env: &Env, token: &Address) -> TokenParams {
+        env.storage()
+            .persistent()
+            .get(&TokenKey::TokenParams(token.clone()))
+            .unwrap_or(TokenParams {
+                decimals: DEFAULT_TOKEN_DECIMALS,
+                min_stake: 0,
+            })
+    }
+
+    /// Whitelist `token` with the decimals it reports and `min_stake`.
+    pub(crate) fn whitelist_token(
+        env: &Env,
+        admin: &Address,
+        token: Address,
+        min_stake: i128,
+    ) -> Result<(), PredifiError> {
+        let decimals = token::Client::new(env, &token).decimals();
+        if decimals > MAX_TOKEN_DECIMALS || min_stake < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let key = DataKey::TokenWhitelist(token.clone());
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(env, &key);
+        let params_key = TokenKey::TokenParams(token.clone());
+        env.storage().persistent().set(
+            &params_key,
+            &TokenParams {
+                decimals,
+                min_stake,
+            },
+        );
+        Self::extend_persistent(env, &params_key);
+
+        TokenWhitelistAddedEvent {
+            admin: admin.clone(),
+            token: token.clone(),
+        }
+        .publish(env);
+        TokenParamsSetEvent {
+            token,
+            decimals,
+            min_stake,
+        }
+        .publish(env);
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl PredifiContract {
+    /// Add a token to the allowed betting whitelist, recording the decimals
+    /// it reports. A token listed again keeps its minimum stake (see
+    /// `set_token_min_stake`). Caller must have Admin role (0).
+    pub fn add_token_to_whitelist(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "add_token_to_whitelist"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let min_stake = Self::token_params(&env, &token).min_stake;
+        Self::whitelist_token(&env, &admin, token, min_stake)
+    }
+
+    /// Set the smallest bet accepted in a whitelisted token (0 for none).
+    /// Caller must have Admin role (0).
+    pub fn set_token_min_stake(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_token_min_stake"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        if !Self::is_token_whitelisted(&env, &token) {
+            return Err(PredifiError::TokenNotWhitelisted);
+        }
+        if min_stake < 0 {
+            return Err(PredifiError::InvalidAmount);
+        }
+        let mut params = Self::token_params(&env, &token);
+        params.min_stake = min_stake;
+        let params_key = TokenKey::TokenParams(token.clone());
+        env.storage().persistent().set(&params_key, &params);
+        Self::extend_persistent(&env, &params_key);
+
+        TokenParamsSetEvent {
+            token,
+            decimals: params.decimals,
+            min_stake,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the decimals and minimum stake registered for a token.
+    pub fn get_token_params(env: Env, token: Address) -> TokenParams {
+        Self::token_params(&env, &token)
+    }
+
+    /// Remove a token from the allowed betting whitelist. Caller must have Admin role (0).
+    pub fn remove_token_from_whitelist(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "remove_token_from_whitelist"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+        let key = DataKey::TokenWhitelist(token.clone());
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .persistent()
+            .remove(&TokenKey::TokenParams(token.clone()));
+
+        TokenWhitelistRemovedEvent {
+            admin: admin.clone(),
+            token: token.clone(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Add or remove several tokens from the betting whitelist in one call.
+    /// An entry `(token, Some(min_stake))` whitelists the token with the
+    /// decimals it reports and that minimum stake; `(token, None)` removes it.
+    /// Caller must have Admin role (0).
+    ///
+    /// At most `MAX_WHITELIST_BATCH_SIZE` entries are applied; returns how many
+    /// were, so the remainder can be resubmitted. Emits the same per-token
+    /// events as `add_token_to_whitelist` / `remove_token_from_whitelist`.
+    pub fn set_token_whitelist_batch(
+        env: Env,
+        admin: Address,
+        entries: Vec<(Address, Option<i128>)>,
+    ) -> Result<u32, PredifiError> {
+        Self::require_not_paused(&env);
+        admin.require_auth();
+        if let Err(e) = Self::require_role(&env, &admin, 0) {
+            UnauthorizedAdminAttemptEvent {
+                caller: admin,
+                operation: Symbol::new(&env, "set_token_whitelist_batch"),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(&env);
+            return Err(e);
+        }
+
+        let processed = core::cmp::min(entries.len(), MAX_WHITELIST_BATCH_SIZE);
+        for i in 0..processed {
+            let (token, min_stake) = entries.get(i).unwrap();
+            if let Some(min_stake) = min_stake {
+                Self::whitelist_token(&env, &admin, token, min_stake)?;
+            } else {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::TokenWhitelist(token.clone()));
+                env.storage()
+                    .persistent()
+                    .remove(&TokenKey::TokenParams(token.clone()));
+                TokenWhitelistRemovedEvent {
+                    admin: admin.clone(),
+                    token,
+                }
+                .publish(&env);
+            }
+        }
+        Ok(processed)
+    }
+}